@@ -27,23 +27,19 @@ fn main() -> Result<(), Error> {
         };
 
         // trying parsing some ip messages located in a udp payload
-        for someip_message in SomeipMsgsIterator::new(udp_slice.payload()) {
-            match someip_message {
-                Ok(value) => {
-                    if value.is_someip_sd() {
-                        println!("someip service discovery packet");
-                    } else {
-                        println!(
-                            "0x{:x} (service id: 0x{:x}, method/event id: 0x{:x})",
-                            value.message_id(),
-                            value.service_id(),
-                            value.event_or_method_id()
-                        );
-                    }
-                    println!("  with payload {:?}", value.payload())
-                }
-                Err(_) => {} //error reading a someip packet (based on size, protocol version value or message type value)
+        // errors (based on size, protocol version value or message type value) are ignored
+        for value in SomeipMsgsIterator::new(udp_slice.payload()).flatten() {
+            if value.is_someip_sd() {
+                println!("someip service discovery packet");
+            } else {
+                println!(
+                    "0x{:x} (service id: 0x{:x}, method/event id: 0x{:x})",
+                    value.message_id(),
+                    value.service_id(),
+                    value.event_or_method_id()
+                );
             }
+            println!("  with payload {:?}", value.payload())
         }
     }
 
@@ -0,0 +1,29 @@
+use someip_parse::capture::SomeipCaptureReader;
+
+/// Prints the SOMEIP messages contained in a pcap or pcapng file.
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .expect("Expected pcap or pcapng file as argument");
+
+    for msg in SomeipCaptureReader::open(&path).unwrap() {
+        match msg {
+            Ok(msg) => {
+                let someip = msg.msg();
+                println!(
+                    "{:?} {:?} {} -> {} 0x{:x} (service id: 0x{:x}, method/event id: 0x{:x}, {:?})",
+                    msg.timestamp,
+                    msg.transport,
+                    msg.source,
+                    msg.destination,
+                    someip.message_id(),
+                    someip.service_id(),
+                    someip.event_or_method_id(),
+                    someip.message_type(),
+                );
+                println!("  with payload {:?}", someip.payload());
+            }
+            Err(err) => println!("Error: {err}"),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::msg_filter::MsgFilter;
+use someip_parse::{capture::JsonPayloadEncoding, MessageType};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Inspects SOMEIP messages in pcap & pcapng capture files.
+
+Usage: someip <COMMAND> [OPTIONS] <FILE>
+
+Commands:
+  dump      Prints every SOMEIP message
+  stats     Counts the SOMEIP messages by message id & message type
+  sd        Prints a timeline of the SOMEIP service discovery messages
+  json      Prints every SOMEIP message as JSON object (one per line)
+  filter    Writes the matching SOMEIP messages to a new capture file
+
+Options:
+      --no-tp            Don't reassemble SOMEIP TP segments
+  -h, --help             Prints this help
+
+Filter options (dump, stats, json & filter):
+      --service <ID>     Only messages with the given service id
+      --method <ID>      Only messages with the given method or event id
+      --type <TYPE>      Only messages of the given type (request,
+                         request-no-return, notification, response, error)
+  -o, --output <FILE>    Output file of the filter command (written as pcapng
+                         if the extension is \".pcapng\", otherwise as pcap)
+      --base64           Encode payloads as base64 instead of hex (json)
+
+Filter options can be repeated. A message matches if it matches at least one
+value of every given filter option. Ids can be decimal or hexadecimal with a
+\"0x\" prefix.";
+
+/// Subcommand selected on the command line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    Dump,
+    Stats,
+    Sd,
+    Json {
+        payload_encoding: JsonPayloadEncoding,
+    },
+    Filter {
+        output: PathBuf,
+    },
+}
+
+/// Parsed command line arguments.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub input: PathBuf,
+    pub reassemble_tp: bool,
+    pub filter: MsgFilter,
+}
+
+impl Args {
+    /// Parses the arguments (without the program name).
+    ///
+    /// Returns `Ok(None)` if the help was requested.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Args>, String> {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            None | Some("-h") | Some("--help") | Some("help") => return Ok(None),
+            Some("dump") => "dump",
+            Some("stats") => "stats",
+            Some("sd") => "sd",
+            Some("json") => "json",
+            Some("filter") => "filter",
+            Some(other) => return Err(format!("unknown command '{other}'")),
+        };
+
+        let mut input = None;
+        let mut output = None;
+        let mut reassemble_tp = true;
+        let mut payload_encoding = JsonPayloadEncoding::Hex;
+        let mut filter = MsgFilter::default();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for '{name}'"))
+            };
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--no-tp" => reassemble_tp = false,
+                "--service" if command != "sd" => {
+                    filter.service_ids.push(parse_id(&value(&arg)?)?);
+                }
+                "--method" if command != "sd" => {
+                    filter.method_ids.push(parse_id(&value(&arg)?)?);
+                }
+                "--type" if command != "sd" => {
+                    filter
+                        .message_types
+                        .push(parse_message_type(&value(&arg)?)?);
+                }
+                "--base64" if command == "json" => payload_encoding = JsonPayloadEncoding::Base64,
+                "-o" | "--output" if command == "filter" => {
+                    output = Some(PathBuf::from(value(&arg)?));
+                }
+                other if other.starts_with('-') && other.len() > 1 => {
+                    return Err(format!("unexpected option '{other}' for '{command}'"));
+                }
+                _ => {
+                    if input.replace(PathBuf::from(arg)).is_some() {
+                        return Err("more than one input file given".into());
+                    }
+                }
+            }
+        }
+
+        let input = input.ok_or("missing input file")?;
+        let command = match command {
+            "dump" => Command::Dump,
+            "stats" => Command::Stats,
+            "sd" => Command::Sd,
+            "json" => Command::Json { payload_encoding },
+            _ => Command::Filter {
+                output: output.ok_or("missing output file ('--output <FILE>')")?,
+            },
+        };
+        Ok(Some(Args {
+            command,
+            input,
+            reassemble_tp,
+            filter,
+        }))
+    }
+}
+
+/// Parses a decimal or hexadecimal ("0x" prefixed) 16 bit id.
+fn parse_id(value: &str) -> Result<u16, String> {
+    let result = if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        u16::from_str_radix(hex, 16)
+    } else {
+        value.parse()
+    };
+    result.map_err(|_| {
+        format!("invalid id '{value}' (expected a 16 bit decimal or 0x prefixed hex value)")
+    })
+}
+
+fn parse_message_type(value: &str) -> Result<MessageType, String> {
+    use MessageType::*;
+    match value {
+        "request" => Ok(Request),
+        "request-no-return" => Ok(RequestNoReturn),
+        "notification" => Ok(Notification),
+        "response" => Ok(Response),
+        "error" => Ok(Error),
+        _ => Err(format!("invalid message type '{value}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn commands() {
+        for (name, command) in [
+            ("dump", Command::Dump),
+            ("stats", Command::Stats),
+            ("sd", Command::Sd),
+            (
+                "json",
+                Command::Json {
+                    payload_encoding: JsonPayloadEncoding::Hex,
+                },
+            ),
+        ] {
+            assert_eq!(
+                Ok(Some(Args {
+                    command,
+                    input: "in.pcap".into(),
+                    reassemble_tp: true,
+                    filter: MsgFilter::default(),
+                })),
+                parse(&[name, "in.pcap"])
+            );
+        }
+        assert_eq!(
+            Ok(Some(Args {
+                command: Command::Filter {
+                    output: "out.pcapng".into()
+                },
+                input: "in.pcap".into(),
+                reassemble_tp: false,
+                filter: MsgFilter {
+                    service_ids: vec![0x1234, 10],
+                    method_ids: vec![0x8001],
+                    message_types: vec![MessageType::Notification],
+                },
+            })),
+            parse(&[
+                "filter",
+                "--service",
+                "0x1234",
+                "in.pcap",
+                "--no-tp",
+                "--service",
+                "10",
+                "--method",
+                "0X8001",
+                "--type",
+                "notification",
+                "-o",
+                "out.pcapng",
+            ])
+        );
+    }
+
+    #[test]
+    fn json_base64() {
+        assert_eq!(
+            Some(Command::Json {
+                payload_encoding: JsonPayloadEncoding::Base64
+            }),
+            parse(&["json", "--base64", "--type", "error", "a.pcap"])
+                .unwrap()
+                .map(|args| args.command)
+        );
+    }
+
+    #[test]
+    fn help() {
+        assert_eq!(Ok(None), parse(&[]));
+        assert_eq!(Ok(None), parse(&["--help"]));
+        assert_eq!(Ok(None), parse(&["dump", "-h"]));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse(&["foo"]).is_err());
+        assert!(parse(&["dump"]).is_err());
+        assert!(parse(&["dump", "a.pcap", "b.pcap"]).is_err());
+        assert!(parse(&["dump", "--output", "b.pcap", "a.pcap"]).is_err());
+        assert!(parse(&["sd", "--service", "1", "a.pcap"]).is_err());
+        assert!(parse(&["dump", "--base64", "a.pcap"]).is_err());
+        assert!(parse(&["filter", "a.pcap"]).is_err());
+        assert!(parse(&["filter", "a.pcap", "-o"]).is_err());
+        assert!(parse(&["dump", "--service", "0x10000", "a.pcap"]).is_err());
+        assert!(parse(&["dump", "--method", "abc", "a.pcap"]).is_err());
+        assert!(parse(&["dump", "--type", "event", "a.pcap"]).is_err());
+    }
+
+    #[test]
+    fn message_types() {
+        use MessageType::*;
+        for (name, expected) in [
+            ("request", Request),
+            ("request-no-return", RequestNoReturn),
+            ("notification", Notification),
+            ("response", Response),
+            ("error", Error),
+        ] {
+            assert_eq!(Ok(expected), parse_message_type(name));
+        }
+    }
+}
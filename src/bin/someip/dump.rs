@@ -0,0 +1,131 @@
+use crate::{message_type_name, msg_filter::MsgFilter, Error, Timestamp};
+use someip_parse::capture::{CaptureTransport, SomeipCaptureReader};
+use std::io::{Read, Write};
+
+/// Prints every (matching) message & the non fatal decoding errors.
+pub fn dump<R: Read, W: Write>(
+    reader: SomeipCaptureReader<R>,
+    filter: &MsgFilter,
+    out: &mut W,
+) -> Result<(), Error> {
+    for captured in reader {
+        let captured = match captured {
+            Ok(captured) => captured,
+            Err(err) if err.is_fatal() => return Err(err.into()),
+            Err(err) => {
+                writeln!(out, "error: {err}")?;
+                continue;
+            }
+        };
+        let msg = captured.msg();
+        if false == filter.matches(&msg) {
+            continue;
+        }
+
+        let transport = match captured.transport {
+            CaptureTransport::Udp => "udp",
+            CaptureTransport::Tcp => "tcp",
+        };
+        write!(
+            out,
+            "{} {transport} {} -> {} ",
+            Timestamp(captured.timestamp),
+            captured.source,
+            captured.destination
+        )?;
+        if msg.is_someip_sd() {
+            writeln!(out, "someip service discovery")?;
+        } else {
+            writeln!(
+                out,
+                "0x{:08x} (service id: 0x{:04x}, method/event id: 0x{:04x}) {}",
+                msg.message_id(),
+                msg.service_id(),
+                msg.event_or_method_id(),
+                message_type_name(&msg.message_type()),
+            )?;
+        }
+        writeln!(
+            out,
+            "  request id: 0x{:08x}, interface version: {}, return code: 0x{:02x}",
+            msg.request_id(),
+            msg.interface_version(),
+            msg.return_code()
+        )?;
+        if let Some(tp_header) = msg.tp_header() {
+            writeln!(
+                out,
+                "  tp segment with offset {}{}",
+                tp_header.offset(),
+                if tp_header.more_segment {
+                    ""
+                } else {
+                    " (last)"
+                }
+            )?;
+        }
+        write!(out, "  payload ({} bytes):", msg.payload().len())?;
+        for byte in msg.payload() {
+            write!(out, " {byte:02x}")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_capture::*;
+    use someip_parse::MessageType;
+
+    fn run(file: &[u8], filter: &MsgFilter) -> String {
+        let mut out = Vec::new();
+        dump(reader(file), filter, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn messages() {
+        assert_eq!(
+            format!(
+                "1.000000005 udp {CLIENT} -> {SERVER} 0x12340001 (service id: 0x1234, method/event id: 0x0001) request\n\
+                 \x20 request id: 0x00010002, interface version: 1, return code: 0x00\n\
+                 \x20 payload (3 bytes): 01 02 03\n\
+                 2.000000000 udp {SERVER} -> {CLIENT} 0x12340001 (service id: 0x1234, method/event id: 0x0001) response\n\
+                 \x20 request id: 0x00010002, interface version: 1, return code: 0x00\n\
+                 \x20 payload (0 bytes):\n\
+                 3.000000000 tcp {SERVER} -> {CLIENT} 0x12348002 (service id: 0x1234, method/event id: 0x8002) notification\n\
+                 \x20 request id: 0x00010002, interface version: 1, return code: 0x00\n\
+                 \x20 payload (1 bytes): 04\n"
+            ),
+            run(&capture(), &MsgFilter::default())
+        );
+    }
+
+    #[test]
+    fn filtered() {
+        let out = run(
+            &capture(),
+            &MsgFilter {
+                message_types: vec![MessageType::Response],
+                ..Default::default()
+            },
+        );
+        assert_eq!(1, out.matches(" -> ").count());
+        assert!(out.contains(" response\n"));
+    }
+
+    #[test]
+    fn fatal_error() {
+        let mut file = capture();
+        // truncate the last packet
+        file.truncate(file.len() - 1);
+        let mut out = Vec::new();
+        assert!(matches!(
+            dump(reader(&file), &MsgFilter::default(), &mut out),
+            Err(Error::Capture(_))
+        ));
+        assert_eq!(2, String::from_utf8(out).unwrap().matches(" -> ").count());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{msg_filter::MsgFilter, Error};
+use someip_parse::capture::{CaptureTransport, SomeipCaptureReader, SomeipCaptureWriter};
+use std::io::{Read, Write};
+
+/// Number of read, written & skipped messages.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FilterSummary {
+    pub read: usize,
+    pub written: usize,
+    pub errors: usize,
+}
+
+/// Writes the matching messages (keeping their timestamps, addresses &
+/// transport protocol) to `writer`.
+///
+/// The messages are written as new packets, so messages that were sent
+/// in one packet end up in separate packets and reassembled SOMEIP TP
+/// messages are split again into segments.
+pub fn filter<R: Read, W: Write>(
+    reader: SomeipCaptureReader<R>,
+    filter: &MsgFilter,
+    writer: &mut SomeipCaptureWriter<W>,
+) -> Result<FilterSummary, Error> {
+    let mut summary = FilterSummary::default();
+    for captured in reader {
+        let captured = match captured {
+            Ok(captured) => captured,
+            Err(err) if err.is_fatal() => return Err(err.into()),
+            Err(_) => {
+                summary.errors += 1;
+                continue;
+            }
+        };
+        summary.read += 1;
+        let msg = captured.msg();
+        if false == filter.matches(&msg) {
+            continue;
+        }
+
+        let header = msg.to_header();
+        match captured.transport {
+            CaptureTransport::Udp => writer.write_udp(
+                captured.timestamp,
+                captured.source,
+                captured.destination,
+                &header,
+                msg.payload(),
+            )?,
+            CaptureTransport::Tcp => writer.write_tcp(
+                captured.timestamp,
+                captured.source,
+                captured.destination,
+                &header,
+                msg.payload(),
+            )?,
+        }
+        summary.written += 1;
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_capture::*;
+    use someip_parse::{capture::CaptureFormat, MessageType};
+
+    fn run(file: &[u8], msg_filter: &MsgFilter) -> (FilterSummary, Vec<u8>) {
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcapng).unwrap();
+        let summary = filter(reader(file), msg_filter, &mut writer).unwrap();
+        (summary, writer.into_inner())
+    }
+
+    #[test]
+    fn all() {
+        let (summary, output) = run(&capture(), &MsgFilter::default());
+        assert_eq!(
+            FilterSummary {
+                read: 3,
+                written: 3,
+                errors: 0
+            },
+            summary
+        );
+        let expected: Vec<_> = reader(&capture()).map(Result::unwrap).collect();
+        let actual: Vec<_> = reader(&output).map(Result::unwrap).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn matching() {
+        let (summary, output) = run(
+            &capture(),
+            &MsgFilter {
+                message_types: vec![MessageType::Request, MessageType::Notification],
+                ..Default::default()
+            },
+        );
+        assert_eq!(2, summary.written);
+        let actual: Vec<_> = reader(&output)
+            .map(|msg| {
+                let msg = msg.unwrap();
+                (msg.transport, msg.msg().message_id())
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (CaptureTransport::Udp, 0x1234_0001),
+                (CaptureTransport::Tcp, 0x1234_8002)
+            ],
+            actual
+        );
+    }
+
+    #[test]
+    fn fatal_error() {
+        let mut file = capture();
+        file.truncate(file.len() - 1);
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcap).unwrap();
+        assert!(filter(reader(&file), &MsgFilter::default(), &mut writer).is_err());
+    }
+}
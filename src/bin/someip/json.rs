@@ -0,0 +1,80 @@
+use crate::{msg_filter::MsgFilter, Error};
+use someip_parse::capture::{JsonLinesWriter, SomeipCaptureReader};
+use std::io::{Read, Write};
+
+/// Writes every (matching) message as JSON object (one per line).
+///
+/// Returns the number of skipped non fatal decoding errors (the JSON
+/// output only contains messages).
+pub fn json<R: Read, W: Write>(
+    reader: SomeipCaptureReader<R>,
+    filter: &MsgFilter,
+    writer: &mut JsonLinesWriter<W>,
+) -> Result<usize, Error> {
+    let mut errors = 0;
+    for captured in reader {
+        let captured = match captured {
+            Ok(captured) => captured,
+            Err(err) if err.is_fatal() => return Err(err.into()),
+            Err(_) => {
+                errors += 1;
+                continue;
+            }
+        };
+        if filter.matches(&captured.msg()) {
+            writer.write(&captured)?;
+        }
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_capture::*;
+    use someip_parse::capture::JsonPayloadEncoding;
+
+    fn run(file: &[u8], filter: &MsgFilter) -> (Result<usize, Error>, String) {
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer.set_payload_encoding(JsonPayloadEncoding::Base64);
+        let result = json(reader(file), filter, &mut writer);
+        (result, String::from_utf8(writer.into_inner()).unwrap())
+    }
+
+    #[test]
+    fn messages() {
+        let (result, out) = run(&capture(), &MsgFilter::default());
+        assert_eq!(0, result.unwrap());
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(3, lines.len());
+        assert!(lines[0].starts_with(
+            "{\"timestamp_ns\":1000000005,\"transport\":\"udp\",\
+             \"source_ip\":\"192.168.0.1\",\"source_port\":40000,\
+             \"destination_ip\":\"192.168.0.2\",\"destination_port\":30501,"
+        ));
+        assert!(lines[2].contains("\"transport\":\"tcp\""));
+    }
+
+    #[test]
+    fn filtered() {
+        let (result, out) = run(
+            &capture(),
+            &MsgFilter {
+                method_ids: vec![0x8002],
+                ..Default::default()
+            },
+        );
+        assert_eq!(0, result.unwrap());
+        assert_eq!(1, out.lines().count());
+        assert!(out.contains("\"method_id\":32770,"));
+    }
+
+    #[test]
+    fn fatal_error() {
+        let mut file = capture();
+        file.truncate(file.len() - 1);
+        let (result, out) = run(&file, &MsgFilter::default());
+        assert!(result.is_err());
+        assert_eq!(2, out.lines().count());
+    }
+}
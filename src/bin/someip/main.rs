@@ -0,0 +1,228 @@
+//! `someip` command line tool for inspecting SOMEIP messages in pcap &
+//! pcapng capture files (requires the `cli` feature).
+//!
+//! Run `someip --help` for the list of subcommands & options.
+#![allow(clippy::bool_comparison)]
+
+mod args;
+mod dump;
+mod filter;
+mod json;
+mod msg_filter;
+mod sd;
+mod stats;
+
+use args::*;
+use someip_parse::{
+    capture::{CaptureFormat, JsonLinesWriter, SomeipCaptureReader, SomeipCaptureWriter},
+    err::CaptureError,
+    MessageType,
+};
+use std::{
+    fmt,
+    io::{self, Write},
+    process::ExitCode,
+    time::Duration,
+};
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        // the output was closed early (e.g. piped into `head`)
+        Err(Error::Io(err)) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    let mut reader = SomeipCaptureReader::open(&args.input)?;
+    reader.set_reassemble_tp(args.reassemble_tp);
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    match &args.command {
+        Command::Dump => dump::dump(reader, &args.filter, &mut out)?,
+        Command::Stats => stats::stats(reader, &args.filter, &mut out)?,
+        Command::Sd => sd::sd(reader, &mut out)?,
+        Command::Json { payload_encoding } => {
+            let mut writer = JsonLinesWriter::new(&mut out);
+            writer.set_payload_encoding(*payload_encoding);
+            let errors = json::json(reader, &args.filter, &mut writer)?;
+            writer.flush()?;
+            if errors > 0 {
+                eprintln!("{errors} errors skipped");
+            }
+        }
+        Command::Filter { output } => {
+            let format = if output
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("pcapng"))
+            {
+                CaptureFormat::Pcapng
+            } else {
+                CaptureFormat::Pcap
+            };
+            let mut writer = SomeipCaptureWriter::create(output, format)?;
+            let summary = filter::filter(reader, &args.filter, &mut writer)?;
+            writer.flush()?;
+            writeln!(
+                out,
+                "wrote {} of {} messages to {} ({} errors skipped)",
+                summary.written,
+                summary.read,
+                output.display(),
+                summary.errors
+            )?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Error aborting a subcommand.
+#[derive(Debug)]
+pub enum Error {
+    /// Error reading or writing a capture file.
+    Capture(CaptureError),
+    /// Error writing to the output.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Capture(err) => err.fmt(f),
+            Error::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<CaptureError> for Error {
+    fn from(err: CaptureError) -> Error {
+        Error::Capture(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+/// Formats a timestamp as seconds with nanosecond precision.
+pub struct Timestamp(pub Duration);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:09}", self.0.as_secs(), self.0.subsec_nanos())
+    }
+}
+
+/// Name of a message type as used on the command line.
+pub fn message_type_name(message_type: &MessageType) -> &'static str {
+    match message_type {
+        MessageType::Request => "request",
+        MessageType::RequestNoReturn => "request-no-return",
+        MessageType::Notification => "notification",
+        MessageType::Response => "response",
+        MessageType::Error => "error",
+    }
+}
+
+#[cfg(test)]
+mod test_capture {
+    use someip_parse::{capture::*, MessageType, SomeipHeader};
+    use std::{net::SocketAddr, time::Duration};
+
+    pub const CLIENT: &str = "192.168.0.1:40000";
+    pub const SERVER: &str = "192.168.0.2:30501";
+
+    /// Builds a capture containing a request & response via UDP and a
+    /// notification via TCP.
+    pub fn capture() -> Vec<u8> {
+        let client: SocketAddr = CLIENT.parse().unwrap();
+        let server: SocketAddr = SERVER.parse().unwrap();
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcap).unwrap();
+        writer
+            .write_udp(
+                Duration::new(1, 5),
+                client,
+                server,
+                &header(0x1234_0001, MessageType::Request),
+                &[1, 2, 3],
+            )
+            .unwrap();
+        writer
+            .write_udp(
+                Duration::new(2, 0),
+                server,
+                client,
+                &header(0x1234_0001, MessageType::Response),
+                &[],
+            )
+            .unwrap();
+        writer
+            .write_tcp(
+                Duration::new(3, 0),
+                server,
+                client,
+                &header(0x1234_8002, MessageType::Notification),
+                &[4],
+            )
+            .unwrap();
+        writer.into_inner()
+    }
+
+    pub fn header(message_id: u32, message_type: MessageType) -> SomeipHeader {
+        SomeipHeader {
+            message_id,
+            request_id: 0x0001_0002,
+            interface_version: 1,
+            message_type,
+            ..Default::default()
+        }
+    }
+
+    pub fn reader(file: &[u8]) -> SomeipCaptureReader<&[u8]> {
+        SomeipCaptureReader::new(file).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp() {
+        assert_eq!("0.000000000", Timestamp(Duration::ZERO).to_string());
+        assert_eq!(
+            "12.000000345",
+            Timestamp(Duration::new(12, 345)).to_string()
+        );
+    }
+
+    #[test]
+    fn error() {
+        let err = Error::from(io::Error::other("oh no"));
+        assert_eq!("oh no", err.to_string());
+        let err = Error::from(CaptureError::UnknownFileFormat { magic: 1 });
+        assert_eq!(
+            CaptureError::UnknownFileFormat { magic: 1 }.to_string(),
+            err.to_string()
+        );
+    }
+}
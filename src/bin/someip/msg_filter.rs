@@ -0,0 +1,72 @@
+use someip_parse::{MessageType, SomeipMsgSlice};
+
+/// Filter selecting SOMEIP messages by service id, method/event id &
+/// message type.
+///
+/// Empty lists match all messages.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MsgFilter {
+    pub service_ids: Vec<u16>,
+    pub method_ids: Vec<u16>,
+    pub message_types: Vec<MessageType>,
+}
+
+impl MsgFilter {
+    /// Returns true if the message matches the filter.
+    pub fn matches(&self, msg: &SomeipMsgSlice) -> bool {
+        (self.service_ids.is_empty() || self.service_ids.contains(&msg.service_id()))
+            && (self.method_ids.is_empty() || self.method_ids.contains(&msg.event_or_method_id()))
+            && (self.message_types.is_empty() || self.message_types.contains(&msg.message_type()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use someip_parse::SomeipHeader;
+
+    #[test]
+    fn matches() {
+        let mut header = SomeipHeader::default();
+        header.set_service_id(0x1234);
+        header.set_method_or_event_id(0x8001);
+        header.message_type = MessageType::Notification;
+        let bytes = header.base_to_bytes();
+        let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+
+        assert!(MsgFilter::default().matches(&msg));
+        for (filter, expected) in [
+            (
+                MsgFilter {
+                    service_ids: vec![1, 0x1234],
+                    method_ids: vec![0x8001],
+                    message_types: vec![MessageType::Notification],
+                },
+                true,
+            ),
+            (
+                MsgFilter {
+                    service_ids: vec![1],
+                    ..Default::default()
+                },
+                false,
+            ),
+            (
+                MsgFilter {
+                    method_ids: vec![1],
+                    ..Default::default()
+                },
+                false,
+            ),
+            (
+                MsgFilter {
+                    message_types: vec![MessageType::Request, MessageType::Response],
+                    ..Default::default()
+                },
+                false,
+            ),
+        ] {
+            assert_eq!(expected, filter.matches(&msg));
+        }
+    }
+}
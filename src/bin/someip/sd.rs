@@ -0,0 +1,123 @@
+use crate::{Error, Timestamp};
+use someip_parse::{capture::SomeipCaptureReader, sd::SdSlice};
+use std::io::{Read, Write};
+
+/// Prints the service discovery messages with their entries & options.
+pub fn sd<R: Read, W: Write>(reader: SomeipCaptureReader<R>, out: &mut W) -> Result<(), Error> {
+    for captured in reader {
+        let captured = match captured {
+            Ok(captured) => captured,
+            Err(err) if err.is_fatal() => return Err(err.into()),
+            // errors of non sd messages are skipped
+            Err(_) => continue,
+        };
+        let msg = captured.msg();
+        if false == msg.is_someip_sd() {
+            continue;
+        }
+
+        write!(
+            out,
+            "{} {} -> {} session 0x{:04x}",
+            Timestamp(captured.timestamp),
+            captured.source,
+            captured.destination,
+            msg.request_id() & 0xffff
+        )?;
+        let sd = match SdSlice::from_someip(&msg) {
+            Ok(sd) => sd,
+            Err(err) => {
+                writeln!(out, "\n  error: {err}")?;
+                continue;
+            }
+        };
+        if sd.flags().reboot {
+            write!(out, " reboot")?;
+        }
+        if sd.flags().unicast {
+            write!(out, " unicast")?;
+        }
+        writeln!(out)?;
+
+        for entry in sd.entries_with_options() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    writeln!(out, "  error: {err}")?;
+                    break;
+                }
+            };
+            writeln!(out, "  {}", entry.entry())?;
+            for option in entry.options_run_1().chain(entry.options_run_2()) {
+                writeln!(out, "    {option}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_capture::*;
+    use someip_parse::{
+        capture::{CaptureFormat, SomeipCaptureWriter},
+        sd::{options::*, SdEntry, SdHeader, SdOption},
+    };
+    use std::{net::SocketAddr, time::Duration};
+
+    #[test]
+    fn timeline() {
+        let client: SocketAddr = CLIENT.parse().unwrap();
+        let multicast: SocketAddr = "224.224.224.245:30490".parse().unwrap();
+
+        let mut offer = SdHeader::empty(true);
+        offer
+            .add_option(SdOption::Ipv4Endpoint(Ipv4EndpointOption {
+                ipv4_address: [192, 168, 0, 2],
+                transport_protocol: TransportProtocol::Udp,
+                port: 30501,
+            }))
+            .unwrap();
+        offer
+            .add_entry(SdEntry::new_offer_service_entry(0, 0, 1, 0, 0x1234, 1, 2, 3, 4).unwrap())
+            .unwrap();
+        let mut stop = SdHeader::empty(false);
+        stop.add_entry(SdEntry::new_stop_offer_service_entry(0, 0, 0, 0, 0x1234, 1, 2, 4).unwrap())
+            .unwrap();
+
+        // mixed with regular messages (ignored)
+        let mut file = Vec::new();
+        let mut writer = SomeipCaptureWriter::new(&mut file, CaptureFormat::Pcapng).unwrap();
+        writer
+            .write_sd(Duration::new(1, 0), client, multicast, 1, &offer)
+            .unwrap();
+        writer
+            .write_udp(
+                Duration::new(2, 0),
+                client,
+                multicast,
+                &header(0x1234_8001, someip_parse::MessageType::Notification),
+                &[],
+            )
+            .unwrap();
+        writer
+            .write_sd(Duration::new(3, 0), client, multicast, 2, &stop)
+            .unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+
+        let mut out = Vec::new();
+        sd(reader(&file), &mut out).unwrap();
+        assert_eq!(
+            format!(
+                "1.000000000 {CLIENT} -> {multicast} session 0x0001 reboot unicast\n\
+                 \x20 Offer Service (Service ID: 0x1234, Instance ID: 0x0001, Version: 2.4, TTL: 3)\n\
+                 \x20   IPv4 Endpoint Option (192.168.0.2:30501, UDP)\n\
+                 3.000000000 {CLIENT} -> {multicast} session 0x0002 unicast\n\
+                 \x20 Stop Offer Service (Service ID: 0x1234, Instance ID: 0x0001, Version: 2.4, TTL: 0)\n"
+            ),
+            String::from_utf8(out).unwrap()
+        );
+    }
+}
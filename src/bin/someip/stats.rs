@@ -0,0 +1,276 @@
+use crate::{message_type_name, msg_filter::MsgFilter, Error};
+use someip_parse::{
+    capture::{CaptureTransport, SomeipCaptureReader},
+    err::{CaptureError, SomeipHeaderError, SomeipSliceError, TpReassembleError},
+    MessageType,
+};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
+/// Message & error counters of a capture.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Stats {
+    udp: usize,
+    tcp: usize,
+    sd: usize,
+    /// Message count by message type (raw value) & name.
+    by_type: BTreeMap<(u8, &'static str), usize>,
+    /// Message count by message id & message type (raw value) & name.
+    by_id: BTreeMap<u32, BTreeMap<(u8, &'static str), usize>>,
+    /// Error count by error kind.
+    errors: BTreeMap<&'static str, usize>,
+}
+
+impl Stats {
+    fn messages(&self) -> usize {
+        self.udp + self.tcp
+    }
+
+    fn error_count(&self) -> usize {
+        self.errors.values().sum()
+    }
+}
+
+/// Counts the (matching) messages by message id & type and the decoding
+/// errors by kind.
+///
+/// The statistics are also printed if a fatal error aborts the decoding.
+pub fn stats<R: Read, W: Write>(
+    reader: SomeipCaptureReader<R>,
+    filter: &MsgFilter,
+    out: &mut W,
+) -> Result<(), Error> {
+    let mut stats = Stats::default();
+    let mut fatal = None;
+    for captured in reader {
+        let captured = match captured {
+            Ok(captured) => captured,
+            Err(err) => {
+                *stats.errors.entry(error_kind(&err)).or_insert(0) += 1;
+                if err.is_fatal() {
+                    fatal = Some(err);
+                    break;
+                }
+                continue;
+            }
+        };
+        let msg = captured.msg();
+        if false == filter.matches(&msg) {
+            continue;
+        }
+
+        match captured.transport {
+            CaptureTransport::Udp => stats.udp += 1,
+            CaptureTransport::Tcp => stats.tcp += 1,
+        }
+        if msg.is_someip_sd() {
+            stats.sd += 1;
+        }
+        let message_type = msg.message_type();
+        let type_key = (
+            message_type_value(&message_type),
+            message_type_name(&message_type),
+        );
+        *stats.by_type.entry(type_key).or_insert(0) += 1;
+        *stats
+            .by_id
+            .entry(msg.message_id())
+            .or_default()
+            .entry(type_key)
+            .or_insert(0) += 1;
+    }
+
+    print(&stats, out)?;
+    match fatal {
+        Some(err) => Err(err.into()),
+        None => Ok(()),
+    }
+}
+
+fn print<W: Write>(stats: &Stats, out: &mut W) -> Result<(), Error> {
+    writeln!(
+        out,
+        "messages: {} (udp: {}, tcp: {}, service discovery: {})",
+        stats.messages(),
+        stats.udp,
+        stats.tcp,
+        stats.sd
+    )?;
+    writeln!(out, "errors: {}", stats.error_count())?;
+
+    if false == stats.by_type.is_empty() {
+        writeln!(out, "\nby message type:")?;
+        for ((_, name), count) in &stats.by_type {
+            writeln!(out, "  {name}: {count}")?;
+        }
+    }
+
+    if false == stats.by_id.is_empty() {
+        writeln!(out, "\nby message id:")?;
+        for (message_id, by_type) in &stats.by_id {
+            let total: usize = by_type.values().sum();
+            write!(
+                out,
+                "  0x{message_id:08x} (service id: 0x{:04x}, method/event id: 0x{:04x}): {total} (",
+                message_id >> 16,
+                message_id & 0xffff,
+            )?;
+            for (index, ((_, name), count)) in by_type.iter().enumerate() {
+                if index > 0 {
+                    write!(out, ", ")?;
+                }
+                write!(out, "{name}: {count}")?;
+            }
+            writeln!(out, ")")?;
+        }
+    }
+
+    if false == stats.errors.is_empty() {
+        writeln!(out, "\nby error:")?;
+        for (kind, count) in &stats.errors {
+            writeln!(out, "  {kind}: {count}")?;
+        }
+    }
+    Ok(())
+}
+
+fn message_type_value(message_type: &MessageType) -> u8 {
+    message_type.clone() as u8
+}
+
+/// Short description of the kind of an error (used to group errors).
+fn error_kind(err: &CaptureError) -> &'static str {
+    use CaptureError::*;
+    match err {
+        Io(_) => "io",
+        UnknownFileFormat { .. } => "unknown file format",
+        InvalidBlock { .. } => "invalid pcapng block",
+        PacketTooBig { .. } => "packet too big",
+        UnknownInterface { .. } => "unknown pcapng interface",
+        IpDefrag(_) => "ip defragmentation",
+        Slice(SomeipSliceError::Len(_)) => "someip message too short",
+        Slice(SomeipSliceError::Content(SomeipHeaderError::UnsupportedProtocolVersion(_))) => {
+            "unsupported someip protocol version"
+        }
+        Slice(SomeipSliceError::Content(SomeipHeaderError::LengthFieldTooSmall(_))) => {
+            "someip length field too small"
+        }
+        Slice(SomeipSliceError::Content(SomeipHeaderError::UnknownMessageType(_))) => {
+            "unknown someip message type"
+        }
+        TpReassemble(TpReassembleError::UnalignedTpPayloadLen { .. }) => {
+            "tp payload length not a multiple of 16"
+        }
+        TpReassemble(TpReassembleError::SegmentTooBig { .. }) => "tp segment too big",
+        TpReassemble(TpReassembleError::ConflictingEnd { .. }) => "tp conflicting end",
+        TpReassemble(TpReassembleError::AllocationFailure { .. }) => "tp allocation failure",
+        TpReassemble(TpReassembleError::SectionsFull { .. }) => "tp too many received ranges",
+        TpReassemble(TpReassembleError::ConflictingOverlap { .. }) => "tp conflicting overlap",
+        TpReassemble(TpReassembleError::MessageIdMismatch { .. }) => "tp message id mismatch",
+        TpReassemble(TpReassembleError::InterfaceVersionMismatch { .. }) => {
+            "tp interface version mismatch"
+        }
+        TpReassemble(TpReassembleError::MessageTypeMismatch { .. }) => "tp message type mismatch",
+        TpReassemble(TpReassembleError::ReturnCodeMismatch { .. }) => "tp return code mismatch",
+        TpReassemble(TpReassembleError::StreamLimitReached { .. }) => "tp stream limit reached",
+        TpReassemble(TpReassembleError::ChannelStreamLimitReached { .. }) => {
+            "tp channel stream limit reached"
+        }
+        TpReassemble(TpReassembleError::BufferedBytesLimitReached { .. }) => {
+            "tp buffered bytes limit reached"
+        }
+        TpReassemble(TpReassembleError::ReorderWindowExceeded { .. }) => {
+            "tp reorder window exceeded"
+        }
+        MessageTooBig { .. } => "tcp message too big",
+        AddressFamilyMismatch { .. } => "address family mismatch",
+        SdValue(_) => "invalid sd value",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_capture::*;
+    use someip_parse::err::LenError;
+
+    fn run(file: &[u8], filter: &MsgFilter) -> (Result<(), Error>, String) {
+        let mut out = Vec::new();
+        let result = stats(reader(file), filter, &mut out);
+        (result, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn counts() {
+        let (result, out) = run(&capture(), &MsgFilter::default());
+        assert!(result.is_ok());
+        assert_eq!(
+            "messages: 3 (udp: 2, tcp: 1, service discovery: 0)\n\
+             errors: 0\n\
+             \n\
+             by message type:\n\
+             \x20 request: 1\n\
+             \x20 notification: 1\n\
+             \x20 response: 1\n\
+             \n\
+             by message id:\n\
+             \x20 0x12340001 (service id: 0x1234, method/event id: 0x0001): 2 (request: 1, response: 1)\n\
+             \x20 0x12348002 (service id: 0x1234, method/event id: 0x8002): 1 (notification: 1)\n",
+            out
+        );
+    }
+
+    #[test]
+    fn filtered() {
+        let (result, out) = run(
+            &capture(),
+            &MsgFilter {
+                method_ids: vec![0x8002],
+                ..Default::default()
+            },
+        );
+        assert!(result.is_ok());
+        assert!(out.starts_with("messages: 1 (udp: 0, tcp: 1, service discovery: 0)\n"));
+    }
+
+    #[test]
+    fn fatal_error() {
+        let mut file = capture();
+        file.truncate(file.len() - 1);
+        let (result, out) = run(&file, &MsgFilter::default());
+        assert!(matches!(result, Err(Error::Capture(CaptureError::Io(_)))));
+        assert!(out.starts_with("messages: 2 (udp: 2, tcp: 0, service discovery: 0)\nerrors: 1\n"));
+        assert!(out.ends_with("by error:\n  io: 1\n"));
+    }
+
+    #[test]
+    fn error_kinds() {
+        assert_eq!(
+            "someip message too short",
+            error_kind(&CaptureError::Slice(SomeipSliceError::Len(LenError {
+                required_len: 16,
+                len: 8,
+                len_source: someip_parse::err::LenSource::Slice,
+                layer: someip_parse::err::Layer::SomeipHeader,
+            })))
+        );
+        assert_eq!(
+            "unknown someip message type",
+            error_kind(&CaptureError::Slice(SomeipSliceError::Content(
+                SomeipHeaderError::UnknownMessageType(3)
+            )))
+        );
+        assert_eq!(
+            "tp allocation failure",
+            error_kind(&CaptureError::TpReassemble(
+                TpReassembleError::AllocationFailure { len: 1 }
+            ))
+        );
+        assert_eq!(
+            "tcp message too big",
+            error_kind(&CaptureError::MessageTooBig { len: 2, max_len: 1 })
+        );
+    }
+}
@@ -0,0 +1,726 @@
+use super::*;
+use core::time::Duration;
+use std::{io::Read, vec::Vec};
+
+/// Default maximum length of a captured packet that is accepted when
+/// reading capture files.
+pub const DEFAULT_CAPTURE_MAX_PACKET_LEN: u32 = 0x4_0000;
+
+/// Magic number of pcap files with microsecond timestamps.
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+
+/// Magic number of pcap files with nanosecond timestamps.
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+
+/// Length of the pcap file header (including the magic number).
+const PCAP_FILE_HEADER_LEN: usize = 24;
+
+/// Length of the pcap packet record header.
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+
+/// Block type of pcapng "section header blocks".
+const PCAPNG_SHB: u32 = 0x0a0d_0d0a;
+
+/// Byte order magic in pcapng "section header blocks".
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+
+/// Block type of pcapng "interface description blocks".
+const PCAPNG_IDB: u32 = 1;
+
+/// Block type of the obsolete pcapng "packet blocks".
+const PCAPNG_PB: u32 = 2;
+
+/// Block type of pcapng "simple packet blocks".
+const PCAPNG_SPB: u32 = 3;
+
+/// Block type of pcapng "enhanced packet blocks".
+const PCAPNG_EPB: u32 = 6;
+
+/// Length of the block type & the two block length fields of pcapng blocks.
+const PCAPNG_BLOCK_OVERHEAD: u32 = 12;
+
+/// Minimum length of a pcapng "section header block".
+const PCAPNG_SHB_MIN_LEN: u32 = 28;
+
+/// Maximum length of the non packet data (fields & options) accepted
+/// in a pcapng block in addition to the maximum packet length.
+const PCAPNG_MAX_EXTRA_LEN: u32 = 0x1_0000;
+
+/// pcapng interface option containing the timestamp resolution.
+const PCAPNG_OPT_IF_TSRESOL: u16 = 9;
+
+/// pcapng interface option containing the timestamp offset in seconds.
+const PCAPNG_OPT_IF_TSOFFSET: u16 = 14;
+
+/// Timestamp resolution of interfaces without an `if_tsresol` option
+/// (microseconds).
+const DEFAULT_TS_RESOL: u8 = 6;
+
+/// Settings of an interface packets were captured on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct CaptureInterface {
+    link_type: LinkType,
+    /// Timestamp resolution as encoded in the pcapng `if_tsresol` option.
+    ts_resol: u8,
+    /// Offset in seconds that has to be added to the timestamps.
+    ts_offset: i64,
+}
+
+/// Reader decoding the packets of pcap & pcapng capture files.
+///
+/// The file format is detected based on the magic number at the start of
+/// the file. Both pcap timestamp resolutions (micro- & nanoseconds) and
+/// byte orders are supported. For pcapng files enhanced, simple & the
+/// obsolete packet blocks are decoded, all other block types are skipped.
+///
+/// # Example
+///
+/// ```no_run
+/// use someip_parse::capture::CaptureFileReader;
+///
+/// let mut reader = CaptureFileReader::open("capture.pcapng").unwrap();
+/// while let Some(packet) = reader.next_packet().unwrap() {
+///     println!("{:?} {:?} {} byte(s)", packet.timestamp, packet.link_type, packet.data.len());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CaptureFileReader<R: Read> {
+    reader: R,
+    format: CaptureFormat,
+    /// True if the multi byte fields of the file (or current pcapng
+    /// section) are encoded in big endian.
+    big_endian: bool,
+    /// Interfaces of the current pcapng section (or the single
+    /// interface of a pcap file).
+    interfaces: Vec<CaptureInterface>,
+    max_packet_len: u32,
+    buf: Vec<u8>,
+}
+
+impl CaptureFileReader<std::io::BufReader<std::fs::File>> {
+    /// Opens the capture file at the given path & reads the file header.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, CaptureError> {
+        let file = std::fs::File::open(path)?;
+        CaptureFileReader::new(std::io::BufReader::new(file))
+    }
+}
+
+impl<R: Read> CaptureFileReader<R> {
+    /// Reads the file header & detects the format of the capture.
+    pub fn new(mut reader: R) -> Result<CaptureFileReader<R>, CaptureError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+
+        let mut result = CaptureFileReader {
+            reader,
+            format: CaptureFormat::Pcap,
+            big_endian: false,
+            interfaces: Vec::new(),
+            max_packet_len: DEFAULT_CAPTURE_MAX_PACKET_LEN,
+            buf: Vec::new(),
+        };
+
+        let magic_be = u32::from_be_bytes(magic);
+        let magic_le = u32::from_le_bytes(magic);
+        if magic_be == PCAPNG_SHB {
+            result.format = CaptureFormat::Pcapng;
+            result.read_pcapng_block(magic)?;
+        } else {
+            let (big_endian, ts_resol) = match (magic_be, magic_le) {
+                (PCAP_MAGIC_MICROS, _) => (true, 6),
+                (PCAP_MAGIC_NANOS, _) => (true, 9),
+                (_, PCAP_MAGIC_MICROS) => (false, 6),
+                (_, PCAP_MAGIC_NANOS) => (false, 9),
+                _ => return Err(CaptureError::UnknownFileFormat { magic: magic_be }),
+            };
+            result.big_endian = big_endian;
+
+            let mut header = [0u8; PCAP_FILE_HEADER_LEN - 4];
+            result.reader.read_exact(&mut header)?;
+            // the upper 16 bits of the link type field can contain
+            // additional informations (e.g. FCS length) and are ignored
+            let link_type = result.u32(&header[16..20]) as u16;
+            result.interfaces.push(CaptureInterface {
+                link_type: LinkType(link_type),
+                ts_resol,
+                ts_offset: 0,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Format of the capture file.
+    #[inline]
+    pub fn format(&self) -> CaptureFormat {
+        self.format
+    }
+
+    /// Maximum accepted length of a captured packet in bytes.
+    #[inline]
+    pub fn max_packet_len(&self) -> u32 {
+        self.max_packet_len
+    }
+
+    /// Sets the maximum accepted length of a captured packet in bytes.
+    ///
+    /// Reading a bigger packet results in a [`CaptureError::PacketTooBig`]
+    /// error.
+    #[inline]
+    pub fn set_max_packet_len(&mut self, max_packet_len: u32) {
+        self.max_packet_len = max_packet_len;
+    }
+
+    /// Returns the link type of the interface with the given id (pcap
+    /// files only have the interface with the id 0).
+    pub fn interface_link_type(&self, interface_id: u32) -> Option<LinkType> {
+        self.interfaces
+            .get(interface_id as usize)
+            .map(|i| i.link_type)
+    }
+
+    /// Reads the next packet from the capture file.
+    ///
+    /// `None` is returned if the end of the file is reached.
+    pub fn next_packet(&mut self) -> Result<Option<CapturePacket<'_>>, CaptureError> {
+        match self.format {
+            CaptureFormat::Pcap => self.next_pcap_packet(),
+            CaptureFormat::Pcapng => self.next_pcapng_packet(),
+        }
+    }
+
+    fn next_pcap_packet(&mut self) -> Result<Option<CapturePacket<'_>>, CaptureError> {
+        let mut header = [0u8; PCAP_RECORD_HEADER_LEN];
+        if false == self.read_exact_or_eof(&mut header)? {
+            return Ok(None);
+        }
+        let ts_sec = self.u32(&header[0..4]);
+        let ts_frac = self.u32(&header[4..8]);
+        let incl_len = self.u32(&header[8..12]);
+        let orig_len = self.u32(&header[12..16]);
+
+        if incl_len > self.max_packet_len {
+            return Err(CaptureError::PacketTooBig {
+                len: incl_len,
+                max_len: self.max_packet_len,
+            });
+        }
+        self.buf.resize(incl_len as usize, 0);
+        self.reader.read_exact(&mut self.buf)?;
+
+        let interface = &self.interfaces[0];
+        let frac_nanos = if interface.ts_resol == 9 {
+            u64::from(ts_frac)
+        } else {
+            u64::from(ts_frac) * 1000
+        };
+        Ok(Some(CapturePacket {
+            timestamp: Duration::from_secs(u64::from(ts_sec))
+                .saturating_add(Duration::from_nanos(frac_nanos)),
+            interface_id: 0,
+            link_type: interface.link_type,
+            orig_len,
+            data: &self.buf[..],
+        }))
+    }
+
+    fn next_pcapng_packet(&mut self) -> Result<Option<CapturePacket<'_>>, CaptureError> {
+        // (interface id, timestamp ticks, data start, data len, orig len)
+        let (interface_id, ticks, start, len, orig_len) = loop {
+            let mut block_type = [0u8; 4];
+            if false == self.read_exact_or_eof(&mut block_type)? {
+                return Ok(None);
+            }
+            let block_type = self.read_pcapng_block(block_type)?;
+            let body = &self.buf[..];
+            let invalid = CaptureError::InvalidBlock {
+                block_type,
+                len: body.len() as u32 + PCAPNG_BLOCK_OVERHEAD,
+            };
+            match block_type {
+                PCAPNG_SHB => {
+                    // interface ids are only valid within a section
+                    self.interfaces.clear();
+                }
+                PCAPNG_IDB => {
+                    if body.len() < 8 {
+                        return Err(invalid);
+                    }
+                    let interface = self.decode_idb();
+                    self.interfaces.push(interface);
+                }
+                PCAPNG_EPB | PCAPNG_PB => {
+                    if body.len() < 20 {
+                        return Err(invalid);
+                    }
+                    let interface_id = if block_type == PCAPNG_EPB {
+                        self.u32(&body[0..4])
+                    } else {
+                        u32::from(self.u16(&body[0..2]))
+                    };
+                    let ticks = (u64::from(self.u32(&body[4..8])) << 32)
+                        | u64::from(self.u32(&body[8..12]));
+                    let cap_len = self.u32(&body[12..16]);
+                    let orig_len = self.u32(&body[16..20]);
+                    if cap_len as usize > body.len() - 20 {
+                        return Err(invalid);
+                    }
+                    break (interface_id, Some(ticks), 20, cap_len as usize, orig_len);
+                }
+                PCAPNG_SPB => {
+                    if body.len() < 4 {
+                        return Err(invalid);
+                    }
+                    let orig_len = self.u32(&body[0..4]);
+                    let cap_len = core::cmp::min(orig_len as usize, body.len() - 4);
+                    break (0, None, 4, cap_len, orig_len);
+                }
+                _ => {
+                    // blocks not containing packets are skipped
+                }
+            }
+        };
+
+        let interface = self
+            .interfaces
+            .get(interface_id as usize)
+            .ok_or(CaptureError::UnknownInterface { interface_id })?;
+        Ok(Some(CapturePacket {
+            timestamp: ticks
+                .map(|t| ticks_to_duration(t, interface.ts_resol, interface.ts_offset))
+                .unwrap_or(Duration::ZERO),
+            interface_id,
+            link_type: interface.link_type,
+            orig_len,
+            data: &self.buf[start..start + len],
+        }))
+    }
+
+    /// Reads the rest of a pcapng block (after the block type) into `buf`
+    /// (without the block type & length fields) and returns the block type.
+    fn read_pcapng_block(&mut self, raw_block_type: [u8; 4]) -> Result<u32, CaptureError> {
+        let max_len = self
+            .max_packet_len
+            .saturating_add(PCAPNG_MAX_EXTRA_LEN)
+            .saturating_add(PCAPNG_BLOCK_OVERHEAD);
+
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+
+        let (block_type, len) = if u32::from_be_bytes(raw_block_type) == PCAPNG_SHB {
+            // the byte order of a section is defined by the byte order magic
+            // following the length field of the section header block
+            let mut byte_order_magic = [0u8; 4];
+            self.reader.read_exact(&mut byte_order_magic)?;
+            self.big_endian = if u32::from_be_bytes(byte_order_magic) == PCAPNG_BYTE_ORDER_MAGIC {
+                true
+            } else if u32::from_le_bytes(byte_order_magic) == PCAPNG_BYTE_ORDER_MAGIC {
+                false
+            } else {
+                return Err(CaptureError::InvalidBlock {
+                    block_type: PCAPNG_SHB,
+                    len: u32::from_le_bytes(len),
+                });
+            };
+            let len = self.u32(&len);
+            if len < PCAPNG_SHB_MIN_LEN || len % 4 != 0 || len > max_len {
+                return Err(CaptureError::InvalidBlock {
+                    block_type: PCAPNG_SHB,
+                    len,
+                });
+            }
+            self.buf.clear();
+            self.buf.extend_from_slice(&byte_order_magic);
+            self.buf.resize((len - PCAPNG_BLOCK_OVERHEAD) as usize, 0);
+            self.reader.read_exact(&mut self.buf[4..])?;
+            (PCAPNG_SHB, len)
+        } else {
+            let block_type = self.u32(&raw_block_type);
+            let len = self.u32(&len);
+            if len < PCAPNG_BLOCK_OVERHEAD || len % 4 != 0 || len > max_len {
+                return Err(CaptureError::InvalidBlock { block_type, len });
+            }
+            self.buf.resize((len - PCAPNG_BLOCK_OVERHEAD) as usize, 0);
+            self.reader.read_exact(&mut self.buf)?;
+            (block_type, len)
+        };
+
+        // the block is terminated with a copy of the length field
+        let mut trailing_len = [0u8; 4];
+        self.reader.read_exact(&mut trailing_len)?;
+        if self.u32(&trailing_len) != len {
+            return Err(CaptureError::InvalidBlock { block_type, len });
+        }
+        Ok(block_type)
+    }
+
+    /// Decodes the interface description block in `buf`.
+    fn decode_idb(&self) -> CaptureInterface {
+        let body = &self.buf[..];
+        let mut result = CaptureInterface {
+            link_type: LinkType(self.u16(&body[0..2])),
+            ts_resol: DEFAULT_TS_RESOL,
+            ts_offset: 0,
+        };
+
+        // decode the options relevant for the timestamps
+        let mut rest = &body[8..];
+        while rest.len() >= 4 {
+            let code = self.u16(&rest[0..2]);
+            let len = usize::from(self.u16(&rest[2..4]));
+            let Some(value) = rest.get(4..4 + len) else {
+                break;
+            };
+            match code {
+                0 => break,
+                PCAPNG_OPT_IF_TSRESOL if len >= 1 => result.ts_resol = value[0],
+                PCAPNG_OPT_IF_TSOFFSET if len >= 8 => {
+                    let mut bytes = [0u8; 8];
+                    bytes.copy_from_slice(&value[..8]);
+                    result.ts_offset = if self.big_endian {
+                        i64::from_be_bytes(bytes)
+                    } else {
+                        i64::from_le_bytes(bytes)
+                    };
+                }
+                _ => {}
+            }
+            // option values are padded to 32 bits
+            let padded_len = len.div_ceil(4) * 4;
+            rest = rest.get(4 + padded_len..).unwrap_or(&[]);
+        }
+        result
+    }
+
+    /// Fills `buf` with data from the reader. Returns `false` if the end of
+    /// the file was reached before the first byte could be read.
+    fn read_exact_or_eof(&mut self, buf: &mut [u8]) -> Result<bool, std::io::Error> {
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) => {
+                    if read == 0 {
+                        return Ok(false);
+                    }
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ));
+                }
+                Ok(len) => read += len,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+
+    #[inline]
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    #[inline]
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+}
+
+/// Converts pcapng timestamp ticks to a duration since the unix epoch
+/// based on the `if_tsresol` & `if_tsoffset` options of the interface.
+fn ticks_to_duration(ticks: u64, ts_resol: u8, ts_offset: i64) -> Duration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    let nanos = if 0 == ts_resol & 0x80 {
+        // resolution is a negative power of 10
+        let exp = u32::from(ts_resol);
+        if exp <= 9 {
+            u128::from(ticks) * 10u128.pow(9 - exp)
+        } else {
+            u128::from(ticks) / 10u128.pow(core::cmp::min(exp - 9, 38))
+        }
+    } else {
+        // resolution is a negative power of 2
+        (u128::from(ticks) * NANOS_PER_SEC) >> u32::from(ts_resol & 0x7f)
+    };
+    let result = Duration::new(
+        u64::try_from(nanos / NANOS_PER_SEC).unwrap_or(u64::MAX),
+        (nanos % NANOS_PER_SEC) as u32,
+    );
+    if ts_offset >= 0 {
+        result.saturating_add(Duration::from_secs(ts_offset as u64))
+    } else {
+        result.saturating_sub(Duration::from_secs(ts_offset.unsigned_abs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::test_capture::*;
+    use std::vec;
+
+    #[test]
+    fn ticks_to_duration() {
+        use super::ticks_to_duration as f;
+        assert_eq!(Duration::new(1, 2_000), f(1_000_002, 6, 0));
+        assert_eq!(Duration::new(1, 2), f(1_000_000_002, 9, 0));
+        assert_eq!(Duration::new(1, 200_000_000), f(12, 1, 0));
+        assert_eq!(Duration::new(1, 0), f(1_000_000_000_000, 12, 0));
+        assert_eq!(Duration::new(1, 500_000_000), f(3, 0x81, 0));
+        assert_eq!(Duration::new(11, 0), f(1_000_000, 6, 10));
+        assert_eq!(Duration::new(0, 0), f(1_000_000, 6, -10));
+        assert_eq!(Duration::new(0, 0), f(u64::MAX, 127, 0));
+    }
+
+    #[test]
+    fn next_packet_pcap() {
+        let packets = [
+            (Duration::new(1, 2_000), vec![1, 2, 3]),
+            (Duration::new(3, 4_000), vec![]),
+        ];
+        for big_endian in [false, true] {
+            for nanos in [false, true] {
+                let file = pcap(big_endian, nanos, LinkType::ETHERNET, &packets);
+                let mut reader = CaptureFileReader::new(&file[..]).unwrap();
+                assert_eq!(CaptureFormat::Pcap, reader.format());
+                assert_eq!(Some(LinkType::ETHERNET), reader.interface_link_type(0));
+                assert_eq!(None, reader.interface_link_type(1));
+                for (timestamp, data) in &packets {
+                    assert_eq!(
+                        Some(CapturePacket {
+                            timestamp: *timestamp,
+                            interface_id: 0,
+                            link_type: LinkType::ETHERNET,
+                            orig_len: data.len() as u32,
+                            data: &data[..],
+                        }),
+                        reader.next_packet().unwrap()
+                    );
+                }
+                assert_eq!(None, reader.next_packet().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn next_packet_pcap_errors() {
+        // unknown magic
+        assert!(matches!(
+            CaptureFileReader::new(&[1, 2, 3, 4][..]),
+            Err(CaptureError::UnknownFileFormat { magic: 0x0102_0304 })
+        ));
+        // file too short
+        assert!(matches!(
+            CaptureFileReader::new(&[1, 2][..]),
+            Err(CaptureError::Io(_))
+        ));
+
+        let file = pcap(
+            false,
+            false,
+            LinkType::RAW,
+            &[(Duration::ZERO, vec![0; 10])],
+        );
+
+        // packet too big
+        {
+            let mut reader = CaptureFileReader::new(&file[..]).unwrap();
+            assert_eq!(DEFAULT_CAPTURE_MAX_PACKET_LEN, reader.max_packet_len());
+            reader.set_max_packet_len(9);
+            assert_eq!(9, reader.max_packet_len());
+            assert!(matches!(
+                reader.next_packet(),
+                Err(CaptureError::PacketTooBig {
+                    len: 10,
+                    max_len: 9
+                })
+            ));
+        }
+
+        // truncated packet
+        for len in [PCAP_FILE_HEADER_LEN + 1, file.len() - 1] {
+            let mut reader = CaptureFileReader::new(&file[..len]).unwrap();
+            assert!(matches!(reader.next_packet(), Err(CaptureError::Io(_))));
+        }
+    }
+
+    #[test]
+    fn next_packet_pcapng() {
+        for big_endian in [false, true] {
+            let mut file = PcapngBuilder::new(big_endian);
+            file.idb(LinkType::ETHERNET, None);
+            file.idb(LinkType::RAW, Some(9));
+            file.block(0x1234, &[1, 2, 3, 4]);
+            file.epb(0, 1_000_002, &[1, 2, 3]);
+            file.epb(1, 1_000_000_002, &[4, 5]);
+            file.spb(&[6]);
+            file.pb(1, 3_000_000_000, &[7, 8]);
+            // new section resets the interfaces
+            file.shb();
+            file.idb(LinkType::LINUX_SLL, None);
+            file.epb(0, 0, &[9]);
+
+            let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+            assert_eq!(CaptureFormat::Pcapng, reader.format());
+            let expected = [
+                (
+                    Duration::new(1, 2_000),
+                    0,
+                    LinkType::ETHERNET,
+                    &[1u8, 2, 3][..],
+                ),
+                (Duration::new(1, 2), 1, LinkType::RAW, &[4, 5][..]),
+                (Duration::ZERO, 0, LinkType::ETHERNET, &[6][..]),
+                (Duration::new(3, 0), 1, LinkType::RAW, &[7, 8][..]),
+                (Duration::ZERO, 0, LinkType::LINUX_SLL, &[9][..]),
+            ];
+            for (timestamp, interface_id, link_type, data) in expected {
+                assert_eq!(
+                    Some(CapturePacket {
+                        timestamp,
+                        interface_id,
+                        link_type,
+                        orig_len: data.len() as u32,
+                        data,
+                    }),
+                    reader.next_packet().unwrap()
+                );
+            }
+            assert_eq!(None, reader.next_packet().unwrap());
+            assert_eq!(Some(LinkType::LINUX_SLL), reader.interface_link_type(0));
+            assert_eq!(None, reader.interface_link_type(1));
+        }
+    }
+
+    #[test]
+    fn next_packet_pcapng_idb_options() {
+        let mut file = PcapngBuilder::new(false);
+        // options: unknown option, if_tsresol (2^-1), if_tsoffset (10s), end
+        let mut body = vec![1, 0, 0, 0, 0, 0, 0, 0];
+        body.extend_from_slice(&[2, 0, 3, 0, 1, 2, 3, 0]);
+        body.extend_from_slice(&[9, 0, 1, 0, 0x81, 0, 0, 0]);
+        body.extend_from_slice(&[14, 0, 8, 0]);
+        body.extend_from_slice(&10i64.to_le_bytes());
+        body.extend_from_slice(&[0, 0, 0, 0]);
+        file.block(PCAPNG_IDB, &body);
+        file.epb(0, 3, &[]);
+
+        let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+        assert_eq!(
+            Duration::new(11, 500_000_000),
+            reader.next_packet().unwrap().unwrap().timestamp
+        );
+    }
+
+    #[test]
+    fn next_packet_pcapng_errors() {
+        // invalid byte order magic
+        {
+            let mut file = PcapngBuilder::new(false);
+            file.data[8] = 0;
+            assert!(matches!(
+                CaptureFileReader::new(&file.data[..]),
+                Err(CaptureError::InvalidBlock {
+                    block_type: PCAPNG_SHB,
+                    len: 28
+                })
+            ));
+        }
+        // invalid section header length
+        for len in [24u32, 29] {
+            let mut file = PcapngBuilder::new(false);
+            file.data[4..8].copy_from_slice(&len.to_le_bytes());
+            assert!(matches!(
+                CaptureFileReader::new(&file.data[..]),
+                Err(CaptureError::InvalidBlock {
+                    block_type: PCAPNG_SHB,
+                    ..
+                })
+            ));
+        }
+        // invalid block lengths
+        for len in [8u32, 13, u32::MAX - 3] {
+            let mut file = PcapngBuilder::new(false);
+            file.data.extend_from_slice(&6u32.to_le_bytes());
+            file.data.extend_from_slice(&len.to_le_bytes());
+            let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+            assert!(matches!(
+                reader.next_packet(),
+                Err(CaptureError::InvalidBlock { block_type: 6, .. })
+            ));
+        }
+        // trailing length differs
+        {
+            let mut file = PcapngBuilder::new(false);
+            file.block(0x1234, &[]);
+            let len = file.data.len();
+            file.data[len - 4] = 0;
+            let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+            assert!(matches!(
+                reader.next_packet(),
+                Err(CaptureError::InvalidBlock {
+                    block_type: 0x1234,
+                    len: 12
+                })
+            ));
+        }
+        // blocks too short for their content
+        for (block_type, body) in [
+            (PCAPNG_IDB, &[0u8; 4][..]),
+            (PCAPNG_EPB, &[0u8; 16][..]),
+            (PCAPNG_PB, &[0u8; 16][..]),
+            (PCAPNG_SPB, &[][..]),
+        ] {
+            let mut file = PcapngBuilder::new(false);
+            file.block(block_type, body);
+            let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+            assert!(matches!(
+                reader.next_packet(),
+                Err(CaptureError::InvalidBlock { block_type: t, .. }) if t == block_type
+            ));
+        }
+        // captured length bigger then the block
+        {
+            let mut file = PcapngBuilder::new(false);
+            file.idb(LinkType::ETHERNET, None);
+            let mut body = vec![0u8; 20];
+            body[12..16].copy_from_slice(&1u32.to_le_bytes());
+            file.block(PCAPNG_EPB, &body);
+            let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+            assert!(matches!(
+                reader.next_packet(),
+                Err(CaptureError::InvalidBlock {
+                    block_type: PCAPNG_EPB,
+                    len: 32
+                })
+            ));
+        }
+        // unknown interface
+        {
+            let mut file = PcapngBuilder::new(false);
+            file.epb(1, 0, &[]);
+            let mut reader = CaptureFileReader::new(&file.data[..]).unwrap();
+            assert!(matches!(
+                reader.next_packet(),
+                Err(CaptureError::UnknownInterface { interface_id: 1 })
+            ));
+        }
+        // truncated block
+        {
+            let mut file = PcapngBuilder::new(false);
+            file.idb(LinkType::ETHERNET, None);
+            let len = file.data.len();
+            let mut reader = CaptureFileReader::new(&file.data[..len - 1]).unwrap();
+            assert!(matches!(reader.next_packet(), Err(CaptureError::Io(_))));
+        }
+    }
+}
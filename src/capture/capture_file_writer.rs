@@ -0,0 +1,240 @@
+use super::*;
+use core::time::Duration;
+use std::io::Write;
+
+/// Writer encoding packets as pcap or pcapng capture file.
+///
+/// pcap files are written in little endian with nanosecond timestamps.
+/// pcapng files consist of a single section with one interface (also
+/// using nanosecond timestamps) and enhanced packet blocks.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::capture::*;
+/// use std::time::Duration;
+///
+/// let mut writer = CaptureFileWriter::new(
+///     Vec::new(),
+///     CaptureFormat::Pcapng,
+///     LinkType::ETHERNET,
+/// ).unwrap();
+/// writer.write_packet(Duration::from_secs(1), &[0u8; 14]).unwrap();
+/// let file = writer.into_inner();
+///
+/// let mut reader = CaptureFileReader::new(&file[..]).unwrap();
+/// assert_eq!(14, reader.next_packet().unwrap().unwrap().data.len());
+/// ```
+#[derive(Debug)]
+pub struct CaptureFileWriter<W: Write> {
+    writer: W,
+    format: CaptureFormat,
+    link_type: LinkType,
+}
+
+impl CaptureFileWriter<std::io::BufWriter<std::fs::File>> {
+    /// Creates (or truncates) the file at the given path & writes the file
+    /// header.
+    pub fn create<P: AsRef<std::path::Path>>(
+        path: P,
+        format: CaptureFormat,
+        link_type: LinkType,
+    ) -> Result<Self, CaptureError> {
+        let file = std::fs::File::create(path)?;
+        CaptureFileWriter::new(std::io::BufWriter::new(file), format, link_type)
+    }
+}
+
+impl<W: Write> CaptureFileWriter<W> {
+    /// Writes the file header (for pcapng the section header & interface
+    /// description block).
+    pub fn new(
+        mut writer: W,
+        format: CaptureFormat,
+        link_type: LinkType,
+    ) -> Result<CaptureFileWriter<W>, CaptureError> {
+        match format {
+            CaptureFormat::Pcap => {
+                let mut header = [0u8; 24];
+                // magic number (nanosecond timestamps)
+                header[0..4].copy_from_slice(&0xa1b2_3c4du32.to_le_bytes());
+                // version 2.4
+                header[4..6].copy_from_slice(&2u16.to_le_bytes());
+                header[6..8].copy_from_slice(&4u16.to_le_bytes());
+                // snap length
+                header[16..20].copy_from_slice(&DEFAULT_CAPTURE_MAX_PACKET_LEN.to_le_bytes());
+                header[20..24].copy_from_slice(&u32::from(link_type.0).to_le_bytes());
+                writer.write_all(&header)?;
+            }
+            CaptureFormat::Pcapng => {
+                // section header block (version 1.0 & unknown section length)
+                let mut shb = [0u8; 16];
+                shb[0..4].copy_from_slice(&0x1a2b_3c4du32.to_le_bytes());
+                shb[4..6].copy_from_slice(&1u16.to_le_bytes());
+                shb[8..16].copy_from_slice(&(-1i64).to_le_bytes());
+                write_pcapng_block(&mut writer, 0x0a0d_0d0a, &[&shb])?;
+
+                // interface description block with the "if_tsresol" option
+                // set to nanoseconds
+                let mut idb = [0u8; 20];
+                idb[0..2].copy_from_slice(&link_type.0.to_le_bytes());
+                idb[8..10].copy_from_slice(&9u16.to_le_bytes());
+                idb[10..12].copy_from_slice(&1u16.to_le_bytes());
+                idb[12] = 9;
+                write_pcapng_block(&mut writer, 1, &[&idb])?;
+            }
+        }
+        Ok(CaptureFileWriter {
+            writer,
+            format,
+            link_type,
+        })
+    }
+
+    /// Format of the written capture file.
+    #[inline]
+    pub fn format(&self) -> CaptureFormat {
+        self.format
+    }
+
+    /// Link-layer header type of the written packets.
+    #[inline]
+    pub fn link_type(&self) -> LinkType {
+        self.link_type
+    }
+
+    /// Writes a packet (starting with the link-layer header) captured at
+    /// the given time (since the unix epoch).
+    pub fn write_packet(&mut self, timestamp: Duration, data: &[u8]) -> Result<(), CaptureError> {
+        let len = u32::try_from(data.len()).map_err(|_| CaptureError::PacketTooBig {
+            len: u32::MAX,
+            max_len: u32::MAX,
+        })?;
+        match self.format {
+            CaptureFormat::Pcap => {
+                let mut header = [0u8; 16];
+                let secs = u32::try_from(timestamp.as_secs()).unwrap_or(u32::MAX);
+                header[0..4].copy_from_slice(&secs.to_le_bytes());
+                header[4..8].copy_from_slice(&timestamp.subsec_nanos().to_le_bytes());
+                header[8..12].copy_from_slice(&len.to_le_bytes());
+                header[12..16].copy_from_slice(&len.to_le_bytes());
+                self.writer.write_all(&header)?;
+                self.writer.write_all(data)?;
+            }
+            CaptureFormat::Pcapng => {
+                let ticks = u64::try_from(timestamp.as_nanos()).unwrap_or(u64::MAX);
+                let mut header = [0u8; 20];
+                header[4..8].copy_from_slice(&((ticks >> 32) as u32).to_le_bytes());
+                header[8..12].copy_from_slice(&(ticks as u32).to_le_bytes());
+                header[12..16].copy_from_slice(&len.to_le_bytes());
+                header[16..20].copy_from_slice(&len.to_le_bytes());
+                write_pcapng_block(&mut self.writer, 6, &[&header, data])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    #[inline]
+    pub fn flush(&mut self) -> Result<(), CaptureError> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Returns a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Writes a little endian pcapng block with the given body parts (padded
+/// to 32 bits).
+fn write_pcapng_block<W: Write>(
+    writer: &mut W,
+    block_type: u32,
+    body: &[&[u8]],
+) -> Result<(), CaptureError> {
+    let body_len: usize = body.iter().map(|part| part.len()).sum();
+    let padding = body_len.next_multiple_of(4) - body_len;
+    let len = u32::try_from(body_len + padding + 12).map_err(|_| CaptureError::PacketTooBig {
+        len: u32::MAX,
+        max_len: u32::MAX,
+    })?;
+
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&len.to_le_bytes())?;
+    for part in body {
+        writer.write_all(part)?;
+    }
+    writer.write_all(&[0u8; 3][..padding])?;
+    writer.write_all(&len.to_le_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{vec, vec::Vec};
+
+    #[test]
+    fn write_read() {
+        let packets = [
+            (Duration::new(1, 2), vec![1u8, 2, 3]),
+            (Duration::new(3, 999_999_999), vec![]),
+            (Duration::new(4, 0), vec![4; 100]),
+        ];
+        for format in [CaptureFormat::Pcap, CaptureFormat::Pcapng] {
+            let mut writer = CaptureFileWriter::new(Vec::new(), format, LinkType::RAW).unwrap();
+            assert_eq!(format, writer.format());
+            assert_eq!(LinkType::RAW, writer.link_type());
+            for (timestamp, data) in &packets {
+                writer.write_packet(*timestamp, data).unwrap();
+            }
+            writer.flush().unwrap();
+            assert!(false == writer.get_ref().is_empty());
+            let file = writer.into_inner();
+
+            let mut reader = CaptureFileReader::new(&file[..]).unwrap();
+            assert_eq!(format, reader.format());
+            for (timestamp, data) in &packets {
+                assert_eq!(
+                    Some(CapturePacket {
+                        timestamp: *timestamp,
+                        interface_id: 0,
+                        link_type: LinkType::RAW,
+                        orig_len: data.len() as u32,
+                        data: &data[..],
+                    }),
+                    reader.next_packet().unwrap()
+                );
+            }
+            assert_eq!(None, reader.next_packet().unwrap());
+        }
+    }
+
+    #[test]
+    fn io_errors() {
+        let mut buf = [0u8; 10];
+        for format in [CaptureFormat::Pcap, CaptureFormat::Pcapng] {
+            assert!(matches!(
+                CaptureFileWriter::new(&mut buf[..], format, LinkType::RAW),
+                Err(CaptureError::Io(_))
+            ));
+        }
+
+        for format in [CaptureFormat::Pcap, CaptureFormat::Pcapng] {
+            let mut buf = [0u8; 80];
+            let mut writer = CaptureFileWriter::new(&mut buf[..], format, LinkType::RAW).unwrap();
+            assert!(matches!(
+                writer.write_packet(Duration::ZERO, &[0; 100]),
+                Err(CaptureError::Io(_))
+            ));
+        }
+    }
+}
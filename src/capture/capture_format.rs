@@ -0,0 +1,8 @@
+/// File format of a capture file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CaptureFormat {
+    /// Classic libpcap format (with micro- or nanosecond timestamps).
+    Pcap,
+    /// PCAP next generation format.
+    Pcapng,
+}
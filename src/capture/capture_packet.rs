@@ -0,0 +1,25 @@
+use super::*;
+use core::time::Duration;
+
+/// Packet read from a pcap or pcapng capture file.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CapturePacket<'a> {
+    /// Time at which the packet was captured (since the unix epoch).
+    ///
+    /// Zero for pcapng "simple packet blocks" as they contain no timestamp.
+    pub timestamp: Duration,
+
+    /// Id of the interface on which the packet was captured (always 0
+    /// for pcap files).
+    pub interface_id: u32,
+
+    /// Link-layer header type of the packet data.
+    pub link_type: LinkType,
+
+    /// Original length of the packet on the wire (can be bigger than the
+    /// captured `data` if the packet was truncated).
+    pub orig_len: u32,
+
+    /// Captured packet data (starting with the link-layer header).
+    pub data: &'a [u8],
+}
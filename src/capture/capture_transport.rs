@@ -0,0 +1,6 @@
+/// Transport protocol via which a captured SOMEIP message was sent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CaptureTransport {
+    Udp,
+    Tcp,
+}
@@ -0,0 +1,108 @@
+use super::*;
+use core::time::Duration;
+use std::{net::SocketAddr, vec::Vec};
+
+/// SOMEIP message decoded from a capture file together with the
+/// metadata of the packet(s) it was contained in.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CapturedMsg {
+    /// Capture time of the (last) packet containing the message.
+    pub timestamp: Duration,
+
+    /// Address & port of the sender.
+    pub source: SocketAddr,
+
+    /// Address & port of the receiver.
+    pub destination: SocketAddr,
+
+    /// Transport protocol via which the message was sent.
+    pub transport: CaptureTransport,
+
+    /// Raw message (already validated as SOMEIP message).
+    data: Vec<u8>,
+}
+
+impl CapturedMsg {
+    /// Creates a captured message from the data of an already validated
+    /// SOMEIP message.
+    pub(crate) fn new(
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        transport: CaptureTransport,
+        msg: &SomeipMsgSlice<'_>,
+    ) -> CapturedMsg {
+        CapturedMsg {
+            timestamp,
+            source,
+            destination,
+            transport,
+            data: msg.slice().to_vec(),
+        }
+    }
+
+    /// Returns the decoded SOMEIP message.
+    #[inline]
+    pub fn msg(&self) -> SomeipMsgSlice<'_> {
+        // validated before construction
+        SomeipMsgSlice::from_slice(&self.data).unwrap()
+    }
+
+    /// Returns the raw message (header & payload).
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the raw message (header & payload).
+    #[inline]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, port))
+    }
+
+    fn raw_msg(payload: &[u8]) -> Vec<u8> {
+        let mut result = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + payload.len() as u32,
+            request_id: 1,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: None,
+        }
+        .base_to_bytes()
+        .to_vec();
+        result.extend_from_slice(payload);
+        result
+    }
+
+    #[test]
+    fn new() {
+        let data = raw_msg(&[1, 2]);
+        let msg = CapturedMsg::new(
+            Duration::new(1, 2),
+            addr(1),
+            addr(2),
+            CaptureTransport::Udp,
+            &SomeipMsgSlice::from_slice(&data).unwrap(),
+        );
+        assert_eq!(Duration::new(1, 2), msg.timestamp);
+        assert_eq!(addr(1), msg.source);
+        assert_eq!(addr(2), msg.destination);
+        assert_eq!(CaptureTransport::Udp, msg.transport);
+        assert_eq!(&data[..], msg.data());
+        assert_eq!(0x1234_8001, msg.msg().message_id());
+        assert_eq!(&[1, 2], msg.msg().payload());
+        assert_eq!(data, msg.clone().into_data());
+    }
+}
@@ -0,0 +1,727 @@
+use super::*;
+use crate::sd::{
+    options::*, ConfigurationItems, SdEntryKind, SdEntrySlice, SdOptionSlice, SdSlice,
+};
+use core::{fmt::Write as _, time::Duration};
+use std::{io::Write, net::SocketAddr, string::String};
+
+/// Encoding of the binary data (payloads & data of unknown SD options)
+/// written by the [`JsonLinesWriter`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum JsonPayloadEncoding {
+    /// Lower case hexadecimal digits without separators (e.g. `"0a1b"`).
+    #[default]
+    Hex,
+    /// Standard base64 alphabet with padding (RFC 4648, e.g. `"Chs="`).
+    Base64,
+}
+
+/// Writer exporting SOMEIP messages as JSON lines (one JSON object per
+/// message & line).
+///
+/// # Schema
+///
+/// Every line contains one object with the following fields (always in
+/// this order, fields that are not applicable are `null`):
+///
+/// | Field | Type | Content |
+/// |-------|------|---------|
+/// | `timestamp_ns` | integer | Capture time in nanoseconds (since the Unix epoch for captures with absolute timestamps). |
+/// | `transport` | string | `"udp"` or `"tcp"`. |
+/// | `source_ip`, `destination_ip` | string | IPv4 or IPv6 address. |
+/// | `source_port`, `destination_port` | integer | UDP or TCP port. |
+/// | `message_id` | integer | Message ID (service ID & method/event ID). |
+/// | `service_id` | integer | Service ID. |
+/// | `method_id` | integer | Method ID or event ID (incl. the event bit). |
+/// | `length` | integer | Length field of the header. |
+/// | `request_id` | integer | Request ID (client ID & session ID). |
+/// | `client_id`, `session_id` | integer | Client ID & session ID. |
+/// | `protocol_version` | integer | Protocol version. |
+/// | `interface_version` | integer | Interface version. |
+/// | `message_type` | string | `"request"`, `"request_no_return"`, `"notification"`, `"response"` or `"error"`. |
+/// | `message_type_raw` | integer | Raw message type (incl. the TP flag). |
+/// | `return_code` | integer | Raw return code. |
+/// | `tp` | object or `null` | `{"offset": integer, "more_segments": bool}` for SOMEIP TP segments. |
+/// | `sd` | object or `null` | Decoded SOMEIP SD content for SD messages (see below). |
+/// | `payload_len` | integer | Length of the payload in bytes. |
+/// | `payload` | string | Payload (encoded as configured via [`JsonPayloadEncoding`]). |
+///
+/// The `sd` object contains the flags `reboot`, `unicast` &
+/// `explicit_initial_data_control` (bools), the `entries` array and an
+/// `error` string (`null` if the SD content could be decoded, otherwise
+/// the entries decoded before the error are still listed).
+///
+/// Every entry contains a `type` (`"find_service"`, `"offer_service"`,
+/// `"stop_offer_service"`, `"subscribe_eventgroup"`,
+/// `"stop_subscribe_eventgroup"`, `"subscribe_eventgroup_ack"` or
+/// `"subscribe_eventgroup_nack"`), `service_id`, `instance_id`,
+/// `major_version` & `ttl`, service entries additionally `minor_version`
+/// and eventgroup entries `counter` & `eventgroup_id`. The `options` array
+/// of an entry contains the options of both option runs (resolved from the
+/// options array).
+///
+/// Every option contains a `type` & `discardable` (bool):
+///
+/// * `"ipv4_endpoint"`, `"ipv6_endpoint"`, `"ipv4_multicast"`,
+///   `"ipv6_multicast"`, `"ipv4_sd_endpoint"` & `"ipv6_sd_endpoint"`
+///   options contain `ip` (string), `port` (integer) & `protocol` (`"udp"`,
+///   `"tcp"` or the hex value of other protocol numbers, e.g. `"0x84"`).
+/// * `"configuration"` options contain the `items` of the configuration
+///   string (array of strings).
+/// * `"load_balancing"` options contain `priority` & `weight` (integers).
+/// * `"unknown"` options contain `option_type` (integer) & `data` (string
+///   encoded like the payload).
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{capture::*, MessageType, SomeipHeader};
+/// use std::time::Duration;
+///
+/// let mut writer = JsonLinesWriter::new(Vec::new());
+/// let header = SomeipHeader {
+///     message_id: 0x1234_8001,
+///     length: 8 + 2,
+///     request_id: 0x0001_0002,
+///     interface_version: 1,
+///     message_type: MessageType::Notification,
+///     return_code: 0,
+///     tp_header: None,
+/// };
+/// let mut data = header.base_to_bytes().to_vec();
+/// data.extend_from_slice(&[0xab, 0xcd]);
+/// writer.write_msg(
+///     Duration::from_secs(1),
+///     "192.168.0.1:30501".parse().unwrap(),
+///     "192.168.0.2:30502".parse().unwrap(),
+///     CaptureTransport::Udp,
+///     &someip_parse::SomeipMsgSlice::from_slice(&data).unwrap(),
+/// ).unwrap();
+///
+/// let json = String::from_utf8(writer.into_inner()).unwrap();
+/// assert!(json.starts_with("{\"timestamp_ns\":1000000000,\"transport\":\"udp\","));
+/// assert!(json.ends_with(",\"payload_len\":2,\"payload\":\"abcd\"}\n"));
+/// ```
+#[derive(Debug)]
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+    payload_encoding: JsonPayloadEncoding,
+    /// Buffer for the line currently being encoded.
+    line: String,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    /// Creates a writer encoding payloads as hex strings.
+    pub fn new(writer: W) -> JsonLinesWriter<W> {
+        JsonLinesWriter {
+            writer,
+            payload_encoding: JsonPayloadEncoding::Hex,
+            line: String::new(),
+        }
+    }
+
+    /// Encoding of payloads & of the data of unknown SD options.
+    #[inline]
+    pub fn payload_encoding(&self) -> JsonPayloadEncoding {
+        self.payload_encoding
+    }
+
+    /// Sets the encoding of payloads & of the data of unknown SD options.
+    #[inline]
+    pub fn set_payload_encoding(&mut self, payload_encoding: JsonPayloadEncoding) {
+        self.payload_encoding = payload_encoding;
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), CaptureError> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes a captured message as one JSON line.
+    pub fn write(&mut self, msg: &CapturedMsg) -> Result<(), CaptureError> {
+        self.write_msg(
+            msg.timestamp,
+            msg.source,
+            msg.destination,
+            msg.transport,
+            &msg.msg(),
+        )
+    }
+
+    /// Writes a SOMEIP message together with its capture metadata as one
+    /// JSON line.
+    pub fn write_msg(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        transport: CaptureTransport,
+        msg: &SomeipMsgSlice<'_>,
+    ) -> Result<(), CaptureError> {
+        self.line.clear();
+        // writing to a string can not fail
+        JsonLine {
+            out: &mut self.line,
+            payload_encoding: self.payload_encoding,
+        }
+        .msg(timestamp, source, destination, transport, msg)
+        .unwrap();
+        self.line.push('\n');
+        self.writer.write_all(self.line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Encoder of one JSON line.
+struct JsonLine<'a> {
+    out: &'a mut String,
+    payload_encoding: JsonPayloadEncoding,
+}
+
+impl JsonLine<'_> {
+    fn msg(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        transport: CaptureTransport,
+        msg: &SomeipMsgSlice<'_>,
+    ) -> core::fmt::Result {
+        let out = &mut *self.out;
+        write!(out, "{{\"timestamp_ns\":{}", timestamp.as_nanos())?;
+        let transport = match transport {
+            CaptureTransport::Udp => "udp",
+            CaptureTransport::Tcp => "tcp",
+        };
+        write!(out, ",\"transport\":\"{transport}\"")?;
+        write!(
+            out,
+            ",\"source_ip\":\"{}\",\"source_port\":{}",
+            source.ip(),
+            source.port()
+        )?;
+        write!(
+            out,
+            ",\"destination_ip\":\"{}\",\"destination_port\":{}",
+            destination.ip(),
+            destination.port()
+        )?;
+        write!(
+            out,
+            ",\"message_id\":{},\"service_id\":{},\"method_id\":{}",
+            msg.message_id(),
+            msg.service_id(),
+            msg.event_or_method_id()
+        )?;
+        write!(
+            out,
+            ",\"length\":{},\"request_id\":{},\"client_id\":{},\"session_id\":{}",
+            msg.length(),
+            msg.request_id(),
+            msg.request_id() >> 16,
+            msg.request_id() & 0xffff
+        )?;
+        write!(
+            out,
+            ",\"protocol_version\":{},\"interface_version\":{}",
+            msg.protocol_version(),
+            msg.interface_version()
+        )?;
+        let message_type = match msg.message_type() {
+            MessageType::Request => "request",
+            MessageType::RequestNoReturn => "request_no_return",
+            MessageType::Notification => "notification",
+            MessageType::Response => "response",
+            MessageType::Error => "error",
+        };
+        write!(
+            out,
+            ",\"message_type\":\"{message_type}\",\"message_type_raw\":{},\"return_code\":{}",
+            msg.message_type_raw(),
+            msg.return_code()
+        )?;
+        match msg.tp_header() {
+            Some(tp) => write!(
+                out,
+                ",\"tp\":{{\"offset\":{},\"more_segments\":{}}}",
+                tp.offset(),
+                tp.more_segment
+            )?,
+            None => write!(out, ",\"tp\":null")?,
+        }
+        write!(out, ",\"sd\":")?;
+        if msg.is_someip_sd() && false == msg.is_tp() {
+            self.sd(msg)?;
+        } else {
+            write!(self.out, "null")?;
+        }
+        write!(
+            self.out,
+            ",\"payload_len\":{},\"payload\":",
+            msg.payload().len()
+        )?;
+        self.data(msg.payload())?;
+        write!(self.out, "}}")
+    }
+
+    fn sd(&mut self, msg: &SomeipMsgSlice<'_>) -> core::fmt::Result {
+        let sd = match SdSlice::from_someip(msg) {
+            Ok(sd) => sd,
+            Err(err) => {
+                write!(
+                    self.out,
+                    "{{\"reboot\":null,\"unicast\":null,\"explicit_initial_data_control\":null,\"entries\":[],\"error\":"
+                )?;
+                string(self.out, &err)?;
+                return write!(self.out, "}}");
+            }
+        };
+        let flags = sd.flags();
+        write!(
+            self.out,
+            "{{\"reboot\":{},\"unicast\":{},\"explicit_initial_data_control\":{},\"entries\":[",
+            flags.reboot, flags.unicast, flags.explicit_initial_data_control
+        )?;
+        let mut error = None;
+        for (index, entry) in sd.entries_with_options().enumerate() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    error = Some(err);
+                    break;
+                }
+            };
+            if index > 0 {
+                write!(self.out, ",")?;
+            }
+            self.entry(&entry.entry())?;
+            write!(self.out, ",\"options\":[")?;
+            for (index, option) in entry
+                .options_run_1()
+                .chain(entry.options_run_2())
+                .enumerate()
+            {
+                if index > 0 {
+                    write!(self.out, ",")?;
+                }
+                self.option(&option)?;
+            }
+            write!(self.out, "]}}")?;
+        }
+        write!(self.out, "],\"error\":")?;
+        match error {
+            Some(err) => string(self.out, &err)?,
+            None => write!(self.out, "null")?,
+        }
+        write!(self.out, "}}")
+    }
+
+    /// Writes the fields of an entry (without closing the object).
+    fn entry(&mut self, entry: &SdEntrySlice<'_>) -> core::fmt::Result {
+        let entry_type = match entry.kind() {
+            SdEntryKind::Find => "find_service",
+            SdEntryKind::Offer => "offer_service",
+            SdEntryKind::StopOffer => "stop_offer_service",
+            SdEntryKind::Subscribe => "subscribe_eventgroup",
+            SdEntryKind::StopSubscribe => "stop_subscribe_eventgroup",
+            SdEntryKind::SubscribeAck => "subscribe_eventgroup_ack",
+            SdEntryKind::SubscribeNack => "subscribe_eventgroup_nack",
+        };
+        write!(
+            self.out,
+            "{{\"type\":\"{entry_type}\",\"service_id\":{},\"instance_id\":{},\"major_version\":{},\"ttl\":{}",
+            entry.service_id(),
+            entry.instance_id(),
+            entry.major_version(),
+            entry.ttl().value()
+        )?;
+        match entry {
+            SdEntrySlice::Service(s) => {
+                write!(self.out, ",\"minor_version\":{}", s.minor_version())
+            }
+            SdEntrySlice::Eventgroup(e) => write!(
+                self.out,
+                ",\"counter\":{},\"eventgroup_id\":{}",
+                e.counter().value(),
+                e.eventgroup_id()
+            ),
+        }
+    }
+
+    fn option(&mut self, option: &SdOptionSlice<'_>) -> core::fmt::Result {
+        use SdOptionSlice::*;
+        let (option_type, discardable) = match option {
+            Configuration(o) => ("configuration", o.discardable()),
+            LoadBalancing(o) => ("load_balancing", o.discardable()),
+            // endpoint & multicast options are never discardable
+            Ipv4Endpoint(_) => ("ipv4_endpoint", false),
+            Ipv6Endpoint(_) => ("ipv6_endpoint", false),
+            Ipv4Multicast(_) => ("ipv4_multicast", false),
+            Ipv6Multicast(_) => ("ipv6_multicast", false),
+            Ipv4SdEndpoint(_) => ("ipv4_sd_endpoint", false),
+            Ipv6SdEndpoint(_) => ("ipv6_sd_endpoint", false),
+            Unknown(o) => ("unknown", o.discardable()),
+        };
+        write!(
+            self.out,
+            "{{\"type\":\"{option_type}\",\"discardable\":{discardable}"
+        )?;
+        if let Some((addr, protocol)) = option.socket_addr() {
+            write!(
+                self.out,
+                ",\"ip\":\"{}\",\"port\":{},\"protocol\":",
+                addr.ip(),
+                addr.port()
+            )?;
+            match protocol {
+                TransportProtocol::Udp => write!(self.out, "\"udp\"")?,
+                TransportProtocol::Tcp => write!(self.out, "\"tcp\"")?,
+                TransportProtocol::Generic(value) => write!(self.out, "\"0x{value:02x}\"")?,
+            }
+        }
+        match option {
+            Configuration(o) => {
+                write!(self.out, ",\"items\":[")?;
+                for (index, item) in ConfigurationItems(o.configuration_string()).enumerate() {
+                    if index > 0 {
+                        write!(self.out, ",")?;
+                    }
+                    string(self.out, &String::from_utf8_lossy(item))?;
+                }
+                write!(self.out, "]")?;
+            }
+            LoadBalancing(o) => write!(
+                self.out,
+                ",\"priority\":{},\"weight\":{}",
+                o.priority(),
+                o.weight()
+            )?,
+            Unknown(o) => {
+                write!(self.out, ",\"option_type\":{},\"data\":", o.option_type())?;
+                // skip the byte containing the discardable flag
+                self.data(&o.slice()[1..])?;
+            }
+            _ => {}
+        }
+        write!(self.out, "}}")
+    }
+
+    /// Writes binary data as string with the configured encoding.
+    fn data(&mut self, data: &[u8]) -> core::fmt::Result {
+        write!(self.out, "\"")?;
+        match self.payload_encoding {
+            JsonPayloadEncoding::Hex => {
+                for byte in data {
+                    write!(self.out, "{byte:02x}")?;
+                }
+            }
+            JsonPayloadEncoding::Base64 => base64(self.out, data),
+        }
+        write!(self.out, "\"")
+    }
+}
+
+/// Writes the display output of a value as JSON string.
+fn string<T: core::fmt::Display + ?Sized>(out: &mut String, value: &T) -> core::fmt::Result {
+    /// Escapes the written characters.
+    struct Escaped<'a>(&'a mut String);
+
+    impl core::fmt::Write for Escaped<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            for c in s.chars() {
+                match c {
+                    '"' => self.0.push_str("\\\""),
+                    '\\' => self.0.push_str("\\\\"),
+                    '\n' => self.0.push_str("\\n"),
+                    '\r' => self.0.push_str("\\r"),
+                    '\t' => self.0.push_str("\\t"),
+                    c if u32::from(c) < 0x20 => write!(self.0, "\\u{:04x}", u32::from(c))?,
+                    c => self.0.push(c),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    out.push('"');
+    write!(Escaped(out), "{value}")?;
+    out.push('"');
+    Ok(())
+}
+
+/// Appends the base64 encoding (standard alphabet with padding).
+fn base64(out: &mut String, data: &[u8]) {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    for chunk in data.chunks(3) {
+        let value = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(
+                    ALPHABET[((value >> (18 - 6 * i)) & 0x3f) as usize],
+                ));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sd::{entries::*, SdEntry, SdHeader, SdOption};
+    use std::{format, vec::Vec};
+
+    const CLIENT: &str = "192.168.0.1:40000";
+    const SERVER: &str = "[fe80::1]:30501";
+
+    fn raw_msg(header: &SomeipHeader, payload: &[u8]) -> Vec<u8> {
+        let mut header = header.clone();
+        header.length = SOMEIP_LEN_OFFSET_TO_PAYLOAD
+            + payload.len() as u32
+            + if header.tp_header.is_some() { 4 } else { 0 };
+        let mut result = header.base_to_bytes().to_vec();
+        if let Some(tp) = &header.tp_header {
+            result.extend_from_slice(&tp.to_bytes());
+        }
+        result.extend_from_slice(payload);
+        result
+    }
+
+    fn to_json(encoding: JsonPayloadEncoding, transport: CaptureTransport, data: &[u8]) -> String {
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer.set_payload_encoding(encoding);
+        assert_eq!(encoding, writer.payload_encoding());
+        writer
+            .write_msg(
+                Duration::new(1, 5),
+                CLIENT.parse().unwrap(),
+                SERVER.parse().unwrap(),
+                transport,
+                &SomeipMsgSlice::from_slice(data).unwrap(),
+            )
+            .unwrap();
+        writer.flush().unwrap();
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn msg() {
+        let header = SomeipHeader {
+            message_id: 0x1234_0001,
+            length: 0,
+            request_id: 0x0001_0002,
+            interface_version: 3,
+            message_type: MessageType::Response,
+            return_code: 0x02,
+            tp_header: None,
+        };
+        assert_eq!(
+            "{\"timestamp_ns\":1000000005,\"transport\":\"tcp\",\
+             \"source_ip\":\"192.168.0.1\",\"source_port\":40000,\
+             \"destination_ip\":\"fe80::1\",\"destination_port\":30501,\
+             \"message_id\":305397761,\"service_id\":4660,\"method_id\":1,\
+             \"length\":12,\"request_id\":65538,\"client_id\":1,\"session_id\":2,\
+             \"protocol_version\":1,\"interface_version\":3,\
+             \"message_type\":\"response\",\"message_type_raw\":128,\"return_code\":2,\
+             \"tp\":null,\"sd\":null,\"payload_len\":4,\"payload\":\"0102abff\"}\n",
+            to_json(
+                JsonPayloadEncoding::Hex,
+                CaptureTransport::Tcp,
+                &raw_msg(&header, &[1, 2, 0xab, 0xff])
+            )
+        );
+    }
+
+    #[test]
+    fn tp() {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id: 1,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: Some(TpHeader::with_offset(32, true).unwrap()),
+        };
+        let json = to_json(
+            JsonPayloadEncoding::Base64,
+            CaptureTransport::Udp,
+            &raw_msg(&header, &[0; 16]),
+        );
+        assert!(json.contains(
+            ",\"message_type\":\"notification\",\"message_type_raw\":34,\"return_code\":0,\
+             \"tp\":{\"offset\":32,\"more_segments\":true},\"sd\":null,\"payload_len\":16,\
+             \"payload\":\"AAAAAAAAAAAAAAAAAAAAAA==\"}\n"
+        ));
+    }
+
+    #[test]
+    fn sd() {
+        let mut sd = SdHeader::empty(true);
+        sd.add_option(SdOption::Ipv4Endpoint(Ipv4EndpointOption {
+            ipv4_address: [192, 168, 0, 2],
+            transport_protocol: TransportProtocol::Udp,
+            port: 30501,
+        }))
+        .unwrap();
+        sd.add_option(SdOption::Configuration(ConfigurationOption {
+            discardable: false,
+            configuration_string: (&b"\x03a=1\x05b=\"\n\xff\x00"[..]).try_into().unwrap(),
+        }))
+        .unwrap();
+        sd.add_option(SdOption::LoadBalancing(LoadBalancingOption {
+            discardable: true,
+            priority: 1,
+            weight: 2,
+        }))
+        .unwrap();
+        sd.add_entry(SdEntry::new_offer_service_entry(0, 2, 1, 1, 0x1234, 1, 2, 3, 4).unwrap())
+            .unwrap();
+        sd.add_entry(
+            SdEntry::new_eventgroup(
+                EventGroupEntryType::SubscribeOrStop,
+                1,
+                0,
+                1,
+                0,
+                0x1234,
+                1,
+                2,
+                3,
+                false,
+                4,
+                5,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        sd.add_entry(SdEntry::new_stop_offer_service_entry(0, 0, 0, 0, 0x1234, 1, 2, 4).unwrap())
+            .unwrap();
+        let payload = sd.to_bytes_vec().unwrap();
+        let json = to_json(
+            JsonPayloadEncoding::Hex,
+            CaptureTransport::Udp,
+            &raw_msg(&SomeipHeader::new_sd_header(0, 1, None), &payload),
+        );
+        let expected = format!(
+            ",\"sd\":{{\"reboot\":true,\"unicast\":true,\"explicit_initial_data_control\":false,\
+             \"entries\":[\
+             {{\"type\":\"offer_service\",\"service_id\":4660,\"instance_id\":1,\
+             \"major_version\":2,\"ttl\":3,\"minor_version\":4,\"options\":[\
+             {{\"type\":\"ipv4_endpoint\",\"discardable\":false,\"ip\":\"192.168.0.2\",\
+             \"port\":30501,\"protocol\":\"udp\"}},\
+             {{\"type\":\"load_balancing\",\"discardable\":true,\"priority\":1,\"weight\":2}}]}},\
+             {{\"type\":\"subscribe_eventgroup\",\"service_id\":4660,\"instance_id\":1,\
+             \"major_version\":2,\"ttl\":3,\"counter\":4,\"eventgroup_id\":5,\"options\":[\
+             {{\"type\":\"configuration\",\"discardable\":false,\
+             \"items\":[\"a=1\",\"b=\\\"\\n\u{fffd}\"]}}]}},\
+             {{\"type\":\"stop_offer_service\",\"service_id\":4660,\"instance_id\":1,\
+             \"major_version\":2,\"ttl\":0,\"minor_version\":4,\"options\":[]}}\
+             ],\"error\":null}},\"payload_len\":{},\"payload\":\"",
+            payload.len()
+        );
+        assert!(json.contains(&expected), "{json}");
+
+        // valid json
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            "b=\"\n\u{fffd}",
+            value["sd"]["entries"][1]["options"][0]["items"][1]
+        );
+    }
+
+    #[test]
+    fn sd_error() {
+        // entries array length exceeding the payload
+        let payload = [0xc0, 0, 0, 0, 0, 0, 0, 0x10];
+        let json = to_json(
+            JsonPayloadEncoding::Hex,
+            CaptureTransport::Udp,
+            &raw_msg(&SomeipHeader::new_sd_header(0, 1, None), &payload),
+        );
+        assert!(json.contains(
+            ",\"sd\":{\"reboot\":null,\"unicast\":null,\"explicit_initial_data_control\":null,\
+             \"entries\":[],\"error\":\""
+        ));
+    }
+
+    #[test]
+    fn unknown_option() {
+        let mut writer = JsonLine {
+            out: &mut String::new(),
+            payload_encoding: JsonPayloadEncoding::Base64,
+        };
+        let data = [0x80, 0xab, 0xcd];
+        writer
+            .option(&SdOptionSlice::Unknown(
+                UnknownSlice::new(0x30, &data).unwrap(),
+            ))
+            .unwrap();
+        assert_eq!(
+            "{\"type\":\"unknown\",\"discardable\":true,\"option_type\":48,\"data\":\"q80=\"}",
+            writer.out
+        );
+    }
+
+    #[test]
+    fn write_captured() {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id: 1,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: None,
+        };
+        let data = raw_msg(&header, &[1]);
+        let msg = CapturedMsg::new(
+            Duration::new(2, 0),
+            CLIENT.parse().unwrap(),
+            SERVER.parse().unwrap(),
+            CaptureTransport::Udp,
+            &SomeipMsgSlice::from_slice(&data).unwrap(),
+        );
+        let mut writer = JsonLinesWriter::new(Vec::new());
+        writer.write(&msg).unwrap();
+        writer.write(&msg).unwrap();
+        let json = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<_> = json.lines().collect();
+        assert_eq!(2, lines.len());
+        assert!(lines[0].starts_with("{\"timestamp_ns\":2000000000,\"transport\":\"udp\","));
+        assert_eq!(lines[0], lines[1]);
+    }
+
+    #[test]
+    fn string_escaping() {
+        let mut out = String::new();
+        string(&mut out, "a\"\\\n\r\t\u{1}ä").unwrap();
+        assert_eq!("\"a\\\"\\\\\\n\\r\\t\\u0001ä\"", out);
+    }
+
+    #[test]
+    fn base64_encoding() {
+        for (data, expected) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+            (&[0xff, 0xfe], "//4="),
+        ] {
+            let mut out = String::new();
+            base64(&mut out, data);
+            assert_eq!(expected, out);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+/// Link-layer header type of captured packets (value of the
+/// `LINKTYPE_*` constants in pcap & pcapng files).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct LinkType(pub u16);
+
+impl LinkType {
+    /// BSD loopback encapsulation (4 byte protocol family in host byte order).
+    pub const NULL: LinkType = LinkType(0);
+    /// Ethernet (including VLAN tags).
+    pub const ETHERNET: LinkType = LinkType(1);
+    /// Raw IPv4 or IPv6 packets.
+    pub const RAW: LinkType = LinkType(101);
+    /// Linux "cooked" capture encapsulation (v1).
+    pub const LINUX_SLL: LinkType = LinkType(113);
+    /// Raw IPv4 packets.
+    pub const IPV4: LinkType = LinkType(228);
+    /// Raw IPv6 packets.
+    pub const IPV6: LinkType = LinkType(229);
+}
+
+impl From<u16> for LinkType {
+    #[inline]
+    fn from(value: u16) -> Self {
+        LinkType(value)
+    }
+}
+
+impl From<LinkType> for u16 {
+    #[inline]
+    fn from(value: LinkType) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from() {
+        assert_eq!(LinkType::ETHERNET, LinkType::from(1u16));
+        assert_eq!(113u16, u16::from(LinkType::LINUX_SLL));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{err::CaptureError, *};
+
+mod capture_file_reader;
+pub use capture_file_reader::*;
+
+mod capture_file_writer;
+pub use capture_file_writer::*;
+
+mod capture_format;
+pub use capture_format::*;
+
+mod capture_packet;
+pub use capture_packet::*;
+
+mod capture_transport;
+pub use capture_transport::*;
+
+mod captured_msg;
+pub use captured_msg::*;
+
+mod json_lines_writer;
+pub use json_lines_writer::*;
+
+mod link_type;
+pub use link_type::*;
+
+mod someip_capture_reader;
+pub use someip_capture_reader::*;
+
+mod someip_capture_writer;
+pub use someip_capture_writer::*;
+
+mod tcp_flow;
+pub(crate) use tcp_flow::*;
+
+/// Helpers for building pcap & pcapng files in tests.
+#[cfg(test)]
+pub(crate) mod test_capture {
+    use super::*;
+    use core::time::Duration;
+    use std::vec::Vec;
+
+    /// Builds a pcap file containing the given packets.
+    pub(crate) fn pcap(
+        big_endian: bool,
+        nanos: bool,
+        link_type: LinkType,
+        packets: &[(Duration, Vec<u8>)],
+    ) -> Vec<u8> {
+        let u16_bytes = |v: u16| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let u32_bytes = |v: u32| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+
+        let mut result = Vec::new();
+        result.extend_from_slice(&u32_bytes(if nanos { 0xa1b2_3c4d } else { 0xa1b2_c3d4 }));
+        result.extend_from_slice(&u16_bytes(2));
+        result.extend_from_slice(&u16_bytes(4));
+        result.extend_from_slice(&u32_bytes(0));
+        result.extend_from_slice(&u32_bytes(0));
+        result.extend_from_slice(&u32_bytes(0xffff));
+        result.extend_from_slice(&u32_bytes(u32::from(link_type.0)));
+        for (timestamp, data) in packets {
+            let frac = if nanos {
+                timestamp.subsec_nanos()
+            } else {
+                timestamp.subsec_micros()
+            };
+            result.extend_from_slice(&u32_bytes(timestamp.as_secs() as u32));
+            result.extend_from_slice(&u32_bytes(frac));
+            result.extend_from_slice(&u32_bytes(data.len() as u32));
+            result.extend_from_slice(&u32_bytes(data.len() as u32));
+            result.extend_from_slice(data);
+        }
+        result
+    }
+
+    /// Builds a pcapng file block by block (starting with a section header).
+    pub(crate) struct PcapngBuilder {
+        pub(crate) big_endian: bool,
+        pub(crate) data: Vec<u8>,
+    }
+
+    impl PcapngBuilder {
+        pub(crate) fn new(big_endian: bool) -> PcapngBuilder {
+            let mut result = PcapngBuilder {
+                big_endian,
+                data: Vec::new(),
+            };
+            result.shb();
+            result
+        }
+
+        fn u16_bytes(&self, v: u16) -> [u8; 2] {
+            if self.big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        }
+
+        fn u32_bytes(&self, v: u32) -> [u8; 4] {
+            if self.big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        }
+
+        /// Adds a block with the given body (padded to 32 bits).
+        pub(crate) fn block(&mut self, block_type: u32, body: &[u8]) {
+            let padded_len = body.len().div_ceil(4) * 4;
+            let len = (padded_len + 12) as u32;
+            self.data.extend_from_slice(&self.u32_bytes(block_type));
+            self.data.extend_from_slice(&self.u32_bytes(len));
+            self.data.extend_from_slice(body);
+            self.data
+                .resize(self.data.len() + padded_len - body.len(), 0);
+            self.data.extend_from_slice(&self.u32_bytes(len));
+        }
+
+        pub(crate) fn shb(&mut self) {
+            let mut body = Vec::new();
+            body.extend_from_slice(&self.u32_bytes(0x1a2b_3c4d));
+            body.extend_from_slice(&self.u16_bytes(1));
+            body.extend_from_slice(&self.u16_bytes(0));
+            body.extend_from_slice(&[0xff; 8]);
+            self.block(0x0a0d_0d0a, &body);
+        }
+
+        pub(crate) fn idb(&mut self, link_type: LinkType, ts_resol: Option<u8>) {
+            let mut body = Vec::new();
+            body.extend_from_slice(&self.u16_bytes(link_type.0));
+            body.extend_from_slice(&self.u16_bytes(0));
+            body.extend_from_slice(&self.u32_bytes(0));
+            if let Some(ts_resol) = ts_resol {
+                body.extend_from_slice(&self.u16_bytes(9));
+                body.extend_from_slice(&self.u16_bytes(1));
+                body.extend_from_slice(&[ts_resol, 0, 0, 0]);
+                body.extend_from_slice(&[0; 4]);
+            }
+            self.block(1, &body);
+        }
+
+        pub(crate) fn epb(&mut self, interface_id: u32, ticks: u64, data: &[u8]) {
+            let mut body = Vec::new();
+            body.extend_from_slice(&self.u32_bytes(interface_id));
+            body.extend_from_slice(&self.u32_bytes((ticks >> 32) as u32));
+            body.extend_from_slice(&self.u32_bytes(ticks as u32));
+            body.extend_from_slice(&self.u32_bytes(data.len() as u32));
+            body.extend_from_slice(&self.u32_bytes(data.len() as u32));
+            body.extend_from_slice(data);
+            self.block(6, &body);
+        }
+
+        pub(crate) fn spb(&mut self, data: &[u8]) {
+            let mut body = Vec::new();
+            body.extend_from_slice(&self.u32_bytes(data.len() as u32));
+            body.extend_from_slice(data);
+            self.block(3, &body);
+        }
+
+        pub(crate) fn pb(&mut self, interface_id: u16, ticks: u64, data: &[u8]) {
+            let mut body = Vec::new();
+            body.extend_from_slice(&self.u16_bytes(interface_id));
+            body.extend_from_slice(&self.u16_bytes(0));
+            body.extend_from_slice(&self.u32_bytes((ticks >> 32) as u32));
+            body.extend_from_slice(&self.u32_bytes(ticks as u32));
+            body.extend_from_slice(&self.u32_bytes(data.len() as u32));
+            body.extend_from_slice(&self.u32_bytes(data.len() as u32));
+            body.extend_from_slice(data);
+            self.block(2, &body);
+        }
+    }
+}
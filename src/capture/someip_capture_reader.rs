@@ -0,0 +1,751 @@
+use super::*;
+use core::time::Duration;
+use etherparse::{
+    defrag::IpDefragPool, IpNumber, NetSlice, SlicedPacket, TcpSlice, TransportSlice, UdpSlice,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    io::Read,
+    net::{IpAddr, SocketAddr},
+};
+
+/// Default time after which incomplete fragmented IP packets & SOMEIP TP
+/// messages are discarded (based on the capture timestamps).
+pub const DEFAULT_CAPTURE_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reader decoding the SOMEIP messages contained in a pcap or pcapng
+/// capture file.
+///
+/// Packets with the link types Ethernet (including VLAN tags), Linux SLL,
+/// BSD loopback & raw IPv4/IPv6 are decoded. Fragmented IP packets are
+/// defragmented, TCP streams are reassembled (per direction) and SOMEIP TP
+/// messages are reassembled per UDP flow (can be disabled via
+/// [`SomeipCaptureReader::set_reassemble_tp`]). Packets that are not
+/// UDP or TCP packets are skipped.
+///
+/// Errors concerning single packets or messages are returned by the
+/// iterator without stopping it. After an error concerning the capture
+/// file itself (see [`CaptureError::is_fatal`]) the iterator ends.
+///
+/// # Example
+///
+/// ```no_run
+/// use someip_parse::capture::SomeipCaptureReader;
+///
+/// for msg in SomeipCaptureReader::open("capture.pcapng").unwrap() {
+///     match msg {
+///         Ok(msg) => println!(
+///             "{:?} {} -> {}: 0x{:08x}",
+///             msg.timestamp,
+///             msg.source,
+///             msg.destination,
+///             msg.msg().message_id()
+///         ),
+///         Err(err) => println!("Error: {err}"),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SomeipCaptureReader<R: Read> {
+    reader: CaptureFileReader<R>,
+    decoder: Decoder,
+    /// Set after the end of the file or a fatal error was reached.
+    done: bool,
+}
+
+impl SomeipCaptureReader<std::io::BufReader<std::fs::File>> {
+    /// Opens the capture file at the given path & reads the file header.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, CaptureError> {
+        Ok(SomeipCaptureReader::from_file_reader(
+            CaptureFileReader::open(path)?,
+        ))
+    }
+}
+
+impl<R: Read> SomeipCaptureReader<R> {
+    /// Reads the file header & detects the format of the capture.
+    pub fn new(reader: R) -> Result<SomeipCaptureReader<R>, CaptureError> {
+        Ok(SomeipCaptureReader::from_file_reader(
+            CaptureFileReader::new(reader)?,
+        ))
+    }
+
+    /// Creates a SOMEIP reader decoding the packets of the given reader.
+    pub fn from_file_reader(reader: CaptureFileReader<R>) -> SomeipCaptureReader<R> {
+        SomeipCaptureReader {
+            reader,
+            decoder: Decoder {
+                defrag_pool: IpDefragPool::new(),
+                tp_pool: TpPool::new(Default::default()),
+                reassemble_tp: true,
+                reassembly_timeout: DEFAULT_CAPTURE_REASSEMBLY_TIMEOUT,
+                tcp_flows: HashMap::new(),
+                max_tcp_message_len: transport::DEFAULT_TCP_MAX_MESSAGE_LEN,
+                pending: VecDeque::new(),
+            },
+            done: false,
+        }
+    }
+
+    /// Underlying reader decoding the capture file.
+    #[inline]
+    pub fn file_reader(&self) -> &CaptureFileReader<R> {
+        &self.reader
+    }
+
+    /// Underlying reader decoding the capture file.
+    #[inline]
+    pub fn file_reader_mut(&mut self) -> &mut CaptureFileReader<R> {
+        &mut self.reader
+    }
+
+    /// Returns true if SOMEIP TP messages are reassembled.
+    #[inline]
+    pub fn reassemble_tp(&self) -> bool {
+        self.decoder.reassemble_tp
+    }
+
+    /// Enables or disables the reassembly of SOMEIP TP messages.
+    ///
+    /// If disabled TP segments are returned as separate messages.
+    #[inline]
+    pub fn set_reassemble_tp(&mut self, reassemble_tp: bool) {
+        self.decoder.reassemble_tp = reassemble_tp;
+    }
+
+    /// Sets the configuration of the buffers used to reassemble SOMEIP
+    /// TP messages (drops all currently incomplete TP messages).
+    pub fn set_tp_buf_config(&mut self, config: TpBufConfig) {
+        self.decoder.tp_pool = TpPool::new(config);
+    }
+
+    /// Time after which incomplete fragmented IP packets, SOMEIP TP
+    /// messages & idle TCP flows are discarded.
+    #[inline]
+    pub fn reassembly_timeout(&self) -> Duration {
+        self.decoder.reassembly_timeout
+    }
+
+    /// Sets the time after which incomplete fragmented IP packets, SOMEIP
+    /// TP messages & idle TCP flows are discarded (based on the capture
+    /// timestamps).
+    #[inline]
+    pub fn set_reassembly_timeout(&mut self, timeout: Duration) {
+        self.decoder.reassembly_timeout = timeout;
+    }
+
+    /// Maximum accepted length of a message (header + payload) received
+    /// via TCP.
+    #[inline]
+    pub fn max_tcp_message_len(&self) -> usize {
+        self.decoder.max_tcp_message_len
+    }
+
+    /// Sets the maximum accepted length of a message (header + payload)
+    /// received via TCP (only applied to TCP connections that are
+    /// encountered afterwards).
+    #[inline]
+    pub fn set_max_tcp_message_len(&mut self, max_len: usize) {
+        self.decoder.max_tcp_message_len = max_len;
+    }
+}
+
+impl<R: Read> Iterator for SomeipCaptureReader<R> {
+    type Item = Result<CapturedMsg, CaptureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.decoder.pending.pop_front() {
+                return Some(result);
+            }
+            if self.done {
+                return None;
+            }
+            match self.reader.next_packet() {
+                Ok(Some(packet)) => self.decoder.decode(&packet),
+                Ok(None) => self.done = true,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+/// State of the packet decoding (separate from the file reader so the
+/// packets borrowed from the reader can be decoded).
+#[derive(Debug)]
+struct Decoder {
+    defrag_pool: IpDefragPool<Duration, ()>,
+    tp_pool: TpPool<(SocketAddr, SocketAddr), Duration>,
+    reassemble_tp: bool,
+    reassembly_timeout: Duration,
+    /// TCP flows & the timestamp of their last received segment.
+    tcp_flows: HashMap<(SocketAddr, SocketAddr), (TcpFlow, Duration)>,
+    max_tcp_message_len: usize,
+    /// Decoded messages & errors that were not yet returned.
+    pending: VecDeque<Result<CapturedMsg, CaptureError>>,
+}
+
+impl Decoder {
+    fn decode(&mut self, packet: &CapturePacket<'_>) {
+        let data = packet.data;
+        let sliced = match packet.link_type {
+            LinkType::ETHERNET => SlicedPacket::from_ethernet(data),
+            LinkType::LINUX_SLL => SlicedPacket::from_linux_sll(data),
+            LinkType::RAW | LinkType::IPV4 | LinkType::IPV6 => SlicedPacket::from_ip(data),
+            LinkType::NULL if data.len() >= 4 => SlicedPacket::from_ip(&data[4..]),
+            _ => return,
+        };
+        let Ok(sliced) = sliced else {
+            return;
+        };
+        let (source, destination) = match &sliced.net {
+            Some(NetSlice::Ipv4(ipv4)) => (
+                IpAddr::V4(ipv4.header().source_addr()),
+                IpAddr::V4(ipv4.header().destination_addr()),
+            ),
+            Some(NetSlice::Ipv6(ipv6)) => (
+                IpAddr::V6(ipv6.header().source_addr()),
+                IpAddr::V6(ipv6.header().destination_addr()),
+            ),
+            _ => return,
+        };
+        let timestamp = packet.timestamp;
+
+        match &sliced.transport {
+            Some(TransportSlice::Udp(udp)) => {
+                self.udp(timestamp, source, destination, udp);
+            }
+            Some(TransportSlice::Tcp(tcp)) => {
+                self.tcp(timestamp, source, destination, tcp);
+            }
+            Some(_) => {}
+            None => {
+                if sliced.is_ip_payload_fragmented() {
+                    self.defrag(timestamp, source, destination, &sliced);
+                }
+            }
+        }
+    }
+
+    fn defrag(
+        &mut self,
+        timestamp: Duration,
+        source: IpAddr,
+        destination: IpAddr,
+        sliced: &SlicedPacket<'_>,
+    ) {
+        let timeout = self.reassembly_timeout;
+        self.defrag_pool
+            .retain(|t| timestamp.saturating_sub(*t) < timeout);
+
+        match self
+            .defrag_pool
+            .process_sliced_packet(sliced, timestamp, ())
+        {
+            Ok(Some(payload)) => {
+                match payload.ip_number {
+                    IpNumber::UDP => {
+                        if let Ok(udp) = UdpSlice::from_slice(&payload.payload) {
+                            self.udp(timestamp, source, destination, &udp);
+                        }
+                    }
+                    IpNumber::TCP => {
+                        if let Ok(tcp) = TcpSlice::from_slice(&payload.payload) {
+                            self.tcp(timestamp, source, destination, &tcp);
+                        }
+                    }
+                    _ => {}
+                }
+                self.defrag_pool.return_buf(payload);
+            }
+            Ok(None) => {}
+            Err(err) => self.pending.push_back(Err(err.into())),
+        }
+    }
+
+    fn udp(
+        &mut self,
+        timestamp: Duration,
+        source: IpAddr,
+        destination: IpAddr,
+        udp: &UdpSlice<'_>,
+    ) {
+        let source = SocketAddr::new(source, udp.source_port());
+        let destination = SocketAddr::new(destination, udp.destination_port());
+        let new_msg = |msg: &SomeipMsgSlice<'_>| {
+            CapturedMsg::new(timestamp, source, destination, CaptureTransport::Udp, msg)
+        };
+
+        for msg in SomeipMsgsIterator::new(udp.payload()) {
+            match msg {
+                Ok(msg) if self.reassemble_tp && msg.is_tp() => {
+                    let timeout = self.reassembly_timeout;
+                    self.tp_pool
+                        .retain(|t| timestamp.saturating_sub(*t) < timeout);
+                    match self.tp_pool.consume((source, destination), timestamp, msg) {
+                        Ok(Some(msg)) => self.pending.push_back(Ok(new_msg(&msg))),
+                        Ok(None) => {}
+                        Err(err) => self.pending.push_back(Err(err.into())),
+                    }
+                }
+                Ok(msg) => self.pending.push_back(Ok(new_msg(&msg))),
+                Err(err) => self.pending.push_back(Err(err.into())),
+            }
+        }
+    }
+
+    fn tcp(
+        &mut self,
+        timestamp: Duration,
+        source: IpAddr,
+        destination: IpAddr,
+        tcp: &TcpSlice<'_>,
+    ) {
+        let source = SocketAddr::new(source, tcp.source_port());
+        let destination = SocketAddr::new(destination, tcp.destination_port());
+        let max_message_len = self.max_tcp_message_len;
+        let timeout = self.reassembly_timeout;
+        self.tcp_flows
+            .retain(|_, (_, t)| timestamp.saturating_sub(*t) < timeout);
+        let (flow, last_timestamp) = self
+            .tcp_flows
+            .entry((source, destination))
+            .or_insert_with(|| (TcpFlow::new(max_message_len), timestamp));
+        *last_timestamp = timestamp;
+
+        let mut seq = tcp.sequence_number();
+        if tcp.syn() {
+            flow.syn(seq);
+            // the SYN flag occupies one sequence number
+            seq = seq.wrapping_add(1);
+        }
+        flow.receive(seq, tcp.payload());
+
+        while let Some(msg) = flow.next() {
+            self.pending.push_back(msg.map(|msg| {
+                CapturedMsg::new(timestamp, source, destination, CaptureTransport::Tcp, &msg)
+            }));
+        }
+
+        if tcp.fin() || tcp.rst() {
+            self.tcp_flows.remove(&(source, destination));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::test_capture::*;
+    use etherparse::{
+        IpFragOffset, IpHeaders, Ipv4Header, LinuxSllPacketType, PacketBuilder, VlanId,
+    };
+    use std::{
+        net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6},
+        vec,
+        vec::Vec,
+    };
+
+    const SRC_MAC: [u8; 6] = [1, 2, 3, 4, 5, 6];
+    const DST_MAC: [u8; 6] = [7, 8, 9, 10, 11, 12];
+    const SRC_IP: [u8; 4] = [192, 168, 0, 1];
+    const DST_IP: [u8; 4] = [192, 168, 0, 2];
+
+    fn src() -> SocketAddr {
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::from(SRC_IP), 30490))
+    }
+
+    fn dst() -> SocketAddr {
+        SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::from(DST_IP), 30501))
+    }
+
+    fn msg(request_id: u32, tp_header: Option<TpHeader>, payload: &[u8]) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD
+                + tp_header
+                    .as_ref()
+                    .map(|_| TP_HEADER_LENGTH as u32)
+                    .unwrap_or(0)
+                + payload.len() as u32,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header,
+        };
+        let mut result = Vec::new();
+        header.write_raw(&mut result).unwrap();
+        result.extend_from_slice(payload);
+        result
+    }
+
+    fn eth_udp(payload: &[u8]) -> Vec<u8> {
+        let builder = PacketBuilder::ethernet2(SRC_MAC, DST_MAC)
+            .single_vlan(VlanId::try_new(12).unwrap())
+            .ipv4(SRC_IP, DST_IP, 20)
+            .udp(30490, 30501);
+        let mut result = Vec::new();
+        builder.write(&mut result, payload).unwrap();
+        result
+    }
+
+    fn eth_tcp(seq: u32, syn: bool, fin: bool, payload: &[u8]) -> Vec<u8> {
+        let mut builder = PacketBuilder::ethernet2(SRC_MAC, DST_MAC)
+            .ipv4(SRC_IP, DST_IP, 20)
+            .tcp(30490, 30501, seq, 1024);
+        if syn {
+            builder = builder.syn();
+        }
+        if fin {
+            builder = builder.fin();
+        }
+        let mut result = Vec::new();
+        builder.write(&mut result, payload).unwrap();
+        result
+    }
+
+    fn read_all(file: &[u8]) -> Vec<Result<CapturedMsg, CaptureError>> {
+        SomeipCaptureReader::new(file).unwrap().collect()
+    }
+
+    fn ok_msgs(results: Vec<Result<CapturedMsg, CaptureError>>) -> Vec<CapturedMsg> {
+        results.into_iter().map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn accessors() {
+        let file = pcap(false, false, LinkType::ETHERNET, &[]);
+        let mut reader = SomeipCaptureReader::new(&file[..]).unwrap();
+        assert_eq!(CaptureFormat::Pcap, reader.file_reader().format());
+        reader.file_reader_mut().set_max_packet_len(10);
+        assert_eq!(10, reader.file_reader().max_packet_len());
+
+        assert!(reader.reassemble_tp());
+        reader.set_reassemble_tp(false);
+        assert_eq!(false, reader.reassemble_tp());
+
+        assert_eq!(
+            DEFAULT_CAPTURE_REASSEMBLY_TIMEOUT,
+            reader.reassembly_timeout()
+        );
+        reader.set_reassembly_timeout(Duration::from_secs(1));
+        assert_eq!(Duration::from_secs(1), reader.reassembly_timeout());
+
+        assert_eq!(
+            transport::DEFAULT_TCP_MAX_MESSAGE_LEN,
+            reader.max_tcp_message_len()
+        );
+        reader.set_max_tcp_message_len(20);
+        assert_eq!(20, reader.max_tcp_message_len());
+
+        reader.set_tp_buf_config(TpBufConfig::new(10, 20).unwrap());
+        assert_eq!(20, reader.decoder.tp_pool.buf_config().tp_max_payload_len());
+
+        let _ = std::format!("{reader:?}");
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn link_types() {
+        let mut payload = msg(1, None, &[1, 2]);
+        payload.extend_from_slice(&msg(2, None, &[]));
+
+        // ethernet with vlan
+        let packets = [(Duration::new(1, 0), eth_udp(&payload))];
+        let msgs = ok_msgs(read_all(&pcap(false, false, LinkType::ETHERNET, &packets)));
+        assert_eq!(2, msgs.len());
+        assert_eq!(Duration::new(1, 0), msgs[0].timestamp);
+        assert_eq!(src(), msgs[0].source);
+        assert_eq!(dst(), msgs[0].destination);
+        assert_eq!(CaptureTransport::Udp, msgs[0].transport);
+        assert_eq!(&msg(1, None, &[1, 2])[..], msgs[0].data());
+        assert_eq!(&msg(2, None, &[])[..], msgs[1].data());
+
+        // linux sll
+        {
+            let mut packet = Vec::new();
+            PacketBuilder::linux_sll(LinuxSllPacketType::HOST, 6, [0; 8])
+                .ipv4(SRC_IP, DST_IP, 20)
+                .udp(30490, 30501)
+                .write(&mut packet, &payload)
+                .unwrap();
+            let packets = [(Duration::ZERO, packet)];
+            let msgs = ok_msgs(read_all(&pcap(true, true, LinkType::LINUX_SLL, &packets)));
+            assert_eq!(2, msgs.len());
+            assert_eq!(src(), msgs[0].source);
+        }
+
+        // raw ipv6 & bsd loopback
+        {
+            let src_ip = [1; 16];
+            let dst_ip = [2; 16];
+            let mut packet = Vec::new();
+            PacketBuilder::ipv6(src_ip, dst_ip, 20)
+                .udp(1, 2)
+                .write(&mut packet, &payload)
+                .unwrap();
+
+            let mut null_packet = vec![24, 0, 0, 0];
+            null_packet.extend_from_slice(&packet);
+
+            for (link_type, packet) in [
+                (LinkType::RAW, packet.clone()),
+                (LinkType::IPV6, packet),
+                (LinkType::NULL, null_packet),
+            ] {
+                let mut file = PcapngBuilder::new(false);
+                file.idb(link_type, None);
+                file.epb(0, 0, &packet);
+                let msgs = ok_msgs(read_all(&file.data));
+                assert_eq!(2, msgs.len());
+                assert_eq!(
+                    SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::from(src_ip), 1, 0, 0)),
+                    msgs[0].source
+                );
+                assert_eq!(
+                    SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::from(dst_ip), 2, 0, 0)),
+                    msgs[0].destination
+                );
+            }
+        }
+
+        // unsupported link types, broken & non ip packets are skipped
+        {
+            let mut arp = Vec::new();
+            arp.extend_from_slice(&DST_MAC);
+            arp.extend_from_slice(&SRC_MAC);
+            arp.extend_from_slice(&[0x08, 0x06]);
+            let mut file = PcapngBuilder::new(false);
+            file.idb(LinkType(147), None);
+            file.idb(LinkType::ETHERNET, None);
+            file.idb(LinkType::NULL, None);
+            file.epb(0, 0, &eth_udp(&payload));
+            file.epb(1, 0, &[1, 2, 3]);
+            file.epb(1, 0, &arp);
+            file.epb(2, 0, &[1, 2]);
+            assert!(read_all(&file.data).is_empty());
+        }
+    }
+
+    #[test]
+    fn ip_defrag() {
+        // udp packet split into two ipv4 fragments
+        let someip = msg(1, None, &[0xab; 40]);
+        let mut udp_packet = Vec::new();
+        PacketBuilder::ipv4(SRC_IP, DST_IP, 20)
+            .udp(30490, 30501)
+            .write(&mut udp_packet, &someip)
+            .unwrap();
+        let ip_payload = &udp_packet[Ipv4Header::MIN_LEN..];
+
+        let fragment = |offset: usize, more: bool, data: &[u8]| {
+            let mut header =
+                Ipv4Header::new(data.len() as u16, 20, IpNumber::UDP, SRC_IP, DST_IP).unwrap();
+            header.identification = 1234;
+            header.more_fragments = more;
+            header.fragment_offset = IpFragOffset::try_new((offset / 8) as u16).unwrap();
+            let mut result = Vec::new();
+            PacketBuilder::ethernet2(SRC_MAC, DST_MAC)
+                .ip(IpHeaders::Ipv4(header, Default::default()))
+                .write(&mut result, IpNumber::UDP, data)
+                .unwrap();
+            result
+        };
+        let packets = [
+            (Duration::new(1, 0), fragment(0, true, &ip_payload[..32])),
+            (Duration::new(2, 0), fragment(32, false, &ip_payload[32..])),
+        ];
+        let msgs = ok_msgs(read_all(&pcap(false, false, LinkType::ETHERNET, &packets)));
+        assert_eq!(1, msgs.len());
+        assert_eq!(Duration::new(2, 0), msgs[0].timestamp);
+        assert_eq!(src(), msgs[0].source);
+        assert_eq!(&someip[..], msgs[0].data());
+
+        // conflicting fragments
+        let packets = [
+            (Duration::new(1, 0), fragment(0, true, &ip_payload[..16])),
+            (
+                Duration::new(1, 0),
+                fragment(32, false, &ip_payload[32..40]),
+            ),
+            (
+                Duration::new(1, 0),
+                fragment(32, false, &ip_payload[32..48]),
+            ),
+        ];
+        let results = read_all(&pcap(false, false, LinkType::ETHERNET, &packets));
+        assert_eq!(1, results.len());
+        assert!(matches!(results[0], Err(CaptureError::IpDefrag(_))));
+
+        // incomplete fragments are dropped after the timeout
+        let packets = [
+            (Duration::new(1, 0), fragment(0, true, &ip_payload[..32])),
+            (Duration::new(20, 0), fragment(32, false, &ip_payload[32..])),
+        ];
+        let file = pcap(false, false, LinkType::ETHERNET, &packets);
+        assert!(read_all(&file).is_empty());
+        let mut reader = SomeipCaptureReader::new(&file[..]).unwrap();
+        reader.set_reassembly_timeout(Duration::from_secs(30));
+        assert_eq!(1, reader.count());
+    }
+
+    #[test]
+    fn tp() {
+        let segment = |offset: u32, more: bool, payload: &[u8]| {
+            msg(
+                1,
+                Some(TpHeader::with_offset(offset, more).unwrap()),
+                payload,
+            )
+        };
+        let packets = [
+            (Duration::new(1, 0), eth_udp(&segment(0, true, &[1; 16]))),
+            (Duration::new(2, 0), eth_udp(&segment(16, false, &[2; 4]))),
+        ];
+        let file = pcap(false, false, LinkType::ETHERNET, &packets);
+
+        // reassembled
+        {
+            let msgs = ok_msgs(read_all(&file));
+            assert_eq!(1, msgs.len());
+            assert_eq!(Duration::new(2, 0), msgs[0].timestamp);
+            assert_eq!(false, msgs[0].msg().is_tp());
+            let mut expected = vec![1; 16];
+            expected.extend_from_slice(&[2; 4]);
+            assert_eq!(&expected[..], msgs[0].msg().payload());
+        }
+
+        // not reassembled
+        {
+            let mut reader = SomeipCaptureReader::new(&file[..]).unwrap();
+            reader.set_reassemble_tp(false);
+            let msgs = ok_msgs(reader.collect());
+            assert_eq!(2, msgs.len());
+            assert!(msgs[0].msg().is_tp());
+            assert!(msgs[1].msg().is_tp());
+        }
+
+        // reassembly error
+        {
+            let packets = [
+                (Duration::new(1, 0), eth_udp(&segment(0, false, &[1; 16]))),
+                (Duration::new(1, 0), eth_udp(&segment(16, true, &[1; 15]))),
+            ];
+            let results = read_all(&pcap(false, false, LinkType::ETHERNET, &packets));
+            assert_eq!(2, results.len());
+            assert!(results[0].is_ok());
+            assert!(matches!(results[1], Err(CaptureError::TpReassemble(_))));
+        }
+    }
+
+    #[test]
+    fn tcp() {
+        let mut data = msg(1, None, &[1, 2, 3]);
+        data.extend_from_slice(&msg(2, None, &[]));
+        data.extend_from_slice(&msg(3, None, &[4]));
+
+        let packets = [
+            (Duration::new(1, 0), eth_tcp(99, true, false, &[])),
+            (Duration::new(2, 0), eth_tcp(130, false, false, &data[30..])),
+            (Duration::new(3, 0), eth_tcp(100, false, false, &data[..30])),
+            (
+                Duration::new(4, 0),
+                eth_tcp(100 + data.len() as u32, false, true, &[]),
+            ),
+            // new connection without syn (fin dropped the old state)
+            (
+                Duration::new(5, 0),
+                eth_tcp(5000, false, false, &data[..19]),
+            ),
+        ];
+        let msgs = ok_msgs(read_all(&pcap(false, false, LinkType::ETHERNET, &packets)));
+        assert_eq!(4, msgs.len());
+        for (i, msg) in msgs.iter().enumerate() {
+            assert_eq!(CaptureTransport::Tcp, msg.transport);
+            assert_eq!(src(), msg.source);
+            assert_eq!(dst(), msg.destination);
+            assert_eq!(if i < 3 { i as u32 + 1 } else { 1 }, msg.msg().request_id());
+        }
+        assert_eq!(Duration::new(3, 0), msgs[0].timestamp);
+        assert_eq!(Duration::new(5, 0), msgs[3].timestamp);
+    }
+
+    #[test]
+    fn tcp_idle_timeout() {
+        let data = msg(1, None, &[1, 2, 3]);
+        let packets = [
+            (Duration::new(1, 0), eth_tcp(99, true, false, &[])),
+            (Duration::new(2, 0), eth_tcp(100, false, false, &data[..10])),
+            // connection was closed without a fin & a new one started
+            (Duration::new(100, 0), eth_tcp(5000, false, false, &data)),
+        ];
+        let file = pcap(false, false, LinkType::ETHERNET, &packets);
+
+        // the idle flow is dropped after the timeout
+        let msgs = ok_msgs(read_all(&file));
+        assert_eq!(1, msgs.len());
+        assert_eq!(Duration::new(100, 0), msgs[0].timestamp);
+        assert_eq!(&[1, 2, 3], msgs[0].msg().payload());
+
+        // the flow is kept within the timeout
+        let mut reader = SomeipCaptureReader::new(&file[..]).unwrap();
+        reader.set_reassembly_timeout(Duration::from_secs(1000));
+        assert_eq!(0, reader.count());
+    }
+
+    #[test]
+    fn errors() {
+        // broken someip message
+        {
+            let packets = [
+                (Duration::ZERO, eth_udp(&[1, 2, 3])),
+                (Duration::ZERO, eth_udp(&msg(1, None, &[]))),
+            ];
+            let results = read_all(&pcap(false, false, LinkType::ETHERNET, &packets));
+            assert_eq!(2, results.len());
+            assert!(matches!(results[0], Err(CaptureError::Slice(_))));
+            assert!(results[1].is_ok());
+        }
+
+        // tcp message too big
+        {
+            let packets = [(
+                Duration::ZERO,
+                eth_tcp(0, false, false, &msg(1, None, &[0; 8])),
+            )];
+            let file = pcap(false, false, LinkType::ETHERNET, &packets);
+            let mut reader = SomeipCaptureReader::new(&file[..]).unwrap();
+            reader.set_max_tcp_message_len(20);
+            let results: Vec<_> = reader.collect();
+            assert_eq!(1, results.len());
+            assert!(matches!(
+                results[0],
+                Err(CaptureError::MessageTooBig {
+                    len: 24,
+                    max_len: 20
+                })
+            ));
+        }
+
+        // fatal file errors end the iteration
+        {
+            let packets = [
+                (Duration::ZERO, eth_udp(&msg(1, None, &[]))),
+                (Duration::ZERO, eth_udp(&msg(2, None, &[]))),
+            ];
+            let file = pcap(false, false, LinkType::ETHERNET, &packets);
+            let results = read_all(&file[..file.len() - 1]);
+            assert_eq!(2, results.len());
+            assert!(results[0].is_ok());
+            assert!(matches!(results[1], Err(CaptureError::Io(_))));
+        }
+
+        // invalid file header
+        assert!(SomeipCaptureReader::new(&[0u8; 4][..]).is_err());
+        assert!(SomeipCaptureReader::open("does/not/exist.pcap").is_err());
+    }
+}
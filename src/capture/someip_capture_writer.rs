@@ -0,0 +1,730 @@
+use super::*;
+use crate::transport::{write_msg, UdpSegments};
+use core::time::Duration;
+use etherparse::{IpHeaders, PacketBuilder, PacketBuilderStep};
+use std::{
+    collections::HashMap,
+    io::Write,
+    net::{IpAddr, SocketAddr},
+    vec::Vec,
+};
+
+/// Maximum payload length of the TCP segments written by the
+/// [`SomeipCaptureWriter`] (MSS of an Ethernet link with IPv4).
+pub const CAPTURE_TCP_MAX_SEGMENT_LEN: usize = 1460;
+
+/// Maximum payload length of an UDP datagram with an IPv4 header.
+const MAX_UDP_PAYLOAD_LEN: usize = 65_507;
+
+/// Time to live (or hop limit) of the written IP packets.
+const TTL: u8 = 64;
+
+/// Window size of the written TCP segments.
+const TCP_WINDOW_SIZE: u16 = 0xffff;
+
+/// Sequence numbers of a TCP connection written to the capture.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct TcpConnection {
+    /// Sequence number of the next byte sent by the client.
+    client_seq: u32,
+    /// Sequence number of the next byte sent by the server.
+    server_seq: u32,
+}
+
+/// Writer generating pcap or pcapng capture files containing SOMEIP
+/// messages.
+///
+/// The messages are wrapped in UDP or TCP, IPv4 or IPv6 & Ethernet headers
+/// (with valid checksums). The MAC addresses are derived from the IP
+/// addresses. Messages sent via UDP with a payload bigger than
+/// [`SOMEIP_MAX_PAYLOAD_LEN_UDP`] are automatically split into SOMEIP TP
+/// segments. For TCP a connection is opened (SYN handshake) when the first
+/// message between two endpoints is written and the messages are split
+/// into segments with consecutive sequence numbers.
+///
+/// The `length` field of the passed headers is ignored and instead
+/// calculated based on the payload.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{capture::*, MessageType, SomeipHeader};
+/// use std::time::Duration;
+///
+/// let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcapng).unwrap();
+/// let header = SomeipHeader {
+///     message_id: 0x1234_8001,
+///     length: 0,
+///     request_id: 1,
+///     interface_version: 1,
+///     message_type: MessageType::Notification,
+///     return_code: 0,
+///     tp_header: None,
+/// };
+/// writer.write_udp(
+///     Duration::from_secs(1),
+///     "192.168.0.1:30501".parse().unwrap(),
+///     "192.168.0.2:30502".parse().unwrap(),
+///     &header,
+///     &[1, 2, 3, 4],
+/// ).unwrap();
+/// let file = writer.into_inner();
+///
+/// let msgs: Vec<_> = SomeipCaptureReader::new(&file[..]).unwrap().collect();
+/// assert_eq!(&[1, 2, 3, 4], msgs[0].as_ref().unwrap().msg().payload());
+/// ```
+#[derive(Debug)]
+pub struct SomeipCaptureWriter<W: Write> {
+    writer: CaptureFileWriter<W>,
+    /// Open TCP connections (key is client & server address).
+    tcp_connections: HashMap<(SocketAddr, SocketAddr), TcpConnection>,
+    /// Buffer for the serialized SOMEIP messages.
+    msg_buf: Vec<u8>,
+    /// Buffer for the serialized packets.
+    packet_buf: Vec<u8>,
+}
+
+impl SomeipCaptureWriter<std::io::BufWriter<std::fs::File>> {
+    /// Creates (or truncates) the file at the given path & writes the file
+    /// header.
+    pub fn create<P: AsRef<std::path::Path>>(
+        path: P,
+        format: CaptureFormat,
+    ) -> Result<Self, CaptureError> {
+        Ok(SomeipCaptureWriter::from_file_writer(
+            CaptureFileWriter::create(path, format, LinkType::ETHERNET)?,
+        ))
+    }
+}
+
+impl<W: Write> SomeipCaptureWriter<W> {
+    /// Writes the file header of a capture with Ethernet packets.
+    pub fn new(writer: W, format: CaptureFormat) -> Result<SomeipCaptureWriter<W>, CaptureError> {
+        Ok(SomeipCaptureWriter::from_file_writer(
+            CaptureFileWriter::new(writer, format, LinkType::ETHERNET)?,
+        ))
+    }
+
+    /// Creates a SOMEIP writer based on a file writer with the link type
+    /// [`LinkType::ETHERNET`].
+    fn from_file_writer(writer: CaptureFileWriter<W>) -> SomeipCaptureWriter<W> {
+        SomeipCaptureWriter {
+            writer,
+            tcp_connections: HashMap::new(),
+            msg_buf: Vec::new(),
+            packet_buf: Vec::new(),
+        }
+    }
+
+    /// Underlying writer encoding the capture file.
+    #[inline]
+    pub fn file_writer(&self) -> &CaptureFileWriter<W> {
+        &self.writer
+    }
+
+    /// Flushes the underlying writer.
+    #[inline]
+    pub fn flush(&mut self) -> Result<(), CaptureError> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    /// Writes a SOMEIP message sent via UDP (split into SOMEIP TP segments
+    /// if the payload is too big for a single datagram).
+    ///
+    /// All segments are written with the same timestamp.
+    pub fn write_udp(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        header: &SomeipHeader,
+        payload: &[u8],
+    ) -> Result<(), CaptureError> {
+        // check the addresses before anything is written
+        ip_builder(source, destination)?;
+
+        for (header, payload) in UdpSegments::new(header, payload)? {
+            self.msg_buf.clear();
+            write_msg(&header, payload, &mut self.msg_buf)?;
+            if self.msg_buf.len() > MAX_UDP_PAYLOAD_LEN {
+                return Err(CaptureError::MessageTooBig {
+                    len: self.msg_buf.len(),
+                    max_len: MAX_UDP_PAYLOAD_LEN,
+                });
+            }
+
+            self.packet_buf.clear();
+            ip_builder(source, destination)?
+                .udp(source.port(), destination.port())
+                .write(&mut self.packet_buf, &self.msg_buf)
+                .map_err(|_| CaptureError::MessageTooBig {
+                    len: self.msg_buf.len(),
+                    max_len: MAX_UDP_PAYLOAD_LEN,
+                })?;
+            self.writer.write_packet(timestamp, &self.packet_buf)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a SOMEIP SD message sent via UDP with the given session id.
+    pub fn write_sd(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        session_id: u16,
+        sd_header: &sd::SdHeader,
+    ) -> Result<(), CaptureError> {
+        let payload = sd_header.to_bytes_vec()?;
+        self.write_udp(
+            timestamp,
+            source,
+            destination,
+            &SomeipHeader::new_sd_header(0, session_id, None),
+            &payload,
+        )
+    }
+
+    /// Writes a SOMEIP message sent via TCP.
+    ///
+    /// If no connection between the two endpoints was written before, a
+    /// connection is opened from `source` to `destination` first. The
+    /// message is split into segments with a payload of up to
+    /// [`CAPTURE_TCP_MAX_SEGMENT_LEN`] bytes.
+    pub fn write_tcp(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        header: &SomeipHeader,
+        payload: &[u8],
+    ) -> Result<(), CaptureError> {
+        ip_builder(source, destination)?;
+
+        self.msg_buf.clear();
+        write_msg(header, payload, &mut self.msg_buf)?;
+
+        if false == self.tcp_connections.contains_key(&(source, destination))
+            && false == self.tcp_connections.contains_key(&(destination, source))
+        {
+            self.open_tcp(timestamp, source, destination)?;
+        }
+
+        let msg_buf = core::mem::take(&mut self.msg_buf);
+        let mut result = Ok(());
+        for segment in msg_buf.chunks(CAPTURE_TCP_MAX_SEGMENT_LEN) {
+            let (seq, ack) = self.advance_tcp(source, destination, segment.len() as u32);
+            result = self.write_tcp_segment(
+                timestamp,
+                source,
+                destination,
+                seq,
+                Some(ack),
+                TcpFlags::Psh,
+                segment,
+            );
+            if result.is_err() {
+                break;
+            }
+        }
+        self.msg_buf = msg_buf;
+        result
+    }
+
+    /// Writes the closing of the TCP connection between the two endpoints
+    /// (initiated by `source`).
+    ///
+    /// Nothing is written if no connection between the endpoints is open.
+    pub fn close_tcp(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+    ) -> Result<(), CaptureError> {
+        if false == self.tcp_connections.contains_key(&(source, destination))
+            && false == self.tcp_connections.contains_key(&(destination, source))
+        {
+            return Ok(());
+        }
+
+        // FIN from the source, FIN from the destination & final ACK
+        let (seq, ack) = self.advance_tcp(source, destination, 1);
+        self.write_tcp_segment(
+            timestamp,
+            source,
+            destination,
+            seq,
+            Some(ack),
+            TcpFlags::Fin,
+            &[],
+        )?;
+        let (seq, ack) = self.advance_tcp(destination, source, 1);
+        self.write_tcp_segment(
+            timestamp,
+            destination,
+            source,
+            seq,
+            Some(ack),
+            TcpFlags::Fin,
+            &[],
+        )?;
+        let (seq, ack) = self.advance_tcp(source, destination, 0);
+        self.write_tcp_segment(
+            timestamp,
+            source,
+            destination,
+            seq,
+            Some(ack),
+            TcpFlags::None,
+            &[],
+        )?;
+
+        self.tcp_connections.remove(&(source, destination));
+        self.tcp_connections.remove(&(destination, source));
+        Ok(())
+    }
+
+    /// Writes the SYN handshake of a connection from the client to the
+    /// server & registers the connection.
+    fn open_tcp(
+        &mut self,
+        timestamp: Duration,
+        client: SocketAddr,
+        server: SocketAddr,
+    ) -> Result<(), CaptureError> {
+        self.tcp_connections.insert(
+            (client, server),
+            TcpConnection {
+                client_seq: 0,
+                server_seq: 0,
+            },
+        );
+        let (seq, _) = self.advance_tcp(client, server, 1);
+        self.write_tcp_segment(timestamp, client, server, seq, None, TcpFlags::Syn, &[])?;
+        let (seq, ack) = self.advance_tcp(server, client, 1);
+        self.write_tcp_segment(
+            timestamp,
+            server,
+            client,
+            seq,
+            Some(ack),
+            TcpFlags::Syn,
+            &[],
+        )?;
+        let (seq, ack) = self.advance_tcp(client, server, 0);
+        self.write_tcp_segment(
+            timestamp,
+            client,
+            server,
+            seq,
+            Some(ack),
+            TcpFlags::None,
+            &[],
+        )
+    }
+
+    /// Returns the sequence & acknowledgment number for data sent from
+    /// `source` to `destination` and advances the sequence number of the
+    /// source by `len`.
+    fn advance_tcp(&mut self, source: SocketAddr, destination: SocketAddr, len: u32) -> (u32, u32) {
+        if let Some(c) = self.tcp_connections.get_mut(&(source, destination)) {
+            let result = (c.client_seq, c.server_seq);
+            c.client_seq = c.client_seq.wrapping_add(len);
+            result
+        } else if let Some(c) = self.tcp_connections.get_mut(&(destination, source)) {
+            let result = (c.server_seq, c.client_seq);
+            c.server_seq = c.server_seq.wrapping_add(len);
+            result
+        } else {
+            (0, 0)
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_tcp_segment(
+        &mut self,
+        timestamp: Duration,
+        source: SocketAddr,
+        destination: SocketAddr,
+        seq: u32,
+        ack: Option<u32>,
+        flags: TcpFlags,
+        payload: &[u8],
+    ) -> Result<(), CaptureError> {
+        let mut builder = ip_builder(source, destination)?.tcp(
+            source.port(),
+            destination.port(),
+            seq,
+            TCP_WINDOW_SIZE,
+        );
+        builder = match flags {
+            TcpFlags::None => builder,
+            TcpFlags::Syn => builder.syn(),
+            TcpFlags::Psh => builder.psh(),
+            TcpFlags::Fin => builder.fin(),
+        };
+        if let Some(ack) = ack {
+            builder = builder.ack(ack);
+        }
+        self.packet_buf.clear();
+        builder
+            .write(&mut self.packet_buf, payload)
+            .map_err(|_| CaptureError::MessageTooBig {
+                len: payload.len(),
+                max_len: CAPTURE_TCP_MAX_SEGMENT_LEN,
+            })?;
+        self.writer.write_packet(timestamp, &self.packet_buf)
+    }
+}
+
+/// Flags set in a written TCP segment (in addition to ACK).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum TcpFlags {
+    None,
+    Syn,
+    Psh,
+    Fin,
+}
+
+/// Returns a packet builder with the Ethernet & IP headers for the given
+/// addresses.
+fn ip_builder(
+    source: SocketAddr,
+    destination: SocketAddr,
+) -> Result<PacketBuilderStep<IpHeaders>, CaptureError> {
+    let builder = PacketBuilder::ethernet2(mac(source.ip()), mac(destination.ip()));
+    match (source.ip(), destination.ip()) {
+        (IpAddr::V4(s), IpAddr::V4(d)) => Ok(builder.ipv4(s.octets(), d.octets(), TTL)),
+        (IpAddr::V6(s), IpAddr::V6(d)) => Ok(builder.ipv6(s.octets(), d.octets(), TTL)),
+        _ => Err(CaptureError::AddressFamilyMismatch {
+            source,
+            destination,
+        }),
+    }
+}
+
+/// Derives the MAC address from an IP address.
+///
+/// Multicast & broadcast addresses are mapped to the corresponding
+/// Ethernet multicast & broadcast addresses, unicast addresses to locally
+/// administered addresses containing the end of the IP address.
+fn mac(ip: IpAddr) -> [u8; 6] {
+    match ip {
+        IpAddr::V4(ip) => {
+            let o = ip.octets();
+            if ip.is_broadcast() {
+                [0xff; 6]
+            } else if ip.is_multicast() {
+                [0x01, 0x00, 0x5e, o[1] & 0x7f, o[2], o[3]]
+            } else {
+                [0x02, 0x00, o[0], o[1], o[2], o[3]]
+            }
+        }
+        IpAddr::V6(ip) => {
+            let o = ip.octets();
+            if ip.is_multicast() {
+                [0x33, 0x33, o[12], o[13], o[14], o[15]]
+            } else {
+                [0x02, o[11], o[12], o[13], o[14], o[15]]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use etherparse::{SlicedPacket, TransportSlice};
+    use std::vec;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    fn header(request_id: u32) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: None,
+        }
+    }
+
+    /// Decodes the written packets & returns the Ethernet/IP/TCP or UDP
+    /// layers as sliced packets.
+    fn packets(file: &[u8]) -> Vec<Vec<u8>> {
+        let mut reader = CaptureFileReader::new(file).unwrap();
+        let mut result = Vec::new();
+        while let Some(packet) = reader.next_packet().unwrap() {
+            assert_eq!(LinkType::ETHERNET, packet.link_type);
+            result.push(packet.data.to_vec());
+        }
+        result
+    }
+
+    #[test]
+    fn mac() {
+        use super::mac as f;
+        assert_eq!([0x02, 0, 192, 168, 0, 1], f("192.168.0.1".parse().unwrap()));
+        assert_eq!([0xff; 6], f("255.255.255.255".parse().unwrap()));
+        assert_eq!(
+            [0x01, 0x00, 0x5e, 0x60, 224, 245],
+            f("224.224.224.245".parse().unwrap())
+        );
+        assert_eq!([0x02, 0, 0, 0, 0, 1], f("::1".parse().unwrap()));
+        assert_eq!([0x33, 0x33, 0, 0, 0, 0xfb], f("ff02::fb".parse().unwrap()));
+    }
+
+    #[test]
+    fn accessors() {
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcap).unwrap();
+        assert_eq!(CaptureFormat::Pcap, writer.file_writer().format());
+        assert_eq!(LinkType::ETHERNET, writer.file_writer().link_type());
+        writer.flush().unwrap();
+        let _ = std::format!("{writer:?}");
+        assert_eq!(24, writer.into_inner().len());
+    }
+
+    #[test]
+    fn write_udp() {
+        for format in [CaptureFormat::Pcap, CaptureFormat::Pcapng] {
+            let mut writer = SomeipCaptureWriter::new(Vec::new(), format).unwrap();
+            let big_payload: Vec<u8> = (0..3000u32).map(|v| v as u8).collect();
+            writer
+                .write_udp(
+                    Duration::new(1, 0),
+                    addr("192.168.0.1:1"),
+                    addr("192.168.0.2:2"),
+                    &header(1),
+                    &[1, 2, 3],
+                )
+                .unwrap();
+            writer
+                .write_udp(
+                    Duration::new(2, 0),
+                    addr("[::1]:3"),
+                    addr("[ff02::fb]:4"),
+                    &header(2),
+                    &big_payload,
+                )
+                .unwrap();
+            let file = writer.into_inner();
+
+            // checksums are valid
+            let packets = packets(&file);
+            assert_eq!(4, packets.len());
+            for packet in &packets {
+                let sliced = SlicedPacket::from_ethernet(packet).unwrap();
+                let Some(TransportSlice::Udp(udp)) = sliced.transport else {
+                    panic!("expected udp");
+                };
+                let checksum = match sliced.net.unwrap() {
+                    etherparse::NetSlice::Ipv4(ip) => {
+                        assert_eq!(
+                            ip.header().header_checksum(),
+                            ip.header().to_header().calc_header_checksum()
+                        );
+                        udp.to_header()
+                            .calc_checksum_ipv4(&ip.header().to_header(), udp.payload())
+                            .unwrap()
+                    }
+                    etherparse::NetSlice::Ipv6(ip) => udp
+                        .to_header()
+                        .calc_checksum_ipv6(&ip.header().to_header(), udp.payload())
+                        .unwrap(),
+                    _ => panic!("expected ip"),
+                };
+                assert_eq!(checksum, udp.checksum());
+            }
+
+            // messages are reassembled by the reader
+            let mut reader = SomeipCaptureReader::new(&file[..]).unwrap();
+            let msg = reader.next().unwrap().unwrap();
+            assert_eq!(Duration::new(1, 0), msg.timestamp);
+            assert_eq!(addr("192.168.0.1:1"), msg.source);
+            assert_eq!(addr("192.168.0.2:2"), msg.destination);
+            assert_eq!(CaptureTransport::Udp, msg.transport);
+            assert_eq!(&[1, 2, 3], msg.msg().payload());
+
+            let msg = reader.next().unwrap().unwrap();
+            assert_eq!(addr("[::1]:3"), msg.source);
+            assert_eq!(addr("[ff02::fb]:4"), msg.destination);
+            assert_eq!(2, msg.msg().request_id());
+            assert_eq!(&big_payload[..], msg.msg().payload());
+            assert!(reader.next().is_none());
+        }
+    }
+
+    #[test]
+    fn write_sd() {
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcapng).unwrap();
+        let sd_header = sd::SdHeader::default();
+        writer
+            .write_sd(
+                Duration::ZERO,
+                addr("192.168.0.1:30490"),
+                addr("224.224.224.245:30490"),
+                12,
+                &sd_header,
+            )
+            .unwrap();
+        let file = writer.into_inner();
+        let msgs: Vec<_> = SomeipCaptureReader::new(&file[..]).unwrap().collect();
+        assert_eq!(1, msgs.len());
+        let msg = msgs[0].as_ref().unwrap();
+        assert!(msg.msg().is_someip_sd());
+        assert_eq!(12, msg.msg().request_id());
+        assert_eq!(
+            sd_header,
+            sd::SdHeader::read(&mut std::io::Cursor::new(msg.msg().payload())).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_tcp() {
+        let client = addr("192.168.0.1:40000");
+        let server = addr("192.168.0.2:30501");
+        let big_payload = vec![0xab; 4000];
+
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcap).unwrap();
+        writer
+            .write_tcp(Duration::new(1, 0), client, server, &header(1), &[1, 2])
+            .unwrap();
+        writer
+            .write_tcp(
+                Duration::new(2, 0),
+                server,
+                client,
+                &header(2),
+                &big_payload,
+            )
+            .unwrap();
+        writer
+            .write_tcp(Duration::new(3, 0), client, server, &header(3), &[])
+            .unwrap();
+        writer
+            .close_tcp(Duration::new(4, 0), client, server)
+            .unwrap();
+        // closing an unknown connection is ignored
+        writer
+            .close_tcp(Duration::new(4, 0), client, server)
+            .unwrap();
+        let file = writer.into_inner();
+
+        // (source port, syn, fin, psh, seq, ack, payload len)
+        let expected = [
+            (40000, true, false, false, 0, None, 0),
+            (30501, true, false, false, 0, Some(1), 0),
+            (40000, false, false, false, 1, Some(1), 0),
+            (40000, false, false, true, 1, Some(1), 18),
+            (30501, false, false, true, 1, Some(19), 1460),
+            (30501, false, false, true, 1461, Some(19), 1460),
+            (30501, false, false, true, 2921, Some(19), 1096),
+            (40000, false, false, true, 19, Some(4017), 16),
+            (40000, false, true, false, 35, Some(4017), 0),
+            (30501, false, true, false, 4017, Some(36), 0),
+            (40000, false, false, false, 36, Some(4018), 0),
+        ];
+        let packets = packets(&file);
+        assert_eq!(expected.len(), packets.len());
+        for (packet, expected) in packets.iter().zip(expected) {
+            let sliced = SlicedPacket::from_ethernet(packet).unwrap();
+            let Some(TransportSlice::Tcp(tcp)) = sliced.transport else {
+                panic!("expected tcp");
+            };
+            let Some(etherparse::NetSlice::Ipv4(ip)) = sliced.net else {
+                panic!("expected ipv4");
+            };
+            assert_eq!(
+                tcp.checksum(),
+                tcp.calc_checksum_ipv4(ip.header().source(), ip.header().destination())
+                    .unwrap()
+            );
+            assert_eq!(
+                expected,
+                (
+                    tcp.source_port(),
+                    tcp.syn(),
+                    tcp.fin(),
+                    tcp.psh(),
+                    tcp.sequence_number(),
+                    if tcp.ack() {
+                        Some(tcp.acknowledgment_number())
+                    } else {
+                        None
+                    },
+                    tcp.payload().len()
+                )
+            );
+        }
+
+        // messages are reassembled by the reader
+        let msgs: Vec<_> = SomeipCaptureReader::new(&file[..])
+            .unwrap()
+            .map(|m| m.unwrap())
+            .collect();
+        assert_eq!(3, msgs.len());
+        assert_eq!(client, msgs[0].source);
+        assert_eq!(CaptureTransport::Tcp, msgs[0].transport);
+        assert_eq!(&[1, 2], msgs[0].msg().payload());
+        assert_eq!(server, msgs[1].source);
+        assert_eq!(Duration::new(2, 0), msgs[1].timestamp);
+        assert_eq!(&big_payload[..], msgs[1].msg().payload());
+        assert_eq!(3, msgs[2].msg().request_id());
+    }
+
+    #[test]
+    fn errors() {
+        let mut writer = SomeipCaptureWriter::new(Vec::new(), CaptureFormat::Pcap).unwrap();
+        let v4 = addr("192.168.0.1:1");
+        let v6 = addr("[::1]:2");
+        assert!(matches!(
+            writer.write_udp(Duration::ZERO, v4, v6, &header(1), &[]),
+            Err(CaptureError::AddressFamilyMismatch { .. })
+        ));
+        assert!(matches!(
+            writer.write_tcp(Duration::ZERO, v6, v4, &header(1), &[]),
+            Err(CaptureError::AddressFamilyMismatch { .. })
+        ));
+
+        // udp message with a tp header that is too big for a datagram
+        let tp_header = SomeipHeader {
+            tp_header: Some(TpHeader::new(false)),
+            ..header(1)
+        };
+        assert!(matches!(
+            writer.write_udp(
+                Duration::ZERO,
+                v4,
+                v4,
+                &tp_header,
+                &vec![0; MAX_UDP_PAYLOAD_LEN]
+            ),
+            Err(CaptureError::MessageTooBig { .. })
+        ));
+
+        // nothing was written
+        assert_eq!(24, writer.into_inner().len());
+
+        // io error
+        let mut buf = [0u8; 30];
+        let mut writer = SomeipCaptureWriter::new(&mut buf[..], CaptureFormat::Pcap).unwrap();
+        assert!(matches!(
+            writer.write_udp(Duration::ZERO, v4, v4, &header(1), &[]),
+            Err(CaptureError::Io(_))
+        ));
+        assert!(matches!(
+            writer.write_tcp(Duration::ZERO, v4, v4, &header(1), &[]),
+            Err(CaptureError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use super::*;
+use crate::transport::TcpRecvBuf;
+use std::vec::Vec;
+
+/// Maximum number of out of order segments that are buffered per flow
+/// before the missing data is considered lost.
+const MAX_OUT_OF_ORDER_SEGMENTS: usize = 64;
+
+/// Reassembles the data of one direction of a captured TCP connection
+/// and splits it into SOMEIP messages.
+#[derive(Debug)]
+pub(crate) struct TcpFlow {
+    /// Sequence number of the next expected byte (`None` if no data or
+    /// SYN was seen yet).
+    next_seq: Option<u32>,
+    /// Segments received ahead of `next_seq` (sequence number & data).
+    out_of_order: Vec<(u32, Vec<u8>)>,
+    /// Reassembled in order data.
+    recv_buf: TcpRecvBuf,
+}
+
+impl TcpFlow {
+    pub(crate) fn new(max_message_len: usize) -> TcpFlow {
+        TcpFlow {
+            next_seq: None,
+            out_of_order: Vec::new(),
+            recv_buf: TcpRecvBuf::new(max_message_len),
+        }
+    }
+
+    /// Restarts the flow after a SYN with the given sequence number.
+    pub(crate) fn syn(&mut self, seq: u32) {
+        self.next_seq = Some(seq.wrapping_add(1));
+        self.out_of_order.clear();
+        self.recv_buf = TcpRecvBuf::new(self.recv_buf.max_message_len());
+    }
+
+    /// Adds the payload of a segment starting at the given sequence number.
+    pub(crate) fn receive(&mut self, seq: u32, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        let next_seq = *self.next_seq.get_or_insert(seq);
+        if (seq.wrapping_sub(next_seq) as i32) > 0 {
+            if self.out_of_order.len() >= MAX_OUT_OF_ORDER_SEGMENTS {
+                self.skip_gap();
+            }
+            self.out_of_order.push((seq, data.to_vec()));
+        } else {
+            self.append(seq, data);
+        }
+
+        // add buffered segments that are now in order
+        while let Some(index) = self
+            .out_of_order
+            .iter()
+            .position(|(seq, _)| (seq.wrapping_sub(self.next_seq.unwrap_or(*seq)) as i32) <= 0)
+        {
+            let (seq, data) = self.out_of_order.swap_remove(index);
+            self.append(seq, &data);
+        }
+    }
+
+    /// Decodes the next message from the reassembled data.
+    pub(crate) fn next(&mut self) -> Option<Result<SomeipMsgSlice<'_>, CaptureError>> {
+        match self.recv_buf.next()? {
+            Ok(range) => Some(Ok(self.recv_buf.get(range))),
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+
+    /// Appends the not yet received part of a segment starting at or
+    /// before `next_seq`.
+    fn append(&mut self, seq: u32, data: &[u8]) {
+        let next_seq = self.next_seq.unwrap_or(seq);
+        let skip = next_seq.wrapping_sub(seq) as usize;
+        if skip >= data.len() {
+            // retransmission of already received data
+            return;
+        }
+        let mut rest = &data[skip..];
+        self.next_seq = Some(next_seq.wrapping_add(rest.len() as u32));
+        while false == rest.is_empty() {
+            let target = self.recv_buf.read_buf();
+            let len = core::cmp::min(target.len(), rest.len());
+            target[..len].copy_from_slice(&rest[..len]);
+            self.recv_buf.advance(len);
+            rest = &rest[len..];
+        }
+    }
+
+    /// Gives up on the missing data & continues with the first buffered
+    /// out of order segment.
+    fn skip_gap(&mut self) {
+        let Some(next_seq) = self.next_seq else {
+            return;
+        };
+        if let Some(seq) = self
+            .out_of_order
+            .iter()
+            .map(|(seq, _)| *seq)
+            .min_by_key(|seq| seq.wrapping_sub(next_seq))
+        {
+            self.next_seq = Some(seq);
+            // the partially received message can not be completed anymore
+            self.recv_buf = TcpRecvBuf::new(self.recv_buf.max_message_len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    fn msg(request_id: u32, payload: &[u8]) -> Vec<u8> {
+        let mut result = SomeipHeader {
+            message_id: 0x1234_0001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + payload.len() as u32,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Request,
+            return_code: 0,
+            tp_header: None,
+        }
+        .base_to_bytes()
+        .to_vec();
+        result.extend_from_slice(payload);
+        result
+    }
+
+    fn request_ids(flow: &mut TcpFlow) -> Vec<u32> {
+        let mut result = Vec::new();
+        while let Some(msg) = flow.next() {
+            result.push(msg.unwrap().request_id());
+        }
+        result
+    }
+
+    #[test]
+    fn in_order() {
+        let mut data = msg(1, &[1, 2, 3]);
+        data.extend_from_slice(&msg(2, &[]));
+
+        let mut flow = TcpFlow::new(1024);
+        flow.syn(u32::MAX - 2);
+        flow.receive(u32::MAX - 1, &data[..10]);
+        assert_eq!(Vec::<u32>::new(), request_ids(&mut flow));
+        flow.receive((u32::MAX - 1).wrapping_add(10), &data[10..]);
+        assert_eq!(vec![1, 2], request_ids(&mut flow));
+        // retransmissions & empty segments are ignored
+        flow.receive(u32::MAX - 1, &data);
+        flow.receive(1234, &[]);
+        assert_eq!(Vec::<u32>::new(), request_ids(&mut flow));
+    }
+
+    #[test]
+    fn without_syn() {
+        let mut flow = TcpFlow::new(1024);
+        flow.receive(1000, &msg(1, &[]));
+        flow.receive(1016, &msg(2, &[]));
+        assert_eq!(vec![1, 2], request_ids(&mut flow));
+    }
+
+    #[test]
+    fn out_of_order_and_overlapping() {
+        let mut data = msg(1, &[1, 2, 3]);
+        data.extend_from_slice(&msg(2, &[4]));
+        data.extend_from_slice(&msg(3, &[]));
+
+        let mut flow = TcpFlow::new(1024);
+        flow.syn(99);
+        flow.receive(130, &data[30..]);
+        flow.receive(110, &data[10..25]);
+        assert_eq!(Vec::<u32>::new(), request_ids(&mut flow));
+        // overlaps with both buffered segments
+        flow.receive(100, &data[..32]);
+        assert_eq!(vec![1, 2, 3], request_ids(&mut flow));
+    }
+
+    #[test]
+    fn lost_segment() {
+        let mut flow = TcpFlow::new(1024);
+        flow.syn(0);
+        // first message is missing its last byte
+        flow.receive(1, &msg(1, &[1, 2])[..17]);
+        let mut seq = 1 + 18;
+        for i in 0..MAX_OUT_OF_ORDER_SEGMENTS as u32 + 1 {
+            let data = msg(i + 2, &[]);
+            flow.receive(seq, &data);
+            seq += data.len() as u32;
+        }
+        let expected: Vec<u32> = (2..MAX_OUT_OF_ORDER_SEGMENTS as u32 + 3).collect();
+        assert_eq!(expected, request_ids(&mut flow));
+    }
+
+    #[test]
+    fn errors() {
+        let mut flow = TcpFlow::new(20);
+        flow.receive(0, &msg(1, &[0; 8]));
+        assert!(matches!(
+            flow.next(),
+            Some(Err(CaptureError::MessageTooBig {
+                len: 24,
+                max_len: 20
+            }))
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+/// SOME/IP client id (upper 16 bits of the [`crate::RequestId`]).
+///
+/// The client id can be split into a configurable prefix (e.g. to make
+/// client ids unique per ECU) and the actual id of the client within that
+/// prefix (see [`ClientId::with_prefix`]).
+///
+/// `Display` writes the id in hex (e.g. `0x1234`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ClientId(pub u16);
+
+impl ClientId {
+    /// Client id used by SOME/IP service discovery messages.
+    pub const SD: ClientId = ClientId(0);
+
+    /// Returns the raw `u16` value.
+    #[inline]
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+
+    /// Creates a client id from a prefix occupying the upper `prefix_bits`
+    /// bits and the id in the remaining lower bits.
+    ///
+    /// Bits of `prefix` & `id` that don't fit into their part are ignored.
+    /// `prefix_bits` values above 16 are treated as 16.
+    ///
+    /// ```
+    /// use someip_parse::ClientId;
+    ///
+    /// let client_id = ClientId::with_prefix(0x12, 8, 0x34);
+    /// assert_eq!(ClientId(0x1234), client_id);
+    /// assert_eq!(0x12, client_id.prefix(8));
+    /// assert_eq!(0x34, client_id.id_without_prefix(8));
+    /// ```
+    #[inline]
+    pub const fn with_prefix(prefix: u16, prefix_bits: u8, id: u16) -> ClientId {
+        let mask = ClientId::prefix_mask(prefix_bits);
+        let shift = ClientId::prefix_shift(prefix_bits);
+        ClientId(((((prefix as u32) << shift) as u16) & mask) | (id & !mask))
+    }
+
+    /// Returns the prefix stored in the upper `prefix_bits` bits.
+    #[inline]
+    pub const fn prefix(self, prefix_bits: u8) -> u16 {
+        ((self.0 & ClientId::prefix_mask(prefix_bits)) as u32
+            >> ClientId::prefix_shift(prefix_bits)) as u16
+    }
+
+    /// Returns the id without the prefix stored in the upper `prefix_bits` bits.
+    #[inline]
+    pub const fn id_without_prefix(self, prefix_bits: u8) -> u16 {
+        self.0 & !ClientId::prefix_mask(prefix_bits)
+    }
+
+    #[inline]
+    const fn prefix_shift(prefix_bits: u8) -> u32 {
+        if prefix_bits >= 16 {
+            0
+        } else {
+            16 - prefix_bits as u32
+        }
+    }
+
+    #[inline]
+    const fn prefix_mask(prefix_bits: u8) -> u16 {
+        ((0xFFFF_u32 << ClientId::prefix_shift(prefix_bits)) & 0xFFFF) as u16
+    }
+}
+
+impl core::fmt::Display for ClientId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:04x}", self.0)
+    }
+}
+
+impl From<u16> for ClientId {
+    #[inline]
+    fn from(value: u16) -> Self {
+        ClientId(value)
+    }
+}
+
+impl From<ClientId> for u16 {
+    #[inline]
+    fn from(value: ClientId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    #[test]
+    fn prefix_edge_cases() {
+        // no prefix
+        assert_eq!(ClientId(0x1234), ClientId::with_prefix(0xff, 0, 0x1234));
+        assert_eq!(0, ClientId(0x1234).prefix(0));
+        assert_eq!(0x1234, ClientId(0x1234).id_without_prefix(0));
+
+        // prefix only
+        assert_eq!(ClientId(0x1234), ClientId::with_prefix(0x1234, 16, 0xff));
+        assert_eq!(0x1234, ClientId(0x1234).prefix(16));
+        assert_eq!(0, ClientId(0x1234).id_without_prefix(16));
+        assert_eq!(0x1234, ClientId(0x1234).prefix(17));
+
+        // values too large for their part
+        assert_eq!(ClientId(0x2fff), ClientId::with_prefix(0x12, 4, 0xffff));
+    }
+
+    proptest! {
+        #[test]
+        fn prefix(value in any::<u16>(), prefix_bits in 0u8..=16) {
+            let id = ClientId(value);
+            prop_assert_eq!(
+                id,
+                ClientId::with_prefix(id.prefix(prefix_bits), prefix_bits, id.id_without_prefix(prefix_bits))
+            );
+        }
+    }
+
+    #[test]
+    fn conversions_display() {
+        let id = ClientId::from(0x1234);
+        assert_eq!(ClientId(0x1234), id);
+        assert_eq!(0x1234u16, u16::from(id));
+        assert_eq!("0x1234", format!("{}", id));
+        assert_eq!("0x0000", format!("{}", ClientId::SD));
+    }
+}
@@ -0,0 +1,85 @@
+//! Helpers for the multi-line ("dissector") output of the `Display`
+//! implementations (selected via the alternate flag `{:#}`).
+use core::fmt;
+
+/// Number of spaces per nesting level.
+const INDENT_LEN: usize = 4;
+
+/// Number of bytes per line of a hex dump.
+const HEX_DUMP_LINE_LEN: usize = 16;
+
+/// Starts a new line with the given nesting level & writes the arguments.
+///
+/// Lines are started (instead of terminated) with a newline so the output
+/// of a `{:#}` formatted value does not end with an empty line.
+pub(crate) fn line(
+    f: &mut fmt::Formatter<'_>,
+    level: usize,
+    args: fmt::Arguments<'_>,
+) -> fmt::Result {
+    write!(f, "\n{:width$}{}", "", args, width = level * INDENT_LEN)
+}
+
+/// Writes a hex dump of the data with offsets & ASCII column (one line per
+/// 16 bytes).
+pub(crate) fn hex_dump(f: &mut fmt::Formatter<'_>, level: usize, data: &[u8]) -> fmt::Result {
+    for (index, chunk) in data.chunks(HEX_DUMP_LINE_LEN).enumerate() {
+        write!(
+            f,
+            "\n{:width$}{:04x} ",
+            "",
+            index * HEX_DUMP_LINE_LEN,
+            width = level * INDENT_LEN
+        )?;
+        for i in 0..HEX_DUMP_LINE_LEN {
+            if i == HEX_DUMP_LINE_LEN / 2 {
+                write!(f, " ")?;
+            }
+            match chunk.get(i) {
+                Some(byte) => write!(f, " {byte:02x}")?,
+                None => write!(f, "   ")?,
+            }
+        }
+        write!(f, "  ")?;
+        for byte in chunk {
+            let c = if byte.is_ascii_graphic() || *byte == b' ' {
+                char::from(*byte)
+            } else {
+                '.'
+            };
+            write!(f, "{c}")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    struct Dump<'a>(usize, &'a [u8]);
+
+    impl fmt::Display for Dump<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "title")?;
+            line(f, self.0, format_args!("line {}", 1))?;
+            hex_dump(f, self.0 + 1, self.1)
+        }
+    }
+
+    #[test]
+    fn line_and_hex_dump() {
+        assert_eq!("title\nline 1", format!("{}", Dump(0, &[])));
+        assert_eq!(
+            "title\n    line 1\n        0000  41 42 00 ff                                       AB..",
+            format!("{}", Dump(1, b"AB\x00\xff"))
+        );
+        let data: [u8; 18] = core::array::from_fn(|i| b'a' + i as u8);
+        assert_eq!(
+            "title\nline 1\n    0000  61 62 63 64 65 66 67 68  69 6a 6b 6c 6d 6e 6f 70  abcdefghijklmnop\
+             \n    0010  71 72                                             qr",
+            format!("{}", Dump(0, &data))
+        );
+    }
+}
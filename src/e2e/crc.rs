@@ -0,0 +1,185 @@
+//! CRC routines used by the E2E profiles (as defined in the AUTOSAR
+//! "Specification of CRC Routines").
+//!
+//! All functions take the current CRC register value & return the updated
+//! register value. This allows the CRC to be calculated over multiple
+//! non-contiguous parts of the data. The `*_START` constants contain the
+//! initial register value and the `*_XOR` constants the final xor value.
+
+/// Initial register value of the CRC8H2F.
+pub(crate) const CRC8H2F_START: u8 = 0xff;
+
+/// Final xor value of the CRC8H2F.
+pub(crate) const CRC8H2F_XOR: u8 = 0xff;
+
+/// Initial register value of the CRC16 (CCITT-FALSE).
+pub(crate) const CRC16_START: u16 = 0xffff;
+
+/// Initial register value of the CRC32P4.
+pub(crate) const CRC32P4_START: u32 = 0xffff_ffff;
+
+/// Final xor value of the CRC32P4.
+pub(crate) const CRC32P4_XOR: u32 = 0xffff_ffff;
+
+/// Initial register value of the CRC64 (ECMA-182).
+pub(crate) const CRC64_START: u64 = 0xffff_ffff_ffff_ffff;
+
+/// Final xor value of the CRC64 (ECMA-182).
+pub(crate) const CRC64_XOR: u64 = 0xffff_ffff_ffff_ffff;
+
+const CRC8H2F_TABLE: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if 0 != crc & 0x80 {
+                (crc << 1) ^ 0x2f
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+const CRC16_TABLE: [u16; 256] = {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if 0 != crc & 0x8000 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+const CRC32P4_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            // reflected polynomial 0xF4ACFB13
+            crc = if 0 != crc & 1 {
+                (crc >> 1) ^ 0xc8df_352f
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+const CRC64_TABLE: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            // reflected polynomial 0x42F0E1EBA9EA3693
+            crc = if 0 != crc & 1 {
+                (crc >> 1) ^ 0xc96c_5795_d787_0f42
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Updates a CRC8H2F register (polynomial 0x2F, not reflected).
+pub(crate) fn crc8h2f(mut crc: u8, data: &[u8]) -> u8 {
+    for b in data {
+        crc = CRC8H2F_TABLE[usize::from(crc ^ b)];
+    }
+    crc
+}
+
+/// Updates a CRC16 register (CCITT-FALSE, polynomial 0x1021, not reflected).
+pub(crate) fn crc16(mut crc: u16, data: &[u8]) -> u16 {
+    for b in data {
+        crc = (crc << 8) ^ CRC16_TABLE[usize::from((crc >> 8) as u8 ^ b)];
+    }
+    crc
+}
+
+/// Updates a CRC32P4 register (polynomial 0xF4ACFB13, reflected).
+pub(crate) fn crc32p4(mut crc: u32, data: &[u8]) -> u32 {
+    for b in data {
+        crc = (crc >> 8) ^ CRC32P4_TABLE[usize::from(crc as u8 ^ b)];
+    }
+    crc
+}
+
+/// Updates a CRC64 register (ECMA-182, polynomial 0x42F0E1EBA9EA3693, reflected).
+pub(crate) fn crc64(mut crc: u64, data: &[u8]) -> u64 {
+    for b in data {
+        crc = (crc >> 8) ^ CRC64_TABLE[usize::from(crc as u8 ^ b)];
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check values from the AUTOSAR "Specification of CRC Routines".
+    const CHECK_DATA: &[u8] = b"123456789";
+
+    #[test]
+    fn check_values() {
+        assert_eq!(0xdf, crc8h2f(CRC8H2F_START, CHECK_DATA) ^ CRC8H2F_XOR);
+        assert_eq!(0x29b1, crc16(CRC16_START, CHECK_DATA));
+        assert_eq!(
+            0x1697_d06a,
+            crc32p4(CRC32P4_START, CHECK_DATA) ^ CRC32P4_XOR
+        );
+        assert_eq!(
+            0x995d_c9bb_df19_39fa,
+            crc64(CRC64_START, CHECK_DATA) ^ CRC64_XOR
+        );
+    }
+
+    #[test]
+    fn split_data() {
+        let (a, b) = CHECK_DATA.split_at(4);
+        assert_eq!(
+            crc8h2f(CRC8H2F_START, CHECK_DATA),
+            crc8h2f(crc8h2f(CRC8H2F_START, a), b)
+        );
+        assert_eq!(
+            crc16(CRC16_START, CHECK_DATA),
+            crc16(crc16(CRC16_START, a), b)
+        );
+        assert_eq!(
+            crc32p4(CRC32P4_START, CHECK_DATA),
+            crc32p4(crc32p4(CRC32P4_START, a), b)
+        );
+        assert_eq!(
+            crc64(CRC64_START, CHECK_DATA),
+            crc64(crc64(CRC64_START, a), b)
+        );
+    }
+}
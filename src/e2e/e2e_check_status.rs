@@ -0,0 +1,50 @@
+/// Result of checking data protected with an E2E profile.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum E2eCheckStatus {
+    /// The data is valid and the counter was incremented by exactly one
+    /// since the last valid data.
+    Ok,
+    /// The data is valid, but some data was lost (the counter was
+    /// incremented by more then one but not more then the configured
+    /// maximum delta).
+    OkSomeLost,
+    /// The data is valid, but the counter is the same as in the last valid
+    /// data (the data was repeated).
+    Repeated,
+    /// The data is valid, but the counter was incremented by more then the
+    /// configured maximum delta.
+    WrongSequence,
+    /// The data is invalid (wrong CRC, data id or length).
+    Error,
+}
+
+impl E2eCheckStatus {
+    /// Returns true if the status is [`E2eCheckStatus::Ok`] or
+    /// [`E2eCheckStatus::OkSomeLost`] (data can be used).
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        matches!(self, E2eCheckStatus::Ok | E2eCheckStatus::OkSomeLost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::E2eCheckStatus::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let status = Ok;
+        let _ = format!("{:?}", status);
+        assert_eq!(status, status.clone());
+    }
+
+    #[test]
+    fn is_ok() {
+        assert!(Ok.is_ok());
+        assert!(OkSomeLost.is_ok());
+        assert_eq!(false, Repeated.is_ok());
+        assert_eq!(false, WrongSequence.is_ok());
+        assert_eq!(false, Error.is_ok());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::err::E2eError;
+
+mod crc;
+use crc::*;
+
+mod e2e_check_status;
+pub use e2e_check_status::*;
+
+mod p04;
+pub use p04::*;
+
+mod p05;
+pub use p05::*;
+
+mod p06;
+pub use p06::*;
+
+mod p07;
+pub use p07::*;
+
+mod p22;
+pub use p22::*;
+
+/// Checks that the data length is in the allowed range.
+fn check_len(len: usize, min_len: usize, max_len: usize) -> Result<(), E2eError> {
+    if len < min_len || len > max_len {
+        Err(E2eError::InvalidDataLen {
+            len,
+            min_len,
+            max_len,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Determines the check status based on the delta between the received &
+/// the last valid counter value. `counter_mask` is the maximum value of
+/// the counter (e.g. `0xffff` for a 16 bit counter).
+fn check_counter(received: u32, last: u32, max_delta: u32, counter_mask: u32) -> E2eCheckStatus {
+    let delta = received.wrapping_sub(last) & counter_mask;
+    if 0 == delta {
+        E2eCheckStatus::Repeated
+    } else if 1 == delta {
+        E2eCheckStatus::Ok
+    } else if delta <= max_delta {
+        E2eCheckStatus::OkSomeLost
+    } else {
+        E2eCheckStatus::WrongSequence
+    }
+}
+
+/// Updates the CRC register with all data except the CRC bytes located at
+/// `crc_offset..crc_offset + crc_len`.
+fn crc_without<T>(
+    start: T,
+    data: &[u8],
+    crc_offset: usize,
+    crc_len: usize,
+    update: fn(T, &[u8]) -> T,
+) -> T {
+    let crc = update(start, &data[..crc_offset]);
+    update(crc, &data[crc_offset + crc_len..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_len() {
+        assert_eq!(Ok(()), super::check_len(2, 2, 3));
+        assert_eq!(Ok(()), super::check_len(3, 2, 3));
+        assert_eq!(
+            Err(E2eError::InvalidDataLen {
+                len: 1,
+                min_len: 2,
+                max_len: 3
+            }),
+            super::check_len(1, 2, 3)
+        );
+        assert_eq!(
+            Err(E2eError::InvalidDataLen {
+                len: 4,
+                min_len: 2,
+                max_len: 3
+            }),
+            super::check_len(4, 2, 3)
+        );
+    }
+
+    #[test]
+    fn check_counter() {
+        use E2eCheckStatus::*;
+        assert_eq!(Repeated, super::check_counter(5, 5, 2, 0xff));
+        assert_eq!(Ok, super::check_counter(6, 5, 2, 0xff));
+        assert_eq!(OkSomeLost, super::check_counter(7, 5, 2, 0xff));
+        assert_eq!(WrongSequence, super::check_counter(8, 5, 2, 0xff));
+        assert_eq!(WrongSequence, super::check_counter(4, 5, 2, 0xff));
+        // wrap around
+        assert_eq!(Ok, super::check_counter(0, 0xff, 2, 0xff));
+        assert_eq!(OkSomeLost, super::check_counter(1, 0xff, 2, 0xff));
+        assert_eq!(Ok, super::check_counter(0, 0xf, 2, 0xf));
+        assert_eq!(Ok, super::check_counter(0, u32::MAX, 2, u32::MAX));
+        assert_eq!(OkSomeLost, super::check_counter(1, u32::MAX, 2, u32::MAX));
+    }
+}
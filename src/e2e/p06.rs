@@ -0,0 +1,288 @@
+use super::*;
+
+/// Configuration of the AUTOSAR E2E profile 6.
+///
+/// Profile 6 protects data with a 5 byte header (big endian) located at
+/// `offset` containing:
+///
+/// | Bytes  | Field                                              |
+/// |--------|----------------------------------------------------|
+/// | 0..2   | CRC (CRC16 over all other bytes & the data id)     |
+/// | 2..4   | Length (length of the protected data)              |
+/// | 4      | Counter                                            |
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct P06Config {
+    /// Data id identifying the protected data (not transmitted, only
+    /// included in the CRC).
+    pub data_id: u16,
+    /// Offset of the E2E header in bytes.
+    pub offset: usize,
+    /// Minimum length of the protected data (including the header) in bytes.
+    pub min_data_len: usize,
+    /// Maximum length of the protected data (including the header) in bytes.
+    pub max_data_len: usize,
+    /// Maximum allowed difference between the counter values of two
+    /// consecutively received data.
+    pub max_delta_counter: u8,
+}
+
+impl P06Config {
+    /// Length of the E2E profile 6 header.
+    pub const HEADER_LEN: usize = 5;
+
+    /// Checks the data length & returns an error if it is not allowed.
+    fn check_len(&self, len: usize) -> Result<(), E2eError> {
+        check_len(
+            len,
+            self.min_data_len.max(self.offset + Self::HEADER_LEN),
+            self.max_data_len.min(usize::from(u16::MAX)),
+        )
+    }
+
+    /// Calculates the CRC of the given data.
+    fn crc(&self, data: &[u8]) -> u16 {
+        let crc = crc_without(CRC16_START, data, self.offset, 2, crc16);
+        crc16(crc, &self.data_id.to_be_bytes())
+    }
+}
+
+/// Protects data using the AUTOSAR E2E profile 6.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::e2e::*;
+///
+/// let config = P06Config {
+///     data_id: 0x1234,
+///     offset: 0,
+///     min_data_len: 8,
+///     max_data_len: 64,
+///     max_delta_counter: 1,
+/// };
+///
+/// // sender
+/// let mut protector = P06Protector::new(config.clone());
+/// let mut data = [0u8; 8];
+/// protector.protect(&mut data).unwrap();
+///
+/// // receiver
+/// let mut checker = P06Checker::new(config);
+/// assert_eq!(E2eCheckStatus::Ok, checker.check(&data));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct P06Protector {
+    config: P06Config,
+    counter: u8,
+}
+
+impl P06Protector {
+    /// Creates a protector starting with the counter value 0.
+    pub fn new(config: P06Config) -> P06Protector {
+        P06Protector { config, counter: 0 }
+    }
+
+    /// Configuration used to protect the data.
+    #[inline]
+    pub fn config(&self) -> &P06Config {
+        &self.config
+    }
+
+    /// Counter value used for the next protected data.
+    #[inline]
+    pub fn counter(&self) -> u8 {
+        self.counter
+    }
+
+    /// Writes the E2E header into the data & increments the counter.
+    pub fn protect(&mut self, data: &mut [u8]) -> Result<(), E2eError> {
+        self.config.check_len(data.len())?;
+        let offset = self.config.offset;
+        // the length is checked to fit into an u16 by check_len
+        let len = data.len() as u16;
+        data[offset + 2..offset + 4].copy_from_slice(&len.to_be_bytes());
+        data[offset + 4] = self.counter;
+        let crc = self.config.crc(data);
+        data[offset..offset + 2].copy_from_slice(&crc.to_be_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+}
+
+/// Checks data protected using the AUTOSAR E2E profile 6.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct P06Checker {
+    config: P06Config,
+    last_counter: u8,
+}
+
+impl P06Checker {
+    /// Creates a checker expecting the counter value 0 next.
+    pub fn new(config: P06Config) -> P06Checker {
+        P06Checker {
+            config,
+            last_counter: u8::MAX,
+        }
+    }
+
+    /// Configuration used to check the data.
+    #[inline]
+    pub fn config(&self) -> &P06Config {
+        &self.config
+    }
+
+    /// Counter value of the last valid data.
+    #[inline]
+    pub fn last_counter(&self) -> u8 {
+        self.last_counter
+    }
+
+    /// Checks the data & updates the last counter value if the data is
+    /// valid.
+    pub fn check(&mut self, data: &[u8]) -> E2eCheckStatus {
+        if self.config.check_len(data.len()).is_err() {
+            return E2eCheckStatus::Error;
+        }
+        let offset = self.config.offset;
+        let crc = u16::from_be_bytes([data[offset], data[offset + 1]]);
+        let len = u16::from_be_bytes([data[offset + 2], data[offset + 3]]);
+        let counter = data[offset + 4];
+        if crc != self.config.crc(data) || usize::from(len) != data.len() {
+            return E2eCheckStatus::Error;
+        }
+        let status = check_counter(
+            counter.into(),
+            self.last_counter.into(),
+            self.config.max_delta_counter.into(),
+            u8::MAX.into(),
+        );
+        self.last_counter = counter;
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use E2eCheckStatus::*;
+
+    fn config(offset: usize) -> P06Config {
+        P06Config {
+            data_id: 0x1234,
+            offset,
+            min_data_len: 8,
+            max_data_len: 32,
+            max_delta_counter: 2,
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let protector = P06Protector::new(config(0));
+        let _ = format!("{:?}", protector);
+        assert_eq!(protector, protector.clone());
+        assert_eq!(&config(0), protector.config());
+        let checker = P06Checker::new(config(0));
+        let _ = format!("{:?}", checker);
+        assert_eq!(checker, checker.clone());
+        assert_eq!(&config(0), checker.config());
+    }
+
+    #[test]
+    fn spec_example() {
+        // example from the AUTOSAR E2E protocol specification
+        let mut protector = P06Protector::new(config(0));
+        let mut data = [0u8; 8];
+        protector.protect(&mut data).unwrap();
+        assert_eq!([0xb1, 0x55, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00], data);
+        assert_eq!(1, protector.counter());
+    }
+
+    #[test]
+    fn protect_check() {
+        for offset in [0, 4] {
+            let mut protector = P06Protector::new(config(offset));
+            let mut checker = P06Checker::new(config(offset));
+            let mut data = [0x5au8; 12];
+
+            // ok
+            protector.protect(&mut data).unwrap();
+            assert_eq!(Ok, checker.check(&data));
+            assert_eq!(0, checker.last_counter());
+
+            // repeated
+            assert_eq!(Repeated, checker.check(&data));
+
+            // some lost
+            protector.protect(&mut data).unwrap();
+            protector.protect(&mut data).unwrap();
+            assert_eq!(OkSomeLost, checker.check(&data));
+
+            // wrong sequence
+            for _ in 0..3 {
+                protector.protect(&mut data).unwrap();
+            }
+            assert_eq!(WrongSequence, checker.check(&data));
+            assert_eq!(5, checker.last_counter());
+
+            // corrupted data
+            {
+                let mut data = data;
+                data[(offset + 5) % 12] ^= 1;
+                assert_eq!(Error, checker.check(&data));
+            }
+            // wrong data id
+            {
+                let mut checker = P06Checker::new(P06Config {
+                    data_id: 0x4321,
+                    ..config(offset)
+                });
+                assert_eq!(Error, checker.check(&data));
+            }
+            // wrong length field (with a valid crc)
+            {
+                let mut data = data;
+                data[offset + 3] = 13;
+                let crc = checker.config().crc(&data);
+                data[offset..offset + 2].copy_from_slice(&crc.to_be_bytes());
+                assert_eq!(Error, checker.check(&data));
+            }
+            // failed checks do not change the counter
+            assert_eq!(5, checker.last_counter());
+        }
+    }
+
+    #[test]
+    fn counter_wrap() {
+        let mut protector = P06Protector::new(config(0));
+        protector.counter = u8::MAX;
+        let mut checker = P06Checker::new(config(0));
+        checker.last_counter = u8::MAX - 1;
+        let mut data = [0u8; 8];
+        protector.protect(&mut data).unwrap();
+        assert_eq!(Ok, checker.check(&data));
+        assert_eq!(0, protector.counter());
+        protector.protect(&mut data).unwrap();
+        assert_eq!(Ok, checker.check(&data));
+    }
+
+    #[test]
+    fn invalid_len() {
+        let mut protector = P06Protector::new(config(4));
+        let mut checker = P06Checker::new(config(4));
+        for len in [7, 8, 33] {
+            let mut data = [0u8; 33];
+            assert_eq!(
+                Err(E2eError::InvalidDataLen {
+                    len,
+                    min_len: 9,
+                    max_len: 32
+                }),
+                protector.protect(&mut data[..len])
+            );
+            assert_eq!(Error, checker.check(&data[..len]));
+        }
+        assert_eq!(0, protector.counter());
+    }
+}
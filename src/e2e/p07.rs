@@ -0,0 +1,307 @@
+use super::*;
+
+/// Configuration of the AUTOSAR E2E profile 7.
+///
+/// Profile 7 protects (big) data with a 20 byte header (big endian)
+/// located at `offset` containing:
+///
+/// | Bytes  | Field                                  |
+/// |--------|----------------------------------------|
+/// | 0..8   | CRC (CRC64 over all other bytes)       |
+/// | 8..12  | Length (length of the protected data)  |
+/// | 12..16 | Counter                                |
+/// | 16..20 | Data ID                                |
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct P07Config {
+    /// Data id identifying the protected data.
+    pub data_id: u32,
+    /// Offset of the E2E header in bytes.
+    pub offset: usize,
+    /// Minimum length of the protected data (including the header) in bytes.
+    pub min_data_len: usize,
+    /// Maximum length of the protected data (including the header) in bytes.
+    pub max_data_len: usize,
+    /// Maximum allowed difference between the counter values of two
+    /// consecutively received data.
+    pub max_delta_counter: u32,
+}
+
+impl P07Config {
+    /// Length of the E2E profile 7 header.
+    pub const HEADER_LEN: usize = 20;
+
+    /// Checks the data length & returns an error if it is not allowed.
+    fn check_len(&self, len: usize) -> Result<(), E2eError> {
+        check_len(
+            len,
+            self.min_data_len.max(self.offset + Self::HEADER_LEN),
+            self.max_data_len
+                .min(usize::try_from(u32::MAX).unwrap_or(usize::MAX)),
+        )
+    }
+
+    /// Calculates the CRC of the given data.
+    fn crc(&self, data: &[u8]) -> u64 {
+        crc_without(CRC64_START, data, self.offset, 8, crc64) ^ CRC64_XOR
+    }
+}
+
+/// Protects data using the AUTOSAR E2E profile 7.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::e2e::*;
+///
+/// let config = P07Config {
+///     data_id: 0x0a0b_0c0d,
+///     offset: 0,
+///     min_data_len: 20,
+///     max_data_len: 4096,
+///     max_delta_counter: 1,
+/// };
+///
+/// // sender
+/// let mut protector = P07Protector::new(config.clone());
+/// let mut data = [0u8; 24];
+/// protector.protect(&mut data).unwrap();
+///
+/// // receiver
+/// let mut checker = P07Checker::new(config);
+/// assert_eq!(E2eCheckStatus::Ok, checker.check(&data));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct P07Protector {
+    config: P07Config,
+    counter: u32,
+}
+
+impl P07Protector {
+    /// Creates a protector starting with the counter value 0.
+    pub fn new(config: P07Config) -> P07Protector {
+        P07Protector { config, counter: 0 }
+    }
+
+    /// Configuration used to protect the data.
+    #[inline]
+    pub fn config(&self) -> &P07Config {
+        &self.config
+    }
+
+    /// Counter value used for the next protected data.
+    #[inline]
+    pub fn counter(&self) -> u32 {
+        self.counter
+    }
+
+    /// Writes the E2E header into the data & increments the counter.
+    pub fn protect(&mut self, data: &mut [u8]) -> Result<(), E2eError> {
+        self.config.check_len(data.len())?;
+        let offset = self.config.offset;
+        // the length is checked to fit into an u32 by check_len
+        let len = data.len() as u32;
+        data[offset + 8..offset + 12].copy_from_slice(&len.to_be_bytes());
+        data[offset + 12..offset + 16].copy_from_slice(&self.counter.to_be_bytes());
+        data[offset + 16..offset + 20].copy_from_slice(&self.config.data_id.to_be_bytes());
+        let crc = self.config.crc(data);
+        data[offset..offset + 8].copy_from_slice(&crc.to_be_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+}
+
+/// Checks data protected using the AUTOSAR E2E profile 7.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct P07Checker {
+    config: P07Config,
+    last_counter: u32,
+}
+
+impl P07Checker {
+    /// Creates a checker expecting the counter value 0 next.
+    pub fn new(config: P07Config) -> P07Checker {
+        P07Checker {
+            config,
+            last_counter: u32::MAX,
+        }
+    }
+
+    /// Configuration used to check the data.
+    #[inline]
+    pub fn config(&self) -> &P07Config {
+        &self.config
+    }
+
+    /// Counter value of the last valid data.
+    #[inline]
+    pub fn last_counter(&self) -> u32 {
+        self.last_counter
+    }
+
+    /// Checks the data & updates the last counter value if the data is
+    /// valid.
+    pub fn check(&mut self, data: &[u8]) -> E2eCheckStatus {
+        if self.config.check_len(data.len()).is_err() {
+            return E2eCheckStatus::Error;
+        }
+        let offset = self.config.offset;
+        let read_u32 = |start: usize| {
+            u32::from_be_bytes([
+                data[start],
+                data[start + 1],
+                data[start + 2],
+                data[start + 3],
+            ])
+        };
+        let crc = (u64::from(read_u32(offset)) << 32) | u64::from(read_u32(offset + 4));
+        let len = read_u32(offset + 8);
+        let counter = read_u32(offset + 12);
+        let data_id = read_u32(offset + 16);
+        if crc != self.config.crc(data)
+            || data_id != self.config.data_id
+            || usize::try_from(len).ok() != Some(data.len())
+        {
+            return E2eCheckStatus::Error;
+        }
+        let status = check_counter(
+            counter,
+            self.last_counter,
+            self.config.max_delta_counter,
+            u32::MAX,
+        );
+        self.last_counter = counter;
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use E2eCheckStatus::*;
+
+    fn config(offset: usize) -> P07Config {
+        P07Config {
+            data_id: 0x0a0b_0c0d,
+            offset,
+            min_data_len: 24,
+            max_data_len: 64,
+            max_delta_counter: 2,
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let protector = P07Protector::new(config(0));
+        let _ = format!("{:?}", protector);
+        assert_eq!(protector, protector.clone());
+        assert_eq!(&config(0), protector.config());
+        let checker = P07Checker::new(config(0));
+        let _ = format!("{:?}", checker);
+        assert_eq!(checker, checker.clone());
+        assert_eq!(&config(0), checker.config());
+    }
+
+    #[test]
+    fn spec_example() {
+        // example from the AUTOSAR E2E protocol specification
+        let mut protector = P07Protector::new(config(0));
+        let mut data = [0u8; 24];
+        protector.protect(&mut data).unwrap();
+        assert_eq!(
+            [
+                0x1f, 0xb2, 0xe7, 0x37, 0xfc, 0xed, 0xbc, 0xd9, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00,
+                0x00, 0x00, 0x0a, 0x0b, 0x0c, 0x0d, 0x00, 0x00, 0x00, 0x00
+            ],
+            data
+        );
+        assert_eq!(1, protector.counter());
+    }
+
+    #[test]
+    fn protect_check() {
+        for offset in [0, 4] {
+            let mut protector = P07Protector::new(config(offset));
+            let mut checker = P07Checker::new(config(offset));
+            let mut data = [0x5au8; 32];
+
+            // ok
+            protector.protect(&mut data).unwrap();
+            assert_eq!(Ok, checker.check(&data));
+            assert_eq!(0, checker.last_counter());
+
+            // repeated
+            assert_eq!(Repeated, checker.check(&data));
+
+            // some lost
+            protector.protect(&mut data).unwrap();
+            protector.protect(&mut data).unwrap();
+            assert_eq!(OkSomeLost, checker.check(&data));
+
+            // wrong sequence
+            for _ in 0..3 {
+                protector.protect(&mut data).unwrap();
+            }
+            assert_eq!(WrongSequence, checker.check(&data));
+            assert_eq!(5, checker.last_counter());
+
+            // corrupted data
+            {
+                let mut data = data;
+                data[offset + 20] ^= 1;
+                assert_eq!(Error, checker.check(&data));
+            }
+            // wrong data id
+            {
+                let mut checker = P07Checker::new(P07Config {
+                    data_id: 1,
+                    ..config(offset)
+                });
+                assert_eq!(Error, checker.check(&data));
+            }
+            // wrong length field (with a valid crc)
+            {
+                let mut data = data;
+                data[offset + 11] = 33;
+                let crc = checker.config().crc(&data);
+                data[offset..offset + 8].copy_from_slice(&crc.to_be_bytes());
+                assert_eq!(Error, checker.check(&data));
+            }
+            // failed checks do not change the counter
+            assert_eq!(5, checker.last_counter());
+        }
+    }
+
+    #[test]
+    fn counter_wrap() {
+        let mut protector = P07Protector::new(config(0));
+        protector.counter = u32::MAX;
+        let mut checker = P07Checker::new(config(0));
+        checker.last_counter = u32::MAX - 1;
+        let mut data = [0u8; 24];
+        protector.protect(&mut data).unwrap();
+        assert_eq!(Ok, checker.check(&data));
+        assert_eq!(0, protector.counter());
+        protector.protect(&mut data).unwrap();
+        assert_eq!(Ok, checker.check(&data));
+    }
+
+    #[test]
+    fn invalid_len() {
+        let mut protector = P07Protector::new(config(8));
+        let mut checker = P07Checker::new(config(8));
+        for len in [23, 27, 65] {
+            let mut data = [0u8; 65];
+            assert_eq!(
+                Err(E2eError::InvalidDataLen {
+                    len,
+                    min_len: 28,
+                    max_len: 64
+                }),
+                protector.protect(&mut data[..len])
+            );
+            assert_eq!(Error, checker.check(&data[..len]));
+        }
+        assert_eq!(0, protector.counter());
+    }
+}
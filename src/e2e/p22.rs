@@ -0,0 +1,306 @@
+use super::*;
+
+/// Configuration of the AUTOSAR E2E profile 22.
+///
+/// Profile 22 protects data of a fixed length with a 2 byte header located
+/// at `offset` containing:
+///
+/// | Bytes  | Field                                                          |
+/// |--------|----------------------------------------------------------------|
+/// | 0      | CRC (CRC8H2F over all other bytes & the data id)               |
+/// | 1      | Counter (lower 4 bits, the upper 4 bits are not protected)     |
+///
+/// The data id included in the CRC is taken from the `data_id_list`
+/// using the counter value as index.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct P22Config {
+    /// Data ids used for each counter value (not transmitted, only included
+    /// in the CRC).
+    pub data_id_list: [u8; 16],
+    /// Offset of the E2E header in bytes.
+    pub offset: usize,
+    /// Length of the protected data (including the header) in bytes.
+    pub data_len: usize,
+    /// Maximum allowed difference between the counter values of two
+    /// consecutively received data.
+    pub max_delta_counter: u8,
+}
+
+impl P22Config {
+    /// Length of the E2E profile 22 header.
+    pub const HEADER_LEN: usize = 2;
+
+    /// Maximum value of the 4 bit counter.
+    pub const MAX_COUNTER: u8 = 0xf;
+
+    /// Checks the data length & returns an error if it is not allowed.
+    fn check_len(&self, len: usize) -> Result<(), E2eError> {
+        check_len(
+            len,
+            self.data_len.max(self.offset + Self::HEADER_LEN),
+            self.data_len,
+        )
+    }
+
+    /// Calculates the CRC of the given data.
+    fn crc(&self, data: &[u8], counter: u8) -> u8 {
+        let crc = crc_without(CRC8H2F_START, data, self.offset, 1, crc8h2f);
+        crc8h2f(
+            crc,
+            &[self.data_id_list[usize::from(counter & Self::MAX_COUNTER)]],
+        ) ^ CRC8H2F_XOR
+    }
+}
+
+/// Protects data using the AUTOSAR E2E profile 22.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::e2e::*;
+///
+/// let config = P22Config {
+///     data_id_list: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+///     offset: 0,
+///     data_len: 8,
+///     max_delta_counter: 1,
+/// };
+///
+/// // sender
+/// let mut protector = P22Protector::new(config.clone());
+/// let mut data = [0u8; 8];
+/// protector.protect(&mut data).unwrap();
+///
+/// // receiver
+/// let mut checker = P22Checker::new(config);
+/// assert_eq!(E2eCheckStatus::Ok, checker.check(&data));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct P22Protector {
+    config: P22Config,
+    counter: u8,
+}
+
+impl P22Protector {
+    /// Creates a protector starting with the counter value 0.
+    pub fn new(config: P22Config) -> P22Protector {
+        P22Protector { config, counter: 0 }
+    }
+
+    /// Configuration used to protect the data.
+    #[inline]
+    pub fn config(&self) -> &P22Config {
+        &self.config
+    }
+
+    /// Counter value used for the next protected data.
+    #[inline]
+    pub fn counter(&self) -> u8 {
+        self.counter
+    }
+
+    /// Writes the E2E header into the data & increments the counter.
+    pub fn protect(&mut self, data: &mut [u8]) -> Result<(), E2eError> {
+        self.config.check_len(data.len())?;
+        let offset = self.config.offset;
+        data[offset + 1] = (data[offset + 1] & !P22Config::MAX_COUNTER) | self.counter;
+        data[offset] = self.config.crc(data, self.counter);
+        self.counter = (self.counter + 1) & P22Config::MAX_COUNTER;
+        Ok(())
+    }
+}
+
+/// Checks data protected using the AUTOSAR E2E profile 22.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct P22Checker {
+    config: P22Config,
+    last_counter: u8,
+}
+
+impl P22Checker {
+    /// Creates a checker expecting the counter value 0 next.
+    pub fn new(config: P22Config) -> P22Checker {
+        P22Checker {
+            config,
+            last_counter: P22Config::MAX_COUNTER,
+        }
+    }
+
+    /// Configuration used to check the data.
+    #[inline]
+    pub fn config(&self) -> &P22Config {
+        &self.config
+    }
+
+    /// Counter value of the last valid data.
+    #[inline]
+    pub fn last_counter(&self) -> u8 {
+        self.last_counter
+    }
+
+    /// Checks the data & updates the last counter value if the data is
+    /// valid.
+    pub fn check(&mut self, data: &[u8]) -> E2eCheckStatus {
+        if self.config.check_len(data.len()).is_err() {
+            return E2eCheckStatus::Error;
+        }
+        let offset = self.config.offset;
+        let counter = data[offset + 1] & P22Config::MAX_COUNTER;
+        if data[offset] != self.config.crc(data, counter) {
+            return E2eCheckStatus::Error;
+        }
+        let status = check_counter(
+            counter.into(),
+            self.last_counter.into(),
+            self.config.max_delta_counter.into(),
+            P22Config::MAX_COUNTER.into(),
+        );
+        self.last_counter = counter;
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use E2eCheckStatus::*;
+
+    fn config(offset: usize) -> P22Config {
+        P22Config {
+            data_id_list: [
+                0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+                0x0e, 0x0f,
+            ],
+            offset,
+            data_len: 8,
+            max_delta_counter: 2,
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let protector = P22Protector::new(config(0));
+        let _ = format!("{:?}", protector);
+        assert_eq!(protector, protector.clone());
+        assert_eq!(&config(0), protector.config());
+        let checker = P22Checker::new(config(0));
+        let _ = format!("{:?}", checker);
+        assert_eq!(checker, checker.clone());
+        assert_eq!(&config(0), checker.config());
+    }
+
+    #[test]
+    fn known_values() {
+        // the CRC input (all bytes except the CRC followed by the data id)
+        // matches the CRC8H2F examples of the AUTOSAR CRC library
+        // specification, so the expected CRCs are taken from there
+        let config = |data_id_list: [u8; 16], data_len| P22Config {
+            data_id_list,
+            offset: 0,
+            data_len,
+            max_delta_counter: 1,
+        };
+
+        // 00 FF 55 11 => 0x77
+        {
+            let mut data_id_list = [0u8; 16];
+            data_id_list[0] = 0x11;
+            let mut protector = P22Protector::new(config(data_id_list, 4));
+            let mut data = [0x00, 0x00, 0xff, 0x55];
+            protector.protect(&mut data).unwrap();
+            assert_eq!([0x77, 0x00, 0xff, 0x55], data);
+        }
+
+        // F2 01 83 => 0xC2 (counter 2 with the unprotected upper bits set)
+        {
+            let mut data_id_list = [0u8; 16];
+            data_id_list[2] = 0x83;
+            let mut protector = P22Protector::new(config(data_id_list, 3));
+            let mut data = [0x00, 0xf0, 0x01];
+            for _ in 0..3 {
+                protector.protect(&mut data).unwrap();
+            }
+            assert_eq!([0xc2, 0xf2, 0x01], data);
+            assert_eq!(3, protector.counter());
+        }
+    }
+
+    #[test]
+    fn protect_check() {
+        for offset in [0, 4] {
+            let mut protector = P22Protector::new(config(offset));
+            let mut checker = P22Checker::new(config(offset));
+            let mut data = [0x5au8; 8];
+
+            // ok
+            protector.protect(&mut data).unwrap();
+            assert_eq!(Ok, checker.check(&data));
+            assert_eq!(0, checker.last_counter());
+            // upper bits of the counter byte are kept
+            assert_eq!(0x50, data[offset + 1]);
+
+            // repeated
+            assert_eq!(Repeated, checker.check(&data));
+
+            // some lost
+            protector.protect(&mut data).unwrap();
+            protector.protect(&mut data).unwrap();
+            assert_eq!(OkSomeLost, checker.check(&data));
+
+            // wrong sequence
+            for _ in 0..3 {
+                protector.protect(&mut data).unwrap();
+            }
+            assert_eq!(WrongSequence, checker.check(&data));
+            assert_eq!(5, checker.last_counter());
+
+            // corrupted data
+            {
+                let mut data = data;
+                data[(offset + 2) % 8] ^= 1;
+                assert_eq!(Error, checker.check(&data));
+            }
+            // wrong data id
+            {
+                let mut other = config(offset);
+                other.data_id_list[5] = 0xff;
+                let mut checker = P22Checker::new(other);
+                assert_eq!(Error, checker.check(&data));
+            }
+            // failed checks do not change the counter
+            assert_eq!(5, checker.last_counter());
+        }
+    }
+
+    #[test]
+    fn counter_wrap() {
+        let mut protector = P22Protector::new(config(0));
+        let mut checker = P22Checker::new(config(0));
+        let mut data = [0u8; 8];
+        for i in 0..40 {
+            assert_eq!(i % 16, protector.counter());
+            protector.protect(&mut data).unwrap();
+            assert_eq!(Ok, checker.check(&data));
+        }
+    }
+
+    #[test]
+    fn invalid_len() {
+        let mut protector = P22Protector::new(config(0));
+        let mut checker = P22Checker::new(config(0));
+        for len in [7, 9] {
+            let mut data = [0u8; 9];
+            assert_eq!(
+                Err(E2eError::InvalidDataLen {
+                    len,
+                    min_len: 8,
+                    max_len: 8
+                }),
+                protector.protect(&mut data[..len])
+            );
+            assert_eq!(Error, checker.check(&data[..len]));
+        }
+        assert_eq!(0, protector.counter());
+    }
+}
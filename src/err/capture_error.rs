@@ -0,0 +1,385 @@
+use super::*;
+
+/// Error when reading or writing a pcap or pcapng capture file or when
+/// decoding or encoding the SOMEIP messages contained in it.
+#[derive(Debug)]
+pub enum CaptureError {
+    /// IO error was encountered while reading or writing the capture file.
+    Io(std::io::Error),
+
+    /// Error if the file starts with an unknown magic number (neither
+    /// pcap nor pcapng).
+    UnknownFileFormat {
+        /// First 4 bytes of the file.
+        magic: u32,
+    },
+
+    /// Error if a pcapng block has an invalid length or content.
+    InvalidBlock {
+        /// Type of the block.
+        block_type: u32,
+        /// Total length of the block in bytes.
+        len: u32,
+    },
+
+    /// Error if a captured packet is bigger then the maximum supported
+    /// length.
+    PacketTooBig {
+        /// Length of the packet in bytes.
+        len: u32,
+        /// Maximum allowed length in bytes.
+        max_len: u32,
+    },
+
+    /// Error if a pcapng packet references an interface that was not
+    /// described in the current section.
+    UnknownInterface {
+        /// Id of the interface referenced by the packet.
+        interface_id: u32,
+    },
+
+    /// Error while defragmenting IPv4 or IPv6 packets.
+    IpDefrag(etherparse::defrag::IpDefragError),
+
+    /// Error while decoding a captured SOMEIP message.
+    Slice(SomeipSliceError),
+
+    /// Error while reassembling a captured SOMEIP TP message.
+    TpReassemble(TpReassembleError),
+
+    /// Error if a message is bigger then the maximum supported length
+    /// (when received via TCP or when writing a message).
+    MessageTooBig {
+        /// Length of the message in bytes.
+        len: usize,
+        /// Maximum allowed length in bytes.
+        max_len: usize,
+    },
+
+    /// Error if a message should be written with source & destination
+    /// addresses of different IP versions.
+    AddressFamilyMismatch {
+        source: std::net::SocketAddr,
+        destination: std::net::SocketAddr,
+    },
+
+    /// Error if an SD header that should be written contains invalid values.
+    SdValue(SdValueError),
+}
+
+impl CaptureError {
+    /// Returns the `std::io::Error` value if the `CaptureError` is `Io`.
+    /// Otherwise `None` is returned.
+    #[inline]
+    pub fn io_error(self) -> Option<std::io::Error> {
+        use CaptureError::*;
+        match self {
+            Io(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the error is caused by the capture file itself
+    /// (reading further packets is not possible) and false if the error
+    /// only concerns the data of a single captured packet.
+    pub fn is_fatal(&self) -> bool {
+        use CaptureError::*;
+        match self {
+            Io(_)
+            | UnknownFileFormat { .. }
+            | InvalidBlock { .. }
+            | PacketTooBig { .. }
+            | UnknownInterface { .. } => true,
+            IpDefrag(_)
+            | Slice(_)
+            | TpReassemble(_)
+            | MessageTooBig { .. }
+            | AddressFamilyMismatch { .. }
+            | SdValue(_) => false,
+        }
+    }
+}
+
+impl core::fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use CaptureError::*;
+        match self {
+            Io(err) => write!(f, "SOMEIP Capture IO Error: {err}"),
+            UnknownFileFormat { magic } => write!(f, "SOMEIP Capture Error: Unknown capture file format with the magic number 0x{magic:08x} (only pcap & pcapng are supported)."),
+            InvalidBlock { block_type, len } => write!(f, "SOMEIP Capture Error: Invalid pcapng block with the type 0x{block_type:08x} and a length of {len} byte(s)."),
+            PacketTooBig { len, max_len } => write!(f, "SOMEIP Capture Error: Captured packet with a length of {len} byte(s) is bigger then the maximum supported length of {max_len} byte(s)."),
+            UnknownInterface { interface_id } => write!(f, "SOMEIP Capture Error: Captured packet references the unknown interface {interface_id}."),
+            IpDefrag(err) => write!(f, "SOMEIP Capture Error: {err}"),
+            Slice(err) => err.fmt(f),
+            TpReassemble(err) => err.fmt(f),
+            MessageTooBig { len, max_len } => write!(f, "SOMEIP message with a length of {len} byte(s) is bigger then the maximum supported length of {max_len} byte(s)."),
+            AddressFamilyMismatch { source, destination } => write!(f, "SOMEIP Capture Error: Source address {source} & destination address {destination} have different IP versions."),
+            SdValue(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for CaptureError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        use CaptureError::*;
+        match self {
+            Io(err) => Some(err),
+            IpDefrag(err) => Some(err),
+            Slice(err) => Some(err),
+            TpReassemble(err) => Some(err),
+            SdValue(err) => Some(err),
+            UnknownFileFormat { .. }
+            | InvalidBlock { .. }
+            | PacketTooBig { .. }
+            | UnknownInterface { .. }
+            | MessageTooBig { .. }
+            | AddressFamilyMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CaptureError {
+    fn from(err: std::io::Error) -> CaptureError {
+        CaptureError::Io(err)
+    }
+}
+
+impl From<etherparse::defrag::IpDefragError> for CaptureError {
+    fn from(err: etherparse::defrag::IpDefragError) -> CaptureError {
+        CaptureError::IpDefrag(err)
+    }
+}
+
+impl From<SomeipSliceError> for CaptureError {
+    fn from(err: SomeipSliceError) -> CaptureError {
+        CaptureError::Slice(err)
+    }
+}
+
+impl From<TpReassembleError> for CaptureError {
+    fn from(err: TpReassembleError) -> CaptureError {
+        CaptureError::TpReassemble(err)
+    }
+}
+
+impl From<SdValueError> for CaptureError {
+    fn from(err: SdValueError) -> CaptureError {
+        CaptureError::SdValue(err)
+    }
+}
+
+impl From<TransportError> for CaptureError {
+    fn from(err: TransportError) -> CaptureError {
+        use TransportError as T;
+        match err {
+            T::Io(err) => CaptureError::Io(err),
+            T::Slice(err) => CaptureError::Slice(err),
+            T::TpReassemble(err) => CaptureError::TpReassemble(err),
+            T::MessageTooBig { len, max_len } => CaptureError::MessageTooBig { len, max_len },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CaptureError::*, *};
+    use alloc::format;
+    use etherparse::defrag::IpDefragError;
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    fn mismatch() -> CaptureError {
+        AddressFamilyMismatch {
+            source: SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1),
+            destination: SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 2),
+        }
+    }
+
+    fn io_err() -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "failed to fill whole buffer",
+        )
+    }
+
+    #[test]
+    fn debug() {
+        let err = UnknownFileFormat { magic: 1 };
+        let _ = format!("{err:?}");
+    }
+
+    #[test]
+    fn io_error() {
+        assert!(Io(io_err()).io_error().is_some());
+        assert!(UnknownFileFormat { magic: 1 }.io_error().is_none());
+    }
+
+    #[test]
+    fn is_fatal() {
+        assert!(Io(io_err()).is_fatal());
+        assert!(UnknownFileFormat { magic: 1 }.is_fatal());
+        assert!(InvalidBlock {
+            block_type: 1,
+            len: 2
+        }
+        .is_fatal());
+        assert!(PacketTooBig { len: 2, max_len: 1 }.is_fatal());
+        assert!(UnknownInterface { interface_id: 1 }.is_fatal());
+        assert_eq!(
+            false,
+            IpDefrag(IpDefragError::AllocationFailure { len: 0 }).is_fatal()
+        );
+        assert_eq!(
+            false,
+            Slice(SomeipSliceError::Content(
+                SomeipHeaderError::UnknownMessageType(3)
+            ))
+            .is_fatal()
+        );
+        assert_eq!(
+            false,
+            TpReassemble(TpReassembleError::AllocationFailure { len: 0 }).is_fatal()
+        );
+        assert_eq!(false, MessageTooBig { len: 2, max_len: 1 }.is_fatal());
+        assert_eq!(false, mismatch().is_fatal());
+        assert_eq!(
+            false,
+            SdValue(SdValueError::TtlZeroIndicatesStopOffering).is_fatal()
+        );
+    }
+
+    #[test]
+    fn fmt() {
+        {
+            let err = io_err();
+            assert_eq!(
+                format!("SOMEIP Capture IO Error: {}", err),
+                format!("{}", Io(err))
+            );
+        }
+        assert_eq!(
+            format!("{}", UnknownFileFormat { magic: 0x1234_5678 }),
+            "SOMEIP Capture Error: Unknown capture file format with the magic number 0x12345678 (only pcap & pcapng are supported)."
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                InvalidBlock {
+                    block_type: 6,
+                    len: 3
+                }
+            ),
+            "SOMEIP Capture Error: Invalid pcapng block with the type 0x00000006 and a length of 3 byte(s)."
+        );
+        assert_eq!(
+            format!("{}", PacketTooBig { len: 2, max_len: 1 }),
+            "SOMEIP Capture Error: Captured packet with a length of 2 byte(s) is bigger then the maximum supported length of 1 byte(s)."
+        );
+        assert_eq!(
+            format!("{}", UnknownInterface { interface_id: 2 }),
+            "SOMEIP Capture Error: Captured packet references the unknown interface 2."
+        );
+        {
+            let err = IpDefragError::AllocationFailure { len: 0 };
+            assert_eq!(
+                format!("SOMEIP Capture Error: {}", err),
+                format!("{}", IpDefrag(err))
+            );
+        }
+        {
+            let err = SomeipSliceError::Content(SomeipHeaderError::UnknownMessageType(3));
+            assert_eq!(format!("{}", err), format!("{}", Slice(err.clone())));
+        }
+        {
+            let err = TpReassembleError::AllocationFailure { len: 0 };
+            assert_eq!(format!("{}", err), format!("{}", TpReassemble(err.clone())));
+        }
+        assert_eq!(
+            format!("{}", MessageTooBig { len: 2, max_len: 1 }),
+            "SOMEIP message with a length of 2 byte(s) is bigger then the maximum supported length of 1 byte(s)."
+        );
+        assert_eq!(
+            format!("{}", mismatch()),
+            "SOMEIP Capture Error: Source address 127.0.0.1:1 & destination address [::1]:2 have different IP versions."
+        );
+        {
+            let err = SdValueError::TtlZeroIndicatesStopOffering;
+            assert_eq!(format!("{}", err), format!("{}", SdValue(err.clone())));
+        }
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(Io(io_err()).source().is_some());
+        assert!(UnknownFileFormat { magic: 1 }.source().is_none());
+        assert!(InvalidBlock {
+            block_type: 1,
+            len: 2
+        }
+        .source()
+        .is_none());
+        assert!(PacketTooBig { len: 2, max_len: 1 }.source().is_none());
+        assert!(UnknownInterface { interface_id: 1 }.source().is_none());
+        assert!(IpDefrag(IpDefragError::AllocationFailure { len: 0 })
+            .source()
+            .is_some());
+        assert!(Slice(SomeipSliceError::Content(
+            SomeipHeaderError::UnknownMessageType(3)
+        ))
+        .source()
+        .is_some());
+        assert!(
+            TpReassemble(TpReassembleError::AllocationFailure { len: 0 })
+                .source()
+                .is_some()
+        );
+        assert!(MessageTooBig { len: 2, max_len: 1 }.source().is_none());
+        assert!(mismatch().source().is_none());
+        assert!(SdValue(SdValueError::TtlZeroIndicatesStopOffering)
+            .source()
+            .is_some());
+    }
+
+    #[test]
+    fn from() {
+        assert!(matches!(CaptureError::from(io_err()), Io(_)));
+        assert!(matches!(
+            CaptureError::from(IpDefragError::AllocationFailure { len: 0 }),
+            IpDefrag(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(SomeipSliceError::Content(
+                SomeipHeaderError::UnknownMessageType(3)
+            )),
+            Slice(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(TpReassembleError::AllocationFailure { len: 0 }),
+            TpReassemble(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(SdValueError::TtlZeroIndicatesStopOffering),
+            SdValue(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(TransportError::Io(io_err())),
+            Io(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(TransportError::Slice(SomeipSliceError::Content(
+                SomeipHeaderError::UnknownMessageType(3)
+            ))),
+            Slice(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(TransportError::TpReassemble(
+                TpReassembleError::AllocationFailure { len: 0 }
+            )),
+            TpReassemble(_)
+        ));
+        assert!(matches!(
+            CaptureError::from(TransportError::MessageTooBig { len: 2, max_len: 1 }),
+            MessageTooBig { len: 2, max_len: 1 }
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+/// Error when protecting data with an AUTOSAR E2E profile.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum E2eError {
+    /// Error if the length of the data is not allowed by the profile
+    /// configuration (or too small to contain the E2E header at the
+    /// configured offset).
+    InvalidDataLen {
+        len: usize,
+        min_len: usize,
+        max_len: usize,
+    },
+}
+
+impl core::fmt::Display for E2eError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use E2eError::*;
+        match self {
+            InvalidDataLen {
+                len,
+                min_len,
+                max_len,
+            } => write!(f, "E2E Error: Data length {len} is not in the allowed range of {min_len} to {max_len} bytes."),
+        }
+    }
+}
+
+impl core::error::Error for E2eError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::E2eError::*;
+
+    #[test]
+    fn debug() {
+        let err = InvalidDataLen {
+            len: 1,
+            min_len: 2,
+            max_len: 3,
+        };
+        let _ = format!("{err:?}");
+    }
+
+    #[test]
+    fn clone_eq_hash_ord() {
+        use core::cmp::Ordering;
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let err = InvalidDataLen {
+            len: 1,
+            min_len: 2,
+            max_len: 3,
+        };
+        assert_eq!(err, err.clone());
+        let hash_a = {
+            let mut hasher = DefaultHasher::new();
+            err.hash(&mut hasher);
+            hasher.finish()
+        };
+        let hash_b = {
+            let mut hasher = DefaultHasher::new();
+            err.clone().hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_a, hash_b);
+        assert_eq!(Ordering::Equal, err.cmp(&err));
+        assert_eq!(Some(Ordering::Equal), err.partial_cmp(&err));
+    }
+
+    #[test]
+    fn fmt() {
+        let err = InvalidDataLen {
+            len: 1,
+            min_len: 2,
+            max_len: 3,
+        };
+        assert_eq!(
+            format!("{err}"),
+            "E2E Error: Data length 1 is not in the allowed range of 2 to 3 bytes."
+        );
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(InvalidDataLen {
+            len: 1,
+            min_len: 2,
+            max_len: 3,
+        }
+        .source()
+        .is_none());
+    }
+}
@@ -0,0 +1,70 @@
+/// Error when a value provided to construct a [`crate::MethodId`] or
+/// [`crate::EventId`] does not fit into 15 bits (aka the event bit 0x8000
+/// is set).
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct IdTooLargeError {
+    /// The value that was too large.
+    pub actual: u16,
+}
+
+impl core::fmt::Display for IdTooLargeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Method or event id 0x{:04x} is too large (maximum is 0x7fff, the event bit is not allowed to be set).",
+            self.actual
+        )
+    }
+}
+
+impl core::error::Error for IdTooLargeError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+
+    #[test]
+    fn debug() {
+        let err = IdTooLargeError { actual: 0x8000 };
+        assert_eq!("IdTooLargeError { actual: 32768 }", format!("{err:?}"));
+    }
+
+    #[test]
+    fn clone_eq_hash_ord() {
+        use core::cmp::Ordering;
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let err = IdTooLargeError { actual: 0x8000 };
+        assert_eq!(err, err.clone());
+        let hash_a = {
+            let mut hasher = DefaultHasher::new();
+            err.hash(&mut hasher);
+            hasher.finish()
+        };
+        let hash_b = {
+            let mut hasher = DefaultHasher::new();
+            err.clone().hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_a, hash_b);
+        assert_eq!(Ordering::Equal, err.cmp(&err));
+        assert_eq!(Some(Ordering::Equal), err.partial_cmp(&err));
+    }
+
+    #[test]
+    fn fmt() {
+        assert_eq!(
+            format!("{}", IdTooLargeError { actual: 0x8001 }),
+            "Method or event id 0x8001 is too large (maximum is 0x7fff, the event bit is not allowed to be set)."
+        );
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(IdTooLargeError { actual: 0x8001 }.source().is_none());
+    }
+}
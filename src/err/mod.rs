@@ -1,3 +1,16 @@
+#[cfg(feature = "capture")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capture")))]
+mod capture_error;
+#[cfg(feature = "capture")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capture")))]
+pub use capture_error::*;
+
+mod e2e_error;
+pub use e2e_error::*;
+
+mod id_too_large_error;
+pub use id_too_large_error::*;
+
 mod layer;
 pub use layer::*;
 
@@ -33,9 +46,15 @@ mod sd_io_write_error;
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub use sd_io_write_error::*;
 
+mod secoc_error;
+pub use secoc_error::*;
+
 mod slice_write_space_error;
 pub use slice_write_space_error::*;
 
+mod someip_header_build_error;
+pub use someip_header_build_error::*;
+
 mod someip_header_error;
 pub use someip_header_error::*;
 
@@ -58,5 +77,26 @@ pub use tp_offset_not_multiple_of_16_error::*;
 mod tp_reassemble_error;
 pub use tp_reassemble_error::*;
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod tp_stream_write_error;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tp_stream_write_error::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod transport_error;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use transport_error::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod runtime_error;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use runtime_error::*;
+
 mod sd_value_error;
 pub use sd_value_error::*;
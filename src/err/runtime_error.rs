@@ -0,0 +1,191 @@
+use super::*;
+
+/// Error returned by the SOMEIP runtime.
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// Error while sending or receiving a message.
+    Transport(TransportError),
+
+    /// Error while decoding a received SOMEIP SD message.
+    Sd(SdSliceError),
+
+    /// Error while building a SOMEIP SD message.
+    SdValue(SdValueError),
+
+    /// No service with the given service & instance id is registered in
+    /// the runtime.
+    UnknownService { service_id: u16, instance_id: u16 },
+
+    /// The field handle is not valid for the service with the given
+    /// service & instance id.
+    UnknownField { service_id: u16, instance_id: u16 },
+}
+
+impl RuntimeError {
+    /// Returns the `TransportError` value if the `RuntimeError` is `Transport`.
+    /// Otherwise `None` is returned.
+    #[inline]
+    pub fn transport_error(self) -> Option<TransportError> {
+        use RuntimeError::*;
+        match self {
+            Transport(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use RuntimeError::*;
+        match self {
+            Transport(err) => err.fmt(f),
+            Sd(err) => err.fmt(f),
+            SdValue(err) => err.fmt(f),
+            UnknownService { service_id, instance_id } => write!(
+                f,
+                "SOMEIP Runtime Error: No service with the service id 0x{service_id:04x} & instance id 0x{instance_id:04x} is registered."
+            ),
+            UnknownField { service_id, instance_id } => write!(
+                f,
+                "SOMEIP Runtime Error: Unknown field handle for the service with the service id 0x{service_id:04x} & instance id 0x{instance_id:04x}."
+            ),
+        }
+    }
+}
+
+impl core::error::Error for RuntimeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        use RuntimeError::*;
+        match self {
+            Transport(err) => Some(err),
+            Sd(err) => Some(err),
+            SdValue(err) => Some(err),
+            UnknownService { .. } => None,
+            UnknownField { .. } => None,
+        }
+    }
+}
+
+impl From<TransportError> for RuntimeError {
+    fn from(err: TransportError) -> RuntimeError {
+        RuntimeError::Transport(err)
+    }
+}
+
+impl From<SdSliceError> for RuntimeError {
+    fn from(err: SdSliceError) -> RuntimeError {
+        RuntimeError::Sd(err)
+    }
+}
+
+impl From<SdValueError> for RuntimeError {
+    fn from(err: SdValueError) -> RuntimeError {
+        RuntimeError::SdValue(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RuntimeError::*, *};
+    use alloc::format;
+
+    #[test]
+    fn debug() {
+        let _ = format!(
+            "{:?}",
+            UnknownService {
+                service_id: 0x1234,
+                instance_id: 0x0001,
+            }
+        );
+    }
+
+    #[test]
+    fn transport_error() {
+        assert!(
+            Transport(TransportError::MessageTooBig { len: 2, max_len: 1 })
+                .transport_error()
+                .is_some()
+        );
+        assert!(UnknownService {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+        }
+        .transport_error()
+        .is_none());
+    }
+
+    #[test]
+    fn fmt() {
+        {
+            let err = TransportError::MessageTooBig { len: 2, max_len: 1 };
+            assert_eq!(format!("{}", err), format!("{}", Transport(err)));
+        }
+        {
+            let err = SdSliceError::UnexpectedEndOfSlice(12);
+            assert_eq!(format!("{}", err), format!("{}", Sd(err.clone())));
+        }
+        {
+            let err = SdValueError::SdEntriesArrayTooLarge;
+            assert_eq!(format!("{}", err), format!("{}", SdValue(err.clone())));
+        }
+        assert_eq!(
+            format!("{}", UnknownService {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+        }),
+            "SOMEIP Runtime Error: No service with the service id 0x1234 & instance id 0x0001 is registered."
+        );
+        assert_eq!(
+            format!("{}", UnknownField {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+        }),
+            "SOMEIP Runtime Error: Unknown field handle for the service with the service id 0x1234 & instance id 0x0001."
+        );
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(
+            Transport(TransportError::MessageTooBig { len: 2, max_len: 1 })
+                .source()
+                .is_some()
+        );
+        assert!(Sd(SdSliceError::UnexpectedEndOfSlice(12))
+            .source()
+            .is_some());
+        assert!(SdValue(SdValueError::SdEntriesArrayTooLarge)
+            .source()
+            .is_some());
+        assert!(UnknownService {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+        }
+        .source()
+        .is_none());
+        assert!(UnknownField {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+        }
+        .source()
+        .is_none());
+    }
+
+    #[test]
+    fn from() {
+        assert!(matches!(
+            RuntimeError::from(TransportError::MessageTooBig { len: 2, max_len: 1 }),
+            Transport(_)
+        ));
+        assert!(matches!(
+            RuntimeError::from(SdSliceError::UnexpectedEndOfSlice(12)),
+            Sd(_)
+        ));
+        assert!(matches!(
+            RuntimeError::from(SdValueError::SdEntriesArrayTooLarge),
+            SdValue(_)
+        ));
+    }
+}
@@ -72,7 +72,7 @@ mod tests {
     use alloc::format;
 
     fn io_err() -> std::io::Error {
-        std::io::Error::new(std::io::ErrorKind::Other, "oh no!")
+        std::io::Error::other("oh no!")
     }
 
     #[test]
@@ -10,6 +10,24 @@ pub enum SdValueError {
     /// A TTL of zero indicates stop offering of service entry.
     TtlZeroIndicatesStopOffering,
 
+    /// A TTL of zero indicates stop subscribing of an eventgroup entry.
+    TtlZeroIndicatesStopSubscribe,
+
+    /// A TTL of zero indicates a negative acknowledgement of an eventgroup
+    /// subscription.
+    TtlZeroIndicatesNack,
+
+    /// The entry does not reference an endpoint option, but the kind of
+    /// entry requires one (e.g. subscribes).
+    MissingEndpointOption(crate::sd::SdEntryKind),
+
+    /// The entry references a unicast endpoint option, but the kind of
+    /// entry only allows multicast endpoints (e.g. subscribe acks).
+    UnexpectedEndpointOption {
+        kind: crate::sd::SdEntryKind,
+        option_index: usize,
+    },
+
     /// Number of options 1 exceeds 4 bit
     NumberOfOption1TooLarge(u8),
 
@@ -57,6 +75,22 @@ impl core::fmt::Display for SdValueError {
                 f,
                 "SOMEIP SD Value Error: A TTL of zero indicates 'stop offering' of a service entry and can not be set explicitly."
             ),
+            TtlZeroIndicatesStopSubscribe => write!(
+                f,
+                "SOMEIP SD Value Error: A TTL of zero indicates 'stop subscribe' of an eventgroup entry and can not be set explicitly."
+            ),
+            TtlZeroIndicatesNack => write!(
+                f,
+                "SOMEIP SD Value Error: A TTL of zero indicates a 'subscribe nack' of an eventgroup entry and can not be set explicitly."
+            ),
+            MissingEndpointOption(kind) => write!(
+                f,
+                "SOMEIP SD Value Error: A '{kind}' entry must reference an endpoint option."
+            ),
+            UnexpectedEndpointOption { kind, option_index } => write!(
+                f,
+                "SOMEIP SD Value Error: A '{kind}' entry is only allowed to reference multicast endpoint options, but the option at index {option_index} is a unicast endpoint."
+            ),
             NumberOfOption1TooLarge(v) => write!(
                 f,
                 "SOMEIP SD Value Error: The 'number of options 1' value '{v}' exceeds the maximum of 4 bits (15)."
@@ -151,6 +185,13 @@ mod tests {
             CounterTooLarge(0x10),
             TtlTooLarge(0x0100_0000),
             TtlZeroIndicatesStopOffering,
+            TtlZeroIndicatesStopSubscribe,
+            TtlZeroIndicatesNack,
+            MissingEndpointOption(crate::sd::SdEntryKind::Subscribe),
+            UnexpectedEndpointOption {
+                kind: crate::sd::SdEntryKind::SubscribeAck,
+                option_index: 1,
+            },
             NumberOfOption1TooLarge(0x10),
             NumberOfOption2TooLarge(0x10),
             SdUnknownDiscardableOption(0xaa),
@@ -167,6 +208,17 @@ mod tests {
             assert!(!format!("{variant}").is_empty());
         }
 
+        assert_eq!(
+            "SOMEIP SD Value Error: A 'Subscribe Eventgroup Ack' entry is only allowed to reference multicast endpoint options, but the option at index 1 is a unicast endpoint.",
+            format!(
+                "{}",
+                UnexpectedEndpointOption {
+                    kind: crate::sd::SdEntryKind::SubscribeAck,
+                    option_index: 1,
+                }
+            )
+        );
+
         // The configuration string variant delegates to the inner error.
         let inner = SdConfigurationStringError::MissingTerminator;
         assert_eq!(
@@ -0,0 +1,109 @@
+use super::*;
+
+/// Error when splitting or generating a SecOC secured SOMEIP payload.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SecOcError {
+    /// The payload is too short to contain the configured freshness value
+    /// & MAC.
+    Len(LenError),
+
+    /// The freshness value passed to generate a secured payload is shorter
+    /// then the configured (truncated) freshness value length.
+    FreshnessTooShort { len: usize, min_len: usize },
+
+    /// Not enough space to write the secured payload.
+    WriteSpace(SliceWriteSpaceError),
+}
+
+impl core::fmt::Display for SecOcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use SecOcError::*;
+        match self {
+            Len(err) => err.fmt(f),
+            FreshnessTooShort { len, min_len } => write!(
+                f,
+                "SecOC Error: Freshness value with a length of {len} byte(s) is shorter then the configured transmitted length of {min_len} byte(s)."
+            ),
+            WriteSpace(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for SecOcError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        use SecOcError::*;
+        match self {
+            Len(err) => Some(err),
+            FreshnessTooShort { .. } => None,
+            WriteSpace(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SecOcError::*, *};
+    use alloc::format;
+
+    fn len_error() -> LenError {
+        LenError {
+            required_len: 2,
+            len: 1,
+            len_source: LenSource::Slice,
+            layer: Layer::SomeipPayload,
+        }
+    }
+
+    fn write_space_error() -> SliceWriteSpaceError {
+        SliceWriteSpaceError {
+            required_len: 2,
+            len: 1,
+            layer: Layer::SomeipPayload,
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq_hash_ord() {
+        use core::cmp::Ordering;
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let err = Len(len_error());
+        assert_eq!(err, err.clone());
+        let _ = format!("{:?}", err);
+        let hash_a = {
+            let mut hasher = DefaultHasher::new();
+            err.hash(&mut hasher);
+            hasher.finish()
+        };
+        let hash_b = {
+            let mut hasher = DefaultHasher::new();
+            err.clone().hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash_a, hash_b);
+        assert_eq!(Ordering::Equal, err.cmp(&err));
+        assert_eq!(Some(Ordering::Equal), err.partial_cmp(&err));
+    }
+
+    #[test]
+    fn fmt() {
+        assert_eq!(format!("{}", len_error()), format!("{}", Len(len_error())));
+        assert_eq!(
+            format!("{}", FreshnessTooShort { len: 1, min_len: 2 }),
+            "SecOC Error: Freshness value with a length of 1 byte(s) is shorter then the configured transmitted length of 2 byte(s)."
+        );
+        assert_eq!(
+            format!("{}", write_space_error()),
+            format!("{}", WriteSpace(write_space_error()))
+        );
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(Len(len_error()).source().is_some());
+        assert!(FreshnessTooShort { len: 1, min_len: 2 }.source().is_none());
+        assert!(WriteSpace(write_space_error()).source().is_some());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{MessageType, ReturnCode};
+
+/// Error when building a [`crate::SomeipHeader`] via a
+/// [`crate::SomeipHeaderBuilder`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SomeipHeaderBuildError {
+    /// Error if the method id of a request has the event bit (0x8000) set.
+    MethodIdWithEventBit(u16),
+
+    /// Error if the return code is not allowed for the message type.
+    ///
+    /// Requests, "fire & forget" requests & notifications must use
+    /// [`ReturnCode::Ok`], error messages must use a return code other
+    /// than [`ReturnCode::Ok`].
+    ReturnCodeNotAllowed {
+        message_type: MessageType,
+        return_code: ReturnCode,
+    },
+
+    /// Error if the payload is too large to be represented in the
+    /// length field of the SOMEIP header.
+    PayloadLenTooLarge { max: usize, actual: usize },
+}
+
+impl core::fmt::Display for SomeipHeaderBuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use SomeipHeaderBuildError::*;
+        match self {
+            MethodIdWithEventBit(method_id) => write!(
+                f,
+                "SOMEIP method id 0x{method_id:04x} has the event bit (0x8000) set."
+            ),
+            ReturnCodeNotAllowed {
+                message_type,
+                return_code,
+            } => write!(
+                f,
+                "SOMEIP return code '{return_code}' is not allowed for the message type '{message_type}'."
+            ),
+            PayloadLenTooLarge { max, actual } => write!(
+                f,
+                "SOMEIP payload length {actual} is bigger then the maximum allowed length {max}."
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SomeipHeaderBuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::SomeipHeaderBuildError::*;
+    use crate::{MessageType, ReturnCode};
+    use alloc::format;
+
+    #[test]
+    fn debug() {
+        let _ = format!("{:?}", MethodIdWithEventBit(0x8001));
+    }
+
+    #[test]
+    fn clone_eq() {
+        let err = ReturnCodeNotAllowed {
+            message_type: MessageType::Request,
+            return_code: ReturnCode::NotOk,
+        };
+        assert_eq!(err, err.clone());
+    }
+
+    #[test]
+    fn fmt() {
+        assert_eq!(
+            "SOMEIP method id 0x8001 has the event bit (0x8000) set.",
+            format!("{}", MethodIdWithEventBit(0x8001))
+        );
+        assert_eq!(
+            format!(
+                "SOMEIP return code '{}' is not allowed for the message type '{}'.",
+                ReturnCode::NotOk,
+                MessageType::Notification
+            ),
+            format!(
+                "{}",
+                ReturnCodeNotAllowed {
+                    message_type: MessageType::Notification,
+                    return_code: ReturnCode::NotOk,
+                }
+            )
+        );
+        assert_eq!(
+            "SOMEIP payload length 12 is bigger then the maximum allowed length 10.",
+            format!(
+                "{}",
+                PayloadLenTooLarge {
+                    max: 10,
+                    actual: 12
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(MethodIdWithEventBit(0x8001).source().is_none());
+    }
+}
@@ -23,6 +23,72 @@ pub enum TpReassembleError {
 
     /// Error if not enough memory could be allocated to store the TP payload.
     AllocationFailure { len: usize },
+
+    /// Error if a fixed capacity TP buffer can not store an additional
+    /// non contiguous range of received data.
+    SectionsFull { max_sections: usize },
+
+    /// Error if a segment overlaps already received data with differing
+    /// bytes (only returned with [`crate::TpOverlapPolicy::RejectConflicting`]).
+    ConflictingOverlap {
+        /// Offset of the rejected segment.
+        offset: u32,
+
+        /// Payload length of the rejected segment.
+        payload_len: usize,
+
+        /// Offset of the first byte differing from the already received data.
+        conflict_offset: u32,
+    },
+
+    /// Error if the message id of a segment differs from the previously
+    /// received segments (only returned if strict header checks are enabled).
+    MessageIdMismatch { expected: u32, actual: u32 },
+
+    /// Error if the interface version of a segment differs from the
+    /// previously received segments (only returned if strict header checks
+    /// are enabled).
+    InterfaceVersionMismatch { expected: u8, actual: u8 },
+
+    /// Error if the message type (without the TP flag) of a segment differs
+    /// from the previously received segments (only returned if strict
+    /// header checks are enabled).
+    MessageTypeMismatch { expected: u8, actual: u8 },
+
+    /// Error if the return code of a segment differs from the previously
+    /// received segments (only returned if strict header checks are enabled).
+    ReturnCodeMismatch { expected: u8, actual: u8 },
+
+    /// Error if a new stream would exceed the maximum number of active
+    /// streams of a [`crate::TpPool`].
+    StreamLimitReached { max_streams: usize },
+
+    /// Error if a new stream would exceed the maximum number of active
+    /// streams per channel of a [`crate::TpPool`].
+    ChannelStreamLimitReached { max_streams_per_channel: usize },
+
+    /// Error if a segment would exceed the maximum number of buffered bytes
+    /// of a [`crate::TpPool`].
+    BufferedBytesLimitReached {
+        /// Configured maximum number of buffered bytes.
+        max_buffered_bytes: usize,
+
+        /// Number of bytes that would have been buffered over all streams.
+        required: usize,
+    },
+
+    /// Error if an out of order segment does not fit into the reorder
+    /// window of a [`crate::TpStreamReassembler`].
+    ReorderWindowExceeded {
+        /// Offset of the rejected segment.
+        offset: u32,
+
+        /// Payload length of the rejected segment.
+        payload_len: usize,
+
+        /// End of the reorder window (payload offset).
+        window_end: u32,
+    },
 }
 
 impl core::fmt::Display for TpReassembleError {
@@ -33,6 +99,16 @@ impl core::fmt::Display for TpReassembleError {
             SegmentTooBig{ offset, payload_len, max } => write!(f, "Overall length of TP segment (offset {offset}, payload len: {payload_len}) bigger then the maximum allowed size of {max}."),
             ConflictingEnd { previous_end, conflicting_end } => write!(f, "Received a TP package (offset + len: {conflicting_end}) which conflicts a package that previously set the end to {previous_end}."),
             AllocationFailure { len } => write!(f, "Faield to allocate {len} bytes of memory to reconstruct the SOMEIP TP packets."),
+            SectionsFull { max_sections } => write!(f, "Failed to store SOMEIP TP segment as the maximum number of {max_sections} non contiguous received ranges is reached."),
+            ConflictingOverlap { offset, payload_len, conflict_offset } => write!(f, "SOMEIP TP segment (offset {offset}, payload len: {payload_len}) overlaps previously received data with differing bytes (first difference at offset {conflict_offset})."),
+            MessageIdMismatch { expected, actual } => write!(f, "SOMEIP TP segment message id 0x{actual:08x} differs from the message id 0x{expected:08x} of the previously received segments."),
+            InterfaceVersionMismatch { expected, actual } => write!(f, "SOMEIP TP segment interface version {actual} differs from the interface version {expected} of the previously received segments."),
+            MessageTypeMismatch { expected, actual } => write!(f, "SOMEIP TP segment message type 0x{actual:02x} differs from the message type 0x{expected:02x} of the previously received segments."),
+            ReturnCodeMismatch { expected, actual } => write!(f, "SOMEIP TP segment return code 0x{actual:02x} differs from the return code 0x{expected:02x} of the previously received segments."),
+            StreamLimitReached { max_streams } => write!(f, "Failed to start a new SOMEIP TP stream as the maximum number of {max_streams} active streams is reached."),
+            ChannelStreamLimitReached { max_streams_per_channel } => write!(f, "Failed to start a new SOMEIP TP stream as the maximum number of {max_streams_per_channel} active streams per channel is reached."),
+            BufferedBytesLimitReached { max_buffered_bytes, required } => write!(f, "Failed to store SOMEIP TP segment as {required} bytes would be buffered (maximum allowed is {max_buffered_bytes} bytes)."),
+            ReorderWindowExceeded { offset, payload_len, window_end } => write!(f, "SOMEIP TP segment (offset {offset}, payload len: {payload_len}) exceeds the reorder window ending at offset {window_end}."),
         }
     }
 }
@@ -81,6 +157,16 @@ mod tests {
             (SegmentTooBig { offset: 1, payload_len: 2, max: 3, }, "Overall length of TP segment (offset 1, payload len: 2) bigger then the maximum allowed size of 3."),
             (ConflictingEnd { previous_end: 1, conflicting_end: 2, }, "Received a TP package (offset + len: 2) which conflicts a package that previously set the end to 1."),
             (AllocationFailure { len: 0 }, "Faield to allocate 0 bytes of memory to reconstruct the SOMEIP TP packets."),
+            (SectionsFull { max_sections: 4 }, "Failed to store SOMEIP TP segment as the maximum number of 4 non contiguous received ranges is reached."),
+            (ConflictingOverlap { offset: 16, payload_len: 32, conflict_offset: 20 }, "SOMEIP TP segment (offset 16, payload len: 32) overlaps previously received data with differing bytes (first difference at offset 20)."),
+            (MessageIdMismatch { expected: 0x1234_0001, actual: 0x1234_0002 }, "SOMEIP TP segment message id 0x12340002 differs from the message id 0x12340001 of the previously received segments."),
+            (InterfaceVersionMismatch { expected: 1, actual: 2 }, "SOMEIP TP segment interface version 2 differs from the interface version 1 of the previously received segments."),
+            (MessageTypeMismatch { expected: 0x02, actual: 0x80 }, "SOMEIP TP segment message type 0x80 differs from the message type 0x02 of the previously received segments."),
+            (ReturnCodeMismatch { expected: 0, actual: 1 }, "SOMEIP TP segment return code 0x01 differs from the return code 0x00 of the previously received segments."),
+            (StreamLimitReached { max_streams: 2 }, "Failed to start a new SOMEIP TP stream as the maximum number of 2 active streams is reached."),
+            (ChannelStreamLimitReached { max_streams_per_channel: 3 }, "Failed to start a new SOMEIP TP stream as the maximum number of 3 active streams per channel is reached."),
+            (BufferedBytesLimitReached { max_buffered_bytes: 4, required: 5 }, "Failed to store SOMEIP TP segment as 5 bytes would be buffered (maximum allowed is 4 bytes)."),
+            (ReorderWindowExceeded { offset: 32, payload_len: 16, window_end: 40 }, "SOMEIP TP segment (offset 32, payload len: 16) exceeds the reorder window ending at offset 40."),
         ];
         for test in tests {
             assert_eq!(format!("{}", test.0), test.1);
@@ -0,0 +1,113 @@
+use super::*;
+
+/// Error when reassembling a SOMEIP TP stream into a `std::io::Write` sink.
+#[derive(Debug)]
+pub enum TpStreamWriteError {
+    /// IO error was encountered while writing the payload.
+    Io(std::io::Error),
+
+    /// Error caused by the received TP segment.
+    Reassemble(TpReassembleError),
+}
+
+impl TpStreamWriteError {
+    /// Returns the `std::io::Error` value if the `TpStreamWriteError` is `Io`.
+    /// Otherwise `None` is returned.
+    #[inline]
+    pub fn io_error(self) -> Option<std::io::Error> {
+        use TpStreamWriteError::*;
+        match self {
+            Io(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`crate::err::TpReassembleError`] value if the `TpStreamWriteError` is `Reassemble`.
+    /// Otherwise `None` is returned.
+    #[inline]
+    pub fn reassemble_error(self) -> Option<TpReassembleError> {
+        use TpStreamWriteError::*;
+        match self {
+            Reassemble(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<TpReassembleError> for TpStreamWriteError {
+    fn from(value: TpReassembleError) -> Self {
+        TpStreamWriteError::Reassemble(value)
+    }
+}
+
+impl core::fmt::Display for TpStreamWriteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use TpStreamWriteError::*;
+        match self {
+            Io(err) => write!(f, "SOMEIP TP Stream IO Error: {}", err),
+            Reassemble(value) => value.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for TpStreamWriteError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        use TpStreamWriteError::*;
+        match self {
+            Io(err) => Some(err),
+            Reassemble(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TpStreamWriteError::*, *};
+
+    fn io_err() -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::WriteZero,
+            "failed to write whole buffer",
+        )
+    }
+
+    #[test]
+    fn debug() {
+        let err = TpReassembleError::AllocationFailure { len: 1 };
+        assert_eq!(
+            format!("Reassemble({:?})", err.clone()),
+            format!("{:?}", Reassemble(err))
+        );
+    }
+
+    #[test]
+    fn fmt() {
+        assert_eq!(
+            format!("SOMEIP TP Stream IO Error: {}", io_err()),
+            format!("{}", Io(io_err()))
+        );
+        let err = TpReassembleError::AllocationFailure { len: 1 };
+        assert_eq!(format!("{}", &err), format!("{}", Reassemble(err.clone())));
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(Io(io_err()).source().is_some());
+        assert!(Reassemble(TpReassembleError::AllocationFailure { len: 1 })
+            .source()
+            .is_some());
+    }
+
+    #[test]
+    fn from_io_reassemble_error() {
+        let err = TpReassembleError::AllocationFailure { len: 1 };
+        assert!(Io(io_err()).io_error().is_some());
+        assert!(Io(io_err()).reassemble_error().is_none());
+        assert!(TpStreamWriteError::from(err.clone()).io_error().is_none());
+        assert_eq!(
+            Some(err.clone()),
+            TpStreamWriteError::from(err).reassemble_error()
+        );
+    }
+}
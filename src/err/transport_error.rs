@@ -0,0 +1,169 @@
+use super::*;
+
+/// Error when sending or receiving SOMEIP messages via a socket.
+#[derive(Debug)]
+pub enum TransportError {
+    /// IO error was encountered on the underlying socket.
+    Io(std::io::Error),
+
+    /// Error while decoding a received SOMEIP message.
+    Slice(SomeipSliceError),
+
+    /// Error while reassembling a received SOMEIP TP message.
+    TpReassemble(TpReassembleError),
+
+    /// Error if a message is bigger then the maximum supported length.
+    MessageTooBig {
+        /// Length of the message in bytes.
+        len: usize,
+        /// Maximum allowed length in bytes.
+        max_len: usize,
+    },
+}
+
+impl TransportError {
+    /// Returns the `std::io::Error` value if the `TransportError` is `Io`.
+    /// Otherwise `None` is returned.
+    #[inline]
+    pub fn io_error(self) -> Option<std::io::Error> {
+        use TransportError::*;
+        match self {
+            Io(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use TransportError::*;
+        match self {
+            Io(err) => write!(f, "SOMEIP Transport IO Error: {err}"),
+            Slice(err) => err.fmt(f),
+            TpReassemble(err) => err.fmt(f),
+            MessageTooBig { len, max_len } => write!(f, "SOMEIP message with a length of {len} byte(s) is bigger then the maximum supported length of {max_len} byte(s)."),
+        }
+    }
+}
+
+impl core::error::Error for TransportError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        use TransportError::*;
+        match self {
+            Io(err) => Some(err),
+            Slice(err) => Some(err),
+            TpReassemble(err) => Some(err),
+            MessageTooBig { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TransportError {
+    fn from(err: std::io::Error) -> TransportError {
+        TransportError::Io(err)
+    }
+}
+
+impl From<SomeipSliceError> for TransportError {
+    fn from(err: SomeipSliceError) -> TransportError {
+        TransportError::Slice(err)
+    }
+}
+
+impl From<TpReassembleError> for TransportError {
+    fn from(err: TpReassembleError) -> TransportError {
+        TransportError::TpReassemble(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransportError::*, *};
+    use alloc::format;
+
+    #[test]
+    fn debug() {
+        let err = MessageTooBig { len: 2, max_len: 1 };
+        let _ = format!("{err:?}");
+    }
+
+    #[test]
+    fn io_error() {
+        assert!(Io(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "failed to fill whole buffer"
+        ))
+        .io_error()
+        .is_some());
+        assert!(MessageTooBig { len: 2, max_len: 1 }.io_error().is_none());
+    }
+
+    #[test]
+    fn fmt() {
+        {
+            let err = std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            );
+            assert_eq!(
+                format!("SOMEIP Transport IO Error: {}", err),
+                format!("{}", Io(err))
+            );
+        }
+        {
+            let err = SomeipSliceError::Content(SomeipHeaderError::UnknownMessageType(3));
+            assert_eq!(format!("{}", err), format!("{}", Slice(err.clone())));
+        }
+        {
+            let err = TpReassembleError::AllocationFailure { len: 0 };
+            assert_eq!(format!("{}", err), format!("{}", TpReassemble(err.clone())));
+        }
+        assert_eq!(
+            format!("{}", MessageTooBig { len: 2, max_len: 1 }),
+            "SOMEIP message with a length of 2 byte(s) is bigger then the maximum supported length of 1 byte(s)."
+        );
+    }
+
+    #[test]
+    fn source() {
+        use core::error::Error;
+        assert!(Io(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "failed to fill whole buffer"
+        ))
+        .source()
+        .is_some());
+        assert!(Slice(SomeipSliceError::Content(
+            SomeipHeaderError::UnknownMessageType(3)
+        ))
+        .source()
+        .is_some());
+        assert!(
+            TpReassemble(TpReassembleError::AllocationFailure { len: 0 })
+                .source()
+                .is_some()
+        );
+        assert!(MessageTooBig { len: 2, max_len: 1 }.source().is_none());
+    }
+
+    #[test]
+    fn from() {
+        assert!(matches!(
+            TransportError::from(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer"
+            )),
+            Io(_)
+        ));
+        assert!(matches!(
+            TransportError::from(SomeipSliceError::Content(
+                SomeipHeaderError::UnknownMessageType(3)
+            )),
+            Slice(_)
+        ));
+        assert!(matches!(
+            TransportError::from(TpReassembleError::AllocationFailure { len: 0 }),
+            TpReassemble(_)
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::err::IdTooLargeError;
+
+/// SOME/IP event id (lower 15 bits of a [`crate::MessageId`] with the
+/// event bit set).
+///
+/// The value never contains the event bit (0x8000), it is added when the
+/// id is combined into a [`crate::MessageId`].
+///
+/// `Display` writes the id in hex (e.g. `0x0001`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u16", into = "u16")
+)]
+pub struct EventId(u16);
+
+impl EventId {
+    /// Maximum value of an event id (15 bits).
+    pub const MAX_U16: u16 = 0x7FFF;
+
+    /// Event id of SOME/IP service discovery messages (0x8100 including
+    /// the event bit).
+    pub const SD: EventId = EventId(0x0100);
+
+    /// Tries to create a [`EventId`] and checks that the passed value
+    /// is smaller or equal than [`EventId::MAX_U16`].
+    ///
+    /// ```
+    /// use someip_parse::{err::IdTooLargeError, EventId};
+    ///
+    /// assert_eq!(0x1234, EventId::try_new(0x1234).unwrap().value());
+    /// assert_eq!(
+    ///     Err(IdTooLargeError { actual: 0x8001 }),
+    ///     EventId::try_new(0x8001)
+    /// );
+    /// ```
+    #[inline]
+    pub const fn try_new(value: u16) -> Result<EventId, IdTooLargeError> {
+        if value <= EventId::MAX_U16 {
+            Ok(EventId(value))
+        } else {
+            Err(IdTooLargeError { actual: value })
+        }
+    }
+
+    /// Creates a [`EventId`] without checking that the value
+    /// is smaller or equal than [`EventId::MAX_U16`].
+    ///
+    /// # Safety
+    ///
+    /// `value` must be smaller or equal than [`EventId::MAX_U16`]
+    /// otherwise the behavior of functions or data structures relying
+    /// on this pre-requirement is undefined.
+    #[inline]
+    pub const unsafe fn new_unchecked(value: u16) -> EventId {
+        debug_assert!(value <= EventId::MAX_U16);
+        EventId(value)
+    }
+
+    /// Returns the raw `u16` value (without the event bit).
+    #[inline]
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for EventId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:04x}", self.0)
+    }
+}
+
+impl From<EventId> for u16 {
+    #[inline]
+    fn from(value: EventId) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<u16> for EventId {
+    type Error = IdTooLargeError;
+
+    #[inline]
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        EventId::try_new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn try_new(value in any::<u16>()) {
+            if value <= EventId::MAX_U16 {
+                let id = EventId::try_new(value).unwrap();
+                prop_assert_eq!(value, id.value());
+                prop_assert_eq!(value, u16::from(id));
+                prop_assert_eq!(Ok(id), EventId::try_from(value));
+                prop_assert_eq!(id, unsafe { EventId::new_unchecked(value) });
+            } else {
+                prop_assert_eq!(Err(IdTooLargeError { actual: value }), EventId::try_new(value));
+                prop_assert_eq!(Err(IdTooLargeError { actual: value }), EventId::try_from(value));
+            }
+        }
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(0x0100, EventId::SD.value());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0x0001", format!("{}", EventId(1)));
+        assert_eq!("0x7fff", format!("{}", EventId(EventId::MAX_U16)));
+        assert_eq!("EventId(1)", format!("{:?}", EventId(1)));
+    }
+}
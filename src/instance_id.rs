@@ -0,0 +1,62 @@
+/// SOME/IP service instance id (used in service discovery entries).
+///
+/// `Display` writes the id in hex (e.g. `0x1234`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct InstanceId(pub u16);
+
+impl InstanceId {
+    /// Wildcard matching all instances of a service (e.g. in find service entries).
+    pub const ANY: InstanceId = InstanceId(0xFFFF);
+
+    /// Returns the raw `u16` value.
+    #[inline]
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for InstanceId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:04x}", self.0)
+    }
+}
+
+impl From<u16> for InstanceId {
+    #[inline]
+    fn from(value: u16) -> Self {
+        InstanceId(value)
+    }
+}
+
+impl From<InstanceId> for u16 {
+    #[inline]
+    fn from(value: InstanceId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn constants() {
+        assert_eq!(0xFFFF, InstanceId::ANY.value());
+    }
+
+    #[test]
+    fn conversions_display() {
+        let id = InstanceId::from(0x1234);
+        assert_eq!(InstanceId(0x1234), id);
+        assert_eq!(0x1234u16, u16::from(id));
+        assert_eq!("0x1234", format!("{}", id));
+        assert_eq!("0x00ab", format!("{}", InstanceId(0xab)));
+        assert_eq!("InstanceId(4660)", format!("{:?}", id));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::*;
+
+/// Message type of a SOME/IP message parsed in lenient mode (see
+/// [`SomeipLenientMsgSlice`]).
+///
+/// Next to the message types of the current specification this also
+/// contains the ACK message types of older SOME/IP revisions and an
+/// `Unknown` arm for all other values. The TP flag is never part of the
+/// value (use [`SomeipLenientMsgSlice::is_tp`] instead).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LenientMessageType {
+    Request,
+    RequestNoReturn,
+    Notification,
+    /// Legacy acknowledgement of a request (0x40).
+    RequestAck,
+    /// Legacy acknowledgement of a request without return (0x41).
+    RequestNoReturnAck,
+    /// Legacy acknowledgement of a notification (0x42).
+    NotificationAck,
+    Response,
+    Error,
+    /// Legacy acknowledgement of a response (0xC0).
+    ResponseAck,
+    /// Legacy acknowledgement of an error (0xC1).
+    ErrorAck,
+    /// Message type value not known (without the TP flag).
+    Unknown(u8),
+}
+
+impl LenientMessageType {
+    /// Decodes the raw message type value. The TP flag is ignored.
+    pub fn from_raw(raw: u8) -> LenientMessageType {
+        use LenientMessageType::*;
+        match raw & !SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG {
+            0x00 => Request,
+            0x01 => RequestNoReturn,
+            0x02 => Notification,
+            0x40 => RequestAck,
+            0x41 => RequestNoReturnAck,
+            0x42 => NotificationAck,
+            0x80 => Response,
+            0x81 => Error,
+            0xC0 => ResponseAck,
+            0xC1 => ErrorAck,
+            value => Unknown(value),
+        }
+    }
+
+    /// Returns the raw message type value (without the TP flag).
+    pub fn to_raw(&self) -> u8 {
+        use LenientMessageType::*;
+        match self {
+            Request => 0x00,
+            RequestNoReturn => 0x01,
+            Notification => 0x02,
+            RequestAck => 0x40,
+            RequestNoReturnAck => 0x41,
+            NotificationAck => 0x42,
+            Response => 0x80,
+            Error => 0x81,
+            ResponseAck => 0xC0,
+            ErrorAck => 0xC1,
+            Unknown(value) => *value,
+        }
+    }
+
+    /// Returns the matching [`MessageType`] if the message type is part of
+    /// the current specification.
+    pub fn to_message_type(&self) -> Option<MessageType> {
+        use LenientMessageType::*;
+        match self {
+            Request => Some(MessageType::Request),
+            RequestNoReturn => Some(MessageType::RequestNoReturn),
+            Notification => Some(MessageType::Notification),
+            Response => Some(MessageType::Response),
+            Error => Some(MessageType::Error),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the message type is one of the legacy ACK types.
+    pub fn is_legacy_ack(&self) -> bool {
+        use LenientMessageType::*;
+        matches!(
+            self,
+            RequestAck | RequestNoReturnAck | NotificationAck | ResponseAck | ErrorAck
+        )
+    }
+}
+
+impl From<MessageType> for LenientMessageType {
+    fn from(value: MessageType) -> Self {
+        LenientMessageType::from_raw(value as u8)
+    }
+}
+
+impl core::fmt::Display for LenientMessageType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use LenientMessageType::*;
+        match self {
+            Request => write!(f, "Request"),
+            RequestNoReturn => write!(f, "Request no Return"),
+            Notification => write!(f, "Notification"),
+            RequestAck => write!(f, "Request ACK"),
+            RequestNoReturnAck => write!(f, "Request no Return ACK"),
+            NotificationAck => write!(f, "Notification ACK"),
+            Response => write!(f, "Response"),
+            Error => write!(f, "Error"),
+            ResponseAck => write!(f, "Response ACK"),
+            ErrorAck => write!(f, "Error ACK"),
+            Unknown(value) => write!(f, "Unknown (0x{value:02x})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn raw() {
+        for raw in 0..=u8::MAX {
+            let value = LenientMessageType::from_raw(raw);
+            assert_eq!(raw & !SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG, value.to_raw());
+        }
+        assert_eq!(
+            LenientMessageType::ResponseAck,
+            LenientMessageType::from_raw(0xC0 | SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG)
+        );
+        assert_eq!(
+            LenientMessageType::Unknown(0x03),
+            LenientMessageType::from_raw(0x03)
+        );
+    }
+
+    #[test]
+    fn to_message_type() {
+        use MessageType as M;
+        for value in [
+            M::Request,
+            M::RequestNoReturn,
+            M::Notification,
+            M::Response,
+            M::Error,
+        ] {
+            let lenient = LenientMessageType::from(value.clone());
+            assert_eq!(Some(value), lenient.to_message_type());
+            assert!(false == lenient.is_legacy_ack());
+        }
+        for raw in [0x40, 0x41, 0x42, 0xC0, 0xC1] {
+            let lenient = LenientMessageType::from_raw(raw);
+            assert_eq!(None, lenient.to_message_type());
+            assert!(lenient.is_legacy_ack());
+        }
+        assert_eq!(None, LenientMessageType::Unknown(0x10).to_message_type());
+        assert!(false == LenientMessageType::Unknown(0x10).is_legacy_ack());
+    }
+
+    #[test]
+    fn display() {
+        use LenientMessageType::*;
+        for (value, expected) in [
+            (Request, "Request"),
+            (RequestNoReturn, "Request no Return"),
+            (Notification, "Notification"),
+            (RequestAck, "Request ACK"),
+            (RequestNoReturnAck, "Request no Return ACK"),
+            (NotificationAck, "Notification ACK"),
+            (Response, "Response"),
+            (Error, "Error"),
+            (ResponseAck, "Response ACK"),
+            (ErrorAck, "Error ACK"),
+            (Unknown(0x3), "Unknown (0x03)"),
+        ] {
+            assert_eq!(expected, format!("{}", value));
+        }
+    }
+}
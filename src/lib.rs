@@ -11,10 +11,22 @@
 //!
 //! # Feature Flags
 //!
-//! * `std` (default): Enables `std::io` read/write helpers and the [`TpPool`]
-//!   helper.
+//! * `std` (default): Enables `std::io` read/write helpers, the [`TpPool`]
+//!   helper, the blocking UDP & TCP endpoints in the [`transport`] module and
+//!   the minimal service runtime in the [`runtime`] module.
 //! * `alloc`: Enables heap-using APIs such as [`TpBuf`] and
 //!   [`sd::SdHeader::to_bytes_vec`] (automatically enabled by `std`).
+//! * `tokio`: Enables asynchronous UDP & TCP sockets based on
+//!   [tokio](https://tokio.rs) in the [`transport`] module.
+//! * `capture`: Enables the `capture` module reading SOMEIP messages
+//!   (including timestamps & socket addresses) from pcap & pcapng files and
+//!   writing synthetic captures of SOMEIP messages or JSON lines exports of
+//!   decoded SOMEIP traffic.
+//! * `cli`: Builds the `someip` command line tool for inspecting captures
+//!   (`dump`, `stats`, `sd`, `json` & `filter` subcommands, implies `capture`).
+//! * `serde`: Implements `Serialize` & `Deserialize` for the owned header,
+//!   SD entry & SD option types and `Serialize` for the slice types (IP
+//!   addresses are written as strings by human readable formats).
 //!
 //! The crate can be used in `no_std` environments with
 //! `default-features = false`. Add the `alloc` feature if heap allocation is
@@ -63,6 +75,7 @@
 //! * [AUTOSAR Foundation](https://www.autosar.org/standards/foundation) \(contains SOMEIP Protocol Specification & SOME/IP Service Discovery Protocol Specification\)
 //! * [SOME/IP Protocol Specification R22-11](https://www.autosar.org/fileadmin/standards/R22-11/FO/AUTOSAR_PRS_SOMEIPProtocol.pdf)
 //! * [SOME/IP Service Discovery Protocol Specification R22-11](https://www.autosar.org/fileadmin/standards/R22-11/FO/AUTOSAR_PRS_SOMEIPServiceDiscoveryProtocol.pdf)
+//! * [E2E Protocol Specification R22-11](https://www.autosar.org/fileadmin/standards/R22-11/FO/AUTOSAR_PRS_E2EProtocol.pdf)
 
 // # Reason for 'bool_comparison' disable:
 //
@@ -79,6 +92,7 @@
 // is more visually striking and is not as easy to overlook as the single
 // character '!'.
 #![allow(clippy::bool_comparison)]
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
@@ -96,24 +110,155 @@ pub mod err;
 /// SOMEIP Service Discovery parsing & writing.
 pub mod sd;
 
+/// AUTOSAR E2E protection profiles (4, 5, 6, 7 & 22) for SOMEIP payloads.
+///
+/// Each profile consists of a configuration, a protector writing the E2E
+/// header into the data & a checker verifying received data and tracking
+/// the counter of the last valid data.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{e2e::*, SomeipMsgSlice};
+///
+/// # let raw = {
+/// #     let mut payload = [0u8; 16];
+/// #     P04Protector::new(P04Config {
+/// #         data_id: 0x0a0b_0c0d,
+/// #         offset: 0,
+/// #         min_data_len: 12,
+/// #         max_data_len: 64,
+/// #         max_delta_counter: 1,
+/// #     }).protect(&mut payload).unwrap();
+/// #     let mut raw = someip_parse::SomeipHeader {
+/// #         message_id: 0x1234_8001,
+/// #         length: 8 + 16,
+/// #         request_id: 1,
+/// #         interface_version: 1,
+/// #         message_type: someip_parse::MessageType::Notification,
+/// #         return_code: 0,
+/// #         tp_header: None,
+/// #     }.base_to_bytes().to_vec();
+/// #     raw.extend_from_slice(&payload);
+/// #     raw
+/// # };
+/// let mut checker = P04Checker::new(P04Config {
+///     data_id: 0x0a0b_0c0d,
+///     offset: 0,
+///     min_data_len: 12,
+///     max_data_len: 64,
+///     max_delta_counter: 1,
+/// });
+///
+/// let msg = SomeipMsgSlice::from_slice(&raw).unwrap();
+/// match checker.check(msg.payload()) {
+///     E2eCheckStatus::Ok => println!("valid data"),
+///     status => println!("E2E check failed: {:?}", status),
+/// }
+/// ```
+pub mod e2e;
+
+/// AUTOSAR SecOC (secure onboard communication) handling of secured SOMEIP
+/// payloads.
+pub mod secoc;
+
+/// Sending & receiving SOMEIP messages via UDP & TCP.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod transport;
+
+/// Minimal SOMEIP runtime dispatching requests to method handlers & sending
+/// events to subscribers.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod runtime;
+
+/// Reading & writing SOMEIP messages from/to pcap & pcapng capture files
+/// (and exporting them as JSON lines).
+#[cfg(feature = "capture")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capture")))]
+pub mod capture;
+
+mod dissect;
+
+#[cfg(feature = "serde")]
+mod serde_util;
+
+mod client_id;
+pub use client_id::*;
+
+mod event_id;
+pub use event_id::*;
+
+mod instance_id;
+pub use instance_id::*;
+
+mod magic_cookie;
+pub use magic_cookie::*;
+
+mod major_version;
+pub use major_version::*;
+
+mod message_id;
+pub use message_id::*;
+
+mod method_id;
+pub use method_id::*;
+
+mod minor_version;
+pub use minor_version::*;
+
+mod request_id;
+pub use request_id::*;
+
+mod service_id;
+pub use service_id::*;
+
+mod session_id;
+pub use session_id::*;
+
 mod message_type;
 pub use message_type::*;
 
+mod lenient_message_type;
+pub use lenient_message_type::*;
+
 mod return_code;
 pub use return_code::*;
 
+mod tp_overlap_policy;
+pub use tp_overlap_policy::*;
+
 mod tp_range;
 pub use tp_range::*;
 
 mod someip_msgs_iterator;
 pub use someip_msgs_iterator::*;
 
+mod someip_lenient_findings;
+pub use someip_lenient_findings::*;
+
+mod someip_lenient_msg_slice;
+pub use someip_lenient_msg_slice::*;
+
+mod someip_lenient_msgs_iterator;
+pub use someip_lenient_msgs_iterator::*;
+
 mod someip_header;
 pub use someip_header::*;
 
+mod someip_header_builder;
+pub use someip_header_builder::*;
+
 mod someip_msg_slice;
 pub use someip_msg_slice::*;
 
+mod someip_msg_slice_mut;
+pub use someip_msg_slice_mut::*;
+
+mod someip_msg_writer;
+pub use someip_msg_writer::*;
+
 mod tp_buf_config;
 pub use tp_buf_config::*;
 
@@ -124,9 +269,38 @@ mod tp_buf;
 #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub use tp_buf::*;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+mod tp_buf_progress;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use tp_buf_progress::*;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+mod tp_stream_reassembler;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use tp_stream_reassembler::*;
+
+mod tp_buf_fixed;
+pub use tp_buf_fixed::*;
+
+mod tp_eviction_policy;
+pub use tp_eviction_policy::*;
+
 mod tp_header;
 pub use tp_header::*;
 
+mod tp_segment;
+pub(crate) use tp_segment::*;
+
+mod tp_stream_key;
+pub use tp_stream_key::*;
+
+mod tp_stream_key_fields;
+pub use tp_stream_key_fields::*;
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 mod tp_pool;
@@ -134,6 +308,44 @@ mod tp_pool;
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub use tp_pool::*;
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod tp_pool_drop_event;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tp_pool_drop_event::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod tp_pool_drop_reason;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tp_pool_drop_reason::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod tp_pool_limits;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tp_pool_limits::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod tp_pool_overflow_policy;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tp_pool_overflow_policy::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod tp_pool_stream;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tp_pool_stream::*;
+
+mod tp_pool_fixed;
+pub use tp_pool_fixed::*;
+
 /// Maximum allowed TP segment length.
 pub const TP_UDP_MAX_SEGMENT_LEN: usize = 1400;
 
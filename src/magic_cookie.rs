@@ -0,0 +1,164 @@
+use crate::*;
+
+/// Magic cookie messages that can be inserted into a SOMEIP TCP stream to
+/// allow the receiver to resynchronize after a broken message.
+///
+/// The magic cookies are complete SOMEIP messages without payload and are
+/// defined by the SOMEIP specification (PRS_SOMEIP_00154 - PRS_SOMEIP_00160)
+/// as:
+///
+/// | Field             | Client -> Server | Server -> Client |
+/// |-------------------|------------------|------------------|
+/// | Message ID        | `0xFFFF_0000`    | `0xFFFF_8000`    |
+/// | Length            | `0x0000_0008`    | `0x0000_0008`    |
+/// | Request ID        | `0xDEAD_BEEF`    | `0xDEAD_BEEF`    |
+/// | Protocol Version  | `0x01`           | `0x01`           |
+/// | Interface Version | `0x01`           | `0x01`           |
+/// | Message Type      | `0x01`           | `0x02`           |
+/// | Return Code       | `0x00`           | `0x00`           |
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{MagicCookie, SomeipMsgSlice};
+///
+/// let bytes = MagicCookie::Client.to_bytes();
+/// let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+/// assert!(slice.is_magic_cookie());
+/// assert_eq!(Some(MagicCookie::Client), MagicCookie::from_msg(&slice));
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum MagicCookie {
+    /// Magic cookie sent by a client to a server.
+    Client,
+    /// Magic cookie sent by a server to a client.
+    Server,
+}
+
+impl MagicCookie {
+    /// Message id of a magic cookie sent by a client.
+    pub const CLIENT_MESSAGE_ID: u32 = 0xFFFF_0000;
+
+    /// Message id of a magic cookie sent by a server.
+    pub const SERVER_MESSAGE_ID: u32 = 0xFFFF_8000;
+
+    /// Request id of all magic cookie messages.
+    pub const REQUEST_ID: u32 = 0xDEAD_BEEF;
+
+    /// Length of a serialized magic cookie message.
+    pub const LEN: usize = SOMEIP_HEADER_LENGTH;
+
+    /// Returns the message id of the magic cookie.
+    #[inline]
+    pub fn message_id(&self) -> u32 {
+        match self {
+            MagicCookie::Client => Self::CLIENT_MESSAGE_ID,
+            MagicCookie::Server => Self::SERVER_MESSAGE_ID,
+        }
+    }
+
+    /// Returns the message type of the magic cookie.
+    #[inline]
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            MagicCookie::Client => MessageType::RequestNoReturn,
+            MagicCookie::Server => MessageType::Notification,
+        }
+    }
+
+    /// Returns the serialized magic cookie message.
+    pub fn to_bytes(&self) -> [u8; MagicCookie::LEN] {
+        SomeipHeader {
+            message_id: self.message_id(),
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            request_id: Self::REQUEST_ID,
+            interface_version: 0x01,
+            message_type: self.message_type(),
+            return_code: ReturnCode::Ok.into(),
+            tp_header: None,
+        }
+        .base_to_bytes()
+    }
+
+    /// Returns the magic cookie type if the given message is a magic cookie.
+    ///
+    /// `None` is returned if any of the header fields do not match the values
+    /// defined for magic cookies.
+    pub fn from_msg(msg: &SomeipMsgSlice) -> Option<MagicCookie> {
+        let cookie = match msg.message_id() {
+            Self::CLIENT_MESSAGE_ID => MagicCookie::Client,
+            Self::SERVER_MESSAGE_ID => MagicCookie::Server,
+            _ => return None,
+        };
+        if msg.slice() == &cookie.to_bytes()[..] {
+            Some(cookie)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let cookie = MagicCookie::Client;
+        let _ = format!("{:?}", cookie);
+        assert_eq!(cookie, cookie.clone());
+        assert_eq!(cookie.cmp(&cookie), core::cmp::Ordering::Equal);
+        assert_eq!(
+            cookie.partial_cmp(&cookie),
+            Some(core::cmp::Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn to_bytes() {
+        assert_eq!(
+            MagicCookie::Client.to_bytes(),
+            [
+                0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x01,
+                0x01, 0x00
+            ]
+        );
+        assert_eq!(
+            MagicCookie::Server.to_bytes(),
+            [
+                0xFF, 0xFF, 0x80, 0x00, 0x00, 0x00, 0x00, 0x08, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x01,
+                0x02, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn from_msg() {
+        for cookie in [MagicCookie::Client, MagicCookie::Server] {
+            let bytes = cookie.to_bytes();
+            let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(Some(cookie), MagicCookie::from_msg(&msg));
+            assert!(msg.is_magic_cookie());
+
+            // any modified field should result in the message not being
+            // detected as a magic cookie
+            for index in [2, 7, 8, 13, 14, 15] {
+                let mut modified = bytes;
+                modified[index] ^= 0b1000_0000;
+                let Ok(msg) = SomeipMsgSlice::from_slice(&modified) else {
+                    continue;
+                };
+                assert_eq!(None, MagicCookie::from_msg(&msg));
+                assert_eq!(false, msg.is_magic_cookie());
+            }
+        }
+
+        // message with payload
+        let mut bytes = [0u8; MagicCookie::LEN + 4];
+        bytes[..MagicCookie::LEN].copy_from_slice(&MagicCookie::Client.to_bytes());
+        bytes[7] = 8 + 4;
+        let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+        assert_eq!(None, MagicCookie::from_msg(&msg));
+    }
+}
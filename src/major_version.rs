@@ -0,0 +1,62 @@
+/// SOME/IP major interface version (used in service discovery entries).
+///
+/// `Display` writes the version in hex (e.g. `0x01`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct MajorVersion(pub u8);
+
+impl MajorVersion {
+    /// Wildcard matching all major versions (e.g. in find service entries).
+    pub const ANY: MajorVersion = MajorVersion(0xFF);
+
+    /// Returns the raw `u8` value.
+    #[inline]
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for MajorVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:02x}", self.0)
+    }
+}
+
+impl From<u8> for MajorVersion {
+    #[inline]
+    fn from(value: u8) -> Self {
+        MajorVersion(value)
+    }
+}
+
+impl From<MajorVersion> for u8 {
+    #[inline]
+    fn from(value: MajorVersion) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn constants() {
+        assert_eq!(0xFF, MajorVersion::ANY.value());
+    }
+
+    #[test]
+    fn conversions_display() {
+        let version = MajorVersion::from(0x12);
+        assert_eq!(MajorVersion(0x12), version);
+        assert_eq!(0x12u8, u8::from(version));
+        assert_eq!("0x12", format!("{}", version));
+        assert_eq!("0x0a", format!("{}", MajorVersion(0xa)));
+        assert_eq!("MajorVersion(18)", format!("{:?}", version));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::*;
+
+/// SOME/IP message id consisting of the [`ServiceId`] (upper 16 bits) and
+/// a [`MethodId`] or an [`EventId`] (lower 16 bits). The event bit (0x8000)
+/// determines if the lower 15 bits are an event or a method id.
+///
+/// `Display` writes the id in hex (e.g. `0x12348001`).
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let id = MessageId::new_event(ServiceId(0x1234), EventId::try_new(1).unwrap());
+/// assert_eq!(MessageId(0x1234_8001), id);
+/// assert_eq!(ServiceId(0x1234), id.service_id());
+/// assert_eq!(Some(EventId::try_new(1).unwrap()), id.event_id());
+/// assert_eq!(None, id.method_id());
+/// ```
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct MessageId(pub u32);
+
+impl MessageId {
+    /// Message id of SOME/IP service discovery messages.
+    pub const SD: MessageId = MessageId(SOMEIP_SD_MESSAGE_ID);
+
+    /// Bit in the lower 16 bits marking the id as an event id.
+    pub const EVENT_BIT: u16 = 0x8000;
+
+    /// Creates a message id of a method.
+    #[inline]
+    pub const fn new_method(service_id: ServiceId, method_id: MethodId) -> MessageId {
+        MessageId(((service_id.value() as u32) << 16) | method_id.value() as u32)
+    }
+
+    /// Creates a message id of an event (sets the event bit).
+    #[inline]
+    pub const fn new_event(service_id: ServiceId, event_id: EventId) -> MessageId {
+        MessageId(
+            ((service_id.value() as u32) << 16) | (MessageId::EVENT_BIT | event_id.value()) as u32,
+        )
+    }
+
+    /// Returns the raw `u32` value.
+    #[inline]
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the service id (upper 16 bits).
+    #[inline]
+    pub const fn service_id(self) -> ServiceId {
+        ServiceId((self.0 >> 16) as u16)
+    }
+
+    /// Returns true if the event bit is set.
+    #[inline]
+    pub const fn is_event(self) -> bool {
+        0 != self.event_or_method_id() & MessageId::EVENT_BIT
+    }
+
+    /// Returns the event id or method id (lower 16 bits, including the
+    /// event bit).
+    #[inline]
+    pub const fn event_or_method_id(self) -> u16 {
+        (self.0 & 0xffff) as u16
+    }
+
+    /// Returns the method id. `None` if the event bit is set.
+    #[inline]
+    pub const fn method_id(self) -> Option<MethodId> {
+        if self.is_event() {
+            None
+        } else {
+            // SAFETY: The event bit is not set so the value fits into 15 bits.
+            Some(unsafe { MethodId::new_unchecked(self.event_or_method_id()) })
+        }
+    }
+
+    /// Returns the event id (without the event bit). `None` if the event
+    /// bit is not set.
+    #[inline]
+    pub const fn event_id(self) -> Option<EventId> {
+        if self.is_event() {
+            // SAFETY: The event bit is masked out so the value fits into 15 bits.
+            Some(unsafe { EventId::new_unchecked(self.event_or_method_id() & EventId::MAX_U16) })
+        } else {
+            None
+        }
+    }
+}
+
+impl core::fmt::Display for MessageId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl From<u32> for MessageId {
+    #[inline]
+    fn from(value: u32) -> Self {
+        MessageId(value)
+    }
+}
+
+impl From<MessageId> for u32 {
+    #[inline]
+    fn from(value: MessageId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(SOMEIP_SD_MESSAGE_ID, MessageId::SD.value());
+        assert_eq!(ServiceId::SD, MessageId::SD.service_id());
+        assert_eq!(Some(EventId::SD), MessageId::SD.event_id());
+        assert_eq!(
+            MessageId::SD,
+            MessageId::new_event(ServiceId::SD, EventId::SD)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn split_join(value in any::<u32>()) {
+            let id = MessageId::from(value);
+            prop_assert_eq!(value, u32::from(id));
+            prop_assert_eq!(ServiceId((value >> 16) as u16), id.service_id());
+            prop_assert_eq!(value as u16, id.event_or_method_id());
+            if 0 != value & 0x8000 {
+                prop_assert!(id.is_event());
+                prop_assert_eq!(None, id.method_id());
+                let event_id = id.event_id().unwrap();
+                prop_assert_eq!((value & 0x7fff) as u16, event_id.value());
+                prop_assert_eq!(id, MessageId::new_event(id.service_id(), event_id));
+            } else {
+                prop_assert!(false == id.is_event());
+                prop_assert_eq!(None, id.event_id());
+                let method_id = id.method_id().unwrap();
+                prop_assert_eq!((value & 0x7fff) as u16, method_id.value());
+                prop_assert_eq!(id, MessageId::new_method(id.service_id(), method_id));
+            }
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0x12348001", format!("{}", MessageId(0x1234_8001)));
+        assert_eq!("0x00010002", format!("{}", MessageId(0x0001_0002)));
+    }
+}
@@ -1,5 +1,6 @@
 ///Message types of a SOME/IP message.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageType {
     Request = 0x0,
     RequestNoReturn = 0x1,
@@ -7,3 +8,36 @@ pub enum MessageType {
     Response = 0x80,
     Error = 0x81,
 }
+
+impl core::fmt::Display for MessageType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use MessageType::*;
+        match self {
+            Request => write!(f, "Request"),
+            RequestNoReturn => write!(f, "Request no Return"),
+            Notification => write!(f, "Notification"),
+            Response => write!(f, "Response"),
+            Error => write!(f, "Error"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn display() {
+        use MessageType::*;
+        for (value, expected) in [
+            (Request, "Request"),
+            (RequestNoReturn, "Request no Return"),
+            (Notification, "Notification"),
+            (Response, "Response"),
+            (Error, "Error"),
+        ] {
+            assert_eq!(expected, format!("{}", value));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::err::IdTooLargeError;
+
+/// SOME/IP method id (lower 15 bits of a [`crate::MessageId`] with the
+/// event bit not set).
+///
+/// The value never contains the event bit (0x8000).
+///
+/// `Display` writes the id in hex (e.g. `0x0001`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u16", into = "u16")
+)]
+pub struct MethodId(u16);
+
+impl MethodId {
+    /// Maximum value of a method id (15 bits).
+    pub const MAX_U16: u16 = 0x7FFF;
+
+    /// Tries to create a [`MethodId`] and checks that the passed value
+    /// is smaller or equal than [`MethodId::MAX_U16`].
+    ///
+    /// ```
+    /// use someip_parse::{err::IdTooLargeError, MethodId};
+    ///
+    /// assert_eq!(0x1234, MethodId::try_new(0x1234).unwrap().value());
+    /// assert_eq!(
+    ///     Err(IdTooLargeError { actual: 0x8001 }),
+    ///     MethodId::try_new(0x8001)
+    /// );
+    /// ```
+    #[inline]
+    pub const fn try_new(value: u16) -> Result<MethodId, IdTooLargeError> {
+        if value <= MethodId::MAX_U16 {
+            Ok(MethodId(value))
+        } else {
+            Err(IdTooLargeError { actual: value })
+        }
+    }
+
+    /// Creates a [`MethodId`] without checking that the value
+    /// is smaller or equal than [`MethodId::MAX_U16`].
+    ///
+    /// # Safety
+    ///
+    /// `value` must be smaller or equal than [`MethodId::MAX_U16`]
+    /// otherwise the behavior of functions or data structures relying
+    /// on this pre-requirement is undefined.
+    #[inline]
+    pub const unsafe fn new_unchecked(value: u16) -> MethodId {
+        debug_assert!(value <= MethodId::MAX_U16);
+        MethodId(value)
+    }
+
+    /// Returns the raw `u16` value (without the event bit).
+    #[inline]
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for MethodId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:04x}", self.0)
+    }
+}
+
+impl From<MethodId> for u16 {
+    #[inline]
+    fn from(value: MethodId) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<u16> for MethodId {
+    type Error = IdTooLargeError;
+
+    #[inline]
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        MethodId::try_new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn try_new(value in any::<u16>()) {
+            if value <= MethodId::MAX_U16 {
+                let id = MethodId::try_new(value).unwrap();
+                prop_assert_eq!(value, id.value());
+                prop_assert_eq!(value, u16::from(id));
+                prop_assert_eq!(Ok(id), MethodId::try_from(value));
+                prop_assert_eq!(id, unsafe { MethodId::new_unchecked(value) });
+            } else {
+                prop_assert_eq!(Err(IdTooLargeError { actual: value }), MethodId::try_new(value));
+                prop_assert_eq!(Err(IdTooLargeError { actual: value }), MethodId::try_from(value));
+            }
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0x0001", format!("{}", MethodId(1)));
+        assert_eq!("0x7fff", format!("{}", MethodId(MethodId::MAX_U16)));
+        assert_eq!("MethodId(1)", format!("{:?}", MethodId(1)));
+    }
+}
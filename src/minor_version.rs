@@ -0,0 +1,61 @@
+/// SOME/IP minor interface version (used in service discovery service entries).
+///
+/// `Display` writes the version in hex (e.g. `0x00000001`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct MinorVersion(pub u32);
+
+impl MinorVersion {
+    /// Wildcard matching all minor versions (e.g. in find service entries).
+    pub const ANY: MinorVersion = MinorVersion(0xFFFF_FFFF);
+
+    /// Returns the raw `u32` value.
+    #[inline]
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for MinorVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl From<u32> for MinorVersion {
+    #[inline]
+    fn from(value: u32) -> Self {
+        MinorVersion(value)
+    }
+}
+
+impl From<MinorVersion> for u32 {
+    #[inline]
+    fn from(value: MinorVersion) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn constants() {
+        assert_eq!(0xFFFF_FFFF, MinorVersion::ANY.value());
+    }
+
+    #[test]
+    fn conversions_display() {
+        let version = MinorVersion::from(0x1234);
+        assert_eq!(MinorVersion(0x1234), version);
+        assert_eq!(0x1234u32, u32::from(version));
+        assert_eq!("0x00001234", format!("{}", version));
+        assert_eq!("MinorVersion(4660)", format!("{:?}", version));
+    }
+}
@@ -16,7 +16,7 @@ fn someip_header_message_type() -> impl Strategy<Value = MessageType> {
 
 prop_compose! {
     pub fn someip_tp_any()(
-        offset in 0..(std::u32::MAX / 16),
+        offset in 0..(u32::MAX / 16),
         more_segments in any::<bool>())
     -> TpHeader
     {
@@ -190,8 +190,8 @@ prop_compose! {
 
 pub fn someip_sd_entry_any() -> impl Strategy<Value = sd::SdEntry> {
     prop_oneof![
-        someip_sd_eventgroup_entry_any().prop_map(|e| sd::SdEntry::Eventgroup(e)),
-        someip_sd_service_entry_any().prop_map(|e| sd::SdEntry::Service(e)),
+        someip_sd_eventgroup_entry_any().prop_map(sd::SdEntry::Eventgroup),
+        someip_sd_service_entry_any().prop_map(sd::SdEntry::Service),
     ]
 }
 
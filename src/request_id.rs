@@ -0,0 +1,91 @@
+use crate::*;
+
+/// SOME/IP request id consisting of the [`ClientId`] (upper 16 bits) and
+/// the [`SessionId`] (lower 16 bits).
+///
+/// `Display` writes the id in hex (e.g. `0x00010002`).
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let id = RequestId::new(ClientId::with_prefix(0x12, 8, 0x34), SessionId(1));
+/// assert_eq!(RequestId(0x1234_0001), id);
+/// assert_eq!(0x12, id.client_id().prefix(8));
+/// assert_eq!(SessionId(1), id.session_id());
+/// ```
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct RequestId(pub u32);
+
+impl RequestId {
+    /// Creates a request id from a client & session id.
+    #[inline]
+    pub const fn new(client_id: ClientId, session_id: SessionId) -> RequestId {
+        RequestId(((client_id.value() as u32) << 16) | session_id.value() as u32)
+    }
+
+    /// Returns the raw `u32` value.
+    #[inline]
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the client id (upper 16 bits).
+    #[inline]
+    pub const fn client_id(self) -> ClientId {
+        ClientId((self.0 >> 16) as u16)
+    }
+
+    /// Returns the session id (lower 16 bits).
+    #[inline]
+    pub const fn session_id(self) -> SessionId {
+        SessionId((self.0 & 0xffff) as u16)
+    }
+}
+
+impl core::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl From<u32> for RequestId {
+    #[inline]
+    fn from(value: u32) -> Self {
+        RequestId(value)
+    }
+}
+
+impl From<RequestId> for u32 {
+    #[inline]
+    fn from(value: RequestId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn split_join(value in any::<u32>()) {
+            let id = RequestId::from(value);
+            prop_assert_eq!(value, u32::from(id));
+            prop_assert_eq!(ClientId((value >> 16) as u16), id.client_id());
+            prop_assert_eq!(SessionId(value as u16), id.session_id());
+            prop_assert_eq!(id, RequestId::new(id.client_id(), id.session_id()));
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0x00010002", format!("{}", RequestId(0x0001_0002)));
+    }
+}
@@ -1,5 +1,6 @@
 ///Return code contained in a SOME/IP header.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReturnCode {
     Ok,                    // 0x00
     NotOk,                 // 0x01
@@ -37,6 +38,51 @@ impl From<ReturnCode> for u8 {
     }
 }
 
+impl From<u8> for ReturnCode {
+    /// Converts a raw return code (values from 0x0b to 0x1f are mapped to
+    /// [`ReturnCode::Generic`] & all values above to
+    /// [`ReturnCode::InterfaceError`]).
+    fn from(value: u8) -> ReturnCode {
+        use ReturnCode::*;
+        match value {
+            0x00 => Ok,
+            0x01 => NotOk,
+            0x02 => UnknownService,
+            0x03 => UnknownMethod,
+            0x04 => NotReady,
+            0x05 => NotReachable,
+            0x06 => Timeout,
+            0x07 => WrongProtocolVersion,
+            0x08 => WrongInterfaceVersion,
+            0x09 => MalformedMessage,
+            0x0a => WrongMessageType,
+            0x0b..=0x1f => Generic(value),
+            _ => InterfaceError(value),
+        }
+    }
+}
+
+impl core::fmt::Display for ReturnCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use ReturnCode::*;
+        match self {
+            Ok => write!(f, "Ok"),
+            NotOk => write!(f, "Not Ok"),
+            UnknownService => write!(f, "Unknown Service"),
+            UnknownMethod => write!(f, "Unknown Method"),
+            NotReady => write!(f, "Not Ready"),
+            NotReachable => write!(f, "Not Reachable"),
+            Timeout => write!(f, "Timeout"),
+            WrongProtocolVersion => write!(f, "Wrong Protocol Version"),
+            WrongInterfaceVersion => write!(f, "Wrong Interface Version"),
+            MalformedMessage => write!(f, "Malformed Message"),
+            WrongMessageType => write!(f, "Wrong Message Type"),
+            Generic(value) => write!(f, "Reserved Generic Error 0x{value:02x}"),
+            InterfaceError(value) => write!(f, "Interface Error 0x{value:02x}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::format;
@@ -94,7 +140,36 @@ mod tests {
             for (ref input, ref expected) in values.iter() {
                 let result: u8 = (*input).into();
                 assert_eq!(*expected, result);
+                assert_eq!(*input, ReturnCode::from(*expected));
             }
         }
     }
+
+    #[test]
+    fn from_u8_reserved() {
+        assert_eq!(ReturnCode::InterfaceError(0x5f), ReturnCode::from(0x5f));
+        assert_eq!(ReturnCode::InterfaceError(0xff), ReturnCode::from(0xff));
+    }
+
+    #[test]
+    fn display() {
+        use crate::ReturnCode::*;
+        for (value, expected) in [
+            (Ok, "Ok"),
+            (NotOk, "Not Ok"),
+            (UnknownService, "Unknown Service"),
+            (UnknownMethod, "Unknown Method"),
+            (NotReady, "Not Ready"),
+            (NotReachable, "Not Reachable"),
+            (Timeout, "Timeout"),
+            (WrongProtocolVersion, "Wrong Protocol Version"),
+            (WrongInterfaceVersion, "Wrong Interface Version"),
+            (MalformedMessage, "Malformed Message"),
+            (WrongMessageType, "Wrong Message Type"),
+            (Generic(0x0b), "Reserved Generic Error 0x0b"),
+            (InterfaceError(0x20), "Interface Error 0x20"),
+        ] {
+            assert_eq!(expected, format!("{}", value));
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use std::vec::Vec;
+
+/// Field of a service consisting of a value and an optional getter method,
+/// setter method and notifier event.
+///
+/// Getter & setter requests are answered by the runtime. A successful
+/// setter request responds with the new value and (like
+/// [`crate::runtime::UdpRuntime::set_field`]) sends the new value via the
+/// notifier event to all subscribers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Field {
+    /// Method id of the getter method.
+    pub getter_id: Option<u16>,
+    /// Method id of the setter method.
+    pub setter_id: Option<u16>,
+    /// Event id (without the event bit) of the notifier event.
+    pub notifier_id: Option<u16>,
+    /// Current (serialized) value of the field.
+    pub value: Vec<u8>,
+}
+
+/// Handle identifying a field of a [`crate::runtime::Service`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FieldHandle(pub(crate) usize);
@@ -0,0 +1,13 @@
+use crate::{
+    err::{SdValueError, TransportError},
+    *,
+};
+
+mod field;
+pub use field::*;
+
+mod service;
+pub use service::*;
+
+mod udp_runtime;
+pub use udp_runtime::*;
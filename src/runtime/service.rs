@@ -0,0 +1,229 @@
+use super::*;
+use std::{boxed::Box, collections::HashMap, net::SocketAddr, vec::Vec};
+
+/// Handler called for a received request of a method.
+///
+/// The handler gets the received request & returns the payload of the
+/// response or the return code of the error response that should be sent.
+/// Responses are only sent for requests of the type
+/// [`MessageType::Request`], the result for [`MessageType::RequestNoReturn`]
+/// messages is ignored.
+pub type MethodHandler = Box<dyn FnMut(&SomeipMsgSlice<'_>) -> Result<Vec<u8>, ReturnCode>>;
+
+/// Eventgroup of a service with the currently known subscribers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Eventgroup {
+    /// Event ids (without event bit) contained in the eventgroup.
+    pub(crate) events: Vec<u16>,
+    /// Addresses of the subscribers.
+    pub(crate) subscribers: Vec<SocketAddr>,
+}
+
+/// Service instance provided by a runtime containing the method handlers,
+/// eventgroups & fields of the service.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{runtime::{Field, Service}, ReturnCode};
+///
+/// let mut service = Service::new(0x1234, 0x0001, 1, 0);
+///
+/// // method 0x0001 echoes the request payload
+/// service.add_method(0x0001, |request| Ok(request.payload().to_vec()));
+///
+/// // method 0x0002 always fails
+/// service.add_method(0x0002, |_| Err(ReturnCode::NotReady));
+///
+/// // event 0x0001 is part of eventgroup 0x0010
+/// service.add_eventgroup(0x0010, &[0x0001, 0x0002]);
+///
+/// // field with getter, setter & notifier (notifier is event 0x0002)
+/// let field = service.add_field(Field {
+///     getter_id: Some(0x0003),
+///     setter_id: Some(0x0004),
+///     notifier_id: Some(0x0002),
+///     value: vec![0],
+/// });
+/// assert_eq!(Some(&[0u8][..]), service.field(field));
+/// ```
+pub struct Service {
+    pub(crate) service_id: u16,
+    pub(crate) instance_id: u16,
+    pub(crate) major_version: u8,
+    pub(crate) minor_version: u32,
+    pub(crate) methods: HashMap<u16, MethodHandler>,
+    pub(crate) eventgroups: HashMap<u16, Eventgroup>,
+    pub(crate) fields: Vec<Field>,
+}
+
+impl Service {
+    /// Creates a service instance without methods, eventgroups & fields.
+    ///
+    /// The `major_version` is also used as the interface version of the
+    /// SOMEIP messages.
+    pub fn new(
+        service_id: u16,
+        instance_id: u16,
+        major_version: u8,
+        minor_version: u32,
+    ) -> Service {
+        Service {
+            service_id,
+            instance_id,
+            major_version,
+            minor_version,
+            methods: HashMap::new(),
+            eventgroups: HashMap::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn service_id(&self) -> u16 {
+        self.service_id
+    }
+
+    #[inline]
+    pub fn instance_id(&self) -> u16 {
+        self.instance_id
+    }
+
+    #[inline]
+    pub fn major_version(&self) -> u8 {
+        self.major_version
+    }
+
+    #[inline]
+    pub fn minor_version(&self) -> u32 {
+        self.minor_version
+    }
+
+    /// Registers the handler for the method with the given id (replacing
+    /// previously registered handlers for the same method).
+    pub fn add_method<F>(&mut self, method_id: u16, handler: F)
+    where
+        F: FnMut(&SomeipMsgSlice<'_>) -> Result<Vec<u8>, ReturnCode> + 'static,
+    {
+        self.methods.insert(method_id, Box::new(handler));
+    }
+
+    /// Adds an eventgroup containing the given events (ids without the
+    /// event bit).
+    ///
+    /// Only eventgroups added to the service can be subscribed via SOMEIP SD.
+    pub fn add_eventgroup(&mut self, eventgroup_id: u16, events: &[u16]) {
+        self.eventgroups.entry(eventgroup_id).or_default().events = events.to_vec();
+    }
+
+    /// Returns the currently known subscribers of the given eventgroup.
+    pub fn subscribers(&self, eventgroup_id: u16) -> &[SocketAddr] {
+        self.eventgroups
+            .get(&eventgroup_id)
+            .map(|e| &e.subscribers[..])
+            .unwrap_or(&[])
+    }
+
+    /// Adds a field & returns the handle to access it.
+    pub fn add_field(&mut self, field: Field) -> FieldHandle {
+        self.fields.push(field);
+        FieldHandle(self.fields.len() - 1)
+    }
+
+    /// Returns the current value of the field.
+    pub fn field(&self, handle: FieldHandle) -> Option<&[u8]> {
+        self.fields.get(handle.0).map(|f| &f.value[..])
+    }
+
+    /// Returns all subscribers of eventgroups containing the given event
+    /// (without duplicates).
+    pub(crate) fn event_subscribers(&self, event_id: u16) -> Vec<SocketAddr> {
+        let mut result = Vec::new();
+        for group in self.eventgroups.values() {
+            if group.events.contains(&event_id) {
+                for s in &group.subscribers {
+                    if false == result.contains(s) {
+                        result.push(*s);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+impl core::fmt::Debug for Service {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut methods: Vec<_> = self.methods.keys().collect();
+        methods.sort();
+        f.debug_struct("Service")
+            .field("service_id", &self.service_id)
+            .field("instance_id", &self.instance_id)
+            .field("major_version", &self.major_version)
+            .field("minor_version", &self.minor_version)
+            .field("methods", &methods)
+            .field("eventgroups", &self.eventgroups)
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        format,
+        net::{Ipv4Addr, SocketAddrV4},
+    };
+
+    #[test]
+    fn debug() {
+        let mut service = Service::new(1, 2, 3, 4);
+        service.add_method(1, |_| Ok(Vec::new()));
+        let _ = format!("{:?}", service);
+    }
+
+    #[test]
+    fn accessors() {
+        let service = Service::new(1, 2, 3, 4);
+        assert_eq!(1, service.service_id());
+        assert_eq!(2, service.instance_id());
+        assert_eq!(3, service.major_version());
+        assert_eq!(4, service.minor_version());
+    }
+
+    #[test]
+    fn fields() {
+        let mut service = Service::new(1, 2, 3, 4);
+        let a = service.add_field(Field {
+            value: std::vec![1],
+            ..Default::default()
+        });
+        let b = service.add_field(Default::default());
+        assert_ne!(a, b);
+        assert_eq!(Some(&[1u8][..]), service.field(a));
+        assert_eq!(Some(&[][..]), service.field(b));
+        assert_eq!(None, service.field(FieldHandle(2)));
+    }
+
+    #[test]
+    fn event_subscribers() {
+        let addr_a = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1));
+        let addr_b = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 2));
+
+        let mut service = Service::new(1, 2, 3, 4);
+        service.add_eventgroup(1, &[1, 2]);
+        service.add_eventgroup(2, &[2, 3]);
+        assert_eq!(0, service.subscribers(1).len());
+        assert_eq!(0, service.subscribers(3).len());
+
+        service.eventgroups.get_mut(&1).unwrap().subscribers = std::vec![addr_a];
+        service.eventgroups.get_mut(&2).unwrap().subscribers = std::vec![addr_a, addr_b];
+        assert_eq!(&[addr_a], service.subscribers(1));
+        assert_eq!(std::vec![addr_a], service.event_subscribers(1));
+        let mut both = service.event_subscribers(2);
+        both.sort();
+        assert_eq!(std::vec![addr_a, addr_b], both);
+        assert_eq!(0, service.event_subscribers(4).len());
+    }
+}
@@ -0,0 +1,1380 @@
+use super::*;
+use crate::{
+    err::RuntimeError,
+    sd::{entries::*, options::*, *},
+    transport::UdpEndpoint,
+};
+use std::{
+    collections::BTreeMap,
+    net::{SocketAddr, ToSocketAddrs},
+    vec::Vec,
+};
+
+/// Default TTL (in seconds) of the service offers sent by an [`UdpRuntime`].
+pub const DEFAULT_OFFER_TTL: u32 = 3;
+
+/// Message that has to be sent after a received message was processed.
+#[derive(Debug)]
+enum Outgoing {
+    /// Response to a request.
+    Response {
+        header: SomeipHeader,
+        payload: Vec<u8>,
+        target: SocketAddr,
+    },
+    /// SOMEIP SD message.
+    Sd {
+        entries: Vec<SdEntry>,
+        options: Vec<SdOption>,
+        target: SocketAddr,
+    },
+    /// Event to a single subscriber or all subscribers (if target is `None`).
+    Event {
+        service_id: u16,
+        instance_id: u16,
+        event_id: u16,
+        payload: Vec<u8>,
+        target: Option<SocketAddr>,
+    },
+}
+
+/// Minimal single threaded SOMEIP runtime providing services via UDP.
+///
+/// The runtime dispatches received requests to the method handlers & fields
+/// of the registered [`Service`]s and automatically sends the responses
+/// (including error responses for unknown services & methods). SOMEIP SD
+/// "FindService" & "SubscribeEventgroup" messages received on the same
+/// endpoint are answered with offers & (negative) acknowledgements and the
+/// subscribers are used as the targets of events.
+///
+/// Services are identified by their service & instance id. As SOMEIP
+/// requests do not contain the instance id (instances are distinguished
+/// by their endpoints), requests are dispatched to the instance of the
+/// service with the lowest instance id. Use one runtime per instance if
+/// multiple instances of the same service should answer requests.
+///
+/// Received messages are processed by calling [`UdpRuntime::poll`] in a
+/// loop. Note that the runtime does not track the TTL of subscriptions
+/// and does not send offers cyclically by itself (use
+/// [`UdpRuntime::offer_services`] for this).
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{runtime::{Service, UdpRuntime}, transport::UdpEndpoint, *};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), someip_parse::err::RuntimeError> {
+/// // server
+/// let mut service = Service::new(0x1234, 0x0001, 1, 0);
+/// service.add_method(0x0001, |request| Ok(request.payload().to_vec()));
+/// let mut runtime = UdpRuntime::bind("127.0.0.1:0")?;
+/// runtime.add_service(service);
+///
+/// // client
+/// let mut client = UdpEndpoint::bind("127.0.0.1:0")?;
+/// client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+/// client.send_to(
+///     &SomeipHeader {
+///         message_id: 0x1234_0001,
+///         length: 0,
+///         request_id: 0x0001_0001,
+///         interface_version: 1,
+///         message_type: MessageType::Request,
+///         return_code: 0,
+///         tp_header: None,
+///     },
+///     &[1, 2, 3],
+///     runtime.endpoint().local_addr().unwrap(),
+/// )?;
+///
+/// // process the request
+/// runtime.poll()?;
+///
+/// let (response, _) = client.recv_from()?;
+/// assert_eq!(MessageType::Response, response.message_type());
+/// assert_eq!(0x0001_0001, response.request_id());
+/// assert_eq!(&[1, 2, 3], response.payload());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct UdpRuntime {
+    endpoint: UdpEndpoint,
+    services: BTreeMap<(ServiceId, InstanceId), Service>,
+    offer_ttl: u32,
+    /// Session id used for the next event.
+    session_id: u16,
+    /// Session id used for the next SOMEIP SD message.
+    sd_session_id: u16,
+    /// Reboot flag set in the SOMEIP SD messages (cleared after the
+    /// first wrap around of the SD session id).
+    sd_reboot: bool,
+}
+
+impl UdpRuntime {
+    /// Creates a runtime using the given endpoint.
+    pub fn new(endpoint: UdpEndpoint) -> UdpRuntime {
+        UdpRuntime {
+            endpoint,
+            services: BTreeMap::new(),
+            offer_ttl: DEFAULT_OFFER_TTL,
+            session_id: 1,
+            sd_session_id: 1,
+            sd_reboot: true,
+        }
+    }
+
+    /// Creates a runtime with an endpoint bound to the given address.
+    ///
+    /// The address is also the one sent in the service offers, so it
+    /// should not be an unspecified address (e.g. `0.0.0.0`).
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<UdpRuntime, RuntimeError> {
+        Ok(UdpRuntime::new(UdpEndpoint::bind(addr)?))
+    }
+
+    /// Endpoint used to send & receive messages.
+    #[inline]
+    pub fn endpoint(&self) -> &UdpEndpoint {
+        &self.endpoint
+    }
+
+    /// TTL (in seconds) of the sent service offers.
+    #[inline]
+    pub fn offer_ttl(&self) -> u32 {
+        self.offer_ttl
+    }
+
+    /// Sets the TTL (in seconds) of the sent service offers.
+    #[inline]
+    pub fn set_offer_ttl(&mut self, offer_ttl: u32) {
+        self.offer_ttl = offer_ttl;
+    }
+
+    /// Adds a service to the runtime. If a service with the same service &
+    /// instance id was already present, it is replaced & returned.
+    pub fn add_service(&mut self, service: Service) -> Option<Service> {
+        self.services.insert(
+            service_key(service.service_id, service.instance_id),
+            service,
+        )
+    }
+
+    /// Returns the service with the given service & instance id.
+    pub fn service(&self, service_id: u16, instance_id: u16) -> Option<&Service> {
+        self.services.get(&service_key(service_id, instance_id))
+    }
+
+    /// Sends an SOMEIP SD message offering all services to the given target
+    /// (e.g. the SD multicast address).
+    pub fn offer_services(&mut self, target: SocketAddr) -> Result<(), RuntimeError> {
+        let entries = self
+            .services
+            .values()
+            .map(|s| offer_entry(s, self.offer_ttl))
+            .collect::<Result<Vec<_>, _>>()?;
+        let options = std::vec![self.endpoint_option()?];
+        self.send_sd(&entries, &options, target)
+    }
+
+    /// Sends the event to all subscribers & returns the number of
+    /// subscribers the event was sent to.
+    pub fn notify(
+        &mut self,
+        service_id: u16,
+        instance_id: u16,
+        event_id: u16,
+        payload: &[u8],
+    ) -> Result<usize, RuntimeError> {
+        let service = self
+            .services
+            .get(&service_key(service_id, instance_id))
+            .ok_or(RuntimeError::UnknownService {
+                service_id,
+                instance_id,
+            })?;
+        let interface_version = service.major_version;
+        let targets = service.event_subscribers(event_id);
+        for target in &targets {
+            self.send_event(service_id, interface_version, event_id, payload, *target)?;
+        }
+        Ok(targets.len())
+    }
+
+    /// Sets the value of a field & sends it to all subscribers of the
+    /// notifier event (if the field has one).
+    ///
+    /// Returns the number of subscribers the new value was sent to.
+    pub fn set_field(
+        &mut self,
+        service_id: u16,
+        instance_id: u16,
+        field: FieldHandle,
+        value: Vec<u8>,
+    ) -> Result<usize, RuntimeError> {
+        let field = self
+            .services
+            .get_mut(&service_key(service_id, instance_id))
+            .ok_or(RuntimeError::UnknownService {
+                service_id,
+                instance_id,
+            })?
+            .fields
+            .get_mut(field.0)
+            .ok_or(RuntimeError::UnknownField {
+                service_id,
+                instance_id,
+            })?;
+        field.value = value;
+        if let Some(notifier_id) = field.notifier_id {
+            let value = field.value.clone();
+            self.notify(service_id, instance_id, notifier_id, &value)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Receives the next message & processes it.
+    ///
+    /// Requests are dispatched to the method handlers & fields of the
+    /// services and SOMEIP SD messages are processed. All other messages
+    /// (including malformed datagrams & SOMEIP SD messages) are ignored.
+    ///
+    /// Blocks until a message is received or the read timeout of the
+    /// endpoint is reached.
+    pub fn poll(&mut self) -> Result<(), RuntimeError> {
+        let endpoint_option = self.endpoint_option()?;
+        let mut outgoing = Vec::new();
+        {
+            let (msg, source) = match self.endpoint.recv_from() {
+                Ok(received) => received,
+                // malformed datagrams are ignored (so the runtime can keep
+                // on polling), only errors of the socket are returned
+                Err(
+                    TransportError::Slice(_)
+                    | TransportError::TpReassemble(_)
+                    | TransportError::MessageTooBig { .. },
+                ) => return Ok(()),
+                Err(err) => return Err(err.into()),
+            };
+            if msg.is_someip_sd() {
+                // malformed messages are ignored before any entry is
+                // processed (so the runtime can keep on polling)
+                if let Ok(sd) = SdSlice::from_someip(&msg) {
+                    if let Ok(entries) = sd.entries_with_options().collect::<Result<Vec<_>, _>>() {
+                        handle_sd(
+                            &mut self.services,
+                            &entries,
+                            source,
+                            &endpoint_option,
+                            self.offer_ttl,
+                            &mut outgoing,
+                        )?;
+                    }
+                }
+            } else if matches!(
+                msg.message_type(),
+                MessageType::Request | MessageType::RequestNoReturn
+            ) {
+                handle_request(&mut self.services, &msg, source, &mut outgoing);
+            }
+        }
+
+        for o in outgoing {
+            match o {
+                Outgoing::Response {
+                    header,
+                    payload,
+                    target,
+                } => self.endpoint.send_to(&header, &payload, target)?,
+                Outgoing::Sd {
+                    entries,
+                    options,
+                    target,
+                } => self.send_sd(&entries, &options, target)?,
+                Outgoing::Event {
+                    service_id,
+                    instance_id,
+                    event_id,
+                    payload,
+                    target,
+                } => {
+                    if let Some(target) = target {
+                        let interface_version = self
+                            .service(service_id, instance_id)
+                            .ok_or(RuntimeError::UnknownService {
+                                service_id,
+                                instance_id,
+                            })?
+                            .major_version;
+                        self.send_event(service_id, interface_version, event_id, &payload, target)?;
+                    } else {
+                        self.notify(service_id, instance_id, event_id, &payload)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Endpoint option containing the local address of the endpoint.
+    fn endpoint_option(&self) -> Result<SdOption, RuntimeError> {
+        let addr = self.endpoint.local_addr().map_err(TransportError::Io)?;
+        Ok(match addr {
+            SocketAddr::V4(v4) => Ipv4EndpointOption {
+                ipv4_address: v4.ip().octets(),
+                transport_protocol: TransportProtocol::Udp,
+                port: v4.port(),
+            }
+            .into(),
+            SocketAddr::V6(v6) => Ipv6EndpointOption {
+                ipv6_address: v6.ip().octets(),
+                transport_protocol: TransportProtocol::Udp,
+                port: v6.port(),
+            }
+            .into(),
+        })
+    }
+
+    fn send_sd(
+        &mut self,
+        entries: &[SdEntry],
+        options: &[SdOption],
+        target: SocketAddr,
+    ) -> Result<(), RuntimeError> {
+        let payload = SdHeader::new(self.sd_reboot, entries, options)?.to_bytes_vec()?;
+        let session_id = self.sd_session_id;
+        if session_id == 0xffff {
+            self.sd_session_id = 1;
+            self.sd_reboot = false;
+        } else {
+            self.sd_session_id += 1;
+        }
+        self.endpoint.send_to(
+            &SomeipHeader::new_sd_header(0, session_id, None),
+            &payload,
+            target,
+        )?;
+        Ok(())
+    }
+
+    fn send_event(
+        &mut self,
+        service_id: u16,
+        interface_version: u8,
+        event_id: u16,
+        payload: &[u8],
+        target: SocketAddr,
+    ) -> Result<(), RuntimeError> {
+        let session_id = self.session_id;
+        self.session_id = if session_id == 0xffff {
+            1
+        } else {
+            session_id + 1
+        };
+        let mut header = SomeipHeader {
+            message_id: 0,
+            length: 0,
+            request_id: u32::from(session_id),
+            interface_version,
+            message_type: MessageType::Notification,
+            return_code: ReturnCode::Ok.into(),
+            tp_header: None,
+        };
+        header.set_service_id(service_id);
+        header.set_event_id(event_id);
+        self.endpoint.send_to(&header, payload, target)?;
+        Ok(())
+    }
+}
+
+/// Key of a service in the runtime.
+#[inline]
+fn service_key(service_id: u16, instance_id: u16) -> (ServiceId, InstanceId) {
+    (ServiceId(service_id), InstanceId(instance_id))
+}
+
+/// Offer service entry referencing the endpoint option at index 0.
+fn offer_entry(service: &Service, ttl: u32) -> Result<SdEntry, SdValueError> {
+    SdEntry::new_offer_service_entry(
+        0,
+        0,
+        1,
+        0,
+        service.service_id,
+        service.instance_id,
+        service.major_version,
+        ttl,
+        service.minor_version,
+    )
+}
+
+/// Dispatches a request to the method handlers & fields of the services.
+fn handle_request(
+    services: &mut BTreeMap<(ServiceId, InstanceId), Service>,
+    msg: &SomeipMsgSlice<'_>,
+    source: SocketAddr,
+    outgoing: &mut Vec<Outgoing>,
+) {
+    // the response is sent before the notifications triggered by the request
+    let response_index = outgoing.len();
+
+    // requests do not contain the instance id, use the lowest instance
+    let service = services
+        .range_mut(service_key(msg.service_id(), 0)..=service_key(msg.service_id(), u16::MAX))
+        .map(|(_, service)| service)
+        .next();
+    let result = match service {
+        None => Err(ReturnCode::UnknownService),
+        Some(service) => match msg.method_id() {
+            None => Err(ReturnCode::UnknownMethod),
+            Some(_) if msg.interface_version() != service.major_version => {
+                Err(ReturnCode::WrongInterfaceVersion)
+            }
+            Some(method_id) => call_method(service, method_id, msg, outgoing),
+        },
+    };
+
+    let (header, payload) = match result {
+        Ok(payload) if MessageType::Request == msg.message_type() => (
+            SomeipHeader {
+                message_id: msg.message_id(),
+                length: 0,
+                request_id: msg.request_id(),
+                interface_version: msg.interface_version(),
+                message_type: MessageType::Response,
+                return_code: ReturnCode::Ok.into(),
+                tp_header: None,
+            },
+            payload,
+        ),
+        Err(return_code) => match SomeipHeader::error_response(msg, return_code) {
+            Some(header) => (header, Vec::new()),
+            None => return,
+        },
+        // no response for requests without return
+        Ok(_) => return,
+    };
+    outgoing.insert(
+        response_index,
+        Outgoing::Response {
+            header,
+            payload,
+            target: source,
+        },
+    );
+}
+
+/// Calls the field getter/setter or method handler with the given id.
+fn call_method(
+    service: &mut Service,
+    method_id: u16,
+    msg: &SomeipMsgSlice<'_>,
+    outgoing: &mut Vec<Outgoing>,
+) -> Result<Vec<u8>, ReturnCode> {
+    if let Some(field) = service
+        .fields
+        .iter()
+        .find(|f| f.getter_id == Some(method_id))
+    {
+        return Ok(field.value.clone());
+    }
+    if let Some(field) = service
+        .fields
+        .iter_mut()
+        .find(|f| f.setter_id == Some(method_id))
+    {
+        field.value = msg.payload().to_vec();
+        if let Some(notifier_id) = field.notifier_id {
+            outgoing.push(Outgoing::Event {
+                service_id: service.service_id,
+                instance_id: service.instance_id,
+                event_id: notifier_id,
+                payload: field.value.clone(),
+                target: None,
+            });
+        }
+        return Ok(field.value.clone());
+    }
+    match service.methods.get_mut(&method_id) {
+        Some(handler) => handler(msg),
+        None => Err(ReturnCode::UnknownMethod),
+    }
+}
+
+/// Processes the entries of a received SOMEIP SD message.
+fn handle_sd(
+    services: &mut BTreeMap<(ServiceId, InstanceId), Service>,
+    sd_entries: &[SdEntryWithOptions<'_, '_>],
+    source: SocketAddr,
+    endpoint_option: &SdOption,
+    offer_ttl: u32,
+    outgoing: &mut Vec<Outgoing>,
+) -> Result<(), RuntimeError> {
+    let mut offers = Vec::new();
+    let mut entries = Vec::new();
+    let mut events = Vec::new();
+    for entry in sd_entries {
+        match entry.entry() {
+            SdEntrySlice::Service(find) if find.entry_type() == SdServiceEntryType::FindService => {
+                for service in services.values() {
+                    if (find.service_id() == 0xffff || find.service_id() == service.service_id)
+                        && (find.instance_id() == 0xffff
+                            || find.instance_id() == service.instance_id)
+                        && (find.major_version() == 0xff
+                            || find.major_version() == service.major_version)
+                    {
+                        offers.push(offer_entry(service, offer_ttl)?);
+                    }
+                }
+            }
+            SdEntrySlice::Eventgroup(sub)
+                if sub.entry_type() == EventGroupEntryType::SubscribeOrStop =>
+            {
+                // determine the address of the subscriber (subscriptions
+                // without an UDP endpoint get a negative acknowledgement)
+                let subscriber = entry
+                    .options_run_1()
+                    .chain(entry.options_run_2())
+                    .find_map(|o| match o {
+                        SdOptionSlice::Ipv4Endpoint(e)
+                            if e.transport_protocol() == TransportProtocol::Udp =>
+                        {
+                            Some(SocketAddr::from((e.ipv4_address(), e.port())))
+                        }
+                        SdOptionSlice::Ipv6Endpoint(e)
+                            if e.transport_protocol() == TransportProtocol::Udp =>
+                        {
+                            Some(SocketAddr::from((e.ipv6_address(), e.port())))
+                        }
+                        _ => None,
+                    });
+
+                let service = services
+                    .get_mut(&service_key(sub.service_id(), sub.instance_id()))
+                    .filter(|s| {
+                        sub.major_version() == 0xff || s.major_version == sub.major_version()
+                    });
+                let group = service
+                    .and_then(|s| {
+                        let fields = &s.fields;
+                        s.eventgroups
+                            .get_mut(&sub.eventgroup_id())
+                            .map(|g| (g, fields, s.service_id, s.instance_id))
+                    })
+                    .zip(subscriber);
+
+                let ttl = sub.ttl().value();
+                if 0 == ttl {
+                    // stop subscription
+                    if let Some(((group, _, _, _), subscriber)) = group {
+                        group.subscribers.retain(|s| *s != subscriber);
+                    }
+                    continue;
+                }
+
+                let ack_ttl =
+                    if let Some(((group, fields, service_id, instance_id), subscriber)) = group {
+                        if false == group.subscribers.contains(&subscriber) {
+                            group.subscribers.push(subscriber);
+                        }
+                        // initial values of the fields
+                        for field in fields.iter() {
+                            if let Some(notifier_id) = field.notifier_id {
+                                if group.events.contains(&notifier_id) {
+                                    events.push(Outgoing::Event {
+                                        service_id,
+                                        instance_id,
+                                        event_id: notifier_id,
+                                        payload: field.value.clone(),
+                                        target: Some(subscriber),
+                                    });
+                                }
+                            }
+                        }
+                        Some(ttl)
+                    } else {
+                        // negative acknowledgement
+                        None
+                    };
+                entries.push(if let Some(ttl) = ack_ttl {
+                    SdEntry::new_subscribe_ack_eventgroup_entry(
+                        0,
+                        0,
+                        0,
+                        0,
+                        sub.service_id(),
+                        sub.instance_id(),
+                        sub.major_version(),
+                        ttl,
+                        sub.counter().value(),
+                        sub.eventgroup_id(),
+                    )?
+                } else {
+                    SdEntry::new_subscribe_nack_eventgroup_entry(
+                        0,
+                        0,
+                        0,
+                        0,
+                        sub.service_id(),
+                        sub.instance_id(),
+                        sub.major_version(),
+                        sub.counter().value(),
+                        sub.eventgroup_id(),
+                    )?
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // offers reference the endpoint option at index 0
+    let options = if offers.is_empty() {
+        Vec::new()
+    } else {
+        std::vec![endpoint_option.clone()]
+    };
+    offers.extend(entries);
+    if false == offers.is_empty() {
+        outgoing.push(Outgoing::Sd {
+            entries: offers,
+            options,
+            target: source,
+        });
+    }
+    // initial events have to be sent after the acknowledgement
+    outgoing.extend(events);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    const SERVICE_ID: u16 = 0x1234;
+    const INSTANCE_ID: u16 = 0x0001;
+
+    fn runtime() -> (UdpRuntime, UdpEndpoint) {
+        let runtime = UdpRuntime::bind("127.0.0.1:0").unwrap();
+        runtime
+            .endpoint()
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let client = UdpEndpoint::bind("127.0.0.1:0").unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        (runtime, client)
+    }
+
+    fn request_header(
+        method_id: u16,
+        interface_version: u8,
+        message_type: MessageType,
+    ) -> SomeipHeader {
+        let mut header = SomeipHeader {
+            message_id: 0,
+            length: 0,
+            request_id: 0x0001_0002,
+            interface_version,
+            message_type,
+            return_code: 0,
+            tp_header: None,
+        };
+        header.set_service_id(SERVICE_ID);
+        header.set_method_or_event_id(method_id);
+        header
+    }
+
+    /// Sends a request to the runtime & lets the runtime process it.
+    fn request(
+        runtime: &mut UdpRuntime,
+        client: &UdpEndpoint,
+        header: &SomeipHeader,
+        payload: &[u8],
+    ) {
+        client
+            .send_to(header, payload, runtime.endpoint().local_addr().unwrap())
+            .unwrap();
+        runtime.poll().unwrap();
+    }
+
+    /// Sends a SOMEIP SD message to the runtime & lets the runtime process it.
+    fn sd_request(
+        runtime: &mut UdpRuntime,
+        client: &UdpEndpoint,
+        entries: &[SdEntry],
+        options: &[SdOption],
+    ) {
+        let payload = SdHeader::new(true, entries, options)
+            .unwrap()
+            .to_bytes_vec()
+            .unwrap();
+        request(
+            runtime,
+            client,
+            &SomeipHeader::new_sd_header(0, 1, None),
+            &payload,
+        );
+    }
+
+    fn subscribe_entry(eventgroup_id: u16, ttl: u32) -> SdEntry {
+        subscribe_instance_entry(INSTANCE_ID, eventgroup_id, ttl, 1)
+    }
+
+    fn subscribe_instance_entry(
+        instance_id: u16,
+        eventgroup_id: u16,
+        ttl: u32,
+        number_of_options: u8,
+    ) -> SdEntry {
+        if 0 == number_of_options {
+            // invalid subscribe without an endpoint (rejected by the
+            // subscribe constructors)
+            SdEntry::new_eventgroup(
+                EventGroupEntryType::SubscribeOrStop,
+                0,
+                0,
+                0,
+                0,
+                SERVICE_ID,
+                instance_id,
+                1,
+                ttl,
+                false,
+                0,
+                eventgroup_id,
+            )
+        } else if 0 == ttl {
+            SdEntry::new_stop_subscribe_eventgroup_entry(
+                0,
+                0,
+                number_of_options,
+                0,
+                SERVICE_ID,
+                instance_id,
+                1,
+                false,
+                0,
+                eventgroup_id,
+            )
+        } else {
+            SdEntry::new_subscribe_eventgroup_entry(
+                0,
+                0,
+                number_of_options,
+                0,
+                SERVICE_ID,
+                instance_id,
+                1,
+                ttl,
+                false,
+                0,
+                eventgroup_id,
+            )
+        }
+        .unwrap()
+    }
+
+    fn client_option(client: &UdpEndpoint) -> SdOption {
+        match client.local_addr().unwrap() {
+            SocketAddr::V4(v4) => Ipv4EndpointOption {
+                ipv4_address: v4.ip().octets(),
+                transport_protocol: TransportProtocol::Udp,
+                port: v4.port(),
+            }
+            .into(),
+            SocketAddr::V6(_) => unreachable!(),
+        }
+    }
+
+    /// Receives a SOMEIP SD message & returns the entries & number of options.
+    fn recv_sd_entries(client: &mut UdpEndpoint) -> (Vec<SdEntry>, usize) {
+        let (msg, _) = client.recv_from().unwrap();
+        assert!(msg.is_someip_sd());
+        let sd = SdSlice::from_someip(&msg).unwrap();
+        let entries = sd.entries().map(|e| e.unwrap().to_owned()).collect();
+        (entries, sd.options().count())
+    }
+
+    #[test]
+    fn accessors() {
+        let (mut runtime, _) = runtime();
+        assert_eq!(DEFAULT_OFFER_TTL, runtime.offer_ttl());
+        runtime.set_offer_ttl(10);
+        assert_eq!(10, runtime.offer_ttl());
+
+        assert!(runtime.service(SERVICE_ID, INSTANCE_ID).is_none());
+        assert!(runtime
+            .add_service(Service::new(SERVICE_ID, INSTANCE_ID, 1, 0))
+            .is_none());
+        assert!(runtime
+            .add_service(Service::new(SERVICE_ID, INSTANCE_ID, 2, 0))
+            .is_some());
+        assert_eq!(
+            2,
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .major_version()
+        );
+
+        // other instances of the same service are added next to it
+        assert!(runtime
+            .add_service(Service::new(SERVICE_ID, INSTANCE_ID + 1, 3, 0))
+            .is_none());
+        assert_eq!(
+            2,
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .major_version()
+        );
+        assert_eq!(
+            3,
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID + 1)
+                .unwrap()
+                .major_version()
+        );
+        let _ = format!("{:?}", runtime);
+    }
+
+    #[test]
+    fn methods() {
+        let (mut runtime, mut client) = runtime();
+        let calls = Rc::new(Cell::new(0));
+        let mut service = Service::new(SERVICE_ID, INSTANCE_ID, 1, 0);
+        {
+            let calls = calls.clone();
+            service.add_method(0x0001, move |request| {
+                calls.set(calls.get() + 1);
+                Ok(request.payload().to_vec())
+            });
+        }
+        service.add_method(0x0002, |_| Err(ReturnCode::NotReady));
+        runtime.add_service(service);
+
+        // response
+        request(
+            &mut runtime,
+            &client,
+            &request_header(0x0001, 1, MessageType::Request),
+            &[1, 2, 3],
+        );
+        {
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Response, msg.message_type());
+            assert_eq!(SERVICE_ID, msg.service_id());
+            assert_eq!(Some(0x0001), msg.method_id());
+            assert_eq!(0x0001_0002, msg.request_id());
+            assert_eq!(u8::from(ReturnCode::Ok), msg.return_code());
+            assert_eq!(&[1, 2, 3], msg.payload());
+        }
+        assert_eq!(1, calls.get());
+
+        // request without response
+        request(
+            &mut runtime,
+            &client,
+            &request_header(0x0001, 1, MessageType::RequestNoReturn),
+            &[1],
+        );
+        assert_eq!(2, calls.get());
+
+        // non requests are ignored
+        request(
+            &mut runtime,
+            &client,
+            &request_header(0x0001, 1, MessageType::Response),
+            &[1],
+        );
+        assert_eq!(2, calls.get());
+
+        // errors
+        let mut unknown_service = request_header(0x0001, 1, MessageType::Request);
+        unknown_service.set_service_id(0x4321);
+        let mut event = request_header(0x0001, 1, MessageType::Request);
+        event.set_event_id(0x0001);
+        for (header, expected) in [
+            (
+                request_header(0x0002, 1, MessageType::Request),
+                ReturnCode::NotReady,
+            ),
+            (
+                request_header(0x0003, 1, MessageType::Request),
+                ReturnCode::UnknownMethod,
+            ),
+            (event, ReturnCode::UnknownMethod),
+            (
+                request_header(0x0001, 2, MessageType::Request),
+                ReturnCode::WrongInterfaceVersion,
+            ),
+            (unknown_service, ReturnCode::UnknownService),
+        ] {
+            request(&mut runtime, &client, &header, &[1]);
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Error, msg.message_type());
+            assert_eq!(header.message_id, msg.message_id());
+            assert_eq!(u8::from(expected), msg.return_code());
+            assert_eq!(0, msg.payload().len());
+        }
+        assert_eq!(2, calls.get());
+    }
+
+    #[test]
+    fn find_service() {
+        let (mut runtime, mut client) = runtime();
+        runtime.add_service(Service::new(SERVICE_ID, INSTANCE_ID, 1, 2));
+        runtime.set_offer_ttl(10);
+
+        let find = |service_id: u16| {
+            SdEntry::new_find_service_entry(0, 0, 0, 0, service_id, 0xffff, 0xff, 3, 0xffff_ffff)
+                .unwrap()
+        };
+
+        // other services are ignored, the matching one gets offered
+        sd_request(
+            &mut runtime,
+            &client,
+            &[find(0x4321), find(SERVICE_ID)],
+            &[],
+        );
+        let (entries, options) = recv_sd_entries(&mut client);
+        assert_eq!(1, entries.len());
+        assert_eq!(1, options);
+        match &entries[0] {
+            SdEntry::Service(s) => {
+                assert_eq!(SdServiceEntryType::OfferService, s.entry_type);
+                assert_eq!(SERVICE_ID, s.service_id);
+                assert_eq!(INSTANCE_ID, s.instance_id);
+                assert_eq!(1, s.major_version);
+                assert_eq!(2, s.minor_version);
+                assert_eq!(10, s.ttl.value());
+            }
+            _ => panic!("expected service entry"),
+        }
+
+        // explicit offer
+        runtime
+            .offer_services(client.local_addr().unwrap())
+            .unwrap();
+        let (entries, options) = recv_sd_entries(&mut client);
+        assert_eq!(1, entries.len());
+        assert_eq!(1, options);
+    }
+
+    #[test]
+    fn events_and_fields() {
+        let (mut runtime, mut client) = runtime();
+        let mut service = Service::new(SERVICE_ID, INSTANCE_ID, 1, 0);
+        service.add_eventgroup(0x0010, &[0x0001, 0x0002]);
+        let field = service.add_field(Field {
+            getter_id: Some(0x0003),
+            setter_id: Some(0x0004),
+            notifier_id: Some(0x0002),
+            value: std::vec![1],
+        });
+        runtime.add_service(service);
+
+        // no subscribers yet
+        assert_eq!(
+            0,
+            runtime
+                .notify(SERVICE_ID, INSTANCE_ID, 0x0001, &[1])
+                .unwrap()
+        );
+        assert!(matches!(
+            runtime.notify(0x4321, INSTANCE_ID, 0x0001, &[1]),
+            Err(RuntimeError::UnknownService {
+                service_id: 0x4321,
+                instance_id: INSTANCE_ID
+            })
+        ));
+
+        // subscribe to an unknown eventgroup
+        sd_request(
+            &mut runtime,
+            &client,
+            &[subscribe_entry(0x0020, 3)],
+            &[client_option(&client)],
+        );
+        {
+            let (entries, _) = recv_sd_entries(&mut client);
+            match &entries[0] {
+                SdEntry::Eventgroup(e) => {
+                    assert_eq!(EventGroupEntryType::SubscribeAckOrNack, e.entry_type);
+                    assert_eq!(0x0020, e.eventgroup_id);
+                    assert_eq!(0, e.ttl.value());
+                }
+                _ => panic!("expected eventgroup entry"),
+            }
+        }
+
+        // subscribe (acknowledgement & initial field value)
+        sd_request(
+            &mut runtime,
+            &client,
+            &[subscribe_entry(0x0010, 3)],
+            &[client_option(&client)],
+        );
+        {
+            let (entries, _) = recv_sd_entries(&mut client);
+            match &entries[0] {
+                SdEntry::Eventgroup(e) => {
+                    assert_eq!(EventGroupEntryType::SubscribeAckOrNack, e.entry_type);
+                    assert_eq!(0x0010, e.eventgroup_id);
+                    assert_eq!(3, e.ttl.value());
+                }
+                _ => panic!("expected eventgroup entry"),
+            }
+        }
+        {
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Notification, msg.message_type());
+            assert_eq!(Some(0x0002), msg.event_id());
+            assert_eq!(&[1], msg.payload());
+        }
+        assert_eq!(
+            &[client.local_addr().unwrap()],
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .subscribers(0x0010)
+        );
+
+        // event
+        assert_eq!(
+            1,
+            runtime
+                .notify(SERVICE_ID, INSTANCE_ID, 0x0001, &[5, 6])
+                .unwrap()
+        );
+        {
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Notification, msg.message_type());
+            assert_eq!(SERVICE_ID, msg.service_id());
+            assert_eq!(Some(0x0001), msg.event_id());
+            assert_eq!(&[5, 6], msg.payload());
+        }
+
+        // field set by the application
+        assert_eq!(
+            1,
+            runtime
+                .set_field(SERVICE_ID, INSTANCE_ID, field, std::vec![2])
+                .unwrap()
+        );
+        assert_eq!(&[2], client.recv_from().unwrap().0.payload());
+        assert!(matches!(
+            runtime.set_field(SERVICE_ID, INSTANCE_ID, FieldHandle(1), std::vec![2]),
+            Err(RuntimeError::UnknownField {
+                service_id: SERVICE_ID,
+                instance_id: INSTANCE_ID
+            })
+        ));
+        assert!(matches!(
+            runtime.set_field(0x4321, INSTANCE_ID, field, std::vec![2]),
+            Err(RuntimeError::UnknownService {
+                service_id: 0x4321,
+                instance_id: INSTANCE_ID
+            })
+        ));
+
+        // getter
+        request(
+            &mut runtime,
+            &client,
+            &request_header(0x0003, 1, MessageType::Request),
+            &[],
+        );
+        {
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Response, msg.message_type());
+            assert_eq!(&[2], msg.payload());
+        }
+
+        // setter (response & notification)
+        request(
+            &mut runtime,
+            &client,
+            &request_header(0x0004, 1, MessageType::Request),
+            &[3],
+        );
+        {
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Response, msg.message_type());
+            assert_eq!(&[3], msg.payload());
+        }
+        {
+            let (msg, _) = client.recv_from().unwrap();
+            assert_eq!(MessageType::Notification, msg.message_type());
+            assert_eq!(&[3], msg.payload());
+        }
+        assert_eq!(
+            Some(&[3u8][..]),
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .field(field)
+        );
+
+        // stop subscription
+        sd_request(
+            &mut runtime,
+            &client,
+            &[subscribe_entry(0x0010, 0)],
+            &[client_option(&client)],
+        );
+        assert_eq!(
+            0,
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .subscribers(0x0010)
+                .len()
+        );
+        assert_eq!(
+            0,
+            runtime
+                .notify(SERVICE_ID, INSTANCE_ID, 0x0001, &[1])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn instances() {
+        let (mut runtime, mut client) = runtime();
+        for instance_id in [INSTANCE_ID + 1, INSTANCE_ID] {
+            let mut service = Service::new(SERVICE_ID, instance_id, 1, 0);
+            service.add_method(0x0001, move |_| Ok(instance_id.to_be_bytes().to_vec()));
+            service.add_eventgroup(0x0010, &[0x0001]);
+            runtime.add_service(service);
+        }
+
+        // requests are dispatched to the lowest instance
+        request(
+            &mut runtime,
+            &client,
+            &request_header(0x0001, 1, MessageType::Request),
+            &[],
+        );
+        assert_eq!(
+            &INSTANCE_ID.to_be_bytes(),
+            client.recv_from().unwrap().0.payload()
+        );
+
+        // find service only offers the requested instance
+        sd_request(
+            &mut runtime,
+            &client,
+            &[SdEntry::new_find_service_entry(
+                0,
+                0,
+                0,
+                0,
+                SERVICE_ID,
+                INSTANCE_ID + 1,
+                0xff,
+                3,
+                0xffff_ffff,
+            )
+            .unwrap()],
+            &[],
+        );
+        {
+            let (entries, _) = recv_sd_entries(&mut client);
+            assert_eq!(1, entries.len());
+            match &entries[0] {
+                SdEntry::Service(s) => assert_eq!(INSTANCE_ID + 1, s.instance_id),
+                _ => panic!("expected service entry"),
+            }
+        }
+
+        // subscriptions only affect the subscribed instance
+        sd_request(
+            &mut runtime,
+            &client,
+            &[subscribe_instance_entry(INSTANCE_ID + 1, 0x0010, 3, 1)],
+            &[client_option(&client)],
+        );
+        {
+            let (entries, _) = recv_sd_entries(&mut client);
+            match &entries[0] {
+                SdEntry::Eventgroup(e) => {
+                    assert_eq!(INSTANCE_ID + 1, e.instance_id);
+                    assert_eq!(3, e.ttl.value());
+                }
+                _ => panic!("expected eventgroup entry"),
+            }
+        }
+        assert_eq!(
+            0,
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .subscribers(0x0010)
+                .len()
+        );
+        assert_eq!(
+            &[client.local_addr().unwrap()],
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID + 1)
+                .unwrap()
+                .subscribers(0x0010)
+        );
+        assert_eq!(
+            0,
+            runtime
+                .notify(SERVICE_ID, INSTANCE_ID, 0x0001, &[1])
+                .unwrap()
+        );
+        assert_eq!(
+            1,
+            runtime
+                .notify(SERVICE_ID, INSTANCE_ID + 1, 0x0001, &[1])
+                .unwrap()
+        );
+        assert_eq!(&[1], client.recv_from().unwrap().0.payload());
+    }
+
+    #[test]
+    fn subscribe_without_endpoint() {
+        let (mut runtime, mut client) = runtime();
+        let mut service = Service::new(SERVICE_ID, INSTANCE_ID, 1, 0);
+        service.add_eventgroup(0x0010, &[0x0001]);
+        runtime.add_service(service);
+
+        // a subscription without an UDP endpoint gets a negative acknowledgement
+        sd_request(
+            &mut runtime,
+            &client,
+            &[subscribe_instance_entry(INSTANCE_ID, 0x0010, 3, 0)],
+            &[],
+        );
+        let (entries, _) = recv_sd_entries(&mut client);
+        match &entries[0] {
+            SdEntry::Eventgroup(e) => {
+                assert_eq!(EventGroupEntryType::SubscribeAckOrNack, e.entry_type);
+                assert_eq!(0x0010, e.eventgroup_id);
+                assert_eq!(0, e.ttl.value());
+            }
+            _ => panic!("expected eventgroup entry"),
+        }
+        assert_eq!(
+            0,
+            runtime
+                .service(SERVICE_ID, INSTANCE_ID)
+                .unwrap()
+                .subscribers(0x0010)
+                .len()
+        );
+    }
+
+    #[test]
+    fn malformed_datagrams() {
+        let (mut runtime, mut client) = runtime();
+        runtime.add_service(Service::new(SERVICE_ID, INSTANCE_ID, 1, 0));
+        let target = runtime.endpoint().local_addr().unwrap();
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        // too short for a SOMEIP header
+        socket.send_to(&[0xff; 3], target).unwrap();
+        assert!(runtime.poll().is_ok());
+
+        // TP segment with a payload length that is not a multiple of 16
+        let mut header = request_header(1, 1, MessageType::RequestNoReturn);
+        header.tp_header = Some(TpHeader::with_offset(0, true).unwrap());
+        let mut datagram = Vec::new();
+        header.write_raw(&mut datagram).unwrap();
+        datagram.extend_from_slice(&[0; 15]);
+        let length = (datagram.len() - 8) as u32;
+        datagram[4..8].copy_from_slice(&length.to_be_bytes());
+        socket.send_to(&datagram, target).unwrap();
+        assert!(runtime.poll().is_ok());
+
+        // the runtime keeps on processing messages
+        sd_request(
+            &mut runtime,
+            &client,
+            &[SdEntry::new_find_service_entry(
+                0,
+                0,
+                0,
+                0,
+                SERVICE_ID,
+                0xffff,
+                0xff,
+                3,
+                0xffff_ffff,
+            )
+            .unwrap()],
+            &[],
+        );
+        let (entries, _) = recv_sd_entries(&mut client);
+        assert_eq!(1, entries.len());
+    }
+
+    #[test]
+    fn malformed_sd() {
+        let (mut runtime, mut client) = runtime();
+        runtime.add_service(Service::new(SERVICE_ID, INSTANCE_ID, 1, 0));
+
+        // malformed SOMEIP SD messages are ignored
+        let mut payload = SdHeader::new(
+            true,
+            &[SdEntry::new_find_service_entry(
+                0,
+                0,
+                0,
+                0,
+                SERVICE_ID,
+                0xffff,
+                0xff,
+                3,
+                0xffff_ffff,
+            )
+            .unwrap()],
+            &[],
+        )
+        .unwrap()
+        .to_bytes_vec()
+        .unwrap();
+        // unknown entry type
+        payload[8] = 0xff;
+        request(
+            &mut runtime,
+            &client,
+            &SomeipHeader::new_sd_header(0, 1, None),
+            &payload,
+        );
+        request(
+            &mut runtime,
+            &client,
+            &SomeipHeader::new_sd_header(0, 2, None),
+            &[0, 0],
+        );
+
+        // the runtime keeps on processing messages
+        sd_request(
+            &mut runtime,
+            &client,
+            &[SdEntry::new_find_service_entry(
+                0,
+                0,
+                0,
+                0,
+                SERVICE_ID,
+                0xffff,
+                0xff,
+                3,
+                0xffff_ffff,
+            )
+            .unwrap()],
+            &[],
+        );
+        let (entries, _) = recv_sd_entries(&mut client);
+        assert_eq!(1, entries.len());
+    }
+}
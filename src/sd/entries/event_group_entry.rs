@@ -6,6 +6,7 @@ use std::io::Write;
 
 /// SOMEIP service discovery entry for an eventgroup.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventGroupEntry {
     pub entry_type: EventGroupEntryType,
     pub index_first_option_run: u8,
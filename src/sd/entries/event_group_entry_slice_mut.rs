@@ -0,0 +1,160 @@
+use crate::err::{SdError, SdSliceError};
+use crate::sd::entries::*;
+
+/// Mutable view over a serialized SOMEIP SD eventgroup entry allowing
+/// fields to be rewritten in place.
+///
+/// The slice is guaranteed to contain exactly [`ENTRY_LEN`] bytes and
+/// a valid [`EventGroupEntryType`] in byte 0. The entry type and the option
+/// run fields can not be changed, as they are validated against the rest
+/// of the SD message.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct EventGroupEntrySliceMut<'a> {
+    slice: &'a mut [u8],
+}
+
+impl<'a> EventGroupEntrySliceMut<'a> {
+    /// Tries to create a [`EventGroupEntrySliceMut`] from the beginning of `slice`.
+    ///
+    /// On success returns the entry and the remaining bytes after the entry.
+    ///
+    /// # Errors
+    ///
+    /// - [`SdSliceError::UnexpectedEndOfSlice`] if `slice.len() < ENTRY_LEN`
+    /// - [`SdError::UnknownSdServiceEntryType`] if byte 0 is not a
+    ///   recognised [`EventGroupEntryType`] value
+    #[inline]
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<(Self, &'a mut [u8]), SdSliceError> {
+        if slice.len() < ENTRY_LEN {
+            return Err(SdSliceError::UnexpectedEndOfSlice(ENTRY_LEN));
+        }
+        match slice[0] {
+            0x06 | 0x07 => {}
+            other => {
+                return Err(SdSliceError::Content(SdError::UnknownSdServiceEntryType(
+                    other,
+                )))
+            }
+        }
+        let (slice, rest) = slice.split_at_mut(ENTRY_LEN);
+        Ok((EventGroupEntrySliceMut { slice }, rest))
+    }
+
+    /// Returns a read only view of the entry.
+    #[inline]
+    pub fn to_slice(&self) -> EventGroupEntrySlice<'_> {
+        // SAFETY: length & type byte are validated during construction and
+        // can not be changed via the setters.
+        unsafe { EventGroupEntrySlice::from_slice_unchecked(self.slice) }
+    }
+
+    /// Returns the underlying byte slice (exactly [`ENTRY_LEN`] bytes).
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        self.slice
+    }
+
+    /// Sets the service id.
+    #[inline]
+    pub fn set_service_id(&mut self, service_id: u16) {
+        self.slice[4..6].copy_from_slice(&service_id.to_be_bytes());
+    }
+
+    /// Sets the instance id.
+    #[inline]
+    pub fn set_instance_id(&mut self, instance_id: u16) {
+        self.slice[6..8].copy_from_slice(&instance_id.to_be_bytes());
+    }
+
+    /// Sets the major version.
+    #[inline]
+    pub fn set_major_version(&mut self, major_version: u8) {
+        self.slice[8] = major_version;
+    }
+
+    /// Sets the time to live (a TTL of 0 turns a subscribe into a stop
+    /// subscribe & an ack into a nack).
+    #[inline]
+    pub fn set_ttl(&mut self, ttl: U24) {
+        self.slice[9..12].copy_from_slice(&ttl.value().to_be_bytes()[1..]);
+    }
+
+    /// Sets the counter, leaving the remaining bits of the byte untouched.
+    #[inline]
+    pub fn set_counter(&mut self, counter: U4) {
+        self.slice[13] = (self.slice[13] & 0xF0) | counter.value();
+    }
+
+    /// Sets the eventgroup id.
+    #[inline]
+    pub fn set_eventgroup_id(&mut self, eventgroup_id: u16) {
+        self.slice[14..16].copy_from_slice(&eventgroup_id.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn from_slice_errors() {
+        let mut buf = [0u8; ENTRY_LEN];
+        buf[0] = 0x06;
+        assert!(matches!(
+            EventGroupEntrySliceMut::from_slice(&mut buf[..ENTRY_LEN - 1]),
+            Err(SdSliceError::UnexpectedEndOfSlice(ENTRY_LEN))
+        ));
+        buf[0] = 0x01;
+        assert!(matches!(
+            EventGroupEntrySliceMut::from_slice(&mut buf),
+            Err(SdSliceError::Content(SdError::UnknownSdServiceEntryType(
+                0x01
+            )))
+        ));
+    }
+
+    #[test]
+    fn set_counter_keeps_flags() {
+        let mut buf = [0u8; ENTRY_LEN];
+        buf[0] = 0x06;
+        buf[13] = 0x83;
+        let (mut slice, _) = EventGroupEntrySliceMut::from_slice(&mut buf).unwrap();
+        slice.set_counter(U4::N12);
+        assert_eq!(0x8c, slice.slice()[13]);
+        assert!(slice.to_slice().initial_data_requested());
+        assert_eq!(U4::N12, slice.to_slice().counter());
+    }
+
+    proptest! {
+        #[test]
+        fn setters(
+            entry in someip_sd_eventgroup_entry_any(),
+            values in someip_sd_eventgroup_entry_any()
+        ) {
+            let mut bytes = [0u8; ENTRY_LEN + 2];
+            bytes[..ENTRY_LEN].copy_from_slice(&entry.to_bytes());
+            let (mut slice, rest) = EventGroupEntrySliceMut::from_slice(&mut bytes).unwrap();
+            assert_eq!(2, rest.len());
+
+            slice.set_service_id(values.service_id);
+            slice.set_instance_id(values.instance_id);
+            slice.set_major_version(values.major_version);
+            slice.set_ttl(values.ttl);
+            slice.set_counter(values.counter);
+            slice.set_eventgroup_id(values.eventgroup_id);
+
+            let expected = EventGroupEntry {
+                service_id: values.service_id,
+                instance_id: values.instance_id,
+                major_version: values.major_version,
+                ttl: values.ttl,
+                counter: values.counter,
+                eventgroup_id: values.eventgroup_id,
+                ..entry
+            };
+            assert_eq!(&expected.to_bytes()[..], slice.slice());
+        }
+    }
+}
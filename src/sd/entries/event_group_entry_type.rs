@@ -1,5 +1,6 @@
 /// The type of a SOMEIP service discovery eventgroup entry.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventGroupEntryType {
     /// Subscription to an eventgroup (if `ttl > 0`) or
     /// stop of subscription (if `ttl = 0`).
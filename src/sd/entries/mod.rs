@@ -4,6 +4,9 @@ pub use event_group_entry::*;
 mod event_group_entry_slice;
 pub use event_group_entry_slice::*;
 
+mod event_group_entry_slice_mut;
+pub use event_group_entry_slice_mut::*;
+
 mod event_group_entry_type;
 pub use event_group_entry_type::*;
 
@@ -13,6 +16,9 @@ pub use service_entry::*;
 mod service_entry_slice;
 pub use service_entry_slice::*;
 
+mod service_entry_slice_mut;
+pub use service_entry_slice_mut::*;
+
 mod service_entry_type;
 pub use service_entry_type::*;
 
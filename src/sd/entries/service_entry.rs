@@ -6,6 +6,7 @@ use std::io::Write;
 
 /// SOMEIP service discovery entry for a service.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ServiceEntry {
     pub entry_type: SdServiceEntryType,
     pub start_index_options_1: u8,
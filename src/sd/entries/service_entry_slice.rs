@@ -127,18 +127,36 @@ impl<'a> ServiceEntrySlice<'a> {
         u16::from_be_bytes([self.slice[4], self.slice[5]])
     }
 
+    /// Returns the service id as a [`crate::ServiceId`].
+    #[inline]
+    pub fn service_id_typed(&self) -> crate::ServiceId {
+        crate::ServiceId(self.service_id())
+    }
+
     /// Returns the instance id.
     #[inline]
     pub fn instance_id(&self) -> u16 {
         u16::from_be_bytes([self.slice[6], self.slice[7]])
     }
 
+    /// Returns the instance id as a [`crate::InstanceId`].
+    #[inline]
+    pub fn instance_id_typed(&self) -> crate::InstanceId {
+        crate::InstanceId(self.instance_id())
+    }
+
     /// Returns the major version.
     #[inline]
     pub fn major_version(&self) -> u8 {
         self.slice[8]
     }
 
+    /// Returns the major version as a [`crate::MajorVersion`].
+    #[inline]
+    pub fn major_version_typed(&self) -> crate::MajorVersion {
+        crate::MajorVersion(self.major_version())
+    }
+
     /// Returns the time to live (24 bit value).
     #[inline]
     pub fn ttl(&self) -> U24 {
@@ -164,6 +182,12 @@ impl<'a> ServiceEntrySlice<'a> {
         ])
     }
 
+    /// Returns the minor version as a [`crate::MinorVersion`].
+    #[inline]
+    pub fn minor_version_typed(&self) -> crate::MinorVersion {
+        crate::MinorVersion(self.minor_version())
+    }
+
     /// Converts the slice into an owned [`ServiceEntry`].
     #[inline]
     pub fn to_owned(&self) -> ServiceEntry {
@@ -233,9 +257,13 @@ mod tests {
             assert_eq!(slice.number_of_options_2(), entry.number_of_options_2);
             assert_eq!(slice.service_id(), entry.service_id);
             assert_eq!(slice.instance_id(), entry.instance_id);
+            assert_eq!(slice.service_id_typed(), crate::ServiceId(entry.service_id));
+            assert_eq!(slice.instance_id_typed(), crate::InstanceId(entry.instance_id));
             assert_eq!(slice.major_version(), entry.major_version);
+            assert_eq!(slice.major_version_typed(), crate::MajorVersion(entry.major_version));
             assert_eq!(slice.ttl(), entry.ttl);
             assert_eq!(slice.minor_version(), entry.minor_version);
+            assert_eq!(slice.minor_version_typed(), crate::MinorVersion(entry.minor_version));
         }
     }
 
@@ -0,0 +1,140 @@
+use crate::err::{SdError, SdSliceError};
+use crate::sd::entries::*;
+
+/// Mutable view over a serialized SOMEIP SD service entry allowing
+/// fields to be rewritten in place.
+///
+/// The slice is guaranteed to contain exactly [`ENTRY_LEN`] bytes and
+/// a valid [`SdServiceEntryType`] in byte 0. The entry type and the option
+/// run fields can not be changed, as they are validated against the rest
+/// of the SD message.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct ServiceEntrySliceMut<'a> {
+    slice: &'a mut [u8],
+}
+
+impl<'a> ServiceEntrySliceMut<'a> {
+    /// Tries to create a [`ServiceEntrySliceMut`] from the beginning of `slice`.
+    ///
+    /// On success returns the entry and the remaining bytes after the entry.
+    ///
+    /// # Errors
+    ///
+    /// - [`SdSliceError::UnexpectedEndOfSlice`] if `slice.len() < ENTRY_LEN`
+    /// - [`SdError::UnknownSdServiceEntryType`] if byte 0 is not a
+    ///   recognised [`SdServiceEntryType`] value
+    #[inline]
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<(Self, &'a mut [u8]), SdSliceError> {
+        if slice.len() < ENTRY_LEN {
+            return Err(SdSliceError::UnexpectedEndOfSlice(ENTRY_LEN));
+        }
+        match slice[0] {
+            0x00 | 0x01 => {}
+            other => {
+                return Err(SdSliceError::Content(SdError::UnknownSdServiceEntryType(
+                    other,
+                )))
+            }
+        }
+        let (slice, rest) = slice.split_at_mut(ENTRY_LEN);
+        Ok((ServiceEntrySliceMut { slice }, rest))
+    }
+
+    /// Returns a read only view of the entry.
+    #[inline]
+    pub fn to_slice(&self) -> ServiceEntrySlice<'_> {
+        // SAFETY: length & type byte are validated during construction and
+        // can not be changed via the setters.
+        unsafe { ServiceEntrySlice::from_slice_unchecked(self.slice) }
+    }
+
+    /// Returns the underlying byte slice (exactly [`ENTRY_LEN`] bytes).
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        self.slice
+    }
+
+    /// Sets the service id.
+    #[inline]
+    pub fn set_service_id(&mut self, service_id: u16) {
+        self.slice[4..6].copy_from_slice(&service_id.to_be_bytes());
+    }
+
+    /// Sets the instance id.
+    #[inline]
+    pub fn set_instance_id(&mut self, instance_id: u16) {
+        self.slice[6..8].copy_from_slice(&instance_id.to_be_bytes());
+    }
+
+    /// Sets the major version.
+    #[inline]
+    pub fn set_major_version(&mut self, major_version: u8) {
+        self.slice[8] = major_version;
+    }
+
+    /// Sets the time to live (a TTL of 0 turns an offer into a stop offer).
+    #[inline]
+    pub fn set_ttl(&mut self, ttl: U24) {
+        self.slice[9..12].copy_from_slice(&ttl.value().to_be_bytes()[1..]);
+    }
+
+    /// Sets the minor version.
+    #[inline]
+    pub fn set_minor_version(&mut self, minor_version: u32) {
+        self.slice[12..16].copy_from_slice(&minor_version.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn from_slice_errors() {
+        let mut buf = [0u8; ENTRY_LEN];
+        assert!(matches!(
+            ServiceEntrySliceMut::from_slice(&mut buf[..ENTRY_LEN - 1]),
+            Err(SdSliceError::UnexpectedEndOfSlice(ENTRY_LEN))
+        ));
+        buf[0] = 0x06;
+        assert!(matches!(
+            ServiceEntrySliceMut::from_slice(&mut buf),
+            Err(SdSliceError::Content(SdError::UnknownSdServiceEntryType(
+                0x06
+            )))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn setters(
+            entry in someip_sd_service_entry_any(),
+            values in someip_sd_service_entry_any()
+        ) {
+            let mut bytes = [0u8; ENTRY_LEN + 2];
+            bytes[..ENTRY_LEN].copy_from_slice(&entry.to_bytes());
+            let (mut slice, rest) = ServiceEntrySliceMut::from_slice(&mut bytes).unwrap();
+            assert_eq!(2, rest.len());
+            assert_eq!(entry, slice.to_slice().to_owned());
+
+            slice.set_service_id(values.service_id);
+            slice.set_instance_id(values.instance_id);
+            slice.set_major_version(values.major_version);
+            slice.set_ttl(values.ttl);
+            slice.set_minor_version(values.minor_version);
+
+            let expected = ServiceEntry {
+                service_id: values.service_id,
+                instance_id: values.instance_id,
+                major_version: values.major_version,
+                ttl: values.ttl,
+                minor_version: values.minor_version,
+                ..entry
+            };
+            assert_eq!(expected, slice.to_slice().to_owned());
+            assert_eq!(&expected.to_bytes()[..], slice.slice());
+        }
+    }
+}
@@ -3,6 +3,11 @@
 /// Used for the `ttl` field in SOME/IP SD entries, which is encoded
 /// as a 24 bit value on the wire.
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u32", into = "u32")
+)]
 pub struct U24(u32);
 
 impl U24 {
@@ -347,4 +352,13 @@ mod test {
             assert_eq!(a, b);
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let max = U24::try_new(16777215).unwrap();
+        assert_eq!("16777215", serde_json::to_string(&max).unwrap());
+        assert_eq!(max, serde_json::from_str("16777215").unwrap());
+        assert!(serde_json::from_str::<U24>("16777216").is_err());
+    }
 }
@@ -3,6 +3,11 @@
 /// Used for fields like `number_of_options_1` and `number_of_options_2`
 /// in SOME/IP SD entries, which are encoded as 4 bit values on the wire.
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
 pub struct U4(u8);
 
 impl U4 {
@@ -337,4 +342,13 @@ mod test {
             assert_eq!(a, b);
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let max = U4::try_new(15).unwrap();
+        assert_eq!("15", serde_json::to_string(&max).unwrap());
+        assert_eq!(max, serde_json::from_str("15").unwrap());
+        assert!(serde_json::from_str::<U4>("16").is_err());
+    }
 }
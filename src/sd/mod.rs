@@ -19,9 +19,15 @@ pub use sd_entries_iterator::*;
 mod sd_entry;
 pub use sd_entry::*;
 
+mod sd_entry_kind;
+pub use sd_entry_kind::*;
+
 mod sd_entry_slice;
 pub use sd_entry_slice::*;
 
+mod sd_entry_slice_mut;
+pub use sd_entry_slice_mut::*;
+
 mod sd_entry_with_options;
 pub use sd_entry_with_options::*;
 
@@ -37,6 +43,9 @@ pub use sd_option::*;
 mod sd_option_slice;
 pub use sd_option_slice::*;
 
+mod sd_option_slice_mut;
+pub use sd_option_slice_mut::*;
+
 mod sd_option_type;
 pub use sd_option_type::*;
 
@@ -52,6 +61,9 @@ pub use sd_options_iterator::*;
 mod sd_slice;
 pub use sd_slice::*;
 
+mod sd_slice_mut;
+pub use sd_slice_mut::*;
+
 ///Length of someip sd header, flags + reserved + entries length + options length
 ///excluding entries and options arrays
 pub const MIN_SD_HEADER_LENGTH: usize = 1 + 3 + 4 + 4;
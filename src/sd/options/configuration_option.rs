@@ -69,6 +69,7 @@ impl core::fmt::Display for SdConfigurationStringError {
 impl core::error::Error for SdConfigurationStringError {}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfigurationOption {
     /// Shall be set to `true` if the option can be discarded by the receiver.
     pub discardable: bool,
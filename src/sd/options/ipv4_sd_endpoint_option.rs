@@ -1,7 +1,9 @@
 use crate::sd::options::*;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ipv4SdEndpointOption {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::ipv4"))]
     pub ipv4_address: [u8; 4],
     pub transport_protocol: TransportProtocol,
     pub port: u16,
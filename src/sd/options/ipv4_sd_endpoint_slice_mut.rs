@@ -0,0 +1,93 @@
+use super::{Ipv4SdEndpointSlice, TransportProtocol};
+use crate::err::{self, Layer, LenSource};
+
+/// Mutable view over the payload of an IPv4 SD endpoint option allowing the
+/// address, transport protocol & port to be rewritten in place.
+///
+/// Like [`Ipv4SdEndpointSlice`] the slice starts with the reserved byte (which
+/// carries the discardable flag) and is exactly [`Ipv4SdEndpointSlice::LEN`] bytes long.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Ipv4SdEndpointSliceMut<'a> {
+    slice: &'a mut [u8],
+}
+
+impl<'a> Ipv4SdEndpointSliceMut<'a> {
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<Self, err::LenError> {
+        if slice.len() != Ipv4SdEndpointSlice::LEN {
+            return Err(err::LenError {
+                required_len: Ipv4SdEndpointSlice::LEN,
+                len: slice.len(),
+                len_source: LenSource::Slice,
+                layer: Layer::SdOption,
+            });
+        }
+        Ok(Self { slice })
+    }
+
+    /// Returns a read only view of the option.
+    #[inline]
+    pub fn to_slice(&self) -> Ipv4SdEndpointSlice<'_> {
+        // the length is checked during construction, so this can not fail
+        Ipv4SdEndpointSlice::from_slice(self.slice).unwrap()
+    }
+
+    #[inline]
+    pub fn set_ipv4_address(&mut self, ipv4_address: [u8; 4]) {
+        self.slice[1..5].copy_from_slice(&ipv4_address);
+    }
+
+    #[inline]
+    pub fn set_transport_protocol(&mut self, transport_protocol: TransportProtocol) {
+        self.slice[6] = transport_protocol.into();
+    }
+
+    #[inline]
+    pub fn set_port(&mut self, port: u16) {
+        self.slice[6 + 1..].copy_from_slice(&port.to_be_bytes());
+    }
+
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        self.slice
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::sd::options::Ipv4SdEndpointOption;
+    use proptest::prelude::*;
+
+    #[test]
+    fn from_slice() {
+        let mut buf = [0u8; Ipv4SdEndpointSlice::LEN + 1];
+        let err = Ipv4SdEndpointSliceMut::from_slice(&mut buf).unwrap_err();
+        assert_eq!(err.required_len, Ipv4SdEndpointSlice::LEN);
+        assert_eq!(err.len, Ipv4SdEndpointSlice::LEN + 1);
+        assert_eq!(err.len_source, LenSource::Slice);
+        assert_eq!(err.layer, Layer::SdOption);
+        assert!(
+            Ipv4SdEndpointSliceMut::from_slice(&mut buf[..Ipv4SdEndpointSlice::LEN - 1]).is_err()
+        );
+        assert!(Ipv4SdEndpointSliceMut::from_slice(&mut buf[..Ipv4SdEndpointSlice::LEN]).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn setters(
+            discardable in any::<bool>(),
+            value in someip_sd_option_ipv4_sd_endpoint_any()
+        ) {
+            let mut buf = [0u8; Ipv4SdEndpointSlice::LEN];
+            buf[0] = if discardable { 0x80 } else { 0 };
+            let mut s = Ipv4SdEndpointSliceMut::from_slice(&mut buf).unwrap();
+            s.set_ipv4_address(value.ipv4_address);
+            s.set_transport_protocol(value.transport_protocol);
+            s.set_port(value.port);
+            assert_eq!(value, Ipv4SdEndpointOption::from(s.to_slice()));
+            // the reserved byte (discardable flag) is left untouched
+            assert_eq!(buf[0], if discardable { 0x80 } else { 0 });
+        }
+    }
+}
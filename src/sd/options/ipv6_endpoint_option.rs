@@ -1,7 +1,9 @@
 use crate::sd::options::*;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ipv6EndpointOption {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::ipv6"))]
     pub ipv6_address: [u8; 16],
     pub transport_protocol: TransportProtocol,
     pub port: u16,
@@ -0,0 +1,93 @@
+use super::{Ipv6SdEndpointSlice, TransportProtocol};
+use crate::err::{self, Layer, LenSource};
+
+/// Mutable view over the payload of an IPv6 SD endpoint option allowing the
+/// address, transport protocol & port to be rewritten in place.
+///
+/// Like [`Ipv6SdEndpointSlice`] the slice starts with the reserved byte (which
+/// carries the discardable flag) and is exactly [`Ipv6SdEndpointSlice::LEN`] bytes long.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Ipv6SdEndpointSliceMut<'a> {
+    slice: &'a mut [u8],
+}
+
+impl<'a> Ipv6SdEndpointSliceMut<'a> {
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<Self, err::LenError> {
+        if slice.len() != Ipv6SdEndpointSlice::LEN {
+            return Err(err::LenError {
+                required_len: Ipv6SdEndpointSlice::LEN,
+                len: slice.len(),
+                len_source: LenSource::Slice,
+                layer: Layer::SdOption,
+            });
+        }
+        Ok(Self { slice })
+    }
+
+    /// Returns a read only view of the option.
+    #[inline]
+    pub fn to_slice(&self) -> Ipv6SdEndpointSlice<'_> {
+        // the length is checked during construction, so this can not fail
+        Ipv6SdEndpointSlice::from_slice(self.slice).unwrap()
+    }
+
+    #[inline]
+    pub fn set_ipv6_address(&mut self, ipv6_address: [u8; 16]) {
+        self.slice[1..17].copy_from_slice(&ipv6_address);
+    }
+
+    #[inline]
+    pub fn set_transport_protocol(&mut self, transport_protocol: TransportProtocol) {
+        self.slice[18] = transport_protocol.into();
+    }
+
+    #[inline]
+    pub fn set_port(&mut self, port: u16) {
+        self.slice[18 + 1..].copy_from_slice(&port.to_be_bytes());
+    }
+
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        self.slice
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::sd::options::Ipv6SdEndpointOption;
+    use proptest::prelude::*;
+
+    #[test]
+    fn from_slice() {
+        let mut buf = [0u8; Ipv6SdEndpointSlice::LEN + 1];
+        let err = Ipv6SdEndpointSliceMut::from_slice(&mut buf).unwrap_err();
+        assert_eq!(err.required_len, Ipv6SdEndpointSlice::LEN);
+        assert_eq!(err.len, Ipv6SdEndpointSlice::LEN + 1);
+        assert_eq!(err.len_source, LenSource::Slice);
+        assert_eq!(err.layer, Layer::SdOption);
+        assert!(
+            Ipv6SdEndpointSliceMut::from_slice(&mut buf[..Ipv6SdEndpointSlice::LEN - 1]).is_err()
+        );
+        assert!(Ipv6SdEndpointSliceMut::from_slice(&mut buf[..Ipv6SdEndpointSlice::LEN]).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn setters(
+            discardable in any::<bool>(),
+            value in someip_sd_option_ipv6_sd_endpoint_any()
+        ) {
+            let mut buf = [0u8; Ipv6SdEndpointSlice::LEN];
+            buf[0] = if discardable { 0x80 } else { 0 };
+            let mut s = Ipv6SdEndpointSliceMut::from_slice(&mut buf).unwrap();
+            s.set_ipv6_address(value.ipv6_address);
+            s.set_transport_protocol(value.transport_protocol);
+            s.set_port(value.port);
+            assert_eq!(value, Ipv6SdEndpointOption::from(s.to_slice()));
+            // the reserved byte (discardable flag) is left untouched
+            assert_eq!(buf[0], if discardable { 0x80 } else { 0 });
+        }
+    }
+}
@@ -1,4 +1,5 @@
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LoadBalancingOption {
     /// Shall be set to `true` if the option can be discarded by the receiver.
     pub discardable: bool,
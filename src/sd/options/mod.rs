@@ -10,36 +10,54 @@ pub use ipv4_endpoint_option::*;
 mod ipv4_endpoint_slice;
 pub use ipv4_endpoint_slice::*;
 
+mod ipv4_endpoint_slice_mut;
+pub use ipv4_endpoint_slice_mut::*;
+
 mod ipv4_multicast_option;
 pub use ipv4_multicast_option::*;
 
 mod ipv4_multicast_slice;
 pub use ipv4_multicast_slice::*;
 
+mod ipv4_multicast_slice_mut;
+pub use ipv4_multicast_slice_mut::*;
+
 mod ipv4_sd_endpoint_option;
 pub use ipv4_sd_endpoint_option::*;
 
 mod ipv4_sd_endpoint_slice;
 pub use ipv4_sd_endpoint_slice::*;
 
+mod ipv4_sd_endpoint_slice_mut;
+pub use ipv4_sd_endpoint_slice_mut::*;
+
 mod ipv6_endpoint_option;
 pub use ipv6_endpoint_option::*;
 
 mod ipv6_endpoint_slice;
 pub use ipv6_endpoint_slice::*;
 
+mod ipv6_endpoint_slice_mut;
+pub use ipv6_endpoint_slice_mut::*;
+
 mod ipv6_multicast_option;
 pub use ipv6_multicast_option::*;
 
 mod ipv6_multicast_slice;
 pub use ipv6_multicast_slice::*;
 
+mod ipv6_multicast_slice_mut;
+pub use ipv6_multicast_slice_mut::*;
+
 mod ipv6_sd_endpoint_option;
 pub use ipv6_sd_endpoint_option::*;
 
 mod ipv6_sd_endpoint_slice;
 pub use ipv6_sd_endpoint_slice::*;
 
+mod ipv6_sd_endpoint_slice_mut;
+pub use ipv6_sd_endpoint_slice_mut::*;
+
 mod load_balancing_option;
 pub use load_balancing_option::*;
 
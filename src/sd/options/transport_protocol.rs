@@ -1,5 +1,6 @@
 /// Protocol numbers based on IANA/IETF
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TransportProtocol {
     Tcp,
     Udp,
@@ -16,9 +17,29 @@ impl From<TransportProtocol> for u8 {
     }
 }
 
+impl core::fmt::Display for TransportProtocol {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TransportProtocol::Tcp => write!(f, "TCP"),
+            TransportProtocol::Udp => write!(f, "UDP"),
+            TransportProtocol::Generic(value) => write!(f, "Protocol 0x{value:02x}"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
+
+    #[test]
+    fn display() {
+        use super::TransportProtocol::*;
+        use alloc::format;
+        assert_eq!("TCP", format!("{}", Tcp));
+        assert_eq!("UDP", format!("{}", Udp));
+        assert_eq!("Protocol 0x84", format!("{}", Generic(0x84)));
+    }
+
     proptest! {
         #[test]
         fn from_u8(argu8 in any::<u8>()) {
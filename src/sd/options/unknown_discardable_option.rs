@@ -6,6 +6,7 @@
 /// this option is passed to a write function an error will be
 /// triggered.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnknownDiscardableOption {
     pub length: u16,
     pub option_type: u8,
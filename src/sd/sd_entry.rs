@@ -1,6 +1,7 @@
 use crate::sd::{entries::*, *};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SdEntry {
     /// SOMEIP service discovery entry for a service.
     Service(ServiceEntry),
@@ -67,10 +68,10 @@ impl SdEntry {
     }
 
     /// Find service instances. Only use when the state of the given service is unknown.
-    /// * `service_id` - Set to 0xFFFF if all service instances should be returned.
-    /// * `instance_id` - Set to 0xFFFF if all instances should be returned.
-    /// * `major_version` - Set to 0xFF if any version should be returned.
-    /// * `minor_version` - Set to 0xFFFF_FFFF if any version should be returned.
+    /// * `service_id` - Set to 0xFFFF ([`crate::ServiceId::ANY`]) if all service instances should be returned.
+    /// * `instance_id` - Set to 0xFFFF ([`crate::InstanceId::ANY`]) if all instances should be returned.
+    /// * `major_version` - Set to 0xFF ([`crate::MajorVersion::ANY`]) if any version should be returned.
+    /// * `minor_version` - Set to 0xFFFF_FFFF ([`crate::MinorVersion::ANY`]) if any version should be returned.
     /// * `ttl` - Ignored by receivers and retained only for backward compatibility.
     #[allow(clippy::too_many_arguments)]
     pub fn new_find_service_entry(
@@ -208,6 +209,241 @@ impl SdEntry {
         }
     }
 
+    /// Subscribe to an eventgroup.
+    ///
+    /// # Errors:
+    ///
+    /// * [`SdValueError::TtlZeroIndicatesStopSubscribe`] if `ttl` is 0 (use
+    ///   [`SdEntry::new_stop_subscribe_eventgroup_entry`] instead).
+    /// * [`SdValueError::MissingEndpointOption`] if no option is referenced,
+    ///   as a subscribe must reference the endpoint of the subscriber (use
+    ///   [`SdEntry::check_options`] to check the types of the options).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_subscribe_eventgroup_entry(
+        index_first_option_run: u8,
+        index_second_option_run: u8,
+        number_of_options_1: u8,
+        number_of_options_2: u8,
+        service_id: u16,
+        instance_id: u16,
+        major_version: u8,
+        ttl: u32,
+        initial_data_requested: bool,
+        counter: u8,
+        eventgroup_id: u16,
+    ) -> Result<Self, SdValueError> {
+        if ttl == 0 {
+            Err(SdValueError::TtlZeroIndicatesStopSubscribe)
+        } else if number_of_options_1 == 0 && number_of_options_2 == 0 {
+            Err(SdValueError::MissingEndpointOption(SdEntryKind::Subscribe))
+        } else {
+            Self::new_eventgroup(
+                EventGroupEntryType::SubscribeOrStop,
+                index_first_option_run,
+                index_second_option_run,
+                number_of_options_1,
+                number_of_options_2,
+                service_id,
+                instance_id,
+                major_version,
+                ttl,
+                initial_data_requested,
+                counter,
+                eventgroup_id,
+            )
+        }
+    }
+
+    /// Stop the subscription of an eventgroup.
+    ///
+    /// The entry has to reference the same options as the subscribe entry
+    /// it stops. [`SdValueError::MissingEndpointOption`] is returned if no
+    /// option is referenced.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_stop_subscribe_eventgroup_entry(
+        index_first_option_run: u8,
+        index_second_option_run: u8,
+        number_of_options_1: u8,
+        number_of_options_2: u8,
+        service_id: u16,
+        instance_id: u16,
+        major_version: u8,
+        initial_data_requested: bool,
+        counter: u8,
+        eventgroup_id: u16,
+    ) -> Result<Self, SdValueError> {
+        if number_of_options_1 == 0 && number_of_options_2 == 0 {
+            Err(SdValueError::MissingEndpointOption(
+                SdEntryKind::StopSubscribe,
+            ))
+        } else {
+            Self::new_eventgroup(
+                EventGroupEntryType::SubscribeOrStop,
+                index_first_option_run,
+                index_second_option_run,
+                number_of_options_1,
+                number_of_options_2,
+                service_id,
+                instance_id,
+                major_version,
+                0,
+                initial_data_requested,
+                counter,
+                eventgroup_id,
+            )
+        }
+    }
+
+    /// Acknowledge the subscription of an eventgroup.
+    ///
+    /// The entry may reference a multicast endpoint option the events of
+    /// the eventgroup are sent to.
+    ///
+    /// # Errors:
+    ///
+    /// `ttl` must not be 0 as this indicates a negative acknowledgement. If
+    /// ttl 0 is passed [`SdValueError::TtlZeroIndicatesNack`] is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_subscribe_ack_eventgroup_entry(
+        index_first_option_run: u8,
+        index_second_option_run: u8,
+        number_of_options_1: u8,
+        number_of_options_2: u8,
+        service_id: u16,
+        instance_id: u16,
+        major_version: u8,
+        ttl: u32,
+        counter: u8,
+        eventgroup_id: u16,
+    ) -> Result<Self, SdValueError> {
+        if ttl == 0 {
+            Err(SdValueError::TtlZeroIndicatesNack)
+        } else {
+            Self::new_eventgroup(
+                EventGroupEntryType::SubscribeAckOrNack,
+                index_first_option_run,
+                index_second_option_run,
+                number_of_options_1,
+                number_of_options_2,
+                service_id,
+                instance_id,
+                major_version,
+                ttl,
+                false,
+                counter,
+                eventgroup_id,
+            )
+        }
+    }
+
+    /// Negatively acknowledge the subscription of an eventgroup.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_subscribe_nack_eventgroup_entry(
+        index_first_option_run: u8,
+        index_second_option_run: u8,
+        number_of_options_1: u8,
+        number_of_options_2: u8,
+        service_id: u16,
+        instance_id: u16,
+        major_version: u8,
+        counter: u8,
+        eventgroup_id: u16,
+    ) -> Result<Self, SdValueError> {
+        Self::new_eventgroup(
+            EventGroupEntryType::SubscribeAckOrNack,
+            index_first_option_run,
+            index_second_option_run,
+            number_of_options_1,
+            number_of_options_2,
+            service_id,
+            instance_id,
+            major_version,
+            0,
+            false,
+            counter,
+            eventgroup_id,
+        )
+    }
+
+    /// Returns the logical kind of the entry (taking into account that a
+    /// TTL of 0 turns offers, subscriptions & their acknowledgements into
+    /// stops & nacks).
+    #[inline]
+    pub fn kind(&self) -> SdEntryKind {
+        match self {
+            SdEntry::Service(e) => SdEntryKind::from_service(e.entry_type, e.ttl),
+            SdEntry::Eventgroup(e) => SdEntryKind::from_eventgroup(e.entry_type, e.ttl),
+        }
+    }
+
+    /// Checks that the options referenced by the entry fulfill the
+    /// requirements of its kind:
+    ///
+    /// * [`SdEntryKind::Subscribe`] & [`SdEntryKind::StopSubscribe`] entries
+    ///   must reference an IPv4 or IPv6 endpoint option.
+    /// * [`SdEntryKind::SubscribeAck`] entries may only reference multicast
+    ///   endpoint options.
+    ///
+    /// `options` has to be the options array of the SD header the entry
+    /// is part of.
+    pub fn check_options(&self, options: &[SdOption]) -> Result<(), SdValueError> {
+        let kind = self.kind();
+        let runs = match self {
+            SdEntry::Service(e) => [
+                (1, e.start_index_options_1, e.number_of_options_1),
+                (2, e.start_index_options_2, e.number_of_options_2),
+            ],
+            SdEntry::Eventgroup(e) => [
+                (1, e.index_first_option_run, e.number_of_options_1),
+                (2, e.index_second_option_run, e.number_of_options_2),
+            ],
+        };
+
+        let mut has_endpoint = false;
+        for (run, start_index, number_of_options) in runs {
+            let number_of_options = number_of_options.value();
+            if number_of_options == 0 {
+                continue;
+            }
+            let start = usize::from(start_index);
+            let end = start + usize::from(number_of_options);
+            if end > options.len() {
+                return Err(SdValueError::SdOptionRunOutOfBounds {
+                    run,
+                    start_index,
+                    number_of_options,
+                    options_len: options.len(),
+                });
+            }
+            for (option_index, option) in options[start..end].iter().enumerate() {
+                let is_endpoint = matches!(
+                    option,
+                    SdOption::Ipv4Endpoint(_) | SdOption::Ipv6Endpoint(_)
+                );
+                let is_unicast = is_endpoint
+                    || matches!(
+                        option,
+                        SdOption::Ipv4SdEndpoint(_) | SdOption::Ipv6SdEndpoint(_)
+                    );
+                if is_unicast && kind == SdEntryKind::SubscribeAck {
+                    return Err(SdValueError::UnexpectedEndpointOption {
+                        kind,
+                        option_index: start + option_index,
+                    });
+                }
+                has_endpoint |= is_endpoint;
+            }
+        }
+
+        if false == has_endpoint
+            && matches!(kind, SdEntryKind::Subscribe | SdEntryKind::StopSubscribe)
+        {
+            Err(SdValueError::MissingEndpointOption(kind))
+        } else {
+            Ok(())
+        }
+    }
+
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
     #[inline]
@@ -578,4 +814,231 @@ mod tests {
             assert_eq!(result, Err(SdValueError::CounterTooLarge(0x10)));
         }
     }
+
+    #[test]
+    fn new_subscribe_eventgroup_entry() {
+        // ok
+        {
+            let result =
+                SdEntry::new_subscribe_eventgroup_entry(0, 0, 1, 0, 1, 2, 3, 4, true, 5, 6)
+                    .unwrap();
+            assert_eq!(SdEntryKind::Subscribe, result.kind());
+            assert_eq!(
+                result,
+                SdEntry::new_eventgroup(
+                    EventGroupEntryType::SubscribeOrStop,
+                    0,
+                    0,
+                    1,
+                    0,
+                    1,
+                    2,
+                    3,
+                    4,
+                    true,
+                    5,
+                    6
+                )
+                .unwrap()
+            );
+        }
+        // zero ttl
+        assert_eq!(
+            SdEntry::new_subscribe_eventgroup_entry(0, 0, 1, 0, 0, 0, 0, 0, false, 0, 0),
+            Err(SdValueError::TtlZeroIndicatesStopSubscribe)
+        );
+        // no endpoint option
+        assert_eq!(
+            SdEntry::new_subscribe_eventgroup_entry(0, 0, 0, 0, 0, 0, 0, 1, false, 0, 0),
+            Err(SdValueError::MissingEndpointOption(SdEntryKind::Subscribe))
+        );
+        // value range errors
+        assert_eq!(
+            SdEntry::new_subscribe_eventgroup_entry(0, 0, 1, 0, 0, 0, 0, 0x0100_0000, false, 0, 0),
+            Err(SdValueError::TtlTooLarge(0x0100_0000))
+        );
+        assert_eq!(
+            SdEntry::new_subscribe_eventgroup_entry(0, 0, 0x10, 0, 0, 0, 0, 1, false, 0, 0),
+            Err(SdValueError::NumberOfOption1TooLarge(0x10))
+        );
+    }
+
+    #[test]
+    fn new_stop_subscribe_eventgroup_entry() {
+        // ok
+        {
+            let result =
+                SdEntry::new_stop_subscribe_eventgroup_entry(0, 0, 0, 1, 1, 2, 3, false, 5, 6)
+                    .unwrap();
+            assert_eq!(SdEntryKind::StopSubscribe, result.kind());
+            assert_eq!(result.to_bytes()[9..12], [0, 0, 0]);
+        }
+        // no endpoint option
+        assert_eq!(
+            SdEntry::new_stop_subscribe_eventgroup_entry(0, 0, 0, 0, 0, 0, 0, false, 0, 0),
+            Err(SdValueError::MissingEndpointOption(
+                SdEntryKind::StopSubscribe
+            ))
+        );
+        // counter too large
+        assert_eq!(
+            SdEntry::new_stop_subscribe_eventgroup_entry(0, 0, 1, 0, 0, 0, 0, false, 0x10, 0),
+            Err(SdValueError::CounterTooLarge(0x10))
+        );
+    }
+
+    #[test]
+    fn new_subscribe_ack_eventgroup_entry() {
+        // ok (with & without multicast option)
+        for number_of_options_1 in [0, 1] {
+            let result = SdEntry::new_subscribe_ack_eventgroup_entry(
+                0,
+                0,
+                number_of_options_1,
+                0,
+                1,
+                2,
+                3,
+                4,
+                5,
+                6,
+            )
+            .unwrap();
+            assert_eq!(SdEntryKind::SubscribeAck, result.kind());
+        }
+        // zero ttl
+        assert_eq!(
+            SdEntry::new_subscribe_ack_eventgroup_entry(0, 0, 0, 0, 0, 0, 0, 0, 0, 0),
+            Err(SdValueError::TtlZeroIndicatesNack)
+        );
+        // number_of_options_2 too large
+        assert_eq!(
+            SdEntry::new_subscribe_ack_eventgroup_entry(0, 0, 0, 0x10, 0, 0, 0, 1, 0, 0),
+            Err(SdValueError::NumberOfOption2TooLarge(0x10))
+        );
+    }
+
+    #[test]
+    fn new_subscribe_nack_eventgroup_entry() {
+        // ok
+        {
+            let result =
+                SdEntry::new_subscribe_nack_eventgroup_entry(0, 0, 0, 0, 1, 2, 3, 5, 6).unwrap();
+            assert_eq!(SdEntryKind::SubscribeNack, result.kind());
+        }
+        // counter too large
+        assert_eq!(
+            SdEntry::new_subscribe_nack_eventgroup_entry(0, 0, 0, 0, 0, 0, 0, 0x10, 0),
+            Err(SdValueError::CounterTooLarge(0x10))
+        );
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!(
+            SdEntryKind::Find,
+            SdEntry::new_find_service_entry(0, 0, 0, 0, 0, 0, 0, 1, 0)
+                .unwrap()
+                .kind()
+        );
+        assert_eq!(
+            SdEntryKind::Offer,
+            SdEntry::new_offer_service_entry(0, 0, 0, 0, 0, 0, 0, 1, 0)
+                .unwrap()
+                .kind()
+        );
+        assert_eq!(
+            SdEntryKind::StopOffer,
+            SdEntry::new_stop_offer_service_entry(0, 0, 0, 0, 0, 0, 0, 0)
+                .unwrap()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn check_options() {
+        use crate::sd::options::*;
+
+        let endpoint = SdOption::Ipv4Endpoint(Ipv4EndpointOption {
+            ipv4_address: [192, 168, 1, 1],
+            transport_protocol: TransportProtocol::Udp,
+            port: 30509,
+        });
+        let multicast = SdOption::Ipv4Multicast(Ipv4MulticastOption {
+            ipv4_address: [239, 0, 0, 1],
+            transport_protocol: TransportProtocol::Udp,
+            port: 30510,
+        });
+        let sd_endpoint = SdOption::Ipv4SdEndpoint(Ipv4SdEndpointOption {
+            ipv4_address: [192, 168, 1, 2],
+            transport_protocol: TransportProtocol::Udp,
+            port: 30490,
+        });
+        let options = [multicast, endpoint, sd_endpoint];
+
+        // subscribe & stop subscribe require an endpoint
+        for (start, ok) in [(0, false), (1, true), (2, false)] {
+            let subscribe =
+                SdEntry::new_subscribe_eventgroup_entry(start, 0, 1, 0, 0, 0, 0, 1, false, 0, 0)
+                    .unwrap();
+            let stop =
+                SdEntry::new_stop_subscribe_eventgroup_entry(0, start, 0, 1, 0, 0, 0, false, 0, 0)
+                    .unwrap();
+            for entry in [subscribe, stop] {
+                assert_eq!(
+                    entry.check_options(&options),
+                    if ok {
+                        Ok(())
+                    } else {
+                        Err(SdValueError::MissingEndpointOption(entry.kind()))
+                    }
+                );
+            }
+        }
+
+        // acks may only reference multicast endpoints
+        for (start, expected) in [
+            (0, Ok(())),
+            (
+                1,
+                Err(SdValueError::UnexpectedEndpointOption {
+                    kind: SdEntryKind::SubscribeAck,
+                    option_index: 1,
+                }),
+            ),
+            (
+                2,
+                Err(SdValueError::UnexpectedEndpointOption {
+                    kind: SdEntryKind::SubscribeAck,
+                    option_index: 2,
+                }),
+            ),
+        ] {
+            let ack = SdEntry::new_subscribe_ack_eventgroup_entry(start, 0, 1, 0, 0, 0, 0, 1, 0, 0)
+                .unwrap();
+            assert_eq!(expected, ack.check_options(&options));
+        }
+        let ack =
+            SdEntry::new_subscribe_ack_eventgroup_entry(0, 0, 0, 0, 0, 0, 0, 1, 0, 0).unwrap();
+        assert_eq!(Ok(()), ack.check_options(&[]));
+
+        // other kinds have no requirements
+        let nack = SdEntry::new_subscribe_nack_eventgroup_entry(1, 0, 2, 0, 0, 0, 0, 0, 0).unwrap();
+        assert_eq!(Ok(()), nack.check_options(&options));
+        let offer = SdEntry::new_offer_service_entry(0, 0, 0, 0, 0, 0, 0, 1, 0).unwrap();
+        assert_eq!(Ok(()), offer.check_options(&[]));
+
+        // option run out of bounds
+        let subscribe =
+            SdEntry::new_subscribe_eventgroup_entry(0, 2, 1, 2, 0, 0, 0, 1, false, 0, 0).unwrap();
+        assert_eq!(
+            subscribe.check_options(&options),
+            Err(SdValueError::SdOptionRunOutOfBounds {
+                run: 2,
+                start_index: 2,
+                number_of_options: 2,
+                options_len: 3,
+            })
+        );
+    }
 }
@@ -0,0 +1,172 @@
+use crate::sd::entries::*;
+
+/// Logical kind of a SOMEIP service discovery entry.
+///
+/// On the wire only the entry types [`SdServiceEntryType`] & [`EventGroupEntryType`]
+/// exist. Stops & negative acknowledgements are encoded via a TTL of 0,
+/// which is taken into account by this enum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SdEntryKind {
+    /// Find service entry.
+    Find,
+    /// Offer service entry with a TTL bigger than 0.
+    Offer,
+    /// Offer service entry with a TTL of 0.
+    StopOffer,
+    /// Subscribe eventgroup entry with a TTL bigger than 0.
+    Subscribe,
+    /// Subscribe eventgroup entry with a TTL of 0.
+    StopSubscribe,
+    /// Subscribe eventgroup ack entry with a TTL bigger than 0.
+    SubscribeAck,
+    /// Subscribe eventgroup ack entry with a TTL of 0.
+    SubscribeNack,
+}
+
+impl SdEntryKind {
+    /// Determines the kind of a service entry.
+    #[inline]
+    pub fn from_service(entry_type: SdServiceEntryType, ttl: U24) -> SdEntryKind {
+        match (entry_type, ttl.value()) {
+            (SdServiceEntryType::FindService, _) => SdEntryKind::Find,
+            (SdServiceEntryType::OfferService, 0) => SdEntryKind::StopOffer,
+            (SdServiceEntryType::OfferService, _) => SdEntryKind::Offer,
+        }
+    }
+
+    /// Determines the kind of an eventgroup entry.
+    #[inline]
+    pub fn from_eventgroup(entry_type: EventGroupEntryType, ttl: U24) -> SdEntryKind {
+        match (entry_type, ttl.value()) {
+            (EventGroupEntryType::SubscribeOrStop, 0) => SdEntryKind::StopSubscribe,
+            (EventGroupEntryType::SubscribeOrStop, _) => SdEntryKind::Subscribe,
+            (EventGroupEntryType::SubscribeAckOrNack, 0) => SdEntryKind::SubscribeNack,
+            (EventGroupEntryType::SubscribeAckOrNack, _) => SdEntryKind::SubscribeAck,
+        }
+    }
+
+    /// Returns the entry type value used on the wire.
+    #[inline]
+    pub fn entry_type_raw(&self) -> u8 {
+        use SdEntryKind::*;
+        match self {
+            Find => SdServiceEntryType::FindService as u8,
+            Offer | StopOffer => SdServiceEntryType::OfferService as u8,
+            Subscribe | StopSubscribe => EventGroupEntryType::SubscribeOrStop as u8,
+            SubscribeAck | SubscribeNack => EventGroupEntryType::SubscribeAckOrNack as u8,
+        }
+    }
+
+    /// Returns true if the kind is encoded as a service entry.
+    #[inline]
+    pub fn is_service(&self) -> bool {
+        matches!(
+            self,
+            SdEntryKind::Find | SdEntryKind::Offer | SdEntryKind::StopOffer
+        )
+    }
+
+    /// Returns true if the kind is encoded as an eventgroup entry.
+    #[inline]
+    pub fn is_eventgroup(&self) -> bool {
+        false == self.is_service()
+    }
+}
+
+impl core::fmt::Display for SdEntryKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use SdEntryKind::*;
+        match self {
+            Find => write!(f, "Find Service"),
+            Offer => write!(f, "Offer Service"),
+            StopOffer => write!(f, "Stop Offer Service"),
+            Subscribe => write!(f, "Subscribe Eventgroup"),
+            StopSubscribe => write!(f, "Stop Subscribe Eventgroup"),
+            SubscribeAck => write!(f, "Subscribe Eventgroup Ack"),
+            SubscribeNack => write!(f, "Subscribe Eventgroup Nack"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn from_entry_type() {
+        use SdEntryKind::*;
+        let ttl = U24::try_new(3).unwrap();
+        for (expected, actual) in [
+            (
+                Find,
+                SdEntryKind::from_service(SdServiceEntryType::FindService, U24::ZERO),
+            ),
+            (
+                Find,
+                SdEntryKind::from_service(SdServiceEntryType::FindService, ttl),
+            ),
+            (
+                StopOffer,
+                SdEntryKind::from_service(SdServiceEntryType::OfferService, U24::ZERO),
+            ),
+            (
+                Offer,
+                SdEntryKind::from_service(SdServiceEntryType::OfferService, ttl),
+            ),
+            (
+                StopSubscribe,
+                SdEntryKind::from_eventgroup(EventGroupEntryType::SubscribeOrStop, U24::ZERO),
+            ),
+            (
+                Subscribe,
+                SdEntryKind::from_eventgroup(EventGroupEntryType::SubscribeOrStop, ttl),
+            ),
+            (
+                SubscribeNack,
+                SdEntryKind::from_eventgroup(EventGroupEntryType::SubscribeAckOrNack, U24::ZERO),
+            ),
+            (
+                SubscribeAck,
+                SdEntryKind::from_eventgroup(EventGroupEntryType::SubscribeAckOrNack, ttl),
+            ),
+        ] {
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn entry_type_raw() {
+        use SdEntryKind::*;
+        for (kind, raw, is_service) in [
+            (Find, 0x00, true),
+            (Offer, 0x01, true),
+            (StopOffer, 0x01, true),
+            (Subscribe, 0x06, false),
+            (StopSubscribe, 0x06, false),
+            (SubscribeAck, 0x07, false),
+            (SubscribeNack, 0x07, false),
+        ] {
+            assert_eq!(raw, kind.entry_type_raw());
+            assert_eq!(is_service, kind.is_service());
+            assert_eq!(false == is_service, kind.is_eventgroup());
+        }
+    }
+
+    #[test]
+    fn display() {
+        use SdEntryKind::*;
+        for (kind, expected) in [
+            (Find, "Find Service"),
+            (Offer, "Offer Service"),
+            (StopOffer, "Stop Offer Service"),
+            (Subscribe, "Subscribe Eventgroup"),
+            (StopSubscribe, "Stop Subscribe Eventgroup"),
+            (SubscribeAck, "Subscribe Eventgroup Ack"),
+            (SubscribeNack, "Subscribe Eventgroup Nack"),
+        ] {
+            assert_eq!(expected, format!("{}", kind));
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use crate::err::{SdError, SdSliceError};
-use crate::sd::{entries::*, SdEntry};
+use crate::sd::{entries::*, SdEntry, SdEntryKind};
 
 /// Zero-copy reference to a serialized SOMEIP SD entry.
 ///
 /// Dispatches to [`ServiceEntrySlice`] or [`EventGroupEntrySlice`]
 /// based on the entry type byte.
+///
+/// `Display` names the entry (e.g. "Stop Offer Service" for an offer with
+/// a TTL of 0) followed by its ids, version & TTL. `{:#}` lists all fields.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum SdEntrySlice<'a> {
     /// A service entry (FindService or OfferService).
@@ -146,6 +149,136 @@ impl<'a> SdEntrySlice<'a> {
             SdEntrySlice::Eventgroup(e) => e.ttl(),
         }
     }
+
+    /// Returns the logical kind of the entry (taking into account that a
+    /// TTL of 0 turns offers, subscriptions & their acknowledgements into
+    /// stops & nacks).
+    #[inline]
+    pub fn kind(&self) -> SdEntryKind {
+        match self {
+            SdEntrySlice::Service(s) => SdEntryKind::from_service(s.entry_type(), s.ttl()),
+            SdEntrySlice::Eventgroup(e) => SdEntryKind::from_eventgroup(e.entry_type(), e.ttl()),
+        }
+    }
+
+    /// Writes the dissector lines of all entry fields.
+    pub(crate) fn write_fields(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        level: usize,
+    ) -> core::fmt::Result {
+        use crate::dissect::line;
+
+        let entry_type = match self {
+            SdEntrySlice::Service(s) => s.entry_type() as u8,
+            SdEntrySlice::Eventgroup(e) => e.entry_type() as u8,
+        };
+        line(
+            f,
+            level,
+            format_args!("Type: {} (0x{:02x})", self.kind(), entry_type),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Index 1st Options: {}", self.start_index_options_1()),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Index 2nd Options: {}", self.start_index_options_2()),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Number of Opts 1: {}", self.number_of_options_1().value()),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Number of Opts 2: {}", self.number_of_options_2().value()),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Service ID: 0x{:04x}", self.service_id()),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Instance ID: 0x{:04x}", self.instance_id()),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Major Version: {}", self.major_version()),
+        )?;
+        line(f, level, format_args!("TTL: {}", self.ttl()))?;
+        match self {
+            SdEntrySlice::Service(s) => {
+                line(
+                    f,
+                    level,
+                    format_args!("Minor Version: {}", s.minor_version()),
+                )?;
+            }
+            SdEntrySlice::Eventgroup(e) => {
+                line(
+                    f,
+                    level,
+                    format_args!("Initial Data Requested: {}", e.initial_data_requested()),
+                )?;
+                line(f, level, format_args!("Counter: {}", e.counter().value()))?;
+                line(
+                    f,
+                    level,
+                    format_args!("Eventgroup ID: 0x{:04x}", e.eventgroup_id()),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for SdEntrySlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} (Service ID: 0x{:04x}, Instance ID: 0x{:04x}, ",
+            self.kind(),
+            self.service_id(),
+            self.instance_id()
+        )?;
+        match self {
+            SdEntrySlice::Service(s) => write!(
+                f,
+                "Version: {}.{}, TTL: {})",
+                s.major_version(),
+                s.minor_version(),
+                s.ttl()
+            )?,
+            SdEntrySlice::Eventgroup(e) => write!(
+                f,
+                "Eventgroup ID: 0x{:04x}, Major Version: {}, TTL: {})",
+                e.eventgroup_id(),
+                e.major_version(),
+                e.ttl()
+            )?,
+        }
+        if f.alternate() {
+            self.write_fields(f, 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes the entry in the same form as the owned [`SdEntry`].
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for SdEntrySlice<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_owned().serialize(serializer)
+    }
 }
 
 #[cfg(test)]
@@ -287,6 +420,16 @@ mod tests {
         assert_eq!(h1, h2);
     }
 
+    proptest! {
+        #[test]
+        fn kind(entry in someip_sd_entry_any()) {
+            let bytes = entry.to_bytes();
+            let slice = SdEntrySlice::from_slice(&bytes).unwrap();
+            prop_assert_eq!(entry.kind(), slice.kind());
+            prop_assert_eq!(slice.kind().entry_type_raw(), bytes[0]);
+        }
+    }
+
     proptest! {
         #[test]
         fn roundtrip_service(entry in someip_sd_service_entry_any()) {
@@ -354,4 +497,70 @@ mod tests {
             prop_assert_eq!(slice.to_owned(), SdEntry::Eventgroup(entry));
         }
     }
+
+    #[test]
+    fn display() {
+        let service = |entry_type: SdServiceEntryType, ttl: u32| {
+            SdEntry::new_service_entry(entry_type, 0, 0, 0, 0, 0x1234, 0x0001, 2, ttl, 3)
+                .unwrap()
+                .to_bytes()
+        };
+        let eventgroup = |entry_type: EventGroupEntryType, ttl: u32| {
+            SdEntry::new_eventgroup(
+                entry_type, 0, 0, 0, 0, 0x1234, 0x0001, 2, ttl, true, 4, 0x10,
+            )
+            .unwrap()
+            .to_bytes()
+        };
+        for (bytes, expected) in [
+            (
+                service(SdServiceEntryType::FindService, 3),
+                "Find Service (Service ID: 0x1234, Instance ID: 0x0001, Version: 2.3, TTL: 3)",
+            ),
+            (
+                service(SdServiceEntryType::OfferService, 3),
+                "Offer Service (Service ID: 0x1234, Instance ID: 0x0001, Version: 2.3, TTL: 3)",
+            ),
+            (
+                service(SdServiceEntryType::OfferService, 0),
+                "Stop Offer Service (Service ID: 0x1234, Instance ID: 0x0001, Version: 2.3, TTL: 0)",
+            ),
+            (
+                eventgroup(EventGroupEntryType::SubscribeOrStop, 3),
+                "Subscribe Eventgroup (Service ID: 0x1234, Instance ID: 0x0001, Eventgroup ID: 0x0010, Major Version: 2, TTL: 3)",
+            ),
+            (
+                eventgroup(EventGroupEntryType::SubscribeOrStop, 0),
+                "Stop Subscribe Eventgroup (Service ID: 0x1234, Instance ID: 0x0001, Eventgroup ID: 0x0010, Major Version: 2, TTL: 0)",
+            ),
+            (
+                eventgroup(EventGroupEntryType::SubscribeAckOrNack, 3),
+                "Subscribe Eventgroup Ack (Service ID: 0x1234, Instance ID: 0x0001, Eventgroup ID: 0x0010, Major Version: 2, TTL: 3)",
+            ),
+            (
+                eventgroup(EventGroupEntryType::SubscribeAckOrNack, 0),
+                "Subscribe Eventgroup Nack (Service ID: 0x1234, Instance ID: 0x0001, Eventgroup ID: 0x0010, Major Version: 2, TTL: 0)",
+            ),
+        ] {
+            let entry = SdEntrySlice::from_slice(&bytes).unwrap();
+            assert_eq!(expected, format!("{}", entry));
+        }
+
+        let bytes = eventgroup(EventGroupEntryType::SubscribeOrStop, 3);
+        let entry = SdEntrySlice::from_slice(&bytes).unwrap();
+        assert!(format!("{:#}", entry).ends_with(
+            "\n    Type: Subscribe Eventgroup (0x06)\
+             \n    Index 1st Options: 0\
+             \n    Index 2nd Options: 0\
+             \n    Number of Opts 1: 0\
+             \n    Number of Opts 2: 0\
+             \n    Service ID: 0x1234\
+             \n    Instance ID: 0x0001\
+             \n    Major Version: 2\
+             \n    TTL: 3\
+             \n    Initial Data Requested: false\
+             \n    Counter: 4\
+             \n    Eventgroup ID: 0x0010"
+        ));
+    }
 }
@@ -0,0 +1,163 @@
+use crate::err::{SdError, SdSliceError};
+use crate::sd::{entries::*, SdEntrySlice};
+
+/// Mutable view over a serialized SOMEIP SD entry.
+///
+/// Dispatches to [`ServiceEntrySliceMut`] or [`EventGroupEntrySliceMut`]
+/// based on the entry type byte. The setters for the fields shared by both
+/// entry kinds are available directly on the enum.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum SdEntrySliceMut<'a> {
+    /// A service entry (FindService or OfferService).
+    Service(ServiceEntrySliceMut<'a>),
+
+    /// An eventgroup entry (Subscribe or SubscribeAck).
+    Eventgroup(EventGroupEntrySliceMut<'a>),
+}
+
+impl<'a> SdEntrySliceMut<'a> {
+    /// Tries to create a mutable view of the SD entry at the beginning of
+    /// `slice`.
+    ///
+    /// On success returns the entry and the remaining bytes after it.
+    ///
+    /// # Errors
+    ///
+    /// - [`SdSliceError::UnexpectedEndOfSlice`] if `slice.len() < ENTRY_LEN`
+    /// - [`SdError::UnknownSdServiceEntryType`] if the type byte is
+    ///   not a recognised entry type
+    #[inline]
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<(Self, &'a mut [u8]), SdSliceError> {
+        if slice.len() < ENTRY_LEN {
+            return Err(SdSliceError::UnexpectedEndOfSlice(ENTRY_LEN));
+        }
+        match slice[0] {
+            0x00 | 0x01 => {
+                let (s, rest) = ServiceEntrySliceMut::from_slice(slice)?;
+                Ok((SdEntrySliceMut::Service(s), rest))
+            }
+            0x06 | 0x07 => {
+                let (e, rest) = EventGroupEntrySliceMut::from_slice(slice)?;
+                Ok((SdEntrySliceMut::Eventgroup(e), rest))
+            }
+            other => Err(SdSliceError::Content(SdError::UnknownSdServiceEntryType(
+                other,
+            ))),
+        }
+    }
+
+    /// Returns a read only view of the entry.
+    #[inline]
+    pub fn to_slice(&self) -> SdEntrySlice<'_> {
+        match self {
+            SdEntrySliceMut::Service(s) => SdEntrySlice::Service(s.to_slice()),
+            SdEntrySliceMut::Eventgroup(e) => SdEntrySlice::Eventgroup(e.to_slice()),
+        }
+    }
+
+    /// Returns the underlying byte slice (exactly [`ENTRY_LEN`] bytes).
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        match self {
+            SdEntrySliceMut::Service(s) => s.slice(),
+            SdEntrySliceMut::Eventgroup(e) => e.slice(),
+        }
+    }
+
+    /// Sets the service id.
+    #[inline]
+    pub fn set_service_id(&mut self, service_id: u16) {
+        match self {
+            SdEntrySliceMut::Service(s) => s.set_service_id(service_id),
+            SdEntrySliceMut::Eventgroup(e) => e.set_service_id(service_id),
+        }
+    }
+
+    /// Sets the instance id.
+    #[inline]
+    pub fn set_instance_id(&mut self, instance_id: u16) {
+        match self {
+            SdEntrySliceMut::Service(s) => s.set_instance_id(instance_id),
+            SdEntrySliceMut::Eventgroup(e) => e.set_instance_id(instance_id),
+        }
+    }
+
+    /// Sets the major version.
+    #[inline]
+    pub fn set_major_version(&mut self, major_version: u8) {
+        match self {
+            SdEntrySliceMut::Service(s) => s.set_major_version(major_version),
+            SdEntrySliceMut::Eventgroup(e) => e.set_major_version(major_version),
+        }
+    }
+
+    /// Sets the time to live.
+    #[inline]
+    pub fn set_ttl(&mut self, ttl: U24) {
+        match self {
+            SdEntrySliceMut::Service(s) => s.set_ttl(ttl),
+            SdEntrySliceMut::Eventgroup(e) => e.set_ttl(ttl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn from_slice_errors() {
+        let mut buf = [0u8; ENTRY_LEN];
+        assert!(matches!(
+            SdEntrySliceMut::from_slice(&mut buf[..ENTRY_LEN - 1]),
+            Err(SdSliceError::UnexpectedEndOfSlice(ENTRY_LEN))
+        ));
+        buf[0] = 0x02;
+        assert!(matches!(
+            SdEntrySliceMut::from_slice(&mut buf),
+            Err(SdSliceError::Content(SdError::UnknownSdServiceEntryType(
+                0x02
+            )))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn common_setters(
+            entry in someip_sd_entry_any(),
+            service_id in any::<u16>(),
+            instance_id in any::<u16>(),
+            major_version in any::<u8>(),
+            ttl in 0..=U24::MAX_U32,
+        ) {
+            let mut bytes = entry.to_bytes();
+            let (mut slice, rest) = SdEntrySliceMut::from_slice(&mut bytes).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(entry, slice.to_slice().to_owned());
+
+            let ttl = U24::try_new(ttl).unwrap();
+            slice.set_service_id(service_id);
+            slice.set_instance_id(instance_id);
+            slice.set_major_version(major_version);
+            slice.set_ttl(ttl);
+
+            let result = slice.to_slice();
+            let (actual_service_id, actual_instance_id, actual_major_version, actual_ttl) =
+                match result {
+                    SdEntrySlice::Service(s) => {
+                        (s.service_id(), s.instance_id(), s.major_version(), s.ttl())
+                    }
+                    SdEntrySlice::Eventgroup(e) => {
+                        (e.service_id(), e.instance_id(), e.major_version(), e.ttl())
+                    }
+                };
+            assert_eq!(service_id, actual_service_id);
+            assert_eq!(instance_id, actual_instance_id);
+            assert_eq!(major_version, actual_major_version);
+            assert_eq!(ttl, actual_ttl);
+            assert_eq!(&bytes[..1], &entry.to_bytes()[..1]);
+        }
+    }
+}
@@ -702,6 +702,161 @@ impl SdHeader {
     }
 }
 
+/// Serializes the flags, entries & options of the header (the entries &
+/// options as [`SdEntry`] & [`SdOption`] values).
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for SdHeader {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::serde_util::SeqResults;
+        use core::convert::Infallible;
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("SdHeader", 3)?;
+        state.serialize_field("flags", &self.flags)?;
+        state.serialize_field(
+            "entries",
+            &SeqResults(self.entries().map(Ok::<_, Infallible>)),
+        )?;
+        state.serialize_field(
+            "options",
+            &SeqResults(self.options().map(Ok::<_, Infallible>)),
+        )?;
+        state.end()
+    }
+}
+
+/// Deserializes the flags, entries & options of a header.
+///
+/// The entries & options are added one by one (without heap allocations)
+/// via [`SdHeader::add_entry`] & [`SdHeader::add_option`], errors of these
+/// functions are returned as deserialization errors.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for SdHeader {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("SdHeader", SD_HEADER_FIELDS, SdHeaderVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+const SD_HEADER_FIELDS: &[&str] = &["flags", "entries", "options"];
+
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, serde::Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum SdHeaderField {
+    Flags,
+    Entries,
+    Options,
+}
+
+#[cfg(feature = "serde")]
+struct SdHeaderVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for SdHeaderVisitor {
+    type Value = SdHeader;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "struct SdHeader")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<SdHeader, A::Error> {
+        use serde::de::Error;
+
+        let mut header = SdHeader {
+            flags: seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?,
+            ..Default::default()
+        };
+        seq.next_element_seed(AddSeed::entries(&mut header))?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        seq.next_element_seed(AddSeed::options(&mut header))?
+            .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+        Ok(header)
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<SdHeader, A::Error> {
+        use serde::de::Error;
+
+        let mut header = SdHeader::default();
+        let mut seen = [false; 3];
+        while let Some(field) = map.next_key()? {
+            let index = field as usize;
+            if seen[index] {
+                return Err(A::Error::duplicate_field(SD_HEADER_FIELDS[index]));
+            }
+            seen[index] = true;
+            match field {
+                SdHeaderField::Flags => header.flags = map.next_value()?,
+                SdHeaderField::Entries => map.next_value_seed(AddSeed::entries(&mut header))?,
+                SdHeaderField::Options => map.next_value_seed(AddSeed::options(&mut header))?,
+            }
+        }
+        if let Some(index) = seen.iter().position(|seen| false == *seen) {
+            return Err(A::Error::missing_field(SD_HEADER_FIELDS[index]));
+        }
+        Ok(header)
+    }
+}
+
+/// Deserializes a sequence of entries or options & adds them to a header.
+#[cfg(feature = "serde")]
+struct AddSeed<'h, T> {
+    header: &'h mut SdHeader,
+    add: fn(&mut SdHeader, T) -> Result<(), SdValueError>,
+    expecting: &'static str,
+}
+
+#[cfg(feature = "serde")]
+impl<'h> AddSeed<'h, SdEntry> {
+    fn entries(header: &'h mut SdHeader) -> Self {
+        AddSeed {
+            header,
+            add: SdHeader::add_entry,
+            expecting: "a sequence of SOMEIP SD entries",
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'h> AddSeed<'h, SdOption> {
+    fn options(header: &'h mut SdHeader) -> Self {
+        AddSeed {
+            header,
+            add: SdHeader::add_option,
+            expecting: "a sequence of SOMEIP SD options",
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::DeserializeSeed<'de> for AddSeed<'_, T> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for AddSeed<'_, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.expecting)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(value) = seq.next_element()? {
+            (self.add)(self.header, value).map_err(serde::de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec::Vec;
@@ -1148,4 +1303,101 @@ mod tests {
         let header_reboot = SdHeader::empty(true);
         assert_eq!(header_reboot.flags.reboot, true);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use alloc::format;
+        use serde_json::json;
+
+        let mut header = SdHeader::empty(true);
+        header
+            .add_option(SdOption::Ipv4Endpoint(Ipv4EndpointOption {
+                ipv4_address: [192, 168, 0, 1],
+                transport_protocol: TransportProtocol::Udp,
+                port: 30501,
+            }))
+            .unwrap();
+        header
+            .add_entry(SdEntry::new_offer_service_entry(0, 0, 1, 0, 0x1234, 1, 2, 3, 4).unwrap())
+            .unwrap();
+
+        let expected = json!({
+            "flags": {
+                "reboot": true,
+                "unicast": true,
+                "explicit_initial_data_control": false,
+            },
+            "entries": [{
+                "Service": {
+                    "entry_type": "OfferService",
+                    "start_index_options_1": 0,
+                    "start_index_options_2": 0,
+                    "number_of_options_1": 1,
+                    "number_of_options_2": 0,
+                    "service_id": 0x1234,
+                    "instance_id": 1,
+                    "major_version": 2,
+                    "ttl": 3,
+                    "minor_version": 4,
+                }
+            }],
+            "options": [{
+                "Ipv4Endpoint": {
+                    "ipv4_address": "192.168.0.1",
+                    "transport_protocol": "Udp",
+                    "port": 30501,
+                }
+            }],
+        });
+        assert_eq!(expected, serde_json::to_value(&header).unwrap());
+        assert_eq!(header, serde_json::from_value(expected.clone()).unwrap());
+
+        // fields in a different order
+        let json = serde_json::to_string(&header).unwrap();
+        let mut reordered = serde_json::Map::new();
+        for key in ["options", "entries", "flags"] {
+            reordered.insert(key.into(), expected[key].clone());
+        }
+        assert_eq!(header, serde_json::from_value(reordered.into()).unwrap());
+        assert_eq!(header, serde_json::from_str(&json).unwrap());
+
+        // missing & duplicate fields
+        let mut missing = expected.clone();
+        missing.as_object_mut().unwrap().remove("options");
+        assert!(serde_json::from_value::<SdHeader>(missing).is_err());
+        assert!(serde_json::from_str::<SdHeader>(r#"{"entries":[],"entries":[]}"#).is_err());
+
+        // errors of add_option are returned
+        let mut unknown = expected.clone();
+        unknown["options"] = json!([{
+            "UnknownDiscardable": { "length": 1, "option_type": 0x30 }
+        }]);
+        let err = serde_json::from_value::<SdHeader>(unknown).unwrap_err();
+        assert!(format!("{err}").contains(&format!(
+            "{}",
+            SdValueError::SdUnknownDiscardableOption(0x30)
+        )));
+    }
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn serde_roundtrip(
+            entries in proptest::collection::vec(someip_sd_entry_any(), 0..8),
+            options in proptest::collection::vec(someip_sd_option_any(), 0..8),
+        ) {
+            let mut header = SdHeader::default();
+            for entry in entries {
+                header.add_entry(entry).unwrap();
+            }
+            for option in options {
+                if header.add_option(option).is_err() {
+                    break;
+                }
+            }
+            let json = serde_json::to_string(&header).unwrap();
+            prop_assert_eq!(&header, &serde_json::from_str::<SdHeader>(&json).unwrap());
+        }
+    }
 }
@@ -2,6 +2,7 @@ use crate::sd::*;
 
 /// Flags at the start of a SOMEIP service discovery header.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SdHeaderFlags {
     pub reboot: bool,
     pub unicast: bool,
@@ -2,6 +2,7 @@ use crate::sd::{options::*, *};
 use arrayvec::ArrayVec;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 // Keeping Configuration inline is intentional: boxing it would violate the
 // allocation-free SD API.
 #[allow(clippy::large_enum_variant)]
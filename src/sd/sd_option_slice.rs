@@ -5,6 +5,9 @@ use crate::sd::options::*;
 ///
 /// Mirrors [`super::SdOption`] but references the underlying byte slice
 /// instead of owning the data.
+///
+/// `Display` names the option type followed by its main content (e.g. the
+/// endpoint address), `{:#}` lists all fields.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum SdOptionSlice<'a> {
     /// Arbitrary configuration strings.
@@ -196,6 +199,230 @@ impl<'a> SdOptionSlice<'a> {
 
         Ok((option, rest))
     }
+
+    /// Name & raw type value of the option.
+    fn type_name_and_value(&self) -> (&'static str, u8) {
+        use SdOptionSlice::*;
+        match self {
+            Configuration(_) => ("Configuration Option", CONFIGURATION_TYPE),
+            LoadBalancing(_) => ("Load Balancing Option", LOAD_BALANCING_TYPE),
+            Ipv4Endpoint(_) => ("IPv4 Endpoint Option", IPV4_ENDPOINT_TYPE),
+            Ipv6Endpoint(_) => ("IPv6 Endpoint Option", IPV6_ENDPOINT_TYPE),
+            Ipv4Multicast(_) => ("IPv4 Multicast Option", IPV4_MULTICAST_TYPE),
+            Ipv6Multicast(_) => ("IPv6 Multicast Option", IPV6_MULTICAST_TYPE),
+            Ipv4SdEndpoint(_) => ("IPv4 SD Endpoint Option", IPV4_SD_ENDPOINT_TYPE),
+            Ipv6SdEndpoint(_) => ("IPv6 SD Endpoint Option", IPV6_SD_ENDPOINT_TYPE),
+            Unknown(o) => ("Unknown Option", o.option_type()),
+        }
+    }
+
+    /// Address, port & transport protocol of endpoint & multicast options.
+    pub(crate) fn socket_addr(&self) -> Option<(core::net::SocketAddr, TransportProtocol)> {
+        use core::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+        use SdOptionSlice::*;
+        let v4 = |address: [u8; 4], port: u16| SocketAddr::from((Ipv4Addr::from(address), port));
+        let v6 = |address: [u8; 16], port: u16| SocketAddr::from((Ipv6Addr::from(address), port));
+        match self {
+            Ipv4Endpoint(o) => Some((v4(o.ipv4_address(), o.port()), o.transport_protocol())),
+            Ipv6Endpoint(o) => Some((v6(o.ipv6_address(), o.port()), o.transport_protocol())),
+            Ipv4Multicast(o) => Some((v4(o.ipv4_address(), o.port()), o.transport_protocol())),
+            Ipv6Multicast(o) => Some((v6(o.ipv6_address(), o.port()), o.transport_protocol())),
+            Ipv4SdEndpoint(o) => Some((v4(o.ipv4_address(), o.port()), o.transport_protocol())),
+            Ipv6SdEndpoint(o) => Some((v6(o.ipv6_address(), o.port()), o.transport_protocol())),
+            Configuration(_) | LoadBalancing(_) | Unknown(_) => None,
+        }
+    }
+
+    /// Writes the dissector lines of all option fields.
+    pub(crate) fn write_fields(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        level: usize,
+    ) -> core::fmt::Result {
+        use crate::dissect::{hex_dump, line};
+        use SdOptionSlice::*;
+
+        let (name, type_value) = self.type_name_and_value();
+        line(f, level, format_args!("Type: {name} (0x{type_value:02x})"))?;
+        if let Some((addr, protocol)) = self.socket_addr() {
+            line(f, level, format_args!("IP Address: {}", addr.ip()))?;
+            line(
+                f,
+                level,
+                format_args!(
+                    "Transport Protocol: {} (0x{:02x})",
+                    protocol,
+                    u8::from(protocol)
+                ),
+            )?;
+            line(f, level, format_args!("Port: {}", addr.port()))?;
+        }
+        match self {
+            Configuration(o) => {
+                line(f, level, format_args!("Discardable: {}", o.discardable()))?;
+                for item in ConfigurationItems(o.configuration_string()) {
+                    line(f, level, format_args!("Item: \"{}\"", EscapedAscii(item)))?;
+                }
+            }
+            LoadBalancing(o) => {
+                line(f, level, format_args!("Discardable: {}", o.discardable()))?;
+                line(f, level, format_args!("Priority: {}", o.priority()))?;
+                line(f, level, format_args!("Weight: {}", o.weight()))?;
+            }
+            Unknown(o) => {
+                line(f, level, format_args!("Discardable: {}", o.discardable()))?;
+                // the first byte is the reserved byte containing the discardable flag
+                let data = &o.slice()[1..];
+                line(f, level, format_args!("Data: {} bytes", data.len()))?;
+                hex_dump(f, level + 1, data)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for SdOptionSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (name, type_value) = self.type_name_and_value();
+        write!(f, "{name} (")?;
+        if let Some((addr, protocol)) = self.socket_addr() {
+            write!(f, "{addr}, {protocol}")?;
+        }
+        match self {
+            SdOptionSlice::Configuration(o) => {
+                for (index, item) in ConfigurationItems(o.configuration_string()).enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "\"{}\"", EscapedAscii(item))?;
+                }
+            }
+            SdOptionSlice::LoadBalancing(o) => {
+                write!(f, "Priority: {}, Weight: {}", o.priority(), o.weight())?
+            }
+            SdOptionSlice::Unknown(_) => write!(f, "Type: 0x{type_value:02x}")?,
+            _ => {}
+        }
+        write!(f, ")")?;
+        if f.alternate() {
+            self.write_fields(f, 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the (length prefixed) items of a configuration string
+/// (stops at the terminating zero length item & truncates items exceeding
+/// the string).
+pub(crate) struct ConfigurationItems<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Iterator for ConfigurationItems<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let (len, rest) = self.0.split_first()?;
+        if *len == 0 {
+            self.0 = &[];
+            return None;
+        }
+        let (item, rest) = rest.split_at(core::cmp::min(usize::from(*len), rest.len()));
+        self.0 = rest;
+        Some(item)
+    }
+}
+
+/// Writes printable ASCII characters as is & all other bytes escaped.
+struct EscapedAscii<'a>(&'a [u8]);
+
+impl core::fmt::Display for EscapedAscii<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for byte in self.0 {
+            match byte {
+                b'"' | b'\\' => write!(f, "\\{}", char::from(*byte))?,
+                0x20..=0x7e => write!(f, "{}", char::from(*byte))?,
+                _ => write!(f, "\\x{byte:02x}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serializes the option in the same form as the owned [`SdOption`].
+///
+/// Options of an unknown type are serialized as an additional `Unknown`
+/// variant containing the option type, the discardable flag & the data
+/// following the discardable flag (these can not be deserialized as
+/// [`SdOption`]).
+///
+/// [`SdOption`]: crate::sd::SdOption
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for SdOptionSlice<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStructVariant;
+        use SdOptionSlice::*;
+
+        // variant indices & names match the ones of SdOption
+        const NAME: &str = "SdOption";
+        match self {
+            Configuration(o) => serializer.serialize_newtype_variant(
+                NAME,
+                0,
+                "Configuration",
+                &ConfigurationOption::from(*o),
+            ),
+            LoadBalancing(o) => serializer.serialize_newtype_variant(
+                NAME,
+                1,
+                "LoadBalancing",
+                &LoadBalancingOption::from(*o),
+            ),
+            Ipv4Endpoint(o) => serializer.serialize_newtype_variant(
+                NAME,
+                2,
+                "Ipv4Endpoint",
+                &Ipv4EndpointOption::from(*o),
+            ),
+            Ipv6Endpoint(o) => serializer.serialize_newtype_variant(
+                NAME,
+                3,
+                "Ipv6Endpoint",
+                &Ipv6EndpointOption::from(*o),
+            ),
+            Ipv4Multicast(o) => serializer.serialize_newtype_variant(
+                NAME,
+                4,
+                "Ipv4Multicast",
+                &Ipv4MulticastOption::from(*o),
+            ),
+            Ipv6Multicast(o) => serializer.serialize_newtype_variant(
+                NAME,
+                5,
+                "Ipv6Multicast",
+                &Ipv6MulticastOption::from(*o),
+            ),
+            Ipv4SdEndpoint(o) => serializer.serialize_newtype_variant(
+                NAME,
+                6,
+                "Ipv4SdEndpoint",
+                &Ipv4SdEndpointOption::from(*o),
+            ),
+            Ipv6SdEndpoint(o) => serializer.serialize_newtype_variant(
+                NAME,
+                7,
+                "Ipv6SdEndpoint",
+                &Ipv6SdEndpointOption::from(*o),
+            ),
+            Unknown(o) => {
+                let mut state = serializer.serialize_struct_variant(NAME, 9, "Unknown", 3)?;
+                state.serialize_field("option_type", &o.option_type())?;
+                state.serialize_field("discardable", &o.discardable())?;
+                state.serialize_field("data", &crate::serde_util::Bytes(&o.slice()[1..]))?;
+                state.end()
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -711,4 +938,134 @@ mod tests {
 
         let _ = format!("{:?}", opt);
     }
+
+    #[test]
+    fn display() {
+        let ipv4 = [0x00, 192, 168, 0, 1, 0x00, 0x11, 0x75, 0x30];
+        let mut ipv6 = [0u8; 21];
+        ipv6[1] = 0xff;
+        ipv6[2] = 0x02;
+        ipv6[16] = 0x01;
+        ipv6[18] = 0x06;
+        ipv6[19..21].copy_from_slice(&30490u16.to_be_bytes());
+        let ipv4_generic = [0x00, 10, 0, 0, 1, 0x00, 0x84, 0x00, 0x01];
+        let load_balancing = [0x80, 0x00, 0x01, 0x00, 0x02];
+        let configuration = *b"\x80\x03a=1\x04b\"\\\x01\x00";
+        let unknown = [0x80, 0xab, 0xcd];
+        for (option, expected) in [
+            (
+                SdOptionSlice::Ipv4Endpoint(Ipv4EndpointSlice::from_slice(&ipv4).unwrap()),
+                "IPv4 Endpoint Option (192.168.0.1:30000, UDP)",
+            ),
+            (
+                SdOptionSlice::Ipv6Endpoint(Ipv6EndpointSlice::from_slice(&ipv6).unwrap()),
+                "IPv6 Endpoint Option ([ff02::1]:30490, TCP)",
+            ),
+            (
+                SdOptionSlice::Ipv4Multicast(Ipv4MulticastSlice::from_slice(&ipv4).unwrap()),
+                "IPv4 Multicast Option (192.168.0.1:30000, UDP)",
+            ),
+            (
+                SdOptionSlice::Ipv6Multicast(Ipv6MulticastSlice::from_slice(&ipv6).unwrap()),
+                "IPv6 Multicast Option ([ff02::1]:30490, TCP)",
+            ),
+            (
+                SdOptionSlice::Ipv4SdEndpoint(
+                    Ipv4SdEndpointSlice::from_slice(&ipv4_generic).unwrap(),
+                ),
+                "IPv4 SD Endpoint Option (10.0.0.1:1, Protocol 0x84)",
+            ),
+            (
+                SdOptionSlice::Ipv6SdEndpoint(Ipv6SdEndpointSlice::from_slice(&ipv6).unwrap()),
+                "IPv6 SD Endpoint Option ([ff02::1]:30490, TCP)",
+            ),
+            (
+                SdOptionSlice::LoadBalancing(
+                    LoadBalancingSlice::from_slice(&load_balancing).unwrap(),
+                ),
+                "Load Balancing Option (Priority: 1, Weight: 2)",
+            ),
+            (
+                SdOptionSlice::Configuration(
+                    ConfigurationSlice::from_slice(&configuration).unwrap(),
+                ),
+                "Configuration Option (\"a=1\", \"b\\\"\\\\\\x01\")",
+            ),
+            (
+                SdOptionSlice::Unknown(UnknownSlice::new(0x30, &unknown).unwrap()),
+                "Unknown Option (Type: 0x30)",
+            ),
+        ] {
+            assert_eq!(expected, format!("{}", option));
+        }
+
+        let option = SdOptionSlice::Ipv4Endpoint(Ipv4EndpointSlice::from_slice(&ipv4).unwrap());
+        assert_eq!(
+            "IPv4 Endpoint Option (192.168.0.1:30000, UDP)\
+             \n    Type: IPv4 Endpoint Option (0x04)\
+             \n    IP Address: 192.168.0.1\
+             \n    Transport Protocol: UDP (0x11)\
+             \n    Port: 30000",
+            format!("{:#}", option)
+        );
+
+        let option =
+            SdOptionSlice::LoadBalancing(LoadBalancingSlice::from_slice(&load_balancing).unwrap());
+        assert!(format!("{:#}", option).ends_with(
+            "\n    Type: Load Balancing Option (0x02)\
+             \n    Discardable: true\
+             \n    Priority: 1\
+             \n    Weight: 2"
+        ));
+
+        // items exceeding the string are truncated
+        let truncated = [0x00, 0x03, b'a', 0x05, b'b'];
+        let option =
+            SdOptionSlice::Configuration(ConfigurationSlice::from_slice(&truncated).unwrap());
+        assert!(format!("{:#}", option).ends_with(
+            "\n    Type: Configuration Option (0x01)\
+             \n    Discardable: false\
+             \n    Item: \"a\\x05b\""
+        ));
+
+        let option = SdOptionSlice::Unknown(UnknownSlice::new(0x30, &unknown).unwrap());
+        assert!(format!("{:#}", option).ends_with(&format!(
+            "\n    Type: Unknown Option (0x30)\
+             \n    Discardable: true\
+             \n    Data: 2 bytes\
+             \n        0000  ab cd{}..",
+            " ".repeat(45)
+        )));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde_json::json;
+
+        let ipv4 = [0x00, 192, 168, 0, 1, 0x00, 0x11, 0x75, 0x30];
+        let option = SdOptionSlice::Ipv4Endpoint(Ipv4EndpointSlice::from_slice(&ipv4).unwrap());
+        assert_eq!(
+            serde_json::to_value(crate::sd::SdOption::from(Ipv4EndpointOption {
+                ipv4_address: [192, 168, 0, 1],
+                transport_protocol: TransportProtocol::Udp,
+                port: 30000,
+            }))
+            .unwrap(),
+            serde_json::to_value(option).unwrap()
+        );
+
+        let unknown = [0x80, 0xab, 0xcd];
+        let option = SdOptionSlice::Unknown(UnknownSlice::new(0x30, &unknown).unwrap());
+        assert_eq!(
+            json!({
+                "Unknown": {
+                    "option_type": 0x30,
+                    "discardable": true,
+                    "data": [0xab, 0xcd],
+                }
+            }),
+            serde_json::to_value(option).unwrap()
+        );
+    }
 }
@@ -0,0 +1,147 @@
+use crate::err::SdOptionSliceError;
+use crate::sd::{options::*, SdOptionSlice};
+
+/// Mutable view over a serialized SD option.
+///
+/// Endpoint & multicast options can be rewritten in place (address,
+/// transport protocol & port). All other options are only accessible
+/// read only via [`SdOptionSliceMut::Other`], as changing their content
+/// could change their length.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum SdOptionSliceMut<'a> {
+    Ipv4Endpoint(Ipv4EndpointSliceMut<'a>),
+    Ipv6Endpoint(Ipv6EndpointSliceMut<'a>),
+    Ipv4Multicast(Ipv4MulticastSliceMut<'a>),
+    Ipv6Multicast(Ipv6MulticastSliceMut<'a>),
+    Ipv4SdEndpoint(Ipv4SdEndpointSliceMut<'a>),
+    Ipv6SdEndpoint(Ipv6SdEndpointSliceMut<'a>),
+    /// Any other option (configuration, load balancing & unknown options).
+    Other(SdOptionSlice<'a>),
+}
+
+impl<'a> SdOptionSliceMut<'a> {
+    /// Reads the next SD option from the beginning of `slice` and returns
+    /// a mutable view of it together with the remaining bytes after the
+    /// option.
+    ///
+    /// The option is validated with the same checks as
+    /// [`SdOptionSlice::from_slice`].
+    pub fn from_slice(
+        slice: &'a mut [u8],
+    ) -> Result<(SdOptionSliceMut<'a>, &'a mut [u8]), SdOptionSliceError> {
+        let (is_endpoint, total_len) = {
+            let (option, rest) = SdOptionSlice::from_slice(slice)?;
+            (option.socket_addr().is_some(), slice.len() - rest.len())
+        };
+        let (option, rest) = slice.split_at_mut(total_len);
+        if false == is_endpoint {
+            let option: &'a [u8] = option;
+            // already validated above
+            let (option, _) = SdOptionSlice::from_validated_slice(option)?;
+            return Ok((SdOptionSliceMut::Other(option), rest));
+        }
+
+        let (header, payload) = option.split_at_mut(3);
+        // the payload length was already validated above
+        let option = match header[2] {
+            IPV4_ENDPOINT_TYPE => SdOptionSliceMut::Ipv4Endpoint(
+                Ipv4EndpointSliceMut::from_slice(payload).map_err(SdOptionSliceError::Len)?,
+            ),
+            IPV6_ENDPOINT_TYPE => SdOptionSliceMut::Ipv6Endpoint(
+                Ipv6EndpointSliceMut::from_slice(payload).map_err(SdOptionSliceError::Len)?,
+            ),
+            IPV4_MULTICAST_TYPE => SdOptionSliceMut::Ipv4Multicast(
+                Ipv4MulticastSliceMut::from_slice(payload).map_err(SdOptionSliceError::Len)?,
+            ),
+            IPV6_MULTICAST_TYPE => SdOptionSliceMut::Ipv6Multicast(
+                Ipv6MulticastSliceMut::from_slice(payload).map_err(SdOptionSliceError::Len)?,
+            ),
+            IPV4_SD_ENDPOINT_TYPE => SdOptionSliceMut::Ipv4SdEndpoint(
+                Ipv4SdEndpointSliceMut::from_slice(payload).map_err(SdOptionSliceError::Len)?,
+            ),
+            // only the IPv6 SD endpoint option is left (see `socket_addr`)
+            _ => SdOptionSliceMut::Ipv6SdEndpoint(
+                Ipv6SdEndpointSliceMut::from_slice(payload).map_err(SdOptionSliceError::Len)?,
+            ),
+        };
+        Ok((option, rest))
+    }
+
+    /// Returns a read only view of the option.
+    pub fn to_slice(&self) -> SdOptionSlice<'_> {
+        use SdOptionSliceMut::*;
+        match self {
+            Ipv4Endpoint(o) => SdOptionSlice::Ipv4Endpoint(o.to_slice()),
+            Ipv6Endpoint(o) => SdOptionSlice::Ipv6Endpoint(o.to_slice()),
+            Ipv4Multicast(o) => SdOptionSlice::Ipv4Multicast(o.to_slice()),
+            Ipv6Multicast(o) => SdOptionSlice::Ipv6Multicast(o.to_slice()),
+            Ipv4SdEndpoint(o) => SdOptionSlice::Ipv4SdEndpoint(o.to_slice()),
+            Ipv6SdEndpoint(o) => SdOptionSlice::Ipv6SdEndpoint(o.to_slice()),
+            Other(o) => *o,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use crate::sd::SdOption;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    fn to_bytes(option: &SdOption) -> Vec<u8> {
+        option.to_bytes().unwrap().to_vec()
+    }
+
+    #[test]
+    fn from_slice_error() {
+        let mut buf = [0x00, 0x00, 0x04];
+        assert_eq!(
+            SdOptionSlice::from_slice(&[0x00, 0x00, 0x04]).unwrap_err(),
+            SdOptionSliceMut::from_slice(&mut buf).unwrap_err()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn from_slice(option in someip_sd_option_any()) {
+            let mut bytes = to_bytes(&option);
+            bytes.push(0xff);
+            let (mut_slice, rest) = SdOptionSliceMut::from_slice(&mut bytes).unwrap();
+            assert_eq!(&[0xff], rest);
+            let is_endpoint = mut_slice.to_slice().socket_addr().is_some();
+            assert_eq!(is_endpoint, false == matches!(mut_slice, SdOptionSliceMut::Other(_)));
+            let expected = to_bytes(&option);
+            assert_eq!(
+                SdOptionSlice::from_slice(&expected).unwrap().0,
+                mut_slice.to_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn setters() {
+        let option = SdOption::Ipv4SdEndpoint(Ipv4SdEndpointOption {
+            ipv4_address: [1, 2, 3, 4],
+            transport_protocol: TransportProtocol::Udp,
+            port: 30490,
+        });
+        let mut bytes = to_bytes(&option);
+        let (mut_slice, _) = SdOptionSliceMut::from_slice(&mut bytes).unwrap();
+        let SdOptionSliceMut::Ipv4SdEndpoint(mut o) = mut_slice else {
+            panic!("unexpected option {:?}", mut_slice);
+        };
+        o.set_ipv4_address([5, 6, 7, 8]);
+        o.set_transport_protocol(TransportProtocol::Tcp);
+        o.set_port(1234);
+        assert_eq!(
+            to_bytes(&SdOption::Ipv4SdEndpoint(Ipv4SdEndpointOption {
+                ipv4_address: [5, 6, 7, 8],
+                transport_protocol: TransportProtocol::Tcp,
+                port: 1234,
+            })),
+            bytes
+        );
+    }
+}
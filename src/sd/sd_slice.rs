@@ -16,6 +16,10 @@ use crate::sd::{
 /// resolved in O(1) (e.g. when iterating entries together with their
 /// options via [`entries_with_options`](Self::entries_with_options)).
 ///
+/// `Display` summarizes the flags, entries & options on one line, the
+/// alternate form (`{:#}`) expands them including the options referenced
+/// by the option runs of each entry.
+///
 /// # Example
 ///
 /// ```
@@ -263,6 +267,154 @@ impl<'a> SdSlice<'a> {
     pub fn entries_with_options(&self) -> SdEntriesWithOptionsIterator<'a, '_> {
         SdEntriesWithOptionsIterator::new(self.entries, &self.options_index)
     }
+
+    /// Writes the dissector lines of the flags, entries (including the
+    /// options referenced by their option runs) & options.
+    pub(crate) fn write_fields(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        level: usize,
+    ) -> core::fmt::Result {
+        use crate::dissect::line;
+
+        let flags = if self.flags.reboot { REBOOT_FLAG } else { 0 }
+            | if self.flags.unicast { UNICAST_FLAG } else { 0 }
+            | if self.flags.explicit_initial_data_control {
+                EXPLICIT_INITIAL_DATA_CONTROL_FLAG
+            } else {
+                0
+            };
+        line(f, level, format_args!("Flags: 0x{flags:02x}"))?;
+        line(f, level + 1, format_args!("Reboot: {}", self.flags.reboot))?;
+        line(
+            f,
+            level + 1,
+            format_args!("Unicast: {}", self.flags.unicast),
+        )?;
+        line(
+            f,
+            level + 1,
+            format_args!(
+                "Explicit Initial Data Control: {}",
+                self.flags.explicit_initial_data_control
+            ),
+        )?;
+
+        line(
+            f,
+            level,
+            format_args!("Length of Entries Array: {}", self.entries.len()),
+        )?;
+        line(f, level, format_args!("Entries Array"))?;
+        for (index, entry) in self.entries_with_options().enumerate() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    line(f, level + 1, format_args!("Entry {index}: {err}"))?;
+                    break;
+                }
+            };
+            line(
+                f,
+                level + 1,
+                format_args!("Entry {index}: {}", entry.entry()),
+            )?;
+            entry.entry().write_fields(f, level + 2)?;
+            for (run, start, options) in [
+                (
+                    1,
+                    entry.entry().start_index_options_1(),
+                    entry.options_run_1(),
+                ),
+                (
+                    2,
+                    entry.entry().start_index_options_2(),
+                    entry.options_run_2(),
+                ),
+            ] {
+                for (offset, option) in options.enumerate() {
+                    if offset == 0 {
+                        line(f, level + 2, format_args!("Options Run {run}"))?;
+                    }
+                    line(
+                        f,
+                        level + 3,
+                        format_args!("Option {}: {}", usize::from(start) + offset, option),
+                    )?;
+                }
+            }
+        }
+
+        line(
+            f,
+            level,
+            format_args!(
+                "Length of Options Array: {}",
+                self.options_index.options().len()
+            ),
+        )?;
+        line(f, level, format_args!("Options Array"))?;
+        for index in 0..self.options_index.len() {
+            if let Some(option) = self.options_index.get(index) {
+                line(f, level + 1, format_args!("Option {index}: {option}"))?;
+                option.write_fields(f, level + 2)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for SdSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "SOME/IP-SD Reboot: {}, Unicast: {}, Entries: [",
+            self.flags.reboot, self.flags.unicast
+        )?;
+        for (index, entry) in self.entries().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            match entry {
+                Ok(entry) => write!(f, "{entry}")?,
+                Err(err) => write!(f, "{err}")?,
+            }
+        }
+        write!(f, "], Options: [")?;
+        for index in 0..self.options_index.len() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            if let Some(option) = self.options_index.get(index) {
+                write!(f, "{option}")?;
+            }
+        }
+        write!(f, "]")?;
+        if f.alternate() {
+            self.write_fields(f, 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes the flags, entries & options in the same form as
+/// [`super::SdHeader`].
+///
+/// Errors encountered while decoding the entries are returned as
+/// serialization errors.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for SdSlice<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::serde_util::SeqResults;
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("SdHeader", 3)?;
+        state.serialize_field("flags", &self.flags)?;
+        state.serialize_field("entries", &SeqResults(self.entries()))?;
+        state.serialize_field("options", &SeqResults(self.options()))?;
+        state.end()
+    }
 }
 
 #[cfg(test)]
@@ -688,4 +840,106 @@ mod tests {
             prop_assert_eq!(header_options, slice_options);
         }
     }
+
+    #[test]
+    fn display() {
+        let mut header = sample_header();
+        header
+            .add_entry(
+                // second run referencing the second option
+                SdEntry::new_eventgroup(
+                    EventGroupEntryType::SubscribeAckOrNack,
+                    0,
+                    1,
+                    0,
+                    1,
+                    0x1234,
+                    0x5678,
+                    1,
+                    0,
+                    false,
+                    0,
+                    0x0001,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let mut buffer = [0u8; 128];
+        let len = header.header_len();
+        header.write_to_slice(&mut buffer[..len]).unwrap();
+        let sd = SdSlice::from_slice(&buffer[..len]).unwrap();
+
+        let summary = "SOME/IP-SD Reboot: false, Unicast: true, Entries: [\
+            Offer Service (Service ID: 0x1234, Instance ID: 0x5678, Version: 1.16777216, TTL: 3600), \
+            Subscribe Eventgroup Nack (Service ID: 0x1234, Instance ID: 0x5678, Eventgroup ID: 0x0001, Major Version: 1, TTL: 0)\
+            ], Options: [IPv4 Endpoint Option (10.0.0.1:100, UDP), IPv4 Endpoint Option (10.0.0.2:200, TCP)]";
+        assert_eq!(summary, format!("{}", sd));
+
+        let option_0 = "IPv4 Endpoint Option (10.0.0.1:100, UDP)";
+        let option_1 = "IPv4 Endpoint Option (10.0.0.2:200, TCP)";
+        let expected = [
+            summary,
+            "    Flags: 0x40",
+            "        Reboot: false",
+            "        Unicast: true",
+            "        Explicit Initial Data Control: false",
+            "    Length of Entries Array: 32",
+            "    Entries Array",
+            "        Entry 0: Offer Service (Service ID: 0x1234, Instance ID: 0x5678, Version: 1.16777216, TTL: 3600)",
+            "            Type: Offer Service (0x01)",
+            "            Index 1st Options: 0",
+            "            Index 2nd Options: 0",
+            "            Number of Opts 1: 2",
+            "            Number of Opts 2: 0",
+            "            Service ID: 0x1234",
+            "            Instance ID: 0x5678",
+            "            Major Version: 1",
+            "            TTL: 3600",
+            "            Minor Version: 16777216",
+            "            Options Run 1",
+            &format!("                Option 0: {option_0}"),
+            &format!("                Option 1: {option_1}"),
+            "        Entry 1: Subscribe Eventgroup Nack (Service ID: 0x1234, Instance ID: 0x5678, Eventgroup ID: 0x0001, Major Version: 1, TTL: 0)",
+            "            Type: Subscribe Eventgroup Nack (0x07)",
+            "            Index 1st Options: 0",
+            "            Index 2nd Options: 1",
+            "            Number of Opts 1: 0",
+            "            Number of Opts 2: 1",
+            "            Service ID: 0x1234",
+            "            Instance ID: 0x5678",
+            "            Major Version: 1",
+            "            TTL: 0",
+            "            Initial Data Requested: false",
+            "            Counter: 0",
+            "            Eventgroup ID: 0x0001",
+            "            Options Run 2",
+            &format!("                Option 1: {option_1}"),
+            "    Length of Options Array: 24",
+            "    Options Array",
+            &format!("        Option 0: {option_0}"),
+            "            Type: IPv4 Endpoint Option (0x04)",
+            "            IP Address: 10.0.0.1",
+            "            Transport Protocol: UDP (0x11)",
+            "            Port: 100",
+            &format!("        Option 1: {option_1}"),
+            "            Type: IPv4 Endpoint Option (0x04)",
+            "            IP Address: 10.0.0.2",
+            "            Transport Protocol: TCP (0x06)",
+            "            Port: 200",
+        ]
+        .join("\n");
+        assert_eq!(expected, format!("{:#}", sd));
+    }
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[test]
+    fn serde() {
+        let header = sample_header();
+        let bytes = header.to_bytes_vec().unwrap();
+        let sd = SdSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_value(&header).unwrap(),
+            serde_json::to_value(&sd).unwrap()
+        );
+    }
 }
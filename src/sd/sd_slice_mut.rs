@@ -0,0 +1,394 @@
+use crate::err::SdSliceError;
+use crate::sd::{
+    entries::ENTRY_LEN, SdEntrySliceMut, SdHeaderFlags, SdOptionSliceMut, SdSlice,
+    EXPLICIT_INITIAL_DATA_CONTROL_FLAG, REBOOT_FLAG, UNICAST_FLAG,
+};
+
+/// Mutable view over a serialized SOMEIP service discovery payload
+/// allowing flags, entry fields & endpoint options to be rewritten in
+/// place.
+///
+/// The payload is validated once during construction (with the same checks
+/// as [`SdSlice`]). Only fields that do not influence the layout of the
+/// payload or the option runs referenced by the entries can be changed, so
+/// the payload stays valid & is never copied.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::sd::{entries::U24, options::*, SdEntry, SdHeader, SdOption, SdSliceMut, SdEntrySliceMut, SdOptionSliceMut};
+///
+/// let mut header = SdHeader::default();
+/// header.add_option(SdOption::Ipv4Endpoint(Ipv4EndpointOption {
+///     ipv4_address: [192, 168, 1, 1],
+///     transport_protocol: TransportProtocol::Udp,
+///     port: 1234,
+/// })).unwrap();
+/// header.add_entry(
+///     SdEntry::new_offer_service_entry(0, 0, 1, 0, 0x1234, 0x5678, 1, 3600, 0x01000000).unwrap()
+/// ).unwrap();
+/// let mut buffer = [0u8; 64];
+/// let len = header.header_len();
+/// header.write_to_slice(&mut buffer[..len]).unwrap();
+///
+/// // rewrite the instance id & the endpoint address in place
+/// let mut sd = SdSliceMut::from_slice(&mut buffer[..len]).unwrap();
+/// sd.set_reboot(true);
+/// let mut entry = sd.entry_mut(0).unwrap();
+/// entry.set_instance_id(0x0001);
+/// entry.set_ttl(U24::try_new(10).unwrap());
+/// if let Some(SdOptionSliceMut::Ipv4Endpoint(mut option)) = sd.option_mut(0) {
+///     option.set_ipv4_address([10, 0, 0, 1]);
+///     option.set_port(30501);
+/// }
+///
+/// let sd = sd.to_slice();
+/// assert!(sd.flags().reboot);
+/// println!("{:#}", sd);
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct SdSliceMut<'a> {
+    slice: &'a mut [u8],
+    entries_len: usize,
+}
+
+impl<'a> SdSliceMut<'a> {
+    /// Validates the payload of a complete SOME/IP-SD message (see
+    /// [`SdSlice::from_someip`]) and returns a mutable view over it.
+    pub fn from_someip(message: crate::SomeipMsgSliceMut<'a>) -> Result<Self, SdSliceError> {
+        Self::from_someip_with_flag(message, false)
+    }
+
+    /// Validates the payload of a complete SOME/IP-SD message, optionally
+    /// accepting unknown non-discardable options.
+    pub fn from_someip_with_flag(
+        message: crate::SomeipMsgSliceMut<'a>,
+        discard_unknown_option: bool,
+    ) -> Result<Self, SdSliceError> {
+        SdSlice::from_someip_with_flag(&message.to_slice(), discard_unknown_option)?;
+        Ok(Self::from_validated(message.into_payload_mut()))
+    }
+
+    /// Validates the SD payload in `slice` (see [`SdSlice::from_slice`])
+    /// and returns a mutable view over it.
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<Self, SdSliceError> {
+        Self::from_slice_with_flag(slice, false)
+    }
+
+    /// Validates the SD payload in `slice`, optionally accepting unknown
+    /// non-discardable options.
+    pub fn from_slice_with_flag(
+        slice: &'a mut [u8],
+        discard_unknown_option: bool,
+    ) -> Result<Self, SdSliceError> {
+        SdSlice::from_slice_with_flag(slice, discard_unknown_option)?;
+        Ok(Self::from_validated(slice))
+    }
+
+    fn from_validated(slice: &'a mut [u8]) -> Self {
+        let entries_len = u32::from_be_bytes([slice[4], slice[5], slice[6], slice[7]]) as usize;
+        Self { slice, entries_len }
+    }
+
+    /// Returns a read only view of the payload.
+    pub fn to_slice(&self) -> SdSlice<'_> {
+        // the setters can not invalidate the payload, so this can not fail
+        SdSlice::from_slice_with_flag(self.slice, true).unwrap()
+    }
+
+    /// Returns the underlying SD payload.
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        self.slice
+    }
+
+    /// Returns the header flags.
+    #[inline]
+    pub fn flags(&self) -> SdHeaderFlags {
+        SdHeaderFlags {
+            reboot: 0 != self.slice[0] & REBOOT_FLAG,
+            unicast: 0 != self.slice[0] & UNICAST_FLAG,
+            explicit_initial_data_control: 0 != self.slice[0] & EXPLICIT_INITIAL_DATA_CONTROL_FLAG,
+        }
+    }
+
+    #[inline]
+    fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.slice[0] |= flag;
+        } else {
+            self.slice[0] &= !flag;
+        }
+    }
+
+    /// Sets the reboot flag.
+    #[inline]
+    pub fn set_reboot(&mut self, reboot: bool) {
+        self.set_flag(REBOOT_FLAG, reboot);
+    }
+
+    /// Sets the unicast flag.
+    #[inline]
+    pub fn set_unicast(&mut self, unicast: bool) {
+        self.set_flag(UNICAST_FLAG, unicast);
+    }
+
+    /// Sets the explicit initial data control flag.
+    #[inline]
+    pub fn set_explicit_initial_data_control(&mut self, explicit_initial_data_control: bool) {
+        self.set_flag(
+            EXPLICIT_INITIAL_DATA_CONTROL_FLAG,
+            explicit_initial_data_control,
+        );
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn entries_count(&self) -> usize {
+        self.entries_len / ENTRY_LEN
+    }
+
+    /// Returns a mutable view of the entry at the given index, or `None`
+    /// if the index is out of range.
+    pub fn entry_mut(&mut self, index: usize) -> Option<SdEntrySliceMut<'_>> {
+        if index >= self.entries_count() {
+            return None;
+        }
+        let start = 8 + index * ENTRY_LEN;
+        // the entry types are validated during construction
+        SdEntrySliceMut::from_slice(&mut self.slice[start..start + ENTRY_LEN])
+            .ok()
+            .map(|(entry, _)| entry)
+    }
+
+    /// Byte offsets of the options array (start & end).
+    #[inline]
+    fn options_range(&self) -> (usize, usize) {
+        (8 + self.entries_len + 4, self.slice.len())
+    }
+
+    /// Returns the byte offset of the option with the given index.
+    fn option_offset(&self, index: usize) -> Option<usize> {
+        let (mut offset, end) = self.options_range();
+        for _ in 0..index {
+            if offset >= end {
+                return None;
+            }
+            // option lengths are validated during construction
+            let len = u16::from_be_bytes([self.slice[offset], self.slice[offset + 1]]);
+            offset += 3 + usize::from(len);
+        }
+        if offset < end {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of options.
+    pub fn options_count(&self) -> usize {
+        let (mut offset, end) = self.options_range();
+        let mut count = 0;
+        while offset < end {
+            let len = u16::from_be_bytes([self.slice[offset], self.slice[offset + 1]]);
+            offset += 3 + usize::from(len);
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns a mutable view of the option at the given index, or `None`
+    /// if the index is out of range.
+    pub fn option_mut(&mut self, index: usize) -> Option<SdOptionSliceMut<'_>> {
+        let offset = self.option_offset(index)?;
+        // the options are validated during construction
+        SdOptionSliceMut::from_slice(&mut self.slice[offset..])
+            .ok()
+            .map(|(option, _)| option)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sd::{
+        entries::*, options::*, SdEntry, SdEntrySlice, SdHeader, SdOption, SdOptionSlice,
+    };
+    use alloc::vec::Vec;
+
+    fn sample_payload() -> Vec<u8> {
+        let mut header = SdHeader::default();
+        header
+            .add_option(SdOption::Ipv4Endpoint(Ipv4EndpointOption {
+                ipv4_address: [10, 0, 0, 1],
+                transport_protocol: TransportProtocol::Udp,
+                port: 100,
+            }))
+            .unwrap();
+        header
+            .add_option(SdOption::Ipv6Endpoint(Ipv6EndpointOption {
+                ipv6_address: [1; 16],
+                transport_protocol: TransportProtocol::Tcp,
+                port: 200,
+            }))
+            .unwrap();
+        header
+            .add_entry(
+                SdEntry::new_offer_service_entry(0, 0, 1, 0, 0x1234, 0x5678, 1, 3600, 0x01000000)
+                    .unwrap(),
+            )
+            .unwrap();
+        header
+            .add_entry(
+                SdEntry::new_eventgroup(
+                    EventGroupEntryType::SubscribeOrStop,
+                    1,
+                    0,
+                    1,
+                    0,
+                    0x1234,
+                    0x5678,
+                    1,
+                    3,
+                    false,
+                    1,
+                    0x0010,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let mut buffer = alloc::vec![0u8; header.header_len()];
+        header.write_to_slice(&mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn from_slice() {
+        let mut bytes = sample_payload();
+        let expected = SdSlice::from_slice(&bytes).unwrap().flags().clone();
+        let sd = SdSliceMut::from_slice(&mut bytes).unwrap();
+        assert_eq!(expected, sd.flags());
+        assert_eq!(2, sd.entries_count());
+        assert_eq!(2, sd.options_count());
+
+        // errors are forwarded
+        let len = bytes.len();
+        let expected = SdSlice::from_slice(&bytes[..len - 1]).unwrap_err();
+        assert_eq!(
+            expected,
+            SdSliceMut::from_slice(&mut bytes[..len - 1]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn flags() {
+        let mut bytes = sample_payload();
+        let mut sd = SdSliceMut::from_slice(&mut bytes).unwrap();
+        sd.set_reboot(true);
+        sd.set_unicast(false);
+        sd.set_explicit_initial_data_control(true);
+        assert_eq!(
+            SdHeaderFlags {
+                reboot: true,
+                unicast: false,
+                explicit_initial_data_control: true,
+            },
+            sd.flags()
+        );
+        assert_eq!(&sd.flags(), sd.to_slice().flags());
+        sd.set_reboot(false);
+        assert!(false == sd.flags().reboot);
+    }
+
+    #[test]
+    fn entries() {
+        let mut bytes = sample_payload();
+        let mut sd = SdSliceMut::from_slice(&mut bytes).unwrap();
+        assert!(sd.entry_mut(2).is_none());
+
+        let mut entry = sd.entry_mut(0).unwrap();
+        entry.set_instance_id(0x0001);
+        entry.set_ttl(U24::ZERO);
+        let Some(SdEntrySliceMut::Eventgroup(mut entry)) = sd.entry_mut(1) else {
+            panic!("expected eventgroup entry");
+        };
+        entry.set_counter(U4::N7);
+        entry.set_eventgroup_id(0x0020);
+
+        let sd = sd.to_slice();
+        let mut entries = sd.entries();
+        let Some(Ok(SdEntrySlice::Service(service))) = entries.next() else {
+            panic!("expected service entry");
+        };
+        assert_eq!(0x0001, service.instance_id());
+        assert_eq!(U24::ZERO, service.ttl());
+        let Some(Ok(SdEntrySlice::Eventgroup(eventgroup))) = entries.next() else {
+            panic!("expected eventgroup entry");
+        };
+        assert_eq!(U4::N7, eventgroup.counter());
+        assert_eq!(0x0020, eventgroup.eventgroup_id());
+    }
+
+    #[test]
+    fn options() {
+        let mut bytes = sample_payload();
+        let mut sd = SdSliceMut::from_slice(&mut bytes).unwrap();
+        assert!(sd.option_mut(2).is_none());
+
+        let Some(SdOptionSliceMut::Ipv4Endpoint(mut option)) = sd.option_mut(0) else {
+            panic!("expected ipv4 endpoint option");
+        };
+        option.set_ipv4_address([192, 168, 0, 1]);
+        option.set_port(30501);
+        let Some(SdOptionSliceMut::Ipv6Endpoint(mut option)) = sd.option_mut(1) else {
+            panic!("expected ipv6 endpoint option");
+        };
+        option.set_ipv6_address([2; 16]);
+        option.set_transport_protocol(TransportProtocol::Udp);
+
+        let sd = sd.to_slice();
+        let Some(SdOptionSlice::Ipv4Endpoint(option)) = sd.options_index().get(0) else {
+            panic!("expected ipv4 endpoint option");
+        };
+        assert_eq!([192, 168, 0, 1], option.ipv4_address());
+        assert_eq!(30501, option.port());
+        let Some(SdOptionSlice::Ipv6Endpoint(option)) = sd.options_index().get(1) else {
+            panic!("expected ipv6 endpoint option");
+        };
+        assert_eq!([2; 16], option.ipv6_address());
+        assert_eq!(TransportProtocol::Udp, option.transport_protocol());
+        assert_eq!(200, option.port());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_someip() {
+        let payload = sample_payload();
+        let header = crate::SomeipHeader::new_sd_header(
+            crate::SOMEIP_LEN_OFFSET_TO_PAYLOAD + payload.len() as u32,
+            1,
+            None,
+        );
+        let mut bytes = Vec::new();
+        header.write_raw(&mut bytes).unwrap();
+        bytes.extend_from_slice(&payload);
+
+        {
+            let msg = crate::SomeipMsgSliceMut::from_slice(&mut bytes).unwrap();
+            let mut sd = SdSliceMut::from_someip(msg).unwrap();
+            sd.set_unicast(false);
+        }
+        assert!(
+            false
+                == SdSlice::from_slice(&bytes[crate::SOMEIP_HEADER_LENGTH..])
+                    .unwrap()
+                    .flags()
+                    .unicast
+        );
+
+        // header checks are applied
+        let mut msg = crate::SomeipMsgSliceMut::from_slice(&mut bytes).unwrap();
+        msg.set_session_id(0);
+        assert!(SdSliceMut::from_someip(msg).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+/// Data over which the MAC of a SecOC secured payload is calculated.
+///
+/// As specified by AUTOSAR SecOC the data to authenticate is the
+/// concatenation of the data id (big endian), the authentic data & the
+/// complete freshness value.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct DataToAuthenticate<'a> {
+    data_id: [u8; 2],
+    authentic_data: &'a [u8],
+    freshness: &'a [u8],
+}
+
+impl<'a> DataToAuthenticate<'a> {
+    /// Creates the data to authenticate from its parts.
+    pub fn new(data_id: u16, authentic_data: &'a [u8], freshness: &'a [u8]) -> Self {
+        DataToAuthenticate {
+            data_id: data_id.to_be_bytes(),
+            authentic_data,
+            freshness,
+        }
+    }
+
+    /// Data id of the secured payload.
+    #[inline]
+    pub fn data_id(&self) -> u16 {
+        u16::from_be_bytes(self.data_id)
+    }
+
+    /// Authentic data (payload without freshness value & MAC).
+    #[inline]
+    pub fn authentic_data(&self) -> &'a [u8] {
+        self.authentic_data
+    }
+
+    /// Freshness value included in the data to authenticate.
+    #[inline]
+    pub fn freshness(&self) -> &'a [u8] {
+        self.freshness
+    }
+
+    /// Returns the parts of the data to authenticate in the order they
+    /// have to be concatenated (data id, authentic data & freshness value).
+    #[inline]
+    pub fn parts(&self) -> [&[u8]; 3] {
+        [&self.data_id, self.authentic_data, self.freshness]
+    }
+
+    /// Length of the concatenated data to authenticate.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data_id.len() + self.authentic_data.len() + self.freshness.len()
+    }
+
+    /// Always false as the data to authenticate at least contains the data
+    /// id.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the concatenated data to authenticate.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_vec(&self) -> alloc::vec::Vec<u8> {
+        let mut result = alloc::vec::Vec::with_capacity(self.len());
+        for part in self.parts() {
+            result.extend_from_slice(part);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let data = DataToAuthenticate::new(1, &[2], &[3]);
+        let _ = format!("{:?}", data);
+        assert_eq!(data, data.clone());
+    }
+
+    #[test]
+    fn accessors() {
+        let data = DataToAuthenticate::new(0x1234, &[1, 2, 3], &[4, 5]);
+        assert_eq!(0x1234, data.data_id());
+        assert_eq!(&[1, 2, 3], data.authentic_data());
+        assert_eq!(&[4, 5], data.freshness());
+        assert_eq!([&[0x12, 0x34][..], &[1, 2, 3], &[4, 5]], data.parts());
+        assert_eq!(7, data.len());
+        assert_eq!(false, data.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn to_vec() {
+        let data = DataToAuthenticate::new(0x1234, &[1, 2, 3], &[4, 5]);
+        assert_eq!(&[0x12, 0x34, 1, 2, 3, 4, 5], &data.to_vec()[..]);
+    }
+}
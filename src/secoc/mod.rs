@@ -0,0 +1,51 @@
+use crate::{err::*, *};
+
+mod data_to_authenticate;
+pub use data_to_authenticate::*;
+
+mod secoc_authenticator;
+pub use secoc_authenticator::*;
+
+mod secoc_config;
+pub use secoc_config::*;
+
+mod secured_payload;
+pub use secured_payload::*;
+
+/// HMAC-SHA256 authenticator used in the tests.
+#[cfg(test)]
+pub(crate) mod test_authenticator {
+    use super::*;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    pub(crate) struct HmacSha256Authenticator {
+        pub(crate) key: &'static [u8],
+    }
+
+    impl SecOcAuthenticator for HmacSha256Authenticator {
+        fn generate_mac(&self, data: &[&[u8]], mac: &mut [u8]) {
+            let mut hmac = Hmac::<Sha256>::new_from_slice(self.key).unwrap();
+            for part in data {
+                hmac.update(part);
+            }
+            let result = hmac.finalize().into_bytes();
+            mac.copy_from_slice(&result[..mac.len()]);
+        }
+    }
+
+    #[test]
+    fn rfc4231_test_case_2() {
+        let auth = HmacSha256Authenticator { key: b"Jefe" };
+        let mut mac = [0u8; 32];
+        auth.generate_mac(&[b"what do ya want ", b"for nothing?"], &mut mac);
+        assert_eq!(
+            [
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43
+            ],
+            mac
+        );
+    }
+}
@@ -0,0 +1,78 @@
+/// Maximum length of a MAC that can be verified by the default
+/// implementation of [`SecOcAuthenticator::verify_mac`].
+pub const SECOC_MAX_MAC_LEN: usize = 64;
+
+/// Generates & verifies the MACs of SecOC secured payloads.
+///
+/// The cryptographic algorithm (e.g. CMAC/AES-128 or HMAC-SHA256) and the
+/// key management are provided by implementations of this trait.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::secoc::SecOcAuthenticator;
+///
+/// /// Example authenticator (NOT secure) "calculating" a MAC by xoring
+/// /// all bytes.
+/// struct XorAuthenticator;
+///
+/// impl SecOcAuthenticator for XorAuthenticator {
+///     fn generate_mac(&self, data: &[&[u8]], mac: &mut [u8]) {
+///         let value = data.iter().flat_map(|d| d.iter()).fold(0, |acc, v| acc ^ v);
+///         mac.fill(value);
+///     }
+/// }
+///
+/// let mut mac = [0u8; 2];
+/// XorAuthenticator.generate_mac(&[&[1, 2], &[4]], &mut mac);
+/// assert_eq!([7, 7], mac);
+/// assert!(XorAuthenticator.verify_mac(&[&[1, 2], &[4]], &[7, 7]));
+/// ```
+pub trait SecOcAuthenticator {
+    /// Calculates the MAC over the concatenation of all `data` parts and
+    /// writes the first `mac.len()` bytes of it (the truncated MAC) into
+    /// `mac`.
+    fn generate_mac(&self, data: &[&[u8]], mac: &mut [u8]);
+
+    /// Returns true if the given (truncated) MAC matches the MAC
+    /// calculated over the concatenation of all `data` parts.
+    ///
+    /// The default implementation generates the MAC via
+    /// [`SecOcAuthenticator::generate_mac`] & compares it in constant time.
+    /// MACs longer then [`SECOC_MAX_MAC_LEN`] are never valid with the
+    /// default implementation.
+    fn verify_mac(&self, data: &[&[u8]], mac: &[u8]) -> bool {
+        if mac.len() > SECOC_MAX_MAC_LEN {
+            return false;
+        }
+        let mut expected = [0u8; SECOC_MAX_MAC_LEN];
+        let expected = &mut expected[..mac.len()];
+        self.generate_mac(data, expected);
+        0 == expected
+            .iter()
+            .zip(mac.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_authenticator::*;
+    use super::*;
+
+    #[test]
+    fn verify_mac() {
+        let auth = HmacSha256Authenticator { key: b"key" };
+        let mut mac = [0u8; 16];
+        auth.generate_mac(&[&[1, 2, 3]], &mut mac);
+        assert!(auth.verify_mac(&[&[1, 2, 3]], &mac));
+        assert!(auth.verify_mac(&[&[1], &[2, 3]], &mac[..4]));
+        assert_eq!(false, auth.verify_mac(&[&[1, 2, 4]], &mac));
+        mac[15] ^= 1;
+        assert_eq!(false, auth.verify_mac(&[&[1, 2, 3]], &mac));
+        assert_eq!(
+            false,
+            auth.verify_mac(&[&[1, 2, 3]], &[0u8; SECOC_MAX_MAC_LEN + 1])
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use super::*;
+
+/// SecOC configuration of the SOMEIP messages with a given message id.
+///
+/// Secured payloads consist of the authentic data followed by the
+/// transmitted (truncated) freshness value & the truncated MAC:
+///
+/// ```text
+/// | authentic data | freshness (freshness_len bytes) | MAC (mac_len bytes) |
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SecOcConfig {
+    /// Message id (service id & method/event id) of the secured messages.
+    pub message_id: u32,
+    /// Data id included in the data to authenticate.
+    pub data_id: u16,
+    /// Length of the transmitted (truncated) freshness value in bytes.
+    pub freshness_len: usize,
+    /// Length of the transmitted (truncated) MAC in bytes.
+    pub mac_len: usize,
+}
+
+impl SecOcConfig {
+    /// Returns the configuration for the given message id (or `None` if
+    /// no configuration for the message id is present).
+    pub fn find(configs: &[SecOcConfig], message_id: u32) -> Option<&SecOcConfig> {
+        configs.iter().find(|c| c.message_id == message_id)
+    }
+
+    /// Writes a secured payload (authentic data, transmitted freshness
+    /// value & MAC) into `buf` & returns the number of written bytes.
+    ///
+    /// `freshness` is the complete freshness value, which is included in
+    /// the data to authenticate. Only the last (least significant)
+    /// `freshness_len` bytes of it are transmitted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use someip_parse::secoc::*;
+    ///
+    /// # struct MyAuthenticator;
+    /// # impl SecOcAuthenticator for MyAuthenticator {
+    /// #     fn generate_mac(&self, _data: &[&[u8]], mac: &mut [u8]) {
+    /// #         mac.fill(0xab);
+    /// #     }
+    /// # }
+    /// let config = SecOcConfig {
+    ///     message_id: 0x1234_8001,
+    ///     data_id: 0x0001,
+    ///     freshness_len: 1,
+    ///     mac_len: 3,
+    /// };
+    ///
+    /// let mut payload = [0u8; 6];
+    /// let len = config
+    ///     .secure(&[1, 2], &[0, 0, 0, 7], &MyAuthenticator, &mut payload)
+    ///     .unwrap();
+    /// assert_eq!(6, len);
+    /// assert_eq!([1, 2, 7, 0xab, 0xab, 0xab], payload);
+    /// ```
+    pub fn secure<A: SecOcAuthenticator + ?Sized>(
+        &self,
+        authentic_data: &[u8],
+        freshness: &[u8],
+        authenticator: &A,
+        buf: &mut [u8],
+    ) -> Result<usize, SecOcError> {
+        if freshness.len() < self.freshness_len {
+            return Err(SecOcError::FreshnessTooShort {
+                len: freshness.len(),
+                min_len: self.freshness_len,
+            });
+        }
+        let len = authentic_data.len() + self.freshness_len + self.mac_len;
+        if buf.len() < len {
+            return Err(SecOcError::WriteSpace(SliceWriteSpaceError {
+                required_len: len,
+                len: buf.len(),
+                layer: Layer::SomeipPayload,
+            }));
+        }
+        let (data_buf, rest) = buf.split_at_mut(authentic_data.len());
+        let (freshness_buf, rest) = rest.split_at_mut(self.freshness_len);
+        data_buf.copy_from_slice(authentic_data);
+        freshness_buf.copy_from_slice(&freshness[freshness.len() - self.freshness_len..]);
+        authenticator.generate_mac(
+            &DataToAuthenticate::new(self.data_id, authentic_data, freshness).parts(),
+            &mut rest[..self.mac_len],
+        );
+        Ok(len)
+    }
+
+    /// Returns a secured payload (authentic data, transmitted freshness
+    /// value & MAC).
+    ///
+    /// See [`SecOcConfig::secure`] for details.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn secure_to_vec<A: SecOcAuthenticator + ?Sized>(
+        &self,
+        authentic_data: &[u8],
+        freshness: &[u8],
+        authenticator: &A,
+    ) -> Result<alloc::vec::Vec<u8>, SecOcError> {
+        let mut result = alloc::vec![0u8; authentic_data.len() + self.freshness_len + self.mac_len];
+        self.secure(authentic_data, freshness, authenticator, &mut result)?;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_authenticator::*;
+    use super::*;
+    use alloc::format;
+
+    fn config() -> SecOcConfig {
+        SecOcConfig {
+            message_id: 0x1234_8001,
+            data_id: 0x0001,
+            freshness_len: 2,
+            mac_len: 4,
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let _ = format!("{:?}", config());
+        assert_eq!(config(), config().clone());
+    }
+
+    #[test]
+    fn find() {
+        let configs = [
+            config(),
+            SecOcConfig {
+                message_id: 0x1234_8002,
+                data_id: 0x0002,
+                ..config()
+            },
+        ];
+        assert_eq!(
+            Some(0x0001),
+            SecOcConfig::find(&configs, 0x1234_8001).map(|c| c.data_id)
+        );
+        assert_eq!(
+            Some(0x0002),
+            SecOcConfig::find(&configs, 0x1234_8002).map(|c| c.data_id)
+        );
+        assert_eq!(None, SecOcConfig::find(&configs, 0x1234_8003));
+    }
+
+    #[test]
+    fn secure() {
+        let auth = HmacSha256Authenticator {
+            key: b"someip-secoc-test",
+        };
+        let mut buf = [0u8; 10];
+        assert_eq!(
+            Ok(9),
+            config().secure(&[1, 2, 3], &[0, 0, 0, 5], &auth, &mut buf)
+        );
+        // HMAC-SHA256(key, 00 01 | 01 02 03 | 00 00 00 05) truncated to 4 bytes
+        assert_eq!([1, 2, 3, 0, 5, 0xe2, 0x4d, 0x42, 0x60, 0], buf);
+
+        // freshness too short
+        assert_eq!(
+            Err(SecOcError::FreshnessTooShort { len: 1, min_len: 2 }),
+            config().secure(&[1, 2, 3], &[5], &auth, &mut buf)
+        );
+
+        // buffer too small
+        assert_eq!(
+            Err(SecOcError::WriteSpace(SliceWriteSpaceError {
+                required_len: 9,
+                len: 8,
+                layer: Layer::SomeipPayload,
+            })),
+            config().secure(&[1, 2, 3], &[0, 5], &auth, &mut buf[..8])
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn secure_to_vec() {
+        let auth = HmacSha256Authenticator {
+            key: b"someip-secoc-test",
+        };
+        assert_eq!(
+            &[1, 2, 3, 0, 5, 0xe2, 0x4d, 0x42, 0x60],
+            &config()
+                .secure_to_vec(&[1, 2, 3], &[0, 0, 0, 5], &auth)
+                .unwrap()[..]
+        );
+        assert!(config().secure_to_vec(&[1], &[], &auth).is_err());
+    }
+}
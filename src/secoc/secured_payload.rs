@@ -0,0 +1,288 @@
+use super::*;
+
+/// SecOC secured SOMEIP payload split into the authentic data, the
+/// transmitted freshness value & the truncated MAC.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{secoc::*, SomeipMsgSlice};
+///
+/// # struct MyAuthenticator;
+/// # impl SecOcAuthenticator for MyAuthenticator {
+/// #     fn generate_mac(&self, _data: &[&[u8]], mac: &mut [u8]) {
+/// #         mac.fill(0xab);
+/// #     }
+/// # }
+/// # let raw = [
+/// #     0x12, 0x34, 0x80, 0x01, // message id
+/// #     0x00, 0x00, 0x00, 0x0e, // length
+/// #     0x00, 0x00, 0x00, 0x01, // request id
+/// #     0x01, 0x01, 0x02, 0x00, // protocol version, interface version, message type, return code
+/// #     0x01, 0x02, 0x07, 0xab, 0xab, 0xab, // payload
+/// # ];
+/// let configs = [SecOcConfig {
+///     message_id: 0x1234_8001,
+///     data_id: 0x0001,
+///     freshness_len: 1,
+///     mac_len: 3,
+/// }];
+///
+/// let msg = SomeipMsgSlice::from_slice(&raw).unwrap();
+/// if let Some(secured) = SecuredPayload::from_msg(&msg, &configs).unwrap() {
+///     assert_eq!(&[1, 2], secured.authentic_data());
+///     assert_eq!(&[7], secured.freshness());
+///     assert_eq!(&[0xab, 0xab, 0xab], secured.mac());
+///     assert!(secured.verify(&MyAuthenticator));
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SecuredPayload<'a> {
+    data_id: u16,
+    authentic_data: &'a [u8],
+    freshness: &'a [u8],
+    mac: &'a [u8],
+}
+
+impl<'a> SecuredPayload<'a> {
+    /// Splits a secured payload based on the given configuration.
+    pub fn from_slice(payload: &'a [u8], config: &SecOcConfig) -> Result<Self, SecOcError> {
+        let trailer_len = config.freshness_len + config.mac_len;
+        if payload.len() < trailer_len {
+            return Err(SecOcError::Len(LenError {
+                required_len: trailer_len,
+                len: payload.len(),
+                len_source: LenSource::Slice,
+                layer: Layer::SomeipPayload,
+            }));
+        }
+        let (authentic_data, rest) = payload.split_at(payload.len() - trailer_len);
+        let (freshness, mac) = rest.split_at(config.freshness_len);
+        Ok(SecuredPayload {
+            data_id: config.data_id,
+            authentic_data,
+            freshness,
+            mac,
+        })
+    }
+
+    /// Splits the payload of a SOMEIP message using the configuration with
+    /// the message id of the message.
+    ///
+    /// Returns `Ok(None)` if no configuration for the message id is present
+    /// (message is not secured).
+    pub fn from_msg(
+        msg: &SomeipMsgSlice<'a>,
+        configs: &[SecOcConfig],
+    ) -> Result<Option<Self>, SecOcError> {
+        match SecOcConfig::find(configs, msg.message_id()) {
+            Some(config) => SecuredPayload::from_slice(msg.payload(), config).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Data id of the configuration used to split the payload.
+    #[inline]
+    pub fn data_id(&self) -> u16 {
+        self.data_id
+    }
+
+    /// Authentic data (payload without freshness value & MAC).
+    #[inline]
+    pub fn authentic_data(&self) -> &'a [u8] {
+        self.authentic_data
+    }
+
+    /// Transmitted (possibly truncated) freshness value.
+    #[inline]
+    pub fn freshness(&self) -> &'a [u8] {
+        self.freshness
+    }
+
+    /// Transmitted (truncated) MAC.
+    #[inline]
+    pub fn mac(&self) -> &'a [u8] {
+        self.mac
+    }
+
+    /// Data to authenticate using the transmitted freshness value.
+    ///
+    /// Note that this is only the data the sender authenticated if the
+    /// complete freshness value is transmitted. Otherwise use
+    /// [`SecuredPayload::data_to_authenticate_with_freshness`] with the
+    /// complete freshness value reconstructed by the receiver.
+    #[inline]
+    pub fn data_to_authenticate(&self) -> DataToAuthenticate<'a> {
+        DataToAuthenticate::new(self.data_id, self.authentic_data, self.freshness)
+    }
+
+    /// Data to authenticate using the given (complete) freshness value.
+    #[inline]
+    pub fn data_to_authenticate_with_freshness<'b>(
+        &self,
+        freshness: &'b [u8],
+    ) -> DataToAuthenticate<'b>
+    where
+        'a: 'b,
+    {
+        DataToAuthenticate::new(self.data_id, self.authentic_data, freshness)
+    }
+
+    /// Verifies the MAC using the transmitted freshness value.
+    pub fn verify<A: SecOcAuthenticator + ?Sized>(&self, authenticator: &A) -> bool {
+        authenticator.verify_mac(&self.data_to_authenticate().parts(), self.mac)
+    }
+
+    /// Verifies the MAC using the given (complete) freshness value.
+    pub fn verify_with_freshness<A: SecOcAuthenticator + ?Sized>(
+        &self,
+        freshness: &[u8],
+        authenticator: &A,
+    ) -> bool {
+        authenticator.verify_mac(
+            &self.data_to_authenticate_with_freshness(freshness).parts(),
+            self.mac,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_authenticator::*;
+    use super::*;
+    use alloc::format;
+
+    const AUTH: HmacSha256Authenticator = HmacSha256Authenticator {
+        key: b"someip-secoc-test",
+    };
+
+    fn config() -> SecOcConfig {
+        SecOcConfig {
+            message_id: 0x1234_8001,
+            data_id: 0x0001,
+            freshness_len: 2,
+            mac_len: 4,
+        }
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let payload = SecuredPayload::from_slice(&[0; 6], &config()).unwrap();
+        let _ = format!("{:?}", payload);
+        assert_eq!(payload, payload.clone());
+    }
+
+    #[test]
+    fn from_slice() {
+        let payload = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let secured = SecuredPayload::from_slice(&payload, &config()).unwrap();
+        assert_eq!(0x0001, secured.data_id());
+        assert_eq!(&[1, 2, 3], secured.authentic_data());
+        assert_eq!(&[4, 5], secured.freshness());
+        assert_eq!(&[6, 7, 8, 9], secured.mac());
+
+        // no authentic data
+        let secured = SecuredPayload::from_slice(&payload[..6], &config()).unwrap();
+        assert_eq!(0, secured.authentic_data().len());
+
+        // too short
+        assert_eq!(
+            Err(SecOcError::Len(LenError {
+                required_len: 6,
+                len: 5,
+                len_source: LenSource::Slice,
+                layer: Layer::SomeipPayload,
+            })),
+            SecuredPayload::from_slice(&payload[..5], &config())
+        );
+    }
+
+    #[test]
+    fn from_msg() {
+        let mut raw = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 8 + 9,
+            request_id: 1,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: None,
+        }
+        .base_to_bytes()
+        .to_vec();
+        raw.extend_from_slice(&[1, 2, 3, 0, 5, 0xe2, 0x4d, 0x42, 0x60]);
+        let msg = SomeipMsgSlice::from_slice(&raw).unwrap();
+
+        // secured message
+        {
+            let secured = SecuredPayload::from_msg(&msg, &[config()])
+                .unwrap()
+                .unwrap();
+            assert_eq!(&[1, 2, 3], secured.authentic_data());
+        }
+
+        // not secured message
+        assert_eq!(
+            Ok(None),
+            SecuredPayload::from_msg(
+                &msg,
+                &[SecOcConfig {
+                    message_id: 0x1234_8002,
+                    ..config()
+                }]
+            )
+        );
+
+        // too short
+        assert!(SecuredPayload::from_msg(
+            &msg,
+            &[SecOcConfig {
+                mac_len: 8,
+                ..config()
+            }]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify() {
+        // fixed vector: HMAC-SHA256(key, 00 01 | 01 02 03 | 00 05) truncated to 4 bytes
+        let payload = [1, 2, 3, 0, 5, 0x19, 0x94, 0x90, 0xdc];
+        let secured = SecuredPayload::from_slice(&payload, &config()).unwrap();
+        assert_eq!(
+            [&[0x00, 0x01][..], &[1, 2, 3], &[0, 5]],
+            secured.data_to_authenticate().parts()
+        );
+        assert!(secured.verify(&AUTH));
+        assert_eq!(false, secured.verify_with_freshness(&[0, 0, 0, 5], &AUTH));
+
+        // fixed vector: HMAC-SHA256(key, 00 01 | 01 02 03 | 00 00 00 05) truncated to 4 bytes
+        let payload = [1, 2, 3, 0, 5, 0xe2, 0x4d, 0x42, 0x60];
+        let secured = SecuredPayload::from_slice(&payload, &config()).unwrap();
+        assert_eq!(
+            [&[0x00, 0x01][..], &[1, 2, 3], &[0, 0, 0, 5]],
+            secured
+                .data_to_authenticate_with_freshness(&[0, 0, 0, 5])
+                .parts()
+        );
+        assert!(secured.verify_with_freshness(&[0, 0, 0, 5], &AUTH));
+        assert_eq!(false, secured.verify(&AUTH));
+
+        // manipulated authentic data
+        let payload = [1, 2, 4, 0, 5, 0x19, 0x94, 0x90, 0xdc];
+        let secured = SecuredPayload::from_slice(&payload, &config()).unwrap();
+        assert_eq!(false, secured.verify(&AUTH));
+
+        // round trip with a different data id
+        let config = SecOcConfig {
+            data_id: 0x0002,
+            ..config()
+        };
+        let mut buf = [0u8; 9];
+        config.secure(&[1, 2, 3], &[0, 5], &AUTH, &mut buf).unwrap();
+        assert_ne!(&payload[5..], &buf[5..]);
+        assert!(SecuredPayload::from_slice(&buf, &config)
+            .unwrap()
+            .verify(&AUTH));
+    }
+}
@@ -0,0 +1,124 @@
+//! Helpers shared by the `serde` implementations.
+//!
+//! IP addresses stored as byte arrays are (de)serialized via
+//! [`core::net::Ipv4Addr`] & [`core::net::Ipv6Addr`] (`serde(with = ...)`),
+//! so human readable formats use the usual address strings ("192.168.0.1",
+//! "ff02::1") while compact formats use the raw octets.
+use core::fmt::Display;
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+
+/// Serializes a byte slice via [`Serializer::serialize_bytes`].
+pub(crate) struct Bytes<'a>(pub &'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Serializes the values of an iterator over results as a sequence.
+///
+/// The first error aborts the serialization (converted via
+/// [`serde::ser::Error::custom`]).
+pub(crate) struct SeqResults<I>(pub I);
+
+impl<I, T, E> Serialize for SeqResults<I>
+where
+    I: Iterator<Item = Result<T, E>> + Clone,
+    T: Serialize,
+    E: Display,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.clone().count()))?;
+        for value in self.0.clone() {
+            seq.serialize_element(&value.map_err(serde::ser::Error::custom)?)?;
+        }
+        seq.end()
+    }
+}
+
+pub(crate) mod ipv4 {
+    use core::net::Ipv4Addr;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        address: &[u8; 4],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Ipv4Addr::from(*address).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[u8; 4], D::Error> {
+        Ipv4Addr::deserialize(deserializer).map(|address| address.octets())
+    }
+}
+
+pub(crate) mod ipv6 {
+    use core::net::Ipv6Addr;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        address: &[u8; 16],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Ipv6Addr::from(*address).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[u8; 16], D::Error> {
+        Ipv6Addr::deserialize(deserializer).map(|address| address.octets())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_tokens, Configure, Token};
+
+    #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+    struct Addresses {
+        #[serde(with = "super::ipv4")]
+        v4: [u8; 4],
+        #[serde(with = "super::ipv6")]
+        v6: [u8; 16],
+    }
+
+    #[test]
+    fn readable_and_compact() {
+        let mut v6 = [0; 16];
+        v6[0] = 0xff;
+        v6[1] = 0x02;
+        v6[15] = 1;
+        let value = Addresses {
+            v4: [192, 168, 0, 1],
+            v6,
+        };
+        assert_eq!(
+            r#"{"v4":"192.168.0.1","v6":"ff02::1"}"#,
+            serde_json::to_string(&value).unwrap()
+        );
+        assert_eq!(
+            value,
+            serde_json::from_str(r#"{"v4":"192.168.0.1","v6":"ff02::1"}"#).unwrap()
+        );
+        assert!(serde_json::from_str::<Addresses>(r#"{"v4":"1.2.3","v6":"::"}"#).is_err());
+
+        let mut tokens = vec![
+            Token::Struct {
+                name: "Addresses",
+                len: 2,
+            },
+            Token::Str("v4"),
+            Token::Tuple { len: 4 },
+        ];
+        tokens.extend([192, 168, 0, 1].map(Token::U8));
+        tokens.extend([Token::TupleEnd, Token::Str("v6"), Token::Tuple { len: 16 }]);
+        tokens.extend(v6.map(Token::U8));
+        tokens.extend([Token::TupleEnd, Token::StructEnd]);
+        assert_tokens(&value.compact(), &tokens);
+    }
+}
@@ -0,0 +1,66 @@
+/// SOME/IP service id (upper 16 bits of the [`crate::MessageId`]).
+///
+/// `Display` writes the id in hex (e.g. `0x1234`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ServiceId(pub u16);
+
+impl ServiceId {
+    /// Service id reserved for SOME/IP service discovery messages.
+    pub const SD: ServiceId = ServiceId(0xFFFF);
+
+    /// Wildcard matching all services (e.g. in find service entries).
+    pub const ANY: ServiceId = ServiceId(0xFFFF);
+
+    /// Returns the raw `u16` value.
+    #[inline]
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for ServiceId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:04x}", self.0)
+    }
+}
+
+impl From<u16> for ServiceId {
+    #[inline]
+    fn from(value: u16) -> Self {
+        ServiceId(value)
+    }
+}
+
+impl From<ServiceId> for u16 {
+    #[inline]
+    fn from(value: ServiceId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn constants() {
+        assert_eq!(0xFFFF, ServiceId::SD.value());
+        assert_eq!(0xFFFF, ServiceId::ANY.value());
+    }
+
+    #[test]
+    fn conversions_display() {
+        let id = ServiceId::from(0x1234);
+        assert_eq!(ServiceId(0x1234), id);
+        assert_eq!(0x1234u16, u16::from(id));
+        assert_eq!("0x1234", format!("{}", id));
+        assert_eq!("0x00ab", format!("{}", ServiceId(0xab)));
+        assert_eq!("ServiceId(4660)", format!("{:?}", id));
+    }
+}
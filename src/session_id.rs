@@ -0,0 +1,78 @@
+/// SOME/IP session id (lower 16 bits of the [`crate::RequestId`]).
+///
+/// `Display` writes the id in hex (e.g. `0x0001`).
+#[derive(Copy, Clone, Default, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SessionId(pub u16);
+
+impl SessionId {
+    /// Session id signaling that session handling is not active.
+    pub const DISABLED: SessionId = SessionId(0);
+
+    /// First session id used when session handling is active.
+    pub const FIRST: SessionId = SessionId(1);
+
+    /// Returns the raw `u16` value.
+    #[inline]
+    pub const fn value(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the session id following this one. After 0xFFFF the
+    /// session id wraps around to [`SessionId::FIRST`] as 0 is reserved
+    /// for [`SessionId::DISABLED`].
+    #[inline]
+    pub const fn next(self) -> SessionId {
+        if self.0 == u16::MAX {
+            SessionId::FIRST
+        } else {
+            SessionId(self.0 + 1)
+        }
+    }
+}
+
+impl core::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:04x}", self.0)
+    }
+}
+
+impl From<u16> for SessionId {
+    #[inline]
+    fn from(value: u16) -> Self {
+        SessionId(value)
+    }
+}
+
+impl From<SessionId> for u16 {
+    #[inline]
+    fn from(value: SessionId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn next() {
+        assert_eq!(SessionId::FIRST, SessionId::DISABLED.next());
+        assert_eq!(SessionId(2), SessionId::FIRST.next());
+        assert_eq!(SessionId::FIRST, SessionId(0xFFFF).next());
+    }
+
+    #[test]
+    fn conversions_display() {
+        let id = SessionId::from(0x1234);
+        assert_eq!(SessionId(0x1234), id);
+        assert_eq!(0x1234u16, u16::from(id));
+        assert_eq!("0x1234", format!("{}", id));
+        assert_eq!("0x0001", format!("{}", SessionId::FIRST));
+    }
+}
@@ -8,7 +8,12 @@ use crate::*;
 pub type SomeIpHeader = SomeipHeader;
 
 ///SOMEIP header (including tp header if present).
+///
+///`Display` writes a one line summary of the header. The alternate form
+///(`{:#}`) additionally lists every field on its own line, similar to the
+///packet details of Wireshark.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SomeipHeader {
     pub message_id: u32,
     pub length: u32,
@@ -107,6 +112,174 @@ impl SomeipHeader {
         }
     }
 
+    ///Returns the message id as a [`MessageId`].
+    #[inline]
+    pub fn message_id_typed(&self) -> MessageId {
+        MessageId(self.message_id)
+    }
+
+    ///Returns the service id as a [`ServiceId`].
+    #[inline]
+    pub fn service_id_typed(&self) -> ServiceId {
+        ServiceId(self.service_id())
+    }
+
+    ///Returns the request id as a [`RequestId`].
+    #[inline]
+    pub fn request_id_typed(&self) -> RequestId {
+        RequestId(self.request_id)
+    }
+
+    ///Returns the client id (upper 16 bits of the request id).
+    #[inline]
+    pub fn client_id_typed(&self) -> ClientId {
+        self.request_id_typed().client_id()
+    }
+
+    ///Returns the session id (lower 16 bits of the request id).
+    #[inline]
+    pub fn session_id_typed(&self) -> SessionId {
+        self.request_id_typed().session_id()
+    }
+
+    ///Returns the header of the error response (message type [`MessageType::Error`])
+    ///that should be sent for the given request.
+    ///
+    ///Returns `None` if no response is allowed for the received message (only
+    ///messages with the message type [`MessageType::Request`] can be answered,
+    ///notifications, responses & [`MessageType::RequestNoReturn`] messages never
+    ///get a response).
+    ///
+    ///The message id, request id & interface version are copied from the request
+    ///and the length field is set for an error response without payload.
+    ///Typical return codes are [`ReturnCode::UnknownMethod`],
+    ///[`ReturnCode::WrongInterfaceVersion`] or [`ReturnCode::WrongMessageType`]
+    ///(e.g. for a request of a fire & forget method).
+    ///
+    ///```
+    ///use someip_parse::{MessageType, ReturnCode, SomeipHeader, SomeipMsgSlice};
+    ///
+    ///let request = SomeipHeader {
+    ///    message_id: 0x1234_0001,
+    ///    length: 8,
+    ///    request_id: 0x0001_0002,
+    ///    interface_version: 2,
+    ///    message_type: MessageType::Request,
+    ///    return_code: 0,
+    ///    tp_header: None,
+    ///}.base_to_bytes();
+    ///let request = SomeipMsgSlice::from_slice(&request).unwrap();
+    ///
+    ///// e.g. the server only supports the interface version 1
+    ///let response = SomeipHeader::error_response(&request, ReturnCode::WrongInterfaceVersion).unwrap();
+    ///assert_eq!(MessageType::Error, response.message_type);
+    ///assert_eq!(0x1234_0001, response.message_id);
+    ///assert_eq!(0x0001_0002, response.request_id);
+    ///assert_eq!(u8::from(ReturnCode::WrongInterfaceVersion), response.return_code);
+    ///```
+    pub fn error_response(
+        request: &SomeipMsgSlice<'_>,
+        return_code: ReturnCode,
+    ) -> Option<SomeipHeader> {
+        if MessageType::Request == request.message_type() {
+            Some(SomeipHeader::error_response_unchecked(
+                request.message_id(),
+                request.request_id(),
+                request.interface_version(),
+                return_code,
+            ))
+        } else {
+            None
+        }
+    }
+
+    ///Returns the header of the error response that should be sent for raw bytes
+    ///that could not be decoded by [`SomeipMsgSlice::from_slice`].
+    ///
+    ///The return code is determined based on the error:
+    ///
+    ///* [`SomeipHeaderError::UnsupportedProtocolVersion`] results in
+    ///  [`ReturnCode::WrongProtocolVersion`].
+    ///* [`SomeipHeaderError::LengthFieldTooSmall`] and a length field
+    ///  bigger then the received data result in [`ReturnCode::MalformedMessage`].
+    ///
+    ///`None` is returned if the raw data is too short to contain a complete
+    ///SOMEIP header, if the message type of the raw data is not a request
+    ///([`MessageType::Request`] with or without the TP flag) or if the message type
+    ///is unknown (as it can not be determined if a response is expected).
+    ///
+    ///Note that a length field bigger then the received data is only a malformed
+    ///message if the complete message was received (e.g. a UDP datagram). For
+    ///streams (e.g. TCP) more data has to be awaited instead.
+    ///
+    ///[`SomeipHeaderError::UnsupportedProtocolVersion`]: crate::err::SomeipHeaderError::UnsupportedProtocolVersion
+    ///[`SomeipHeaderError::LengthFieldTooSmall`]: crate::err::SomeipHeaderError::LengthFieldTooSmall
+    ///
+    ///```
+    ///use someip_parse::{MessageType, ReturnCode, SomeipHeader, SomeipMsgSlice};
+    ///
+    ///let mut raw = SomeipHeader {
+    ///    message_id: 0x1234_0001,
+    ///    length: 8,
+    ///    request_id: 0x0001_0002,
+    ///    interface_version: 1,
+    ///    message_type: MessageType::Request,
+    ///    return_code: 0,
+    ///    tp_header: None,
+    ///}.base_to_bytes();
+    ///// set an unsupported protocol version
+    ///raw[12] = 2;
+    ///
+    ///let err = SomeipMsgSlice::from_slice(&raw).unwrap_err();
+    ///let response = SomeipHeader::error_response_for_slice_error(&raw, &err).unwrap();
+    ///assert_eq!(MessageType::Error, response.message_type);
+    ///assert_eq!(u8::from(ReturnCode::WrongProtocolVersion), response.return_code);
+    ///```
+    pub fn error_response_for_slice_error(
+        raw: &[u8],
+        err: &err::SomeipSliceError,
+    ) -> Option<SomeipHeader> {
+        use err::{SomeipHeaderError::*, SomeipSliceError::*};
+
+        if raw.len() < SOMEIP_HEADER_LENGTH {
+            return None;
+        }
+        // only requests (optionally segmented via SOMEIP TP) can be answered
+        if MessageType::Request as u8 != raw[14] & !SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG {
+            return None;
+        }
+        let return_code = match err {
+            Len(_) => ReturnCode::MalformedMessage,
+            Content(UnsupportedProtocolVersion(_)) => ReturnCode::WrongProtocolVersion,
+            Content(LengthFieldTooSmall(_)) => ReturnCode::MalformedMessage,
+            Content(UnknownMessageType(_)) => return None,
+        };
+        Some(SomeipHeader::error_response_unchecked(
+            u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]]),
+            u32::from_be_bytes([raw[8], raw[9], raw[10], raw[11]]),
+            raw[13],
+            return_code,
+        ))
+    }
+
+    ///Error response header without payload.
+    fn error_response_unchecked(
+        message_id: u32,
+        request_id: u32,
+        interface_version: u8,
+        return_code: ReturnCode,
+    ) -> SomeipHeader {
+        SomeipHeader {
+            message_id,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            request_id,
+            interface_version,
+            message_type: MessageType::Error,
+            return_code: return_code.into(),
+            tp_header: None,
+        }
+    }
+
     ///Serialize the header.
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
@@ -232,6 +405,134 @@ impl Default for SomeipHeader {
     }
 }
 
+impl core::fmt::Display for SomeipHeader {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "SOME/IP Service ID: 0x{:04x}, {}: 0x{:04x}, Client ID: 0x{:04x}, Session ID: 0x{:04x}, Message Type: {}, Return Code: {}, Length: {}",
+            self.service_id(),
+            self.method_or_event_label(),
+            self.event_or_method_id(),
+            self.request_id >> 16,
+            self.request_id & 0xffff,
+            self.message_type,
+            ReturnCode::from(self.return_code),
+            self.length
+        )?;
+        if let Some(tp_header) = &self.tp_header {
+            write!(
+                f,
+                ", TP Offset: {}, More Segments: {}",
+                tp_header.offset(),
+                tp_header.more_segment
+            )?;
+        }
+        if f.alternate() {
+            self.write_fields(f, 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl SomeipHeader {
+    fn method_or_event_label(&self) -> &'static str {
+        if self.is_event() {
+            "Event ID"
+        } else {
+            "Method ID"
+        }
+    }
+
+    /// Writes the dissector lines of all header fields.
+    pub(crate) fn write_fields(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+        level: usize,
+    ) -> core::fmt::Result {
+        use crate::dissect::line;
+
+        line(
+            f,
+            level,
+            format_args!("Message ID: 0x{:08x}", self.message_id),
+        )?;
+        line(
+            f,
+            level + 1,
+            format_args!("Service ID: 0x{:04x}", self.service_id()),
+        )?;
+        line(
+            f,
+            level + 1,
+            format_args!(
+                "{}: 0x{:04x}",
+                self.method_or_event_label(),
+                self.event_or_method_id()
+            ),
+        )?;
+        line(f, level, format_args!("Length: {}", self.length))?;
+        line(
+            f,
+            level,
+            format_args!("Request ID: 0x{:08x}", self.request_id),
+        )?;
+        line(
+            f,
+            level + 1,
+            format_args!("Client ID: 0x{:04x}", self.request_id >> 16),
+        )?;
+        line(
+            f,
+            level + 1,
+            format_args!("Session ID: 0x{:04x}", self.request_id & 0xffff),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Protocol Version: {}", SOMEIP_PROTOCOL_VERSION),
+        )?;
+        line(
+            f,
+            level,
+            format_args!("Interface Version: {}", self.interface_version),
+        )?;
+        let message_type_raw = if self.tp_header.is_some() {
+            self.message_type.clone() as u8 | SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG
+        } else {
+            self.message_type.clone() as u8
+        };
+        line(
+            f,
+            level,
+            format_args!(
+                "Message Type: {} (0x{:02x})",
+                self.message_type, message_type_raw
+            ),
+        )?;
+        match ReturnCode::from(self.return_code) {
+            // the reserved & interface specific codes already contain the value
+            code @ (ReturnCode::Generic(_) | ReturnCode::InterfaceError(_)) => {
+                line(f, level, format_args!("Return Code: {code}"))?
+            }
+            code => line(
+                f,
+                level,
+                format_args!("Return Code: {code} (0x{:02x})", self.return_code),
+            )?,
+        }
+        if let Some(tp_header) = &self.tp_header {
+            line(f, level, format_args!("SOME/IP-TP"))?;
+            line(f, level + 1, format_args!("Offset: {}", tp_header.offset()))?;
+            line(
+                f,
+                level + 1,
+                format_args!("More Segments: {}", tp_header.more_segment),
+            )?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec::Vec;
@@ -496,11 +797,189 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "std")]
+    fn request_header(message_type: MessageType, tp_header: Option<TpHeader>) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_0001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD
+                + if tp_header.is_some() {
+                    TP_HEADER_LENGTH as u32
+                } else {
+                    0
+                },
+            request_id: 0x0001_0002,
+            interface_version: 3,
+            message_type,
+            return_code: 0,
+            tp_header,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn expected_error_response(return_code: ReturnCode) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_0001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            request_id: 0x0001_0002,
+            interface_version: 3,
+            message_type: MessageType::Error,
+            return_code: return_code.into(),
+            tp_header: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_response() {
+        for message_type in MESSAGE_TYPE_VALUES {
+            for tp_header in [None, Some(TpHeader::new(false))] {
+                let mut buffer = Vec::new();
+                request_header(message_type.clone(), tp_header)
+                    .write_raw(&mut buffer)
+                    .unwrap();
+                let slice = SomeipMsgSlice::from_slice(&buffer).unwrap();
+                assert_eq!(
+                    SomeipHeader::error_response(&slice, ReturnCode::NotReady),
+                    if Request == *message_type {
+                        Some(expected_error_response(ReturnCode::NotReady))
+                    } else {
+                        None
+                    }
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_response_for_slice_error() {
+        use err::{SomeipHeaderError::*, SomeipSliceError::*};
+
+        let request = {
+            let mut buffer = Vec::new();
+            request_header(Request, None)
+                .write_raw(&mut buffer)
+                .unwrap();
+            buffer
+        };
+
+        // too short for a header
+        {
+            let err = SomeipMsgSlice::from_slice(&request[..SOMEIP_HEADER_LENGTH - 1]).unwrap_err();
+            assert_eq!(
+                None,
+                SomeipHeader::error_response_for_slice_error(
+                    &request[..SOMEIP_HEADER_LENGTH - 1],
+                    &err
+                )
+            );
+        }
+
+        // length field bigger then the data
+        {
+            let mut raw = request.clone();
+            raw[7] = 9;
+            let err = SomeipMsgSlice::from_slice(&raw).unwrap_err();
+            assert!(matches!(err, Len(_)));
+            assert_eq!(
+                Some(expected_error_response(ReturnCode::MalformedMessage)),
+                SomeipHeader::error_response_for_slice_error(&raw, &err)
+            );
+        }
+
+        // length field too small
+        {
+            let mut raw = request.clone();
+            raw[7] = 7;
+            let err = SomeipMsgSlice::from_slice(&raw).unwrap_err();
+            assert_eq!(Content(LengthFieldTooSmall(7)), err);
+            assert_eq!(
+                Some(expected_error_response(ReturnCode::MalformedMessage)),
+                SomeipHeader::error_response_for_slice_error(&raw, &err)
+            );
+        }
+
+        // unsupported protocol version
+        {
+            let mut raw = request.clone();
+            raw[12] = SOMEIP_PROTOCOL_VERSION + 1;
+            let err = SomeipMsgSlice::from_slice(&raw).unwrap_err();
+            assert_eq!(
+                Some(expected_error_response(ReturnCode::WrongProtocolVersion)),
+                SomeipHeader::error_response_for_slice_error(&raw, &err)
+            );
+
+            // tp flag set
+            raw[14] = SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG;
+            assert_eq!(
+                Some(expected_error_response(ReturnCode::WrongProtocolVersion)),
+                SomeipHeader::error_response_for_slice_error(&raw, &err)
+            );
+        }
+
+        // no response for messages that are not requests
+        for message_type in [RequestNoReturn, Notification, Response, Error] {
+            let mut raw = request.clone();
+            raw[12] = SOMEIP_PROTOCOL_VERSION + 1;
+            raw[14] = message_type as u8;
+            let err = SomeipMsgSlice::from_slice(&raw).unwrap_err();
+            assert_eq!(
+                None,
+                SomeipHeader::error_response_for_slice_error(&raw, &err)
+            );
+        }
+
+        // unknown message type
+        {
+            let mut raw = request.clone();
+            raw[14] = 0x03;
+            let err = SomeipMsgSlice::from_slice(&raw).unwrap_err();
+            assert_eq!(Content(UnknownMessageType(0x03)), err);
+            assert_eq!(
+                None,
+                SomeipHeader::error_response_for_slice_error(&raw, &err)
+            );
+
+            // can not happen with a request message type, but still no response
+            assert_eq!(
+                None,
+                SomeipHeader::error_response_for_slice_error(&request, &err)
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    proptest! {
+        #[test]
+        fn typed_ids(packet in someip_header_with_payload_any()) {
+            let header = packet.0.clone();
+            let message_id = MessageId(header.message_id);
+            let request_id = RequestId(header.request_id);
+            assert_eq!(message_id, header.message_id_typed());
+            assert_eq!(ServiceId(header.service_id()), header.service_id_typed());
+            assert_eq!(request_id, header.request_id_typed());
+            assert_eq!(request_id.client_id(), header.client_id_typed());
+            assert_eq!(request_id.session_id(), header.session_id_typed());
+
+            //serialize and check the slice methods
+            let mut buffer = Vec::new();
+            header.write_raw(&mut buffer).unwrap();
+            buffer.write_all(&packet.1[..]).unwrap();
+            let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
+
+            assert_eq!(message_id, slice.message_id_typed());
+            assert_eq!(message_id.service_id(), slice.service_id_typed());
+            assert_eq!(request_id, slice.request_id_typed());
+            assert_eq!(request_id.client_id(), slice.client_id_typed());
+            assert_eq!(request_id.session_id(), slice.session_id_typed());
+        }
+    }
+
     #[cfg(feature = "std")]
     proptest! {
         #[test]
         fn service_id(packet in someip_header_with_payload_any(),
-                      service_id in 0x0u16..std::u16::MAX)
+                      service_id in 0x0u16..u16::MAX)
         {
             let mut header = packet.0.clone();
             header.set_service_id(service_id);
@@ -509,7 +988,7 @@ mod tests {
             //serialize and check the slice methods
             let mut buffer = Vec::new();
             header.write_raw(&mut buffer).unwrap();
-            buffer.write(&packet.1[..]).unwrap();
+            buffer.write_all(&packet.1[..]).unwrap();
             let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
 
             assert_eq!(service_id, slice.service_id());
@@ -533,7 +1012,7 @@ mod tests {
             //serialize and check the slice methods
             let mut buffer = Vec::new();
             header.write_raw(&mut buffer).unwrap();
-            buffer.write(&packet.1[..]).unwrap();
+            buffer.write_all(&packet.1[..]).unwrap();
             let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
 
             assert_eq!(false, slice.is_event());
@@ -562,7 +1041,7 @@ mod tests {
             //serialize and check the slice methods
             let mut buffer = Vec::new();
             header.write_raw(&mut buffer).unwrap();
-            buffer.write(&packet.1[..]).unwrap();
+            buffer.write_all(&packet.1[..]).unwrap();
             let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
 
             assert_eq!(true, slice.is_event());
@@ -576,7 +1055,7 @@ mod tests {
     proptest! {
         #[test]
         fn set_method_or_event_id(packet in someip_header_with_payload_any(),
-                                  id in 0x0u16..std::u16::MAX)
+                                  id in 0x0u16..u16::MAX)
         {
             let mut header = packet.0.clone();
             header.set_method_or_event_id(id);
@@ -587,7 +1066,7 @@ mod tests {
             //serialize and check the slice methods
             let mut buffer = Vec::new();
             header.write_raw(&mut buffer).unwrap();
-            buffer.write(&packet.1[..]).unwrap();
+            buffer.write_all(&packet.1[..]).unwrap();
             let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
 
             assert_eq!(id >= 0x8000, slice.is_event());
@@ -613,7 +1092,7 @@ mod tests {
             {
                 let mut buffer = Vec::new();
                 header.write_raw(&mut buffer).unwrap();
-                buffer.write(&packet.1[..]).unwrap();
+                buffer.write_all(&packet.1[..]).unwrap();
                 let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
 
                 assert_eq!(true, slice.is_someip_sd());
@@ -622,11 +1101,77 @@ mod tests {
             {
                 let mut buffer = Vec::new();
                 packet.0.write_raw(&mut buffer).unwrap();
-                buffer.write(&packet.1[..]).unwrap();
+                buffer.write_all(&packet.1[..]).unwrap();
                 let slice = SomeipMsgSlice::from_slice(&buffer[..]).unwrap();
 
                 assert_eq!(packet.0.message_id == SD_MESSAGE_ID, slice.is_someip_sd());
             }
         }
     }
+
+    #[test]
+    fn display() {
+        use alloc::format;
+
+        let mut header = SomeipHeader {
+            message_id: 0x1234_0001,
+            length: 24,
+            request_id: 0x0001_0002,
+            interface_version: 3,
+            message_type: Request,
+            return_code: 0x01,
+            tp_header: None,
+        };
+        assert_eq!(
+            "SOME/IP Service ID: 0x1234, Method ID: 0x0001, Client ID: 0x0001, \
+             Session ID: 0x0002, Message Type: Request, Return Code: Not Ok, Length: 24",
+            format!("{}", header)
+        );
+        assert_eq!(
+            "SOME/IP Service ID: 0x1234, Method ID: 0x0001, Client ID: 0x0001, \
+             Session ID: 0x0002, Message Type: Request, Return Code: Not Ok, Length: 24\n\
+             \x20   Message ID: 0x12340001\n\
+             \x20       Service ID: 0x1234\n\
+             \x20       Method ID: 0x0001\n\
+             \x20   Length: 24\n\
+             \x20   Request ID: 0x00010002\n\
+             \x20       Client ID: 0x0001\n\
+             \x20       Session ID: 0x0002\n\
+             \x20   Protocol Version: 1\n\
+             \x20   Interface Version: 3\n\
+             \x20   Message Type: Request (0x00)\n\
+             \x20   Return Code: Not Ok (0x01)",
+            format!("{:#}", header)
+        );
+
+        // event with tp header & interface specific return code
+        header.message_id = 0x1234_8001;
+        header.message_type = Notification;
+        header.return_code = 0x20;
+        header.tp_header = Some(TpHeader::with_offset(32, true).unwrap());
+        assert_eq!(
+            "SOME/IP Service ID: 0x1234, Event ID: 0x8001, Client ID: 0x0001, \
+             Session ID: 0x0002, Message Type: Notification, Return Code: Interface Error 0x20, \
+             Length: 24, TP Offset: 32, More Segments: true",
+            format!("{}", header)
+        );
+        let alternate = format!("{:#}", header);
+        assert!(alternate.contains("\n        Event ID: 0x8001\n"));
+        assert!(alternate.contains("\n    Message Type: Notification (0x22)\n"));
+        assert!(alternate.ends_with(
+            "\n    Return Code: Interface Error 0x20\
+             \n    SOME/IP-TP\
+             \n        Offset: 32\
+             \n        More Segments: true"
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    proptest! {
+        #[test]
+        fn serde_roundtrip(ref header in someip_header_any()) {
+            let json = serde_json::to_string(header).unwrap();
+            prop_assert_eq!(header, &serde_json::from_str::<SomeipHeader>(&json).unwrap());
+        }
+    }
 }
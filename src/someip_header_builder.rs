@@ -0,0 +1,437 @@
+use crate::*;
+
+/// Builder for [`SomeipHeader`]s that computes the length field from the
+/// payload length & validates the combination of message type & return code.
+///
+/// A builder is started for a specific kind of message:
+///
+/// * [`SomeipHeaderBuilder::request`] & [`SomeipHeaderBuilder::request_no_return`]
+///   for (fire & forget) method calls,
+/// * [`SomeipHeaderBuilder::notification`] for events,
+/// * [`SomeipHeaderBuilder::response`] & [`SomeipHeaderBuilder::error`] to answer
+///   a received request (message id, request id & interface version are
+///   copied from the request).
+///
+/// All other fields default to 0 and [`ReturnCode::Ok`] and can be changed
+/// via the chained setters. [`SomeipHeaderBuilder::build`] then creates the
+/// header for a given payload length (the 4 bytes of the TP header are added
+/// automatically if one is set).
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let payload = [1, 2, 3, 4];
+/// let request = SomeipHeaderBuilder::request(0x1234, 0x0001)
+///     .client_id(0x0010)
+///     .session_id(1)
+///     .interface_version(1)
+///     .build(payload.len())
+///     .unwrap();
+/// assert_eq!(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4, request.length);
+/// assert_eq!(0x0010_0001, request.request_id);
+///
+/// // answer the request
+/// let mut bytes = request.base_to_bytes().to_vec();
+/// bytes.extend_from_slice(&payload);
+/// let request = SomeipMsgSlice::from_slice(&bytes).unwrap();
+/// let response = SomeipHeaderBuilder::response(&request)
+///     .unwrap()
+///     .build(2)
+///     .unwrap();
+/// assert_eq!(MessageType::Response, response.message_type);
+/// assert_eq!(0x0010_0001, response.request_id);
+/// assert_eq!(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 2, response.length);
+///
+/// // notifications must not carry an error return code
+/// assert!(SomeipHeaderBuilder::notification(0x1234, 0x8001)
+///     .return_code(ReturnCode::NotOk)
+///     .build(0)
+///     .is_err());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SomeipHeaderBuilder {
+    message_id: u32,
+    request_id: u32,
+    interface_version: u8,
+    message_type: MessageType,
+    return_code: ReturnCode,
+    tp_header: Option<TpHeader>,
+}
+
+impl SomeipHeaderBuilder {
+    fn new(message_id: u32, message_type: MessageType) -> SomeipHeaderBuilder {
+        SomeipHeaderBuilder {
+            message_id,
+            request_id: 0,
+            interface_version: 0,
+            message_type,
+            return_code: ReturnCode::Ok,
+            tp_header: None,
+        }
+    }
+
+    /// Starts a request ([`MessageType::Request`]) for the given method.
+    ///
+    /// The method id must not have the event bit (0x8000) set, otherwise
+    /// [`SomeipHeaderBuilder::build`] returns an error.
+    #[inline]
+    pub fn request(service_id: u16, method_id: u16) -> SomeipHeaderBuilder {
+        Self::new(
+            (u32::from(service_id) << 16) | u32::from(method_id),
+            MessageType::Request,
+        )
+    }
+
+    /// Starts a "fire & forget" request ([`MessageType::RequestNoReturn`])
+    /// for the given method.
+    ///
+    /// The method id must not have the event bit (0x8000) set, otherwise
+    /// [`SomeipHeaderBuilder::build`] returns an error.
+    #[inline]
+    pub fn request_no_return(service_id: u16, method_id: u16) -> SomeipHeaderBuilder {
+        Self::new(
+            (u32::from(service_id) << 16) | u32::from(method_id),
+            MessageType::RequestNoReturn,
+        )
+    }
+
+    /// Starts a notification ([`MessageType::Notification`]) for the given
+    /// event. The event bit (0x8000) is set automatically.
+    #[inline]
+    pub fn notification(service_id: u16, event_id: u16) -> SomeipHeaderBuilder {
+        Self::new(
+            (u32::from(service_id) << 16) | u32::from(0x8000 | event_id),
+            MessageType::Notification,
+        )
+    }
+
+    /// Starts a response ([`MessageType::Response`]) to the given request.
+    ///
+    /// The message id, request id & interface version are copied from the
+    /// request. Returns `None` if the message is not a [`MessageType::Request`]
+    /// (only requests can be answered).
+    pub fn response(request: &SomeipMsgSlice<'_>) -> Option<SomeipHeaderBuilder> {
+        Self::answer(request, MessageType::Response, ReturnCode::Ok)
+    }
+
+    /// Starts an error response ([`MessageType::Error`]) to the given request.
+    ///
+    /// The message id, request id & interface version are copied from the
+    /// request. Returns `None` if the message is not a [`MessageType::Request`]
+    /// (only requests can be answered). The return code must not be
+    /// [`ReturnCode::Ok`], otherwise [`SomeipHeaderBuilder::build`] returns an
+    /// error.
+    pub fn error(
+        request: &SomeipMsgSlice<'_>,
+        return_code: ReturnCode,
+    ) -> Option<SomeipHeaderBuilder> {
+        Self::answer(request, MessageType::Error, return_code)
+    }
+
+    fn answer(
+        request: &SomeipMsgSlice<'_>,
+        message_type: MessageType,
+        return_code: ReturnCode,
+    ) -> Option<SomeipHeaderBuilder> {
+        if MessageType::Request == request.message_type() {
+            Some(SomeipHeaderBuilder {
+                request_id: request.request_id(),
+                interface_version: request.interface_version(),
+                return_code,
+                ..Self::new(request.message_id(), message_type)
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Sets the request id (client id & session id).
+    #[inline]
+    pub fn request_id(mut self, request_id: u32) -> SomeipHeaderBuilder {
+        self.request_id = request_id;
+        self
+    }
+
+    /// Sets the client id (upper 16 bits of the request id).
+    #[inline]
+    pub fn client_id(mut self, client_id: u16) -> SomeipHeaderBuilder {
+        self.request_id = (self.request_id & 0x0000_ffff) | (u32::from(client_id) << 16);
+        self
+    }
+
+    /// Sets the session id (lower 16 bits of the request id).
+    #[inline]
+    pub fn session_id(mut self, session_id: u16) -> SomeipHeaderBuilder {
+        self.request_id = (self.request_id & 0xffff_0000) | u32::from(session_id);
+        self
+    }
+
+    /// Sets the interface version.
+    #[inline]
+    pub fn interface_version(mut self, interface_version: u8) -> SomeipHeaderBuilder {
+        self.interface_version = interface_version;
+        self
+    }
+
+    /// Sets the return code.
+    #[inline]
+    pub fn return_code(mut self, return_code: ReturnCode) -> SomeipHeaderBuilder {
+        self.return_code = return_code;
+        self
+    }
+
+    /// Sets the TP header (the message is a segment of a larger payload).
+    #[inline]
+    pub fn tp_header(mut self, tp_header: TpHeader) -> SomeipHeaderBuilder {
+        self.tp_header = Some(tp_header);
+        self
+    }
+
+    /// Builds the header for a message with a payload of `payload_len` bytes
+    /// (not including the TP header).
+    ///
+    /// # Errors
+    ///
+    /// - [`err::SomeipHeaderBuildError::MethodIdWithEventBit`] if the method
+    ///   id of a request has the event bit set.
+    /// - [`err::SomeipHeaderBuildError::ReturnCodeNotAllowed`] if a request or
+    ///   notification has a return code other than [`ReturnCode::Ok`] or if an
+    ///   error has the return code [`ReturnCode::Ok`].
+    /// - [`err::SomeipHeaderBuildError::PayloadLenTooLarge`] if the length does
+    ///   not fit into the length field.
+    pub fn build(&self, payload_len: usize) -> Result<SomeipHeader, err::SomeipHeaderBuildError> {
+        use err::SomeipHeaderBuildError::*;
+
+        if matches!(
+            self.message_type,
+            MessageType::Request | MessageType::RequestNoReturn
+        ) && 0 != self.message_id & 0x8000
+        {
+            return Err(MethodIdWithEventBit(self.message_id as u16));
+        }
+
+        let return_code_allowed = match self.message_type {
+            MessageType::Request | MessageType::RequestNoReturn | MessageType::Notification => {
+                ReturnCode::Ok == self.return_code
+            }
+            MessageType::Response => true,
+            MessageType::Error => ReturnCode::Ok != self.return_code,
+        };
+        if false == return_code_allowed {
+            return Err(ReturnCodeNotAllowed {
+                message_type: self.message_type.clone(),
+                return_code: self.return_code,
+            });
+        }
+
+        let offset = SOMEIP_LEN_OFFSET_TO_PAYLOAD
+            + if self.tp_header.is_some() {
+                TP_HEADER_LENGTH as u32
+            } else {
+                0
+            };
+        let max = (u32::MAX - offset) as usize;
+        let length = u32::try_from(payload_len)
+            .ok()
+            .and_then(|len| len.checked_add(offset))
+            .ok_or(PayloadLenTooLarge {
+                max,
+                actual: payload_len,
+            })?;
+
+        Ok(SomeipHeader {
+            message_id: self.message_id,
+            length,
+            request_id: self.request_id,
+            interface_version: self.interface_version,
+            message_type: self.message_type.clone(),
+            return_code: self.return_code.into(),
+            tp_header: self.tp_header.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use err::SomeipHeaderBuildError::*;
+
+    fn request_bytes(message_type: MessageType) -> [u8; SOMEIP_HEADER_LENGTH] {
+        SomeipHeader {
+            message_id: 0x1234_0002,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            request_id: 0x0003_0004,
+            interface_version: 5,
+            message_type,
+            return_code: 0,
+            tp_header: None,
+        }
+        .base_to_bytes()
+    }
+
+    #[test]
+    fn request() {
+        let header = SomeipHeaderBuilder::request(0x1234, 0x0001)
+            .request_id(0x1111_2222)
+            .interface_version(3)
+            .build(10)
+            .unwrap();
+        assert_eq!(
+            SomeipHeader {
+                message_id: 0x1234_0001,
+                length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 10,
+                request_id: 0x1111_2222,
+                interface_version: 3,
+                message_type: MessageType::Request,
+                return_code: 0,
+                tp_header: None,
+            },
+            header
+        );
+
+        let header = SomeipHeaderBuilder::request_no_return(0x1234, 0x0001)
+            .client_id(0x0010)
+            .session_id(0x0020)
+            .build(0)
+            .unwrap();
+        assert_eq!(MessageType::RequestNoReturn, header.message_type);
+        assert_eq!(0x0010_0020, header.request_id);
+
+        for builder in [
+            SomeipHeaderBuilder::request(0x1234, 0x8001),
+            SomeipHeaderBuilder::request_no_return(0x1234, 0x8001),
+        ] {
+            assert_eq!(Err(MethodIdWithEventBit(0x8001)), builder.build(0));
+        }
+    }
+
+    #[test]
+    fn notification() {
+        let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+            .build(0)
+            .unwrap();
+        assert_eq!(0x1234_8001, header.message_id);
+        assert_eq!(MessageType::Notification, header.message_type);
+        assert_eq!(SOMEIP_LEN_OFFSET_TO_PAYLOAD, header.length);
+    }
+
+    #[test]
+    fn response() {
+        let bytes = request_bytes(MessageType::Request);
+        let request = SomeipMsgSlice::from_slice(&bytes).unwrap();
+        let header = SomeipHeaderBuilder::response(&request)
+            .unwrap()
+            .build(3)
+            .unwrap();
+        assert_eq!(
+            SomeipHeader {
+                message_id: 0x1234_0002,
+                length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 3,
+                request_id: 0x0003_0004,
+                interface_version: 5,
+                message_type: MessageType::Response,
+                return_code: 0,
+                tp_header: None,
+            },
+            header
+        );
+
+        // responses may carry application errors
+        let header = SomeipHeaderBuilder::response(&request)
+            .unwrap()
+            .return_code(ReturnCode::InterfaceError(0x21))
+            .build(0)
+            .unwrap();
+        assert_eq!(0x21, header.return_code);
+
+        // only requests can be answered
+        for message_type in [
+            MessageType::RequestNoReturn,
+            MessageType::Notification,
+            MessageType::Response,
+            MessageType::Error,
+        ] {
+            let bytes = request_bytes(message_type);
+            let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            assert!(SomeipHeaderBuilder::response(&msg).is_none());
+            assert!(SomeipHeaderBuilder::error(&msg, ReturnCode::NotOk).is_none());
+        }
+    }
+
+    #[test]
+    fn error() {
+        let bytes = request_bytes(MessageType::Request);
+        let request = SomeipMsgSlice::from_slice(&bytes).unwrap();
+        let header = SomeipHeaderBuilder::error(&request, ReturnCode::UnknownMethod)
+            .unwrap()
+            .build(0)
+            .unwrap();
+        assert_eq!(
+            SomeipHeader::error_response(&request, ReturnCode::UnknownMethod),
+            Some(header)
+        );
+
+        assert_eq!(
+            Err(ReturnCodeNotAllowed {
+                message_type: MessageType::Error,
+                return_code: ReturnCode::Ok,
+            }),
+            SomeipHeaderBuilder::error(&request, ReturnCode::Ok)
+                .unwrap()
+                .build(0)
+        );
+    }
+
+    #[test]
+    fn return_code_not_allowed() {
+        for builder in [
+            SomeipHeaderBuilder::request(1, 2),
+            SomeipHeaderBuilder::request_no_return(1, 2),
+            SomeipHeaderBuilder::notification(1, 2),
+        ] {
+            let message_type = builder.message_type.clone();
+            assert_eq!(
+                Err(ReturnCodeNotAllowed {
+                    message_type,
+                    return_code: ReturnCode::NotReady,
+                }),
+                builder.return_code(ReturnCode::NotReady).build(0)
+            );
+        }
+    }
+
+    #[test]
+    fn tp_length() {
+        let tp = TpHeader::with_offset(16, true).unwrap();
+        let header = SomeipHeaderBuilder::notification(1, 2)
+            .tp_header(tp.clone())
+            .build(16)
+            .unwrap();
+        assert_eq!(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 + 16, header.length);
+        assert_eq!(Some(tp), header.tp_header);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn payload_len_too_large() {
+        let max = (u32::MAX - SOMEIP_LEN_OFFSET_TO_PAYLOAD) as usize;
+        let builder = SomeipHeaderBuilder::request(1, 2);
+        assert_eq!(u32::MAX, builder.build(max).unwrap().length);
+        assert_eq!(
+            Err(PayloadLenTooLarge {
+                max,
+                actual: max + 1
+            }),
+            builder.build(max + 1)
+        );
+
+        let builder = builder.tp_header(TpHeader::new(false));
+        assert_eq!(
+            Err(PayloadLenTooLarge {
+                max: max - 4,
+                actual: max
+            }),
+            builder.build(max)
+        );
+    }
+}
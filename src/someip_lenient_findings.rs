@@ -0,0 +1,90 @@
+use crate::err::SomeipHeaderError;
+
+/// Header validation findings of a message parsed in lenient mode (see
+/// [`crate::SomeipLenientMsgSlice::findings`]).
+///
+/// Each finding is a check that [`crate::SomeipMsgSlice::from_slice`] would
+/// have rejected the message for.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SomeipLenientFindings {
+    /// Protocol version if it differs from [`crate::SOMEIP_PROTOCOL_VERSION`].
+    pub unsupported_protocol_version: Option<u8>,
+
+    /// Raw message type (including the TP flag) if it is one of the legacy
+    /// ACK message types (see [`crate::LenientMessageType::is_legacy_ack`]).
+    pub legacy_message_type: Option<u8>,
+
+    /// Raw message type (including the TP flag) if it is not known.
+    pub unknown_message_type: Option<u8>,
+}
+
+impl SomeipLenientFindings {
+    /// Returns true if the header passed all checks.
+    pub fn is_empty(&self) -> bool {
+        self.unsupported_protocol_version.is_none()
+            && self.legacy_message_type.is_none()
+            && self.unknown_message_type.is_none()
+    }
+
+    /// Returns the findings as the errors a strict parse would have
+    /// reported (in the order the strict parser checks them).
+    pub fn errors(&self) -> impl Iterator<Item = SomeipHeaderError> {
+        use SomeipHeaderError::*;
+        [
+            self.unsupported_protocol_version
+                .map(UnsupportedProtocolVersion),
+            self.legacy_message_type
+                .or(self.unknown_message_type)
+                .map(UnknownMessageType),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec::Vec};
+
+    #[test]
+    fn debug_clone_eq() {
+        let value = SomeipLenientFindings::default();
+        assert_eq!(value, value.clone());
+        assert_eq!(
+            "SomeipLenientFindings { unsupported_protocol_version: None, legacy_message_type: None, unknown_message_type: None }",
+            format!("{:?}", value)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        use SomeipHeaderError::*;
+
+        let empty = SomeipLenientFindings::default();
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.errors().count());
+
+        let legacy = SomeipLenientFindings {
+            unsupported_protocol_version: Some(2),
+            legacy_message_type: Some(0x40),
+            unknown_message_type: None,
+        };
+        assert!(false == legacy.is_empty());
+        assert_eq!(
+            alloc::vec![UnsupportedProtocolVersion(2), UnknownMessageType(0x40)],
+            legacy.errors().collect::<Vec<_>>()
+        );
+
+        let unknown = SomeipLenientFindings {
+            unsupported_protocol_version: None,
+            legacy_message_type: None,
+            unknown_message_type: Some(0x23),
+        };
+        assert!(false == unknown.is_empty());
+        assert_eq!(
+            alloc::vec![UnknownMessageType(0x23)],
+            unknown.errors().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use crate::*;
+
+/// A slice containing a some ip header & payload of that message, parsed
+/// in lenient mode.
+///
+/// In contrast to [`SomeipMsgSlice`] only the lengths are verified during
+/// parsing. Messages with a protocol version other than
+/// [`SOMEIP_PROTOCOL_VERSION`], legacy ACK message types or unknown message
+/// types are accepted and the violated checks are reported via
+/// [`SomeipLenientMsgSlice::findings`] instead.
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let mut bytes = SomeipHeader {
+///     message_id: 0x1234_8001,
+///     length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+///     ..Default::default()
+/// }
+/// .base_to_bytes();
+/// // legacy "Request ACK" message type
+/// bytes[14] = 0x40;
+///
+/// assert!(SomeipMsgSlice::from_slice(&bytes).is_err());
+///
+/// let msg = SomeipLenientMsgSlice::from_slice(&bytes).unwrap();
+/// assert_eq!(LenientMessageType::RequestAck, msg.message_type());
+/// assert_eq!(Some(0x40), msg.findings().legacy_message_type);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SomeipLenientMsgSlice<'a> {
+    msg: SomeipMsgSlice<'a>,
+}
+
+impl<'a> SomeipLenientMsgSlice<'a> {
+    /// Parses a message from the slice, only verifying the lengths.
+    pub fn from_slice(slice: &'a [u8]) -> Result<SomeipLenientMsgSlice<'a>, err::SomeipSliceError> {
+        Ok(SomeipLenientMsgSlice {
+            msg: SomeipMsgSlice::from_slice_len_checked(slice)?,
+        })
+    }
+
+    ///Return the slice that contains the someip header
+    #[inline]
+    pub fn slice(&self) -> &'a [u8] {
+        self.msg.slice()
+    }
+
+    ///Returns the message id of the message.
+    #[inline]
+    pub fn message_id(&self) -> u32 {
+        self.msg.message_id()
+    }
+
+    ///Returns the service id (first 16 bits of the message id)
+    #[inline]
+    pub fn service_id(&self) -> u16 {
+        self.msg.service_id()
+    }
+
+    ///Returns true if the event or notification bit in the message id is set
+    #[inline]
+    pub fn is_event(&self) -> bool {
+        self.msg.is_event()
+    }
+
+    ///Return the event id or method id. This number includes the "event bit".
+    #[inline]
+    pub fn event_or_method_id(&self) -> u16 {
+        self.msg.event_or_method_id()
+    }
+
+    ///Return the event id. `None` if event bit is not set.
+    #[inline]
+    pub fn event_id(&self) -> Option<u16> {
+        self.msg.event_id()
+    }
+
+    ///Return the method id. `None` if event bit is set.
+    #[inline]
+    pub fn method_id(&self) -> Option<u16> {
+        self.msg.method_id()
+    }
+
+    ///Returns the message id as a [`MessageId`].
+    #[inline]
+    pub fn message_id_typed(&self) -> MessageId {
+        MessageId(self.message_id())
+    }
+
+    ///Returns the service id as a [`ServiceId`].
+    #[inline]
+    pub fn service_id_typed(&self) -> ServiceId {
+        ServiceId(self.service_id())
+    }
+
+    ///Returns the request id as a [`RequestId`].
+    #[inline]
+    pub fn request_id_typed(&self) -> RequestId {
+        RequestId(self.request_id())
+    }
+
+    ///Returns the client id (upper 16 bits of the request id).
+    #[inline]
+    pub fn client_id_typed(&self) -> ClientId {
+        self.request_id_typed().client_id()
+    }
+
+    ///Returns the session id (lower 16 bits of the request id).
+    #[inline]
+    pub fn session_id_typed(&self) -> SessionId {
+        self.request_id_typed().session_id()
+    }
+
+    /// Returns the length contained in the header (see [`SomeipMsgSlice::length`]).
+    #[inline]
+    pub fn length(&self) -> u32 {
+        self.msg.length()
+    }
+
+    ///Returns the request id of the message.
+    #[inline]
+    pub fn request_id(&self) -> u32 {
+        self.msg.request_id()
+    }
+
+    ///Return the value of the protocol version field of the message (not verified).
+    #[inline]
+    pub fn protocol_version(&self) -> u8 {
+        self.msg.slice()[12]
+    }
+
+    ///Returns the interface version field of the message.
+    #[inline]
+    pub fn interface_version(&self) -> u8 {
+        self.msg.interface_version()
+    }
+
+    ///Return the message type (does not contain the tp flag, use the is_tp method for
+    ///checking if this is a tp message).
+    #[inline]
+    pub fn message_type(&self) -> LenientMessageType {
+        LenientMessageType::from_raw(self.message_type_raw())
+    }
+
+    ///Returns the raw message type value (contains the tp flag).
+    #[inline]
+    pub fn message_type_raw(&self) -> u8 {
+        self.msg.message_type_raw()
+    }
+
+    ///Returns true if the tp flag in the message type is set.
+    #[inline]
+    pub fn is_tp(&self) -> bool {
+        self.msg.is_tp()
+    }
+
+    ///Returns the return code of the message.
+    #[inline]
+    pub fn return_code(&self) -> u8 {
+        self.msg.return_code()
+    }
+
+    /// Return a slice to the payload (after the tp header if present).
+    #[inline]
+    pub fn payload(&self) -> &'a [u8] {
+        self.msg.payload()
+    }
+
+    ///Returns the tp header if there should be one present.
+    #[inline]
+    pub fn tp_header(&self) -> Option<TpHeader> {
+        self.msg.tp_header()
+    }
+
+    /// Returns the checks of the strict parser the header violates.
+    pub fn findings(&self) -> SomeipLenientFindings {
+        let protocol_version = self.protocol_version();
+        let message_type = self.message_type();
+        SomeipLenientFindings {
+            unsupported_protocol_version: (SOMEIP_PROTOCOL_VERSION != protocol_version)
+                .then_some(protocol_version),
+            legacy_message_type: message_type
+                .is_legacy_ack()
+                .then_some(self.message_type_raw()),
+            unknown_message_type: matches!(message_type, LenientMessageType::Unknown(_))
+                .then_some(self.message_type_raw()),
+        }
+    }
+
+    /// Converts the message to a [`SomeipMsgSlice`] if it has no findings.
+    /// Otherwise the first finding is returned as error.
+    pub fn to_strict(&self) -> Result<SomeipMsgSlice<'a>, err::SomeipHeaderError> {
+        match self.findings().errors().next() {
+            Some(err) => Err(err),
+            None => Ok(self.msg.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::proptest_generators::*;
+    #[cfg(feature = "std")]
+    use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    use proptest::prelude::*;
+
+    fn base_bytes(protocol_version: u8, message_type: u8) -> [u8; SOMEIP_HEADER_LENGTH] {
+        let mut bytes = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            request_id: 0x0001_0002,
+            interface_version: 3,
+            ..Default::default()
+        }
+        .base_to_bytes();
+        bytes[12] = protocol_version;
+        bytes[14] = message_type;
+        bytes
+    }
+
+    #[cfg(feature = "std")]
+    proptest! {
+        #[test]
+        fn from_slice_valid((header, payload) in someip_header_with_payload_any()) {
+            let mut buffer = Vec::new();
+            header.write_raw(&mut buffer).unwrap();
+            buffer.extend_from_slice(&payload);
+            buffer.push(0xff);
+
+            let strict = SomeipMsgSlice::from_slice(&buffer).unwrap();
+            let lenient = SomeipLenientMsgSlice::from_slice(&buffer).unwrap();
+            prop_assert_eq!(strict.slice(), lenient.slice());
+            prop_assert_eq!(strict.message_id(), lenient.message_id());
+            prop_assert_eq!(strict.service_id(), lenient.service_id());
+            prop_assert_eq!(strict.is_event(), lenient.is_event());
+            prop_assert_eq!(strict.event_or_method_id(), lenient.event_or_method_id());
+            prop_assert_eq!(strict.event_id(), lenient.event_id());
+            prop_assert_eq!(strict.method_id(), lenient.method_id());
+            prop_assert_eq!(strict.length(), lenient.length());
+            prop_assert_eq!(strict.request_id(), lenient.request_id());
+            prop_assert_eq!(strict.message_id_typed(), lenient.message_id_typed());
+            prop_assert_eq!(strict.service_id_typed(), lenient.service_id_typed());
+            prop_assert_eq!(strict.request_id_typed(), lenient.request_id_typed());
+            prop_assert_eq!(strict.client_id_typed(), lenient.client_id_typed());
+            prop_assert_eq!(strict.session_id_typed(), lenient.session_id_typed());
+            prop_assert_eq!(strict.protocol_version(), lenient.protocol_version());
+            prop_assert_eq!(strict.interface_version(), lenient.interface_version());
+            prop_assert_eq!(Some(strict.message_type()), lenient.message_type().to_message_type());
+            prop_assert_eq!(strict.message_type_raw(), lenient.message_type_raw());
+            prop_assert_eq!(strict.is_tp(), lenient.is_tp());
+            prop_assert_eq!(strict.return_code(), lenient.return_code());
+            prop_assert_eq!(strict.payload(), lenient.payload());
+            prop_assert_eq!(strict.tp_header(), lenient.tp_header());
+            prop_assert!(lenient.findings().is_empty());
+            prop_assert_eq!(Ok(strict), lenient.to_strict());
+        }
+    }
+
+    #[test]
+    fn from_slice_lenient() {
+        use err::SomeipHeaderError::*;
+
+        // unsupported protocol version
+        {
+            let bytes = base_bytes(2, 0x80);
+            let msg = SomeipLenientMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(2, msg.protocol_version());
+            assert_eq!(LenientMessageType::Response, msg.message_type());
+            assert_eq!(
+                SomeipLenientFindings {
+                    unsupported_protocol_version: Some(2),
+                    ..Default::default()
+                },
+                msg.findings()
+            );
+            assert_eq!(Err(UnsupportedProtocolVersion(2)), msg.to_strict());
+        }
+
+        // legacy ack types
+        for (raw, expected) in [
+            (0x40, LenientMessageType::RequestAck),
+            (0x41, LenientMessageType::RequestNoReturnAck),
+            (0x42, LenientMessageType::NotificationAck),
+            (0xC0, LenientMessageType::ResponseAck),
+            (0xC1, LenientMessageType::ErrorAck),
+        ] {
+            let bytes = base_bytes(SOMEIP_PROTOCOL_VERSION, raw);
+            let msg = SomeipLenientMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(expected, msg.message_type());
+            assert_eq!(false, msg.is_tp());
+            assert_eq!(
+                SomeipLenientFindings {
+                    legacy_message_type: Some(raw),
+                    ..Default::default()
+                },
+                msg.findings()
+            );
+            assert_eq!(Err(UnknownMessageType(raw)), msg.to_strict());
+        }
+
+        // unknown message type with tp flag
+        {
+            let mut bytes = [0u8; SOMEIP_HEADER_LENGTH + TP_HEADER_LENGTH];
+            bytes[..SOMEIP_HEADER_LENGTH].copy_from_slice(&base_bytes(
+                SOMEIP_PROTOCOL_VERSION,
+                0x13 | SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG,
+            ));
+            bytes[4..8].copy_from_slice(&(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4).to_be_bytes());
+            let msg = SomeipLenientMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(LenientMessageType::Unknown(0x13), msg.message_type());
+            assert!(msg.is_tp());
+            assert_eq!(Some(TpHeader::new(false)), msg.tp_header());
+            assert_eq!(
+                SomeipLenientFindings {
+                    unknown_message_type: Some(0x33),
+                    ..Default::default()
+                },
+                msg.findings()
+            );
+            assert_eq!(Err(UnknownMessageType(0x33)), msg.to_strict());
+        }
+    }
+
+    #[test]
+    fn from_slice_errors() {
+        use err::{SomeipHeaderError::*, SomeipSliceError::*, *};
+
+        // slice too small for header
+        {
+            let bytes = base_bytes(2, 0x40);
+            assert_eq!(
+                Err(Len(LenError {
+                    required_len: SOMEIP_HEADER_LENGTH,
+                    len: SOMEIP_HEADER_LENGTH - 1,
+                    len_source: LenSource::Slice,
+                    layer: Layer::SomeipHeader,
+                })),
+                SomeipLenientMsgSlice::from_slice(&bytes[..SOMEIP_HEADER_LENGTH - 1])
+            );
+        }
+
+        // length field too small
+        {
+            let mut bytes = base_bytes(2, 0x40);
+            bytes[4..8].copy_from_slice(&7u32.to_be_bytes());
+            assert_eq!(
+                Err(Content(LengthFieldTooSmall(7))),
+                SomeipLenientMsgSlice::from_slice(&bytes)
+            );
+        }
+
+        // length field too small for tp header
+        {
+            let bytes = base_bytes(2, 0x40 | SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG);
+            assert_eq!(
+                Err(Content(LengthFieldTooSmall(SOMEIP_LEN_OFFSET_TO_PAYLOAD))),
+                SomeipLenientMsgSlice::from_slice(&bytes)
+            );
+        }
+
+        // payload missing
+        {
+            let mut bytes = base_bytes(2, 0x40);
+            bytes[4..8].copy_from_slice(&(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 1).to_be_bytes());
+            assert_eq!(
+                Err(Len(LenError {
+                    required_len: SOMEIP_HEADER_LENGTH + 1,
+                    len: SOMEIP_HEADER_LENGTH,
+                    len_source: LenSource::SomeipHeaderLength,
+                    layer: Layer::SomeipPayload,
+                })),
+                SomeipLenientMsgSlice::from_slice(&bytes)
+            );
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::*;
+
+/// Allows iterating over the someip messages in a udp or tcp payload,
+/// parsing them in lenient mode (see [`SomeipLenientMsgSlice`]).
+///
+/// Only length errors end the iteration. Header checks violated by a
+/// message are reported via [`SomeipLenientMsgSlice::findings`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SomeipLenientMsgsIterator<'a> {
+    slice: &'a [u8],
+}
+
+impl<'a> SomeipLenientMsgsIterator<'a> {
+    pub fn new(slice: &'a [u8]) -> SomeipLenientMsgsIterator<'a> {
+        SomeipLenientMsgsIterator { slice }
+    }
+}
+
+impl<'a> Iterator for SomeipLenientMsgsIterator<'a> {
+    type Item = Result<SomeipLenientMsgSlice<'a>, err::SomeipSliceError>;
+
+    fn next(&mut self) -> Option<Result<SomeipLenientMsgSlice<'a>, err::SomeipSliceError>> {
+        if !self.slice.is_empty() {
+            // parse
+            let result = SomeipLenientMsgSlice::from_slice(self.slice);
+
+            // move the slice depending on the result
+            match &result {
+                Err(_) => {
+                    // error => move the slice to an len = 0 position so that the iterator ends
+                    let len = self.slice.len();
+                    self.slice = &self.slice[len..];
+                }
+                Ok(ref value) => {
+                    // by the length just taken by the slice
+                    self.slice = &self.slice[value.slice().len()..];
+                }
+            }
+
+            // return parse result
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn iterator() {
+        use err::{SomeipSliceError::*, *};
+
+        let mut buffer = Vec::new();
+        for (protocol_version, message_type) in [
+            (SOMEIP_PROTOCOL_VERSION, 0x02),
+            (SOMEIP_PROTOCOL_VERSION, 0xC0),
+            (3, 0x80),
+            (SOMEIP_PROTOCOL_VERSION, 0x17),
+        ] {
+            let mut bytes = SomeipHeader {
+                length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 1,
+                ..Default::default()
+            }
+            .base_to_bytes();
+            bytes[12] = protocol_version;
+            bytes[14] = message_type;
+            buffer.extend_from_slice(&bytes);
+            buffer.push(message_type);
+        }
+        // incomplete message at the end
+        buffer.extend_from_slice(&[0; 4]);
+
+        let mut iterator = SomeipLenientMsgsIterator::new(&buffer);
+        for (message_type, findings) in [
+            (
+                LenientMessageType::Notification,
+                SomeipLenientFindings::default(),
+            ),
+            (
+                LenientMessageType::ResponseAck,
+                SomeipLenientFindings {
+                    legacy_message_type: Some(0xC0),
+                    ..Default::default()
+                },
+            ),
+            (
+                LenientMessageType::Response,
+                SomeipLenientFindings {
+                    unsupported_protocol_version: Some(3),
+                    ..Default::default()
+                },
+            ),
+            (
+                LenientMessageType::Unknown(0x17),
+                SomeipLenientFindings {
+                    unknown_message_type: Some(0x17),
+                    ..Default::default()
+                },
+            ),
+        ] {
+            let msg = iterator.next().unwrap().unwrap();
+            assert_eq!(message_type, msg.message_type());
+            assert_eq!(&[message_type.to_raw()], msg.payload());
+            assert_eq!(findings, msg.findings());
+        }
+        assert_eq!(
+            Some(Err(Len(LenError {
+                required_len: SOMEIP_HEADER_LENGTH,
+                len: 4,
+                len_source: LenSource::Slice,
+                layer: Layer::SomeipHeader,
+            }))),
+            iterator.next()
+        );
+        assert_eq!(None, iterator.next());
+        assert_eq!(None, iterator.next());
+    }
+}
@@ -5,6 +5,34 @@ use crate::*;
 pub type SomeIpHeaderSlice<'a> = SomeipMsgSlice<'a>;
 
 /// A slice containing an some ip header & payload of that message.
+///
+/// `Display` writes a one line summary of the header. The alternate form
+/// (`{:#}`) writes a multi-line dissection of the message: all header
+/// fields, the decoded service discovery payload (for SD messages) or a
+/// hex dump of the payload.
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let header = SomeipHeader {
+///     message_id: 0x1234_8001,
+///     length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 2,
+///     request_id: 0x0001_0002,
+///     interface_version: 1,
+///     message_type: MessageType::Notification,
+///     ..Default::default()
+/// };
+/// let mut bytes = header.base_to_bytes().to_vec();
+/// bytes.extend_from_slice(&[0x12, 0x34]);
+/// let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+///
+/// assert_eq!(
+///     "SOME/IP Service ID: 0x1234, Event ID: 0x8001, Client ID: 0x0001, \
+///      Session ID: 0x0002, Message Type: Notification, Return Code: Ok, Length: 10",
+///     format!("{}", msg)
+/// );
+/// println!("{:#}", msg);
+/// ```
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct SomeipMsgSlice<'a> {
     /// If true a TP header is following the SOME/IP header.
@@ -13,161 +41,91 @@ pub struct SomeipMsgSlice<'a> {
 }
 
 impl<'a> SomeipMsgSlice<'a> {
-    #[cfg(target_pointer_width = "64")]
     pub fn from_slice(slice: &'a [u8]) -> Result<SomeipMsgSlice<'a>, err::SomeipSliceError> {
-        use err::{SomeipHeaderError::*, SomeipSliceError::*, *};
-        //first check the length
-        if slice.len() < SOMEIP_HEADER_LENGTH {
-            Err(Len(LenError {
-                required_len: SOMEIP_HEADER_LENGTH,
-                len: slice.len(),
-                len_source: LenSource::Slice,
-                layer: Layer::SomeipHeader,
-            }))
-        } else {
-            //check length
-            let len = {
-                // SAFETY:
-                // Read is save as it is checked before that the slice has at least
-                // SOMEIP_HEADER_LENGTH 16 bytes.
-                unsafe { get_unchecked_be_u32(slice.as_ptr().add(4)) }
-            };
-            if len < SOMEIP_LEN_OFFSET_TO_PAYLOAD {
-                return Err(Content(LengthFieldTooSmall(len)));
-            }
-            //NOTE: In case you want to write a 32 bit version, a check needs to be added, so that
-            //      no accidental overflow when adding the 4*2 bytes happens.
-            let total_length = (len as usize) + 4 * 2;
-            if slice.len() < total_length {
-                return Err(Len(LenError {
-                    required_len: total_length,
-                    len: slice.len(),
-                    len_source: LenSource::SomeipHeaderLength,
-                    layer: Layer::SomeipPayload,
-                }));
-            }
-            //check protocol version
-            let protocol_version = {
-                // SAFETY:
-                // Read is save as it is checked before that the slice has at least
-                // SOMEIP_HEADER_LENGTH 16 (4*4) bytes.
-                unsafe { *slice.get_unchecked(4 * 3) }
-            };
-            if SOMEIP_PROTOCOL_VERSION != protocol_version {
-                return Err(Content(UnsupportedProtocolVersion(protocol_version)));
-            }
-
-            //check message type
-            let message_type = {
-                // SAFETY:
-                // Read is save as it is checked before that the slice has at least
-                // SOMEIP_HEADER_LENGTH 16 (4*4) bytes.
-                unsafe { *slice.get_unchecked(4 * 3 + 2) }
-            };
+        use err::{SomeipHeaderError::*, SomeipSliceError::*};
 
-            //check the length is still ok, in case of a tp flag
-            let tp = 0 != message_type & SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG;
-            if tp && len < SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 {
-                return Err(Content(LengthFieldTooSmall(len)));
-            }
+        let msg = SomeipMsgSlice::from_slice_len_checked(slice)?;
 
-            //make sure the message type is known
-            match message_type & !(SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG) {
-                0x0 | 0x1 | 0x2 | 0x80 | 0x81 => {}
-                _ => return Err(Content(UnknownMessageType(message_type))),
-            }
+        //check protocol version
+        let protocol_version = msg.slice[4 * 3];
+        if SOMEIP_PROTOCOL_VERSION != protocol_version {
+            return Err(Content(UnsupportedProtocolVersion(protocol_version)));
+        }
 
-            //all good generate the slice
-            Ok(SomeipMsgSlice {
-                tp,
-                // SAFETY: Check is preformed above to ensure slice has at least total length
-                slice: unsafe { core::slice::from_raw_parts(slice.as_ptr(), total_length) },
-            })
+        //make sure the message type is known
+        let message_type = msg.slice[4 * 3 + 2];
+        match message_type & !(SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG) {
+            0x0 | 0x1 | 0x2 | 0x80 | 0x81 => {}
+            _ => return Err(Content(UnknownMessageType(message_type))),
         }
+
+        Ok(msg)
     }
 
-    #[cfg(target_pointer_width = "32")]
-    pub fn from_slice(slice: &'a [u8]) -> Result<SomeipMsgSlice<'a>, err::SomeipSliceError> {
+    /// Parses a message from the slice, only verifying the lengths (the
+    /// protocol version & message type are not checked). Shared by
+    /// [`SomeipMsgSlice::from_slice`] & [`SomeipLenientMsgSlice::from_slice`].
+    pub(crate) fn from_slice_len_checked(
+        slice: &'a [u8],
+    ) -> Result<SomeipMsgSlice<'a>, err::SomeipSliceError> {
         use err::{SomeipHeaderError::*, SomeipSliceError::*, *};
         //first check the length
         if slice.len() < SOMEIP_HEADER_LENGTH {
-            Err(Len(LenError {
+            return Err(Len(LenError {
                 required_len: SOMEIP_HEADER_LENGTH,
                 len: slice.len(),
                 len_source: LenSource::Slice,
                 layer: Layer::SomeipHeader,
-            }))
-        } else {
-            //check length
-            let len = {
-                // SAFETY:
-                // Read is save as it is checked before that the slice has at least
-                // SOMEIP_HEADER_LENGTH 16 bytes.
-                unsafe { get_unchecked_be_u32(slice.as_ptr().add(4)) }
-            };
-            if len < SOMEIP_LEN_OFFSET_TO_PAYLOAD {
-                return Err(Content(LengthFieldTooSmall(len)));
-            }
-
-            //NOTE: This additional check is needed for 32 bit systems, as otherwise an overflow could potentially be happening
-            const MAX_SUPPORTED_LEN: usize = usize::MAX - 4 * 2;
-            let len_usize = len as usize;
-            if len_usize > MAX_SUPPORTED_LEN {
-                return Err(Len(LenError {
-                    required_len: MAX_SUPPORTED_LEN,
-                    len: slice.len(),
-                    len_source: LenSource::SomeipHeaderLength,
-                    layer: Layer::SomeipPayload,
-                }));
-            }
-
-            let total_length = len_usize + 4 * 2;
-            if slice.len() < total_length {
-                return Err(Len(LenError {
-                    required_len: total_length,
-                    len: slice.len(),
-                    len_source: LenSource::SomeipHeaderLength,
-                    layer: Layer::SomeipPayload,
-                }));
-            }
-            //check protocol version
-            let protocol_version = {
-                // SAFETY:
-                // Read is save as it is checked before that the slice has at least
-                // SOMEIP_HEADER_LENGTH 16 (4*4) bytes.
-                unsafe { *slice.get_unchecked(4 * 3) }
-            };
-            if SOMEIP_PROTOCOL_VERSION != protocol_version {
-                return Err(Content(UnsupportedProtocolVersion(protocol_version)));
-            }
-
-            //check message type
-            let message_type = {
-                // SAFETY:
-                // Read is save as it is checked before that the slice has at least
-                // SOMEIP_HEADER_LENGTH 16 (4*4) bytes.
-                unsafe { *slice.get_unchecked(4 * 3 + 2) }
-            };
+            }));
+        }
 
-            //check the length is still ok, in case of a tp flag
-            let tp = 0 != message_type & SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG;
-            if tp && len < SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 {
-                return Err(Content(LengthFieldTooSmall(len)));
-            }
+        //check length
+        let len = {
+            // SAFETY:
+            // Read is save as it is checked before that the slice has at least
+            // SOMEIP_HEADER_LENGTH 16 bytes.
+            unsafe { get_unchecked_be_u32(slice.as_ptr().add(4)) }
+        };
+        if len < SOMEIP_LEN_OFFSET_TO_PAYLOAD {
+            return Err(Content(LengthFieldTooSmall(len)));
+        }
 
-            //make sure the message type is known
-            match message_type & !(SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG) {
-                0x0 | 0x1 | 0x2 | 0x80 | 0x81 => {}
-                _ => return Err(Content(UnknownMessageType(message_type))),
-            }
+        //NOTE: checked add as the addition can overflow on 32 bit systems
+        const MAX_SUPPORTED_LEN: usize = usize::MAX - 4 * 2;
+        let Some(total_length) = (len as usize).checked_add(4 * 2) else {
+            return Err(Len(LenError {
+                required_len: MAX_SUPPORTED_LEN,
+                len: slice.len(),
+                len_source: LenSource::SomeipHeaderLength,
+                layer: Layer::SomeipPayload,
+            }));
+        };
+        if slice.len() < total_length {
+            return Err(Len(LenError {
+                required_len: total_length,
+                len: slice.len(),
+                len_source: LenSource::SomeipHeaderLength,
+                layer: Layer::SomeipPayload,
+            }));
+        }
 
-            //all good generate the slice
-            Ok(SomeipMsgSlice {
-                tp,
-                // SAFETY: Check is preformed above to ensure slice has at least total length
-                slice: unsafe { core::slice::from_raw_parts(slice.as_ptr(), total_length) },
-            })
+        //check the length is still ok, in case of a tp flag
+        let message_type = {
+            // SAFETY:
+            // Read is save as it is checked before that the slice has at least
+            // SOMEIP_HEADER_LENGTH 16 (4*4) bytes.
+            unsafe { *slice.get_unchecked(4 * 3 + 2) }
+        };
+        let tp = 0 != message_type & SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG;
+        if tp && len < SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 {
+            return Err(Content(LengthFieldTooSmall(len)));
         }
+
+        Ok(SomeipMsgSlice {
+            tp,
+            // SAFETY: Check is preformed above to ensure slice has at least total length
+            slice: unsafe { core::slice::from_raw_parts(slice.as_ptr(), total_length) },
+        })
     }
 
     ///Return the slice that contains the someip header
@@ -232,12 +190,48 @@ impl<'a> SomeipMsgSlice<'a> {
         }
     }
 
+    ///Returns the message id as a [`MessageId`].
+    #[inline]
+    pub fn message_id_typed(&self) -> MessageId {
+        MessageId(self.message_id())
+    }
+
+    ///Returns the service id as a [`ServiceId`].
+    #[inline]
+    pub fn service_id_typed(&self) -> ServiceId {
+        ServiceId(self.service_id())
+    }
+
+    ///Returns the request id as a [`RequestId`].
+    #[inline]
+    pub fn request_id_typed(&self) -> RequestId {
+        RequestId(self.request_id())
+    }
+
+    ///Returns the client id (upper 16 bits of the request id).
+    #[inline]
+    pub fn client_id_typed(&self) -> ClientId {
+        self.request_id_typed().client_id()
+    }
+
+    ///Returns the session id (lower 16 bits of the request id).
+    #[inline]
+    pub fn session_id_typed(&self) -> SessionId {
+        self.request_id_typed().session_id()
+    }
+
     ///Returns true if the message has the message id of a some ip service discovery message.
     #[inline]
     pub fn is_someip_sd(&self) -> bool {
         SOMEIP_SD_MESSAGE_ID == self.message_id()
     }
 
+    ///Returns true if the message is a magic cookie (see [`MagicCookie`]).
+    #[inline]
+    pub fn is_magic_cookie(&self) -> bool {
+        MagicCookie::from_msg(self).is_some()
+    }
+
     /// Returns the length contained in the header. WARNING: the length paritally
     /// contains the header and partially the payload, use the payload() method
     /// instead if you want to access the payload slice).
@@ -381,6 +375,50 @@ impl<'a> SomeipMsgSlice<'a> {
     }
 }
 
+impl core::fmt::Display for SomeipMsgSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use crate::dissect::{hex_dump, line};
+
+        let header = self.to_header();
+        write!(f, "{header}")?;
+        if false == f.alternate() {
+            return Ok(());
+        }
+
+        header.write_fields(f, 1)?;
+        if self.is_someip_sd() && false == self.is_tp() {
+            match sd::SdSlice::from_someip(self) {
+                Ok(sd) => {
+                    line(f, 1, format_args!("SOME/IP-SD"))?;
+                    return sd.write_fields(f, 2);
+                }
+                Err(err) => line(f, 1, format_args!("SOME/IP-SD Error: {err}"))?,
+            }
+        }
+        line(
+            f,
+            1,
+            format_args!("Payload: {} bytes", self.payload().len()),
+        )?;
+        hex_dump(f, 2, self.payload())
+    }
+}
+
+/// Serializes the message as its header (see [`SomeipMsgSlice::to_header`])
+/// and payload.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for SomeipMsgSlice<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("SomeipMsg", 2)?;
+        state.serialize_field("header", &self.to_header())?;
+        state.serialize_field("payload", &crate::serde_util::Bytes(self.payload()))?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::format;
@@ -456,4 +494,119 @@ mod tests {
             slice.message_type();
         }
     }
+
+    #[test]
+    fn display() {
+        fn msg(header: &SomeipHeader, payload: &[u8]) -> alloc::vec::Vec<u8> {
+            let mut result = header.base_to_bytes().to_vec();
+            if let Some(tp_header) = &header.tp_header {
+                result.extend_from_slice(&tp_header.to_bytes());
+            }
+            result.extend_from_slice(payload);
+            result
+        }
+
+        // payload as hex dump
+        {
+            let header = SomeipHeader {
+                message_id: 0x1234_0001,
+                length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 3,
+                ..Default::default()
+            };
+            let bytes = msg(&header, b"ab\0");
+            let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(format!("{}", header), format!("{}", slice));
+            assert_eq!(
+                format!(
+                    "{:#}\n    Payload: 3 bytes\n        0000  61 62 00{}ab.",
+                    header,
+                    " ".repeat(42)
+                ),
+                format!("{:#}", slice)
+            );
+        }
+
+        // sd message
+        {
+            let mut sd_header = sd::SdHeader::empty(false);
+            sd_header
+                .add_entry(
+                    sd::SdEntry::new_find_service_entry(0, 0, 0, 0, 0x1234, 0xffff, 0xff, 3, 0)
+                        .unwrap(),
+                )
+                .unwrap();
+            let mut payload = [0u8; 28];
+            sd_header.write_to_slice(&mut payload).unwrap();
+            let header = SomeipHeader::new_sd_header(
+                SOMEIP_LEN_OFFSET_TO_PAYLOAD + payload.len() as u32,
+                1,
+                None,
+            );
+            let bytes = msg(&header, &payload);
+            let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            let sd = sd::SdSlice::from_someip(&slice).unwrap();
+            let mut expected = format!("{:#}\n    SOME/IP-SD", header);
+            for line in format!("{:#}", sd).lines().skip(1) {
+                expected.push_str("\n    ");
+                expected.push_str(line);
+            }
+            assert_eq!(expected, format!("{:#}", slice));
+            assert!(expected.contains(
+                "\n            Entry 0: Find Service (Service ID: 0x1234, Instance ID: 0xffff, \
+                 Version: 255.0, TTL: 3)\n"
+            ));
+        }
+
+        // sd message that can not be decoded
+        {
+            let header = SomeipHeader::new_sd_header(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 1, 1, None);
+            let bytes = msg(&header, &[0xc0]);
+            let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            let output = format!("{:#}", slice);
+            assert!(output.contains("\n    SOME/IP-SD Error: "));
+            assert!(output.ends_with(&format!(
+                "\n    Payload: 1 bytes\n        0000  c0{}.",
+                " ".repeat(48)
+            )));
+        }
+
+        // tp segment
+        {
+            let header = SomeipHeader {
+                message_id: 0x1234_0001,
+                length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 + 16,
+                tp_header: Some(TpHeader::with_offset(16, false).unwrap()),
+                ..Default::default()
+            };
+            let bytes = msg(&header, &[0u8; 16]);
+            let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(format!("{}", header), format!("{}", slice));
+            assert!(format!("{:#}", slice)
+                .contains("\n        More Segments: false\n    Payload: 16 bytes\n"));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 8 + 2,
+            request_id: 0x0001_0002,
+            interface_version: 1,
+            message_type: Notification,
+            return_code: 0,
+            tp_header: None,
+        };
+        let mut bytes = header.base_to_bytes().to_vec();
+        bytes.extend_from_slice(&[0xab, 0xcd]);
+        let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "header": serde_json::to_value(&header).unwrap(),
+                "payload": [0xab, 0xcd],
+            }),
+            serde_json::to_value(&msg).unwrap()
+        );
+    }
 }
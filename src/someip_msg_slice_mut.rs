@@ -0,0 +1,291 @@
+use crate::*;
+
+/// Mutable view over a serialized SOMEIP message allowing the header
+/// fields to be rewritten in place.
+///
+/// The view is validated once during construction (with the same checks
+/// as [`SomeipMsgSlice::from_slice`]) and all setters only accept values
+/// that keep the message valid. Fields that determine the layout of the
+/// message (the length, the protocol version & the TP flag of the message
+/// type) can not be changed, so the payload is never moved or copied.
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let header = SomeipHeader {
+///     message_id: 0x1234_8001,
+///     length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 2,
+///     request_id: 0x0001_0002,
+///     interface_version: 1,
+///     message_type: MessageType::Notification,
+///     ..Default::default()
+/// };
+/// let mut bytes = header.base_to_bytes().to_vec();
+/// bytes.extend_from_slice(&[0x12, 0x34]);
+///
+/// let mut msg = SomeipMsgSliceMut::from_slice(&mut bytes).unwrap();
+/// msg.set_service_id(0x4321);
+/// msg.set_session_id(0x0003);
+/// msg.payload_mut()[0] = 0xff;
+///
+/// let msg = SomeipMsgSlice::from_slice(&bytes).unwrap();
+/// assert_eq!(0x4321_8001, msg.message_id());
+/// assert_eq!(0x0001_0003, msg.request_id());
+/// assert_eq!(&[0xff, 0x34], msg.payload());
+/// ```
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct SomeipMsgSliceMut<'a> {
+    /// If true a TP header is following the SOME/IP header.
+    tp: bool,
+    slice: &'a mut [u8],
+}
+
+impl<'a> SomeipMsgSliceMut<'a> {
+    /// Validates the SOMEIP message at the start of `slice` and returns a
+    /// mutable view over it.
+    ///
+    /// The view only covers the message itself (header + the number of
+    /// bytes given by the length field), any following bytes are ignored.
+    pub fn from_slice(slice: &'a mut [u8]) -> Result<SomeipMsgSliceMut<'a>, err::SomeipSliceError> {
+        let (tp, len) = {
+            let msg = SomeipMsgSlice::from_slice(slice)?;
+            (msg.is_tp(), msg.slice().len())
+        };
+        Ok(SomeipMsgSliceMut {
+            tp,
+            slice: &mut slice[..len],
+        })
+    }
+
+    /// Returns a read only view of the message.
+    #[inline]
+    pub fn to_slice(&self) -> SomeipMsgSlice<'_> {
+        // the setters can not invalidate the message, so this can not fail
+        SomeipMsgSlice::from_slice(self.slice).unwrap()
+    }
+
+    /// Returns the slice containing the message (header & payload).
+    #[inline]
+    pub fn slice(&self) -> &[u8] {
+        self.slice
+    }
+
+    /// Returns true if a TP header is present after the SOMEIP header.
+    #[inline]
+    pub fn is_tp(&self) -> bool {
+        self.tp
+    }
+
+    /// Sets the message id (service id & method/event id).
+    #[inline]
+    pub fn set_message_id(&mut self, message_id: u32) {
+        self.slice[..4].copy_from_slice(&message_id.to_be_bytes());
+    }
+
+    /// Sets the service id (upper 16 bits of the message id).
+    #[inline]
+    pub fn set_service_id(&mut self, service_id: u16) {
+        self.slice[..2].copy_from_slice(&service_id.to_be_bytes());
+    }
+
+    /// Sets the event id or method id. This number must include the "event bit".
+    #[inline]
+    pub fn set_method_or_event_id(&mut self, method_or_event_id: u16) {
+        self.slice[2..4].copy_from_slice(&method_or_event_id.to_be_bytes());
+    }
+
+    /// Sets the request id (client id & session id).
+    #[inline]
+    pub fn set_request_id(&mut self, request_id: u32) {
+        self.slice[8..12].copy_from_slice(&request_id.to_be_bytes());
+    }
+
+    /// Sets the client id (upper 16 bits of the request id).
+    #[inline]
+    pub fn set_client_id(&mut self, client_id: u16) {
+        self.slice[8..10].copy_from_slice(&client_id.to_be_bytes());
+    }
+
+    /// Sets the session id (lower 16 bits of the request id).
+    #[inline]
+    pub fn set_session_id(&mut self, session_id: u16) {
+        self.slice[10..12].copy_from_slice(&session_id.to_be_bytes());
+    }
+
+    /// Sets the interface version.
+    #[inline]
+    pub fn set_interface_version(&mut self, interface_version: u8) {
+        self.slice[13] = interface_version;
+    }
+
+    /// Sets the message type.
+    ///
+    /// The TP flag is preserved as it determines the layout of the message.
+    #[inline]
+    pub fn set_message_type(&mut self, message_type: MessageType) {
+        let tp_flag = if self.tp {
+            SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG
+        } else {
+            0
+        };
+        self.slice[14] = (message_type as u8) | tp_flag;
+    }
+
+    /// Sets the return code.
+    #[inline]
+    pub fn set_return_code(&mut self, return_code: u8) {
+        self.slice[15] = return_code;
+    }
+
+    /// Overwrites the TP header (offset & more segments flag).
+    ///
+    /// Returns `false` & leaves the message unchanged if the message has
+    /// no TP header.
+    #[inline]
+    pub fn set_tp_header(&mut self, tp_header: &TpHeader) -> bool {
+        if self.tp {
+            self.slice[SOMEIP_HEADER_LENGTH..SOMEIP_HEADER_LENGTH + TP_HEADER_LENGTH]
+                .copy_from_slice(&tp_header.to_bytes());
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn payload_offset(&self) -> usize {
+        if self.tp {
+            SOMEIP_HEADER_LENGTH + TP_HEADER_LENGTH
+        } else {
+            SOMEIP_HEADER_LENGTH
+        }
+    }
+
+    /// Returns the payload (the bytes after the SOMEIP & TP header).
+    #[inline]
+    pub fn payload(&self) -> &[u8] {
+        &self.slice[self.payload_offset()..]
+    }
+
+    /// Returns the payload (the bytes after the SOMEIP & TP header) for
+    /// in place modification.
+    #[inline]
+    pub fn payload_mut(&mut self) -> &mut [u8] {
+        let offset = self.payload_offset();
+        &mut self.slice[offset..]
+    }
+
+    /// Consumes the view and returns the payload with the lifetime of the
+    /// underlying slice.
+    #[inline]
+    pub fn into_payload_mut(self) -> &'a mut [u8] {
+        let offset = self.payload_offset();
+        &mut self.slice[offset..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn msg_bytes(tp_header: Option<TpHeader>, payload: &[u8]) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id: 0x1234_0001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD
+                + payload.len() as u32
+                + if tp_header.is_some() { 4 } else { 0 },
+            request_id: 0x0001_0002,
+            interface_version: 1,
+            message_type: MessageType::Request,
+            return_code: 0,
+            tp_header,
+        };
+        let mut result = header.base_to_bytes().to_vec();
+        if let Some(tp) = header.tp_header {
+            result.extend_from_slice(&tp.to_bytes());
+        }
+        result.extend_from_slice(payload);
+        result
+    }
+
+    #[test]
+    fn from_slice() {
+        // trailing bytes are not part of the view
+        {
+            let mut bytes = msg_bytes(None, &[1, 2]);
+            bytes.extend_from_slice(&[3, 4]);
+            let msg = SomeipMsgSliceMut::from_slice(&mut bytes).unwrap();
+            assert!(false == msg.is_tp());
+            assert_eq!(SOMEIP_HEADER_LENGTH + 2, msg.slice().len());
+            assert_eq!(&[1, 2], msg.payload());
+        }
+        // errors are forwarded
+        {
+            let mut bytes = msg_bytes(None, &[1, 2]);
+            let expected = SomeipMsgSlice::from_slice(&bytes[..bytes.len() - 1]).unwrap_err();
+            let len = bytes.len();
+            assert_eq!(
+                expected,
+                SomeipMsgSliceMut::from_slice(&mut bytes[..len - 1]).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn setters() {
+        let mut bytes = msg_bytes(None, &[1, 2, 3]);
+        let mut msg = SomeipMsgSliceMut::from_slice(&mut bytes).unwrap();
+
+        msg.set_message_id(0x1111_2222);
+        assert_eq!(0x1111_2222, msg.to_slice().message_id());
+        msg.set_service_id(0x3333);
+        assert_eq!(0x3333_2222, msg.to_slice().message_id());
+        msg.set_method_or_event_id(0x8004);
+        assert_eq!(0x3333_8004, msg.to_slice().message_id());
+        assert!(msg.to_slice().is_event());
+
+        msg.set_request_id(0x4444_5555);
+        assert_eq!(0x4444_5555, msg.to_slice().request_id());
+        msg.set_client_id(0x6666);
+        assert_eq!(0x6666_5555, msg.to_slice().request_id());
+        msg.set_session_id(0x7777);
+        assert_eq!(0x6666_7777, msg.to_slice().request_id());
+
+        msg.set_interface_version(0x12);
+        assert_eq!(0x12, msg.to_slice().interface_version());
+        msg.set_message_type(MessageType::Notification);
+        assert_eq!(MessageType::Notification, msg.to_slice().message_type());
+        msg.set_return_code(0x0b);
+        assert_eq!(0x0b, msg.to_slice().return_code());
+
+        // no tp header present
+        assert!(false == msg.set_tp_header(&TpHeader::new(true)));
+        assert_eq!(None, msg.to_slice().tp_header());
+
+        msg.payload_mut()[1] = 0xff;
+        assert_eq!(&[1, 0xff, 3], msg.into_payload_mut());
+        assert_eq!(
+            &[1, 0xff, 3],
+            SomeipMsgSlice::from_slice(&bytes).unwrap().payload()
+        );
+    }
+
+    #[test]
+    fn tp_setters() {
+        let mut bytes = msg_bytes(Some(TpHeader::new(false)), &[1, 2]);
+        let mut msg = SomeipMsgSliceMut::from_slice(&mut bytes).unwrap();
+        assert!(msg.is_tp());
+
+        // tp flag is preserved when changing the message type
+        msg.set_message_type(MessageType::Response);
+        assert_eq!(MessageType::Response, msg.to_slice().message_type());
+        assert!(msg.to_slice().is_tp());
+        assert_eq!(0x80 | SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG, msg.slice()[14]);
+
+        let tp = TpHeader::with_offset(32, true).unwrap();
+        assert!(msg.set_tp_header(&tp));
+        assert_eq!(Some(tp), msg.to_slice().tp_header());
+        assert_eq!(&[1, 2], msg.payload());
+    }
+}
@@ -0,0 +1,270 @@
+use crate::*;
+
+/// Writes SOMEIP messages (header, optional TP header & payload) into a
+/// caller provided buffer without allocating.
+///
+/// The length field of the header is always computed from the number of
+/// payload bytes actually written (the `length` value of the given
+/// [`SomeipHeader`] is ignored).
+///
+/// A complete message can be written with [`SomeipMsgWriter::write`]:
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+///     .build(0)
+///     .unwrap();
+/// let mut buffer = [0u8; 64];
+/// let len = SomeipMsgWriter::write(&header, &[1, 2, 3], &mut buffer).unwrap();
+///
+/// let msg = SomeipMsgSlice::from_slice(&buffer[..len]).unwrap();
+/// assert_eq!(&[1, 2, 3], msg.payload());
+/// ```
+///
+/// Alternatively the header can be written first and the payload filled
+/// in place afterwards (e.g. directly into a DMA buffer), in which case the
+/// length field gets patched when calling [`SomeipMsgWriter::finish`]:
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+///     .build(0)
+///     .unwrap();
+/// let mut buffer = [0u8; 64];
+///
+/// let mut writer = SomeipMsgWriter::new(&header, &mut buffer).unwrap();
+/// let payload = writer.payload_mut();
+/// payload[..2].copy_from_slice(&[0xab, 0xcd]);
+/// let len = writer.finish(2).unwrap();
+///
+/// let msg = SomeipMsgSlice::from_slice(&buffer[..len]).unwrap();
+/// assert_eq!(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 2, msg.length());
+/// assert_eq!(&[0xab, 0xcd], msg.payload());
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct SomeipMsgWriter<'a> {
+    buffer: &'a mut [u8],
+    /// Length of the SOMEIP header plus TP header (if present).
+    header_len: usize,
+}
+
+impl<'a> SomeipMsgWriter<'a> {
+    /// Maximum payload length (without TP header) that can be represented
+    /// by the length field of a SOMEIP header.
+    const MAX_PAYLOAD_LEN: u32 = u32::MAX - SOMEIP_LEN_OFFSET_TO_PAYLOAD;
+
+    /// Writes the header (and TP header if present) at the start of `buffer`
+    /// and returns a writer for filling the payload in place.
+    ///
+    /// Until [`SomeipMsgWriter::finish`] is called the length field is set
+    /// to a message without payload.
+    pub fn new(
+        header: &SomeipHeader,
+        buffer: &'a mut [u8],
+    ) -> Result<SomeipMsgWriter<'a>, err::SliceWriteSpaceError> {
+        if buffer.len() < SOMEIP_HEADER_LENGTH {
+            return Err(err::SliceWriteSpaceError {
+                required_len: SOMEIP_HEADER_LENGTH,
+                len: buffer.len(),
+                layer: err::Layer::SomeipHeader,
+            });
+        }
+        let header_len = if let Some(tp_header) = &header.tp_header {
+            const LEN: usize = SOMEIP_HEADER_LENGTH + TP_HEADER_LENGTH;
+            if buffer.len() < LEN {
+                return Err(err::SliceWriteSpaceError {
+                    required_len: LEN,
+                    len: buffer.len(),
+                    layer: err::Layer::SomeipTpHeader,
+                });
+            }
+            buffer[SOMEIP_HEADER_LENGTH..LEN].copy_from_slice(&tp_header.to_bytes());
+            LEN
+        } else {
+            SOMEIP_HEADER_LENGTH
+        };
+        buffer[..SOMEIP_HEADER_LENGTH].copy_from_slice(&header.base_to_bytes());
+
+        let mut result = SomeipMsgWriter { buffer, header_len };
+        result.set_payload_len(0);
+        Ok(result)
+    }
+
+    /// Writes a complete message (header, TP header if present & payload)
+    /// to the start of `buffer` and returns the number of bytes written.
+    pub fn write(
+        header: &SomeipHeader,
+        payload: &[u8],
+        buffer: &'a mut [u8],
+    ) -> Result<usize, err::SliceWriteSpaceError> {
+        let mut writer = SomeipMsgWriter::new(header, buffer)?;
+        let max_payload_len = writer.max_payload_len();
+        if payload.len() > max_payload_len {
+            return Err(writer.payload_space_error(payload.len()));
+        }
+        writer.payload_mut()[..payload.len()].copy_from_slice(payload);
+        writer.finish(payload.len())
+    }
+
+    /// Length of the written SOMEIP header plus the TP header (if present).
+    #[inline]
+    pub fn header_len(&self) -> usize {
+        self.header_len
+    }
+
+    /// Maximum number of payload bytes that can be written (limited by
+    /// the buffer size & the maximum value of the length field).
+    #[inline]
+    pub fn max_payload_len(&self) -> usize {
+        let max_len_field =
+            (Self::MAX_PAYLOAD_LEN as usize) - (self.header_len - SOMEIP_HEADER_LENGTH);
+        core::cmp::min(self.buffer.len() - self.header_len, max_len_field)
+    }
+
+    /// Returns the buffer space after the header that can be filled with
+    /// the payload.
+    #[inline]
+    pub fn payload_mut(&mut self) -> &mut [u8] {
+        let end = self.header_len + self.max_payload_len();
+        &mut self.buffer[self.header_len..end]
+    }
+
+    /// Sets the length field of the header to a message with `payload_len`
+    /// bytes of payload and returns the total length of the message
+    /// (headers & payload).
+    pub fn finish(mut self, payload_len: usize) -> Result<usize, err::SliceWriteSpaceError> {
+        if payload_len > self.max_payload_len() {
+            return Err(self.payload_space_error(payload_len));
+        }
+        self.set_payload_len(payload_len);
+        Ok(self.header_len + payload_len)
+    }
+
+    fn payload_space_error(&self, payload_len: usize) -> err::SliceWriteSpaceError {
+        err::SliceWriteSpaceError {
+            required_len: self.header_len.saturating_add(payload_len),
+            len: self.header_len + self.max_payload_len(),
+            layer: err::Layer::SomeipPayload,
+        }
+    }
+
+    /// Writes the length field (the caller has to ensure `payload_len`
+    /// does not exceed `max_payload_len`).
+    fn set_payload_len(&mut self, payload_len: usize) {
+        let length = (self.header_len - SOMEIP_HEADER_LENGTH + payload_len) as u32
+            + SOMEIP_LEN_OFFSET_TO_PAYLOAD;
+        self.buffer[4..8].copy_from_slice(&length.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proptest_generators::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn write(
+            header in someip_header_any(),
+            payload in proptest::collection::vec(any::<u8>(), 0..100),
+            extra_len in 0usize..10,
+        ) {
+            let header_len = SOMEIP_HEADER_LENGTH
+                + if header.tp_header.is_some() { TP_HEADER_LENGTH } else { 0 };
+            let total_len = header_len + payload.len();
+            let mut buffer = alloc::vec![0u8; total_len + extra_len];
+
+            let written = SomeipMsgWriter::write(&header, &payload, &mut buffer).unwrap();
+            assert_eq!(total_len, written);
+
+            let expected = {
+                let header = SomeipHeader {
+                    length: (total_len - 8) as u32,
+                    ..header.clone()
+                };
+                let mut bytes = header.base_to_bytes().to_vec();
+                if let Some(tp) = &header.tp_header {
+                    bytes.extend_from_slice(&tp.to_bytes());
+                }
+                bytes.extend_from_slice(&payload);
+                bytes
+            };
+            assert_eq!(&expected[..], &buffer[..written]);
+
+            // not enough space
+            for len in [0, SOMEIP_HEADER_LENGTH - 1, header_len - 1] {
+                let mut buffer = alloc::vec![0u8; len];
+                let err = SomeipMsgWriter::write(&header, &payload, &mut buffer).unwrap_err();
+                assert_eq!(len, err.len);
+                if len < SOMEIP_HEADER_LENGTH {
+                    assert_eq!(err.layer, err::Layer::SomeipHeader);
+                    assert_eq!(err.required_len, SOMEIP_HEADER_LENGTH);
+                } else {
+                    assert_eq!(err.layer, err::Layer::SomeipTpHeader);
+                    assert_eq!(err.required_len, header_len);
+                }
+            }
+            if false == payload.is_empty() {
+                let mut buffer = alloc::vec![0u8; total_len - 1];
+                assert_eq!(
+                    err::SliceWriteSpaceError {
+                        required_len: total_len,
+                        len: total_len - 1,
+                        layer: err::Layer::SomeipPayload,
+                    },
+                    SomeipMsgWriter::write(&header, &payload, &mut buffer).unwrap_err()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn in_place() {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 1234,
+            tp_header: Some(TpHeader::with_offset(32, true).unwrap()),
+            message_type: MessageType::Notification,
+            ..Default::default()
+        };
+        let mut buffer = [0u8; 30];
+        let mut writer = SomeipMsgWriter::new(&header, &mut buffer).unwrap();
+        assert_eq!(SOMEIP_HEADER_LENGTH + TP_HEADER_LENGTH, writer.header_len());
+        assert_eq!(10, writer.max_payload_len());
+        assert_eq!(10, writer.payload_mut().len());
+        writer.payload_mut()[..3].copy_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            Err(err::SliceWriteSpaceError {
+                required_len: 31,
+                len: 30,
+                layer: err::Layer::SomeipPayload,
+            }),
+            SomeipMsgWriter::new(&header, &mut [0u8; 30])
+                .unwrap()
+                .finish(11)
+        );
+        assert_eq!(23, writer.finish(3).unwrap());
+
+        let msg = SomeipMsgSlice::from_slice(&buffer[..23]).unwrap();
+        assert_eq!(SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 + 3, msg.length());
+        assert_eq!(header.tp_header, msg.tp_header());
+        assert_eq!(&[1, 2, 3], msg.payload());
+    }
+
+    #[test]
+    fn new_sets_empty_length() {
+        let header = SomeipHeader {
+            length: 1234,
+            ..Default::default()
+        };
+        let mut buffer = [0xffu8; 20];
+        SomeipMsgWriter::new(&header, &mut buffer).unwrap();
+        assert_eq!(
+            SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            SomeipMsgSlice::from_slice(&buffer).unwrap().length()
+        );
+    }
+}
@@ -63,7 +63,7 @@ mod tests {
             let mut buffer = Vec::new();
             for (message, payload) in expected.iter() {
                 message.write_raw(&mut buffer).unwrap();
-                buffer.write(&payload[..]).unwrap();
+                buffer.write_all(&payload[..]).unwrap();
             }
 
             //read message with iterator
@@ -93,7 +93,7 @@ mod tests {
             //serialize
             let mut buffer = Vec::new();
             packet.0.write_raw(&mut buffer).unwrap();
-            buffer.write(&packet.1[..]).unwrap();
+            buffer.write_all(&packet.1[..]).unwrap();
 
             //generate iterator
             let len = buffer.len();
@@ -104,12 +104,12 @@ mod tests {
             assert_eq!(iterator.next(), Some(Err(Len(LenError{
                 required_len: len,
                 len: len - 1,
-                len_source: if len - 1 >= SOMEIP_HEADER_LENGTH {
+                len_source: if len > SOMEIP_HEADER_LENGTH {
                     LenSource::SomeipHeaderLength
                 } else {
                     LenSource::Slice
                 },
-                layer: if len - 1 >= SOMEIP_HEADER_LENGTH {
+                layer: if len > SOMEIP_HEADER_LENGTH {
                     Layer::SomeipPayload
                 } else {
                     Layer::SomeipHeader
@@ -123,17 +123,35 @@ pub struct TpBuf {
     end: Option<u32>,
     /// TP config
     config: TpBufConfig,
+    /// Number of segments whose data was completely received before.
+    duplicate_count: u64,
+    /// Number of segments whose data was partially received before.
+    overlap_count: u64,
+    /// SOMEIP header of the first received segment (used for the strict
+    /// header checks).
+    first_header: Option<[u8; SOMEIP_HEADER_LENGTH]>,
+    /// Number of successfully consumed segments.
+    segment_count: u64,
 }
 
 impl TpBuf {
     pub fn new(config: TpBufConfig) -> TpBuf {
+        let alloc_len = SOMEIP_HEADER_LENGTH + config.tp_buffer_start_payload_alloc_len;
+        TpBuf::with_alloc_len(config, alloc_len)
+    }
+
+    /// Creates a buffer with the given number of bytes allocated (instead
+    /// of the start allocation length of the config).
+    pub(crate) fn with_alloc_len(config: TpBufConfig, alloc_len: usize) -> TpBuf {
         TpBuf {
-            data: Vec::with_capacity(
-                SOMEIP_HEADER_LENGTH + config.tp_buffer_start_payload_alloc_len,
-            ),
+            data: Vec::with_capacity(alloc_len),
             sections: Vec::with_capacity(4),
             end: None,
             config,
+            duplicate_count: 0,
+            overlap_count: 0,
+            first_header: None,
+            segment_count: 0,
         }
     }
 
@@ -142,88 +160,202 @@ impl TpBuf {
         self.data.clear();
         self.sections.clear();
         self.end = None;
+        self.duplicate_count = 0;
+        self.overlap_count = 0;
+        self.first_header = None;
+        self.segment_count = 0;
     }
 
-    /// Consume a TP SOMEIP slice (caller must ensure that `someip_slice.is_tp()` is `true`).
-    #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
-    pub fn consume_tp(
+    /// Ranges of the payload received so far (not sorted).
+    #[inline]
+    pub fn received_ranges(&self) -> &[TpRange] {
+        &self.sections
+    }
+
+    /// End of the payload, known once the segment without the "more
+    /// segments" flag was received.
+    #[inline]
+    pub fn expected_end(&self) -> Option<u32> {
+        self.end
+    }
+
+    /// Number of payload bytes received so far (bytes received multiple
+    /// times are only counted once).
+    pub fn received_len(&self) -> u32 {
+        // sections are merged on insertion, so they never overlap
+        self.sections.iter().map(|s| s.end - s.start).sum()
+    }
+
+    /// Ranges of the payload that were not yet received (sorted by offset).
+    ///
+    /// If the end is not yet known only the gaps before the highest
+    /// received offset are returned.
+    pub fn missing_ranges(&self) -> Vec<TpRange> {
+        let mut sorted = self.sections.clone();
+        sorted.sort_unstable();
+        let mut result = Vec::new();
+        let mut pos = 0;
+        for section in sorted {
+            if pos < section.start {
+                result.push(TpRange {
+                    start: pos,
+                    end: section.start,
+                });
+            }
+            pos = core::cmp::max(pos, section.end);
+        }
+        if let Some(end) = self.end {
+            if pos < end {
+                result.push(TpRange { start: pos, end });
+            }
+        }
+        result
+    }
+
+    /// Number of successfully consumed segments.
+    #[inline]
+    pub fn segment_count(&self) -> u64 {
+        self.segment_count
+    }
+
+    /// Returns the reassembly progress (received & missing data).
+    pub fn progress(&self) -> TpBufProgress {
+        TpBufProgress {
+            received_len: self.received_len(),
+            expected_end: self.end,
+            segment_count: self.segment_count,
+            missing_ranges: self.missing_ranges(),
+        }
+    }
+
+    /// Number of bytes currently used to store the SOMEIP header and the
+    /// payload received so far (up to the highest received offset).
+    #[inline]
+    pub fn buffered_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Number of bytes currently allocated to store the SOMEIP header and
+    /// the payload (including memory reserved for not yet received data).
+    #[inline]
+    pub fn allocated_len(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Changes the allocation so exactly `alloc_len` bytes (but at least
+    /// [`TpBuf::buffered_len`] bytes) are allocated.
+    #[cfg(feature = "std")]
+    pub(crate) fn try_set_alloc_len(
         &mut self,
-        someip_slice: SomeipMsgSlice,
+        alloc_len: usize,
     ) -> Result<(), err::TpReassembleError> {
-        use err::TpReassembleError::*;
+        let alloc_len = core::cmp::max(alloc_len, self.data.len());
+        if alloc_len < self.data.capacity() {
+            self.data.shrink_to(alloc_len);
+        } else if self
+            .data
+            .try_reserve_exact(alloc_len - self.data.len())
+            .is_err()
+        {
+            return Err(err::TpReassembleError::AllocationFailure { len: alloc_len });
+        }
+        Ok(())
+    }
 
-        assert!(someip_slice.is_tp());
+    /// Makes sure enough memory is allocated to grow the buffer to
+    /// `required_len` bytes without a further allocation.
+    pub(crate) fn try_reserve_len(
+        &mut self,
+        required_len: usize,
+    ) -> Result<(), err::TpReassembleError> {
+        if self.data.len() < required_len
+            && self
+                .data
+                .try_reserve(required_len - self.data.len())
+                .is_err()
+        {
+            return Err(err::TpReassembleError::AllocationFailure { len: required_len });
+        }
+        Ok(())
+    }
 
-        // validate lengths
-        let tp_header = someip_slice.tp_header().unwrap();
-        let payload = someip_slice.payload();
+    /// Number of consumed segments whose data was completely received
+    /// before (including segments rejected because of differing bytes).
+    #[inline]
+    pub fn duplicate_count(&self) -> u64 {
+        self.duplicate_count
+    }
+
+    /// Number of consumed segments whose data was partially received
+    /// before (including segments rejected because of differing bytes).
+    #[inline]
+    pub fn overlap_count(&self) -> u64 {
+        self.overlap_count
+    }
+
+    /// Checks that the TP SOMEIP slice would be accepted by
+    /// [`TpBuf::consume_tp`] (without changing the buffer).
+    #[cfg(feature = "std")]
+    pub(crate) fn check_tp<'a>(
+        &self,
+        someip_slice: &SomeipMsgSlice<'a>,
+    ) -> Result<TpSegment<'a>, err::TpReassembleError> {
+        let segment = self.check_segment(someip_slice)?;
+        segment.check_conflicts(&self.data, &self.sections, self.config.overlap_policy)?;
+        Ok(segment)
+    }
 
+    /// Validates the segment against the config, the end & the header
+    /// of the stream.
+    fn check_segment<'a>(
+        &self,
+        someip_slice: &SomeipMsgSlice<'a>,
+    ) -> Result<TpSegment<'a>, err::TpReassembleError> {
         // should be guranteed by config constructor
         debug_assert!(self.config.tp_max_payload_len() <= u32::MAX - (SOMEIP_HEADER_LENGTH as u32));
-        if (self.config.tp_max_payload_len() < tp_header.offset())
-            || ((self.config.tp_max_payload_len() - tp_header.offset()) as usize) < payload.len()
-        {
-            return Err(SegmentTooBig {
-                offset: tp_header.offset(),
-                payload_len: payload.len(),
-                max: self.config.tp_max_payload_len(),
-            });
-        }
-
-        // validate that the payload len is a multiple of 16 in case it is not the end
-        if tp_header.more_segment && 0 != payload.len() & 0b1111 {
-            return Err(UnalignedTpPayloadLen {
-                offset: tp_header.offset(),
-                payload_len: payload.len(),
-            });
-        }
-
-        let end = tp_header.offset() + payload.len() as u32;
-
-        // check the section is not already ended
-        if let Some(previous_end) = self.end {
-            // either the end is after the current position
-            if previous_end < end || ((false == tp_header.more_segment) && end != previous_end) {
-                return Err(ConflictingEnd {
-                    previous_end,
-                    conflicting_end: end,
-                });
+        let segment =
+            TpSegment::from_slice(someip_slice, self.config.tp_max_payload_len(), self.end)?;
+
+        // check the header against the first received segment (segments
+        // received before the offset 0 segment are validated retroactively
+        // as the offset 0 segment gets checked against them)
+        if self.config.strict_header_check {
+            if let Some(first_header) = &self.first_header {
+                segment.check_header(first_header)?;
             }
         }
+        Ok(segment)
+    }
 
-        // get enough memory to store a SOMEIP header + tp reassembled payload
-        let required_len = SOMEIP_HEADER_LENGTH + (tp_header.offset() as usize) + payload.len();
-        if self.data.len() < required_len {
-            if self.data.capacity() < required_len
-                && self
-                    .data
-                    .try_reserve(required_len - self.data.len())
-                    .is_err()
-            {
-                return Err(AllocationFailure { len: required_len });
-            }
-            unsafe {
-                self.data.set_len(required_len);
-            }
+    /// Consume a TP SOMEIP slice (caller must ensure that `someip_slice.is_tp()` is `true`).
+    #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+    pub fn consume_tp(
+        &mut self,
+        someip_slice: SomeipMsgSlice,
+    ) -> Result<(), err::TpReassembleError> {
+        let segment = self.check_segment(&someip_slice)?;
+
+        // count & check the overlap with the already received data before
+        // the buffer gets changed (rejected segments leave it untouched)
+        match segment.overlap(&self.sections) {
+            TpSegmentOverlap::None => {}
+            TpSegmentOverlap::Duplicate => self.duplicate_count += 1,
+            TpSegmentOverlap::Overlap => self.overlap_count += 1,
         }
+        segment.check_conflicts(&self.data, &self.sections, self.config.overlap_policy)?;
 
-        if 0 == tp_header.offset() {
-            // copy header
-            self.data[..SOMEIP_HEADER_LENGTH]
-                .copy_from_slice(&someip_slice.slice()[..SOMEIP_HEADER_LENGTH]);
-            // remove TP flag
-            self.data[4 * 3 + 2] &= 0b1101_1111;
+        // get enough memory to store a SOMEIP header + tp reassembled payload
+        let required_len = SOMEIP_HEADER_LENGTH + (segment.end() as usize);
+        self.try_reserve_len(required_len)?;
+        if self.data.len() < required_len {
+            self.data.resize(required_len, 0);
         }
 
         // insert new data
-        let data_offset = SOMEIP_HEADER_LENGTH + (tp_header.offset() as usize);
-        self.data[data_offset..data_offset + payload.len()].copy_from_slice(payload);
+        segment.write_to(&mut self.data, &self.sections, self.config.overlap_policy)?;
 
         // update sections
-        let mut new_section = TpRange {
-            start: tp_header.offset(),
-            end: tp_header.offset() + (payload.len() as u32),
-        };
+        let mut new_section = segment.range();
         // merge overlapping section into new section and remove them
         self.sections.retain(|it| -> bool {
             if let Some(merged) = new_section.merge(*it) {
@@ -236,8 +368,15 @@ impl TpBuf {
         self.sections.push(new_section);
 
         // set end
-        if false == tp_header.more_segment {
-            self.end = Some(tp_header.offset() + payload.len() as u32);
+        if false == segment.more_segments {
+            self.end = Some(segment.end());
+        }
+
+        self.segment_count += 1;
+        if self.first_header.is_none() {
+            let mut header = [0u8; SOMEIP_HEADER_LENGTH];
+            header.copy_from_slice(segment.header);
+            self.first_header = Some(header);
         }
 
         Ok(())
@@ -270,7 +409,7 @@ impl TpBuf {
 #[cfg(test)]
 mod test {
     use crate::*;
-    use alloc::{format, vec::Vec};
+    use alloc::{format, vec, vec::Vec};
 
     #[test]
     fn debug_clone_eq() {
@@ -306,7 +445,7 @@ mod test {
             TestPacket {
                 offset,
                 more_segments,
-                payload: payload.iter().copied().collect(),
+                payload: payload.to_vec(),
             }
         }
 
@@ -513,6 +652,253 @@ mod test {
         }
     }
 
+    #[test]
+    fn overlap_policy() {
+        use err::TpReassembleError::*;
+
+        let config = |overlap_policy| {
+            let mut config = TpBufConfig::new(1024, 2048).unwrap();
+            config.overlap_policy = overlap_policy;
+            config
+        };
+
+        // accept last (overwrites)
+        {
+            let mut buffer = TpBuf::new(config(TpOverlapPolicy::AcceptLast));
+            TestPacket::new(0, true, &[1; 32])
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            TestPacket::new(16, false, &[2; 32])
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            assert_eq!(
+                &[[1u8; 16], [2u8; 16], [2u8; 16]].concat()[..],
+                buffer.try_finalize().unwrap().payload()
+            );
+        }
+
+        // accept first (only fills gaps)
+        {
+            let mut buffer = TpBuf::new(config(TpOverlapPolicy::AcceptFirst));
+            TestPacket::new(16, true, &[1; 16])
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            TestPacket::new(0, false, &[2; 48])
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            TestPacket::new(0, false, &[3; 48])
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            let result = buffer.try_finalize().unwrap();
+            assert_eq!(result.to_header(), TestPacket::result_header(48));
+            assert_eq!(
+                &[[2u8; 16], [1u8; 16], [2u8; 16]].concat()[..],
+                result.payload()
+            );
+        }
+
+        // reject conflicting
+        {
+            let mut buffer = TpBuf::new(config(TpOverlapPolicy::RejectConflicting));
+            TestPacket::new(0, true, &sequence(0, 32))
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            // identical overlapping bytes are accepted
+            TestPacket::new(16, true, &sequence(16, 32))
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            let mut conflicting = sequence(32, 32);
+            conflicting[3] = 0xff;
+            let buffered_len = buffer.buffered_len();
+            assert_eq!(
+                Err(ConflictingOverlap {
+                    offset: 32,
+                    payload_len: 32,
+                    conflict_offset: 35
+                }),
+                TestPacket::new(32, false, &conflicting).send_to_buffer(&mut buffer)
+            );
+            // rejected segments leave the buffer unchanged
+            assert!(false == buffer.is_complete());
+            assert_eq!(buffered_len, buffer.buffered_len());
+            TestPacket::new(48, false, &sequence(48, 16))
+                .send_to_buffer(&mut buffer)
+                .unwrap();
+            assert_eq!(
+                &sequence(0, 64)[..],
+                buffer.try_finalize().unwrap().payload()
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_overlap_count() {
+        let mut buffer = TpBuf::new(TpBufConfig::new(1024, 2048).unwrap());
+        TestPacket::new(16, true, &sequence(16, 16))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        // adjacent segments are not overlapping
+        TestPacket::new(32, true, &sequence(32, 16))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        assert_eq!((0, 0), (buffer.duplicate_count(), buffer.overlap_count()));
+
+        TestPacket::new(16, true, &sequence(16, 32))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        TestPacket::new(32, true, &sequence(32, 16))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        assert_eq!((2, 0), (buffer.duplicate_count(), buffer.overlap_count()));
+
+        TestPacket::new(0, false, &sequence(0, 32))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        assert_eq!((2, 1), (buffer.duplicate_count(), buffer.overlap_count()));
+
+        buffer.clear();
+        assert_eq!((0, 0), (buffer.duplicate_count(), buffer.overlap_count()));
+    }
+
+    #[test]
+    fn strict_header_check() {
+        use err::TpReassembleError::*;
+
+        let send = |buffer: &mut TpBuf, offset: u32, more: bool, patch: (usize, u8)| {
+            let mut packet = TestPacket::new(offset, more, &sequence(offset as usize, 16)).to_vec();
+            packet[patch.0] = patch.1;
+            buffer.consume_tp(SomeipMsgSlice::from_slice(&packet).unwrap())
+        };
+        let strict_buffer = || {
+            let mut config = TpBufConfig::new(1024, 2048).unwrap();
+            config.strict_header_check = true;
+            TpBuf::new(config)
+        };
+
+        // mismatching fields (checked against the first received segment,
+        // even if the offset 0 segment arrives later)
+        for (offset, patch, expected) in [
+            (
+                0,
+                (3, 0xd3),
+                MessageIdMismatch {
+                    expected: 1234,
+                    actual: 1235,
+                },
+            ),
+            (
+                16,
+                (13, 2),
+                InterfaceVersionMismatch {
+                    expected: 1,
+                    actual: 2,
+                },
+            ),
+            (
+                0,
+                (14, 0x20),
+                MessageTypeMismatch {
+                    expected: 0x02,
+                    actual: 0x00,
+                },
+            ),
+            (
+                16,
+                (15, 1),
+                ReturnCodeMismatch {
+                    expected: 0,
+                    actual: 1,
+                },
+            ),
+        ] {
+            let mut buffer = strict_buffer();
+            // unchanged byte of the header (message id lower byte is 1234 & 0xff)
+            send(&mut buffer, 32, false, (3, 0xd2)).unwrap();
+            assert_eq!(Err(expected), send(&mut buffer, offset, true, patch));
+        }
+
+        // matching segments get reassembled
+        {
+            let mut buffer = strict_buffer();
+            send(&mut buffer, 16, false, (3, 0xd2)).unwrap();
+            send(&mut buffer, 0, true, (3, 0xd2)).unwrap();
+            assert_eq!(
+                buffer.try_finalize().unwrap().to_header(),
+                TestPacket::result_header(32)
+            );
+            // first header is reset on clear
+            buffer.clear();
+            send(&mut buffer, 0, true, (13, 2)).unwrap();
+        }
+
+        // no checks if not enabled
+        {
+            let mut buffer = TpBuf::new(TpBufConfig::new(1024, 2048).unwrap());
+            send(&mut buffer, 16, false, (15, 1)).unwrap();
+            send(&mut buffer, 0, true, (3, 0xd3)).unwrap();
+            assert!(buffer.is_complete());
+        }
+    }
+
+    #[test]
+    fn progress() {
+        let mut buffer = TpBuf::new(TpBufConfig::new(1024, 2048).unwrap());
+        assert_eq!(
+            TpBufProgress {
+                received_len: 0,
+                expected_end: None,
+                segment_count: 0,
+                missing_ranges: Vec::new(),
+            },
+            buffer.progress()
+        );
+
+        // end unknown (only gaps before the highest received offset)
+        TestPacket::new(64, true, &sequence(64, 16))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        TestPacket::new(16, true, &sequence(16, 16))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        TestPacket::new(16, true, &sequence(16, 32))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        assert_eq!(48, buffer.received_len());
+        assert_eq!(3, buffer.segment_count());
+        assert_eq!(
+            vec![
+                TpRange { start: 0, end: 16 },
+                TpRange { start: 48, end: 64 }
+            ],
+            buffer.missing_ranges()
+        );
+
+        // end known
+        TestPacket::new(96, false, &sequence(96, 4))
+            .send_to_buffer(&mut buffer)
+            .unwrap();
+        let progress = buffer.progress();
+        assert_eq!(Some(100), progress.expected_end);
+        assert_eq!(
+            vec![
+                TpRange { start: 0, end: 16 },
+                TpRange { start: 48, end: 64 },
+                TpRange { start: 80, end: 96 }
+            ],
+            progress.missing_ranges
+        );
+
+        // errors are not counted as segments
+        assert!(TestPacket::new(0, true, &sequence(0, 15))
+            .send_to_buffer(&mut buffer)
+            .is_err());
+        assert_eq!(4, buffer.segment_count());
+
+        buffer.clear();
+        assert_eq!(0, buffer.segment_count());
+        assert!(buffer.missing_ranges().is_empty());
+    }
+
     #[test]
     fn try_finalize() {
         let mut buffer = TpBuf::new(TpBufConfig::new(1024, 2048).unwrap());
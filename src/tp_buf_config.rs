@@ -9,6 +9,15 @@ pub struct TpBufConfig {
 
     /// Maximum allowed payload length (in bytes) of the final reconstructed packet.
     tp_max_payload_len: u32,
+
+    /// Handling of segments overlapping already received data.
+    pub overlap_policy: TpOverlapPolicy,
+
+    /// If set to `true` all segments must have the same message id,
+    /// interface version, message type & return code as the first received
+    /// segment (segments received before the segment with offset 0 are
+    /// checked against it once it arrives).
+    pub strict_header_check: bool,
 }
 
 impl TpBufConfig {
@@ -72,6 +81,8 @@ impl TpBufConfig {
             Ok(TpBufConfig {
                 tp_buffer_start_payload_alloc_len,
                 tp_max_payload_len,
+                overlap_policy: TpOverlapPolicy::AcceptLast,
+                strict_header_check: false,
             })
         }
     }
@@ -82,6 +93,8 @@ impl core::default::Default for TpBufConfig {
         Self {
             tp_buffer_start_payload_alloc_len: 0x40000,
             tp_max_payload_len: TpBufConfig::MAX_TP_PAYLOAD_LEN,
+            overlap_policy: TpOverlapPolicy::AcceptLast,
+            strict_header_check: false,
         }
     }
 }
@@ -123,6 +136,8 @@ mod test {
         let actual: TpBufConfig = Default::default();
         assert_eq!(0x40000, actual.tp_buffer_start_payload_alloc_len);
         assert_eq!(TpBufConfig::MAX_TP_PAYLOAD_LEN, actual.tp_max_payload_len);
+        assert_eq!(TpOverlapPolicy::AcceptLast, actual.overlap_policy);
+        assert!(false == actual.strict_header_check);
     }
 
     #[test]
@@ -139,6 +154,8 @@ mod test {
             .unwrap();
             assert_eq!(actual.tp_buffer_start_payload_alloc_len, 1024);
             assert_eq!(actual.tp_max_payload_len(), TpBufConfig::MAX_TP_PAYLOAD_LEN);
+            assert_eq!(actual.overlap_policy, TpOverlapPolicy::AcceptLast);
+            assert!(false == actual.strict_header_check);
         }
 
         use crate::err::TpBufConfigError::*;
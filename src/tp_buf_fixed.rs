@@ -0,0 +1,348 @@
+use crate::*;
+use arrayvec::ArrayVec;
+
+/// Default number of non contiguous received ranges a [`TpBufFixed`]
+/// can keep track of.
+pub const TP_BUF_FIXED_DEFAULT_SECTIONS: usize = 8;
+
+/// Allocation free buffer to reconstruct one SOMEIP TP packet stream
+/// (for `no_std` targets without a heap).
+///
+/// Works like [`TpBuf`] (same validation rules & same caller
+/// responsibilities regarding the message id & request id) but stores the
+/// SOMEIP header & reassembled payload in an array of `N` bytes and keeps
+/// track of at most `SECTIONS` non contiguous received ranges.
+///
+/// The maximum payload length that can be reassembled is
+/// `N - SOMEIP_HEADER_LENGTH`, bigger segments are rejected with
+/// [`err::TpReassembleError::SegmentTooBig`]. If segments arrive so out of
+/// order that more than `SECTIONS` separate ranges would be needed
+/// [`err::TpReassembleError::SectionsFull`] is returned.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::*;
+///
+/// // build two segments
+/// let mut segments = [[0u8; SOMEIP_HEADER_LENGTH + 4 + 16]; 2];
+/// for (i, segment) in segments.iter_mut().enumerate() {
+///     let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+///         .tp_header(TpHeader::with_offset(16 * i as u32, i == 0).unwrap())
+///         .build(16)
+///         .unwrap();
+///     SomeipMsgWriter::write(&header, &[i as u8; 16], segment).unwrap();
+/// }
+///
+/// // up to 1024 - 16 bytes of payload can be reassembled
+/// let mut buf = TpBufFixed::<1024>::new();
+/// for segment in &segments {
+///     buf.consume_tp(SomeipMsgSlice::from_slice(segment).unwrap()).unwrap();
+/// }
+/// let reassembled = buf.try_finalize().unwrap();
+/// assert!(false == reassembled.is_tp());
+/// assert_eq!(32, reassembled.payload().len());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TpBufFixed<const N: usize, const SECTIONS: usize = TP_BUF_FIXED_DEFAULT_SECTIONS> {
+    /// Data buffer that should contain the SOMEIP header + reconstructed payload in the end.
+    data: [u8; N],
+    /// Contains the ranges filled with data.
+    sections: ArrayVec<TpRange, SECTIONS>,
+    /// Set to the extended end size.
+    end: Option<u32>,
+    /// Handling of segments overlapping already received data.
+    overlap_policy: TpOverlapPolicy,
+    /// Number of segments whose data was completely received before.
+    duplicate_count: u64,
+    /// Number of segments whose data was partially received before.
+    overlap_count: u64,
+}
+
+impl<const N: usize, const SECTIONS: usize> TpBufFixed<N, SECTIONS> {
+    /// Maximum payload length that can be reassembled in the buffer.
+    pub const MAX_PAYLOAD_LEN: u32 = {
+        let len = N.saturating_sub(SOMEIP_HEADER_LENGTH);
+        if len > TpBufConfig::MAX_TP_PAYLOAD_LEN as usize {
+            TpBufConfig::MAX_TP_PAYLOAD_LEN
+        } else {
+            len as u32
+        }
+    };
+
+    pub const fn new() -> TpBufFixed<N, SECTIONS> {
+        TpBufFixed::with_overlap_policy(TpOverlapPolicy::AcceptLast)
+    }
+
+    /// Creates a buffer handling segments overlapping already received
+    /// data based on the given policy.
+    pub const fn with_overlap_policy(overlap_policy: TpOverlapPolicy) -> TpBufFixed<N, SECTIONS> {
+        TpBufFixed {
+            data: [0u8; N],
+            sections: ArrayVec::new_const(),
+            end: None,
+            overlap_policy,
+            duplicate_count: 0,
+            overlap_count: 0,
+        }
+    }
+
+    #[inline]
+    pub fn overlap_policy(&self) -> TpOverlapPolicy {
+        self.overlap_policy
+    }
+
+    #[inline]
+    pub fn set_overlap_policy(&mut self, overlap_policy: TpOverlapPolicy) {
+        self.overlap_policy = overlap_policy;
+    }
+
+    /// Number of consumed segments whose data was completely received
+    /// before (including rejected segments).
+    #[inline]
+    pub fn duplicate_count(&self) -> u64 {
+        self.duplicate_count
+    }
+
+    /// Number of consumed segments whose data was partially received
+    /// before (including rejected segments).
+    #[inline]
+    pub fn overlap_count(&self) -> u64 {
+        self.overlap_count
+    }
+
+    /// Reset buffer to starting state (the overlap policy is kept).
+    pub fn clear(&mut self) {
+        self.sections.clear();
+        self.end = None;
+        self.duplicate_count = 0;
+        self.overlap_count = 0;
+    }
+
+    /// Consume a TP SOMEIP slice (caller must ensure that `someip_slice.is_tp()` is `true`).
+    pub fn consume_tp(
+        &mut self,
+        someip_slice: SomeipMsgSlice,
+    ) -> Result<(), err::TpReassembleError> {
+        let segment = TpSegment::from_slice(&someip_slice, Self::MAX_PAYLOAD_LEN, self.end)?;
+
+        // merge overlapping sections into the new section (done before
+        // copying the data so a full section list leaves the buffer untouched)
+        let mut new_section = segment.range();
+        let mut merged_count = 0;
+        for it in self.sections.iter() {
+            if let Some(merged) = new_section.merge(*it) {
+                new_section = merged;
+                merged_count += 1;
+            }
+        }
+        if 0 == merged_count && self.sections.is_full() {
+            return Err(err::TpReassembleError::SectionsFull {
+                max_sections: SECTIONS,
+            });
+        }
+
+        // insert new data
+        match segment.overlap(&self.sections) {
+            TpSegmentOverlap::None => {}
+            TpSegmentOverlap::Duplicate => self.duplicate_count += 1,
+            TpSegmentOverlap::Overlap => self.overlap_count += 1,
+        }
+        segment.write_to(&mut self.data, &self.sections, self.overlap_policy)?;
+
+        self.sections.retain(|it| new_section.merge(*it).is_none());
+        self.sections.push(new_section);
+
+        // set end
+        if false == segment.more_segments {
+            self.end = Some(segment.end());
+        }
+
+        Ok(())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.end.is_some() && 1 == self.sections.len() && 0 == self.sections[0].start
+    }
+
+    /// Try finalizing the reconstructed TP packet and return a reference to it
+    /// if the stream reconstruction was completed.
+    pub fn try_finalize(&mut self) -> Option<SomeipMsgSlice<'_>> {
+        if false == self.is_complete() {
+            return None;
+        }
+        // reinject length into fake header
+        let section = self.sections[0];
+        self.data[4..8].copy_from_slice(&(section.end + 8).to_be_bytes());
+        Some(
+            SomeipMsgSlice::from_slice(&self.data[..SOMEIP_HEADER_LENGTH + section.end as usize])
+                .unwrap(),
+        )
+    }
+}
+
+impl<const N: usize, const SECTIONS: usize> Default for TpBufFixed<N, SECTIONS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use alloc::{format, vec::Vec};
+    use err::TpReassembleError::*;
+
+    fn packet(offset: u32, more_segments: bool, payload: &[u8]) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id: 1234,
+            length: 8 + 4 + payload.len() as u32,
+            request_id: 23,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: Some(TpHeader::with_offset(offset, more_segments).unwrap()),
+        };
+        let mut result = Vec::with_capacity(SOMEIP_HEADER_LENGTH + 4 + payload.len());
+        result.extend_from_slice(&header.base_to_bytes());
+        result.extend_from_slice(&header.tp_header.as_ref().unwrap().to_bytes());
+        result.extend_from_slice(payload);
+        result
+    }
+
+    fn send<const N: usize, const S: usize>(
+        buf: &mut TpBufFixed<N, S>,
+        offset: u32,
+        more_segments: bool,
+        payload: &[u8],
+    ) -> Result<(), err::TpReassembleError> {
+        let packet = packet(offset, more_segments, payload);
+        buf.consume_tp(SomeipMsgSlice::from_slice(&packet).unwrap())
+    }
+
+    /// Returns a u8 vec counting up from "start" until len is reached (truncating bits greater then u8).
+    fn sequence(start: usize, len: usize) -> Vec<u8> {
+        (start..start + len).map(|i| (i & 0xff) as u8).collect()
+    }
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let buf = TpBufFixed::<32>::default();
+        let _ = format!("{:?}", buf);
+        assert_eq!(buf, buf.clone());
+        assert_eq!(buf, TpBufFixed::<32>::new());
+        assert_eq!(16, TpBufFixed::<32>::MAX_PAYLOAD_LEN);
+        assert_eq!(0, TpBufFixed::<8>::MAX_PAYLOAD_LEN);
+    }
+
+    #[test]
+    fn consume() {
+        // out of order reconstruction
+        let mut buf = TpBufFixed::<{ 16 + 64 }>::new();
+        send(&mut buf, 48, false, &sequence(48, 16)).unwrap();
+        assert!(buf.try_finalize().is_none());
+        send(&mut buf, 0, true, &sequence(0, 16)).unwrap();
+        assert!(false == buf.is_complete());
+        send(&mut buf, 16, true, &sequence(16, 32)).unwrap();
+        assert!(buf.is_complete());
+        {
+            let result = buf.try_finalize().unwrap();
+            assert_eq!(
+                SomeipHeader {
+                    message_id: 1234,
+                    length: 8 + 64,
+                    request_id: 23,
+                    interface_version: 1,
+                    message_type: MessageType::Notification,
+                    return_code: 0,
+                    tp_header: None,
+                },
+                result.to_header()
+            );
+            assert_eq!(result.payload(), &sequence(0, 64));
+        }
+
+        // re-use after clear
+        buf.clear();
+        assert!(buf.try_finalize().is_none());
+        send(&mut buf, 0, false, &sequence(1, 3)).unwrap();
+        assert_eq!(buf.try_finalize().unwrap().payload(), &sequence(1, 3));
+    }
+
+    #[test]
+    fn consume_errors() {
+        let mut buf = TpBufFixed::<{ 16 + 32 }>::new();
+
+        // shared validation with TpBuf
+        assert_eq!(
+            Err(SegmentTooBig {
+                offset: 16,
+                payload_len: 32,
+                max: 32
+            }),
+            send(&mut buf, 16, true, &sequence(0, 32))
+        );
+        assert_eq!(
+            Err(UnalignedTpPayloadLen {
+                offset: 0,
+                payload_len: 15
+            }),
+            send(&mut buf, 0, true, &sequence(0, 15))
+        );
+        send(&mut buf, 0, false, &sequence(0, 16)).unwrap();
+        assert_eq!(
+            Err(ConflictingEnd {
+                previous_end: 16,
+                conflicting_end: 32
+            }),
+            send(&mut buf, 16, true, &sequence(16, 16))
+        );
+    }
+
+    #[test]
+    fn overlap_policy() {
+        let mut buf =
+            TpBufFixed::<{ 16 + 64 }>::with_overlap_policy(TpOverlapPolicy::RejectConflicting);
+        assert_eq!(TpOverlapPolicy::RejectConflicting, buf.overlap_policy());
+        send(&mut buf, 0, true, &sequence(0, 32)).unwrap();
+        assert_eq!(
+            Err(ConflictingOverlap {
+                offset: 16,
+                payload_len: 16,
+                conflict_offset: 16
+            }),
+            send(&mut buf, 16, true, &[0; 16])
+        );
+        assert_eq!((1, 0), (buf.duplicate_count(), buf.overlap_count()));
+
+        buf.set_overlap_policy(TpOverlapPolicy::AcceptFirst);
+        send(&mut buf, 16, false, &[0; 32]).unwrap();
+        assert_eq!((1, 1), (buf.duplicate_count(), buf.overlap_count()));
+        assert_eq!(
+            &[&sequence(0, 32)[..], &[0; 16]].concat()[..],
+            buf.try_finalize().unwrap().payload()
+        );
+
+        // clear keeps the policy but resets the counters
+        buf.clear();
+        assert_eq!(TpOverlapPolicy::AcceptFirst, buf.overlap_policy());
+        assert_eq!((0, 0), (buf.duplicate_count(), buf.overlap_count()));
+    }
+
+    #[test]
+    fn sections_full() {
+        let mut buf = TpBufFixed::<{ 16 + 16 * 8 }, 2>::new();
+        send(&mut buf, 16, true, &sequence(16, 16)).unwrap();
+        send(&mut buf, 48, true, &sequence(48, 16)).unwrap();
+        assert_eq!(
+            Err(SectionsFull { max_sections: 2 }),
+            send(&mut buf, 80, true, &sequence(80, 16))
+        );
+        // adjacent segments can still be merged
+        send(&mut buf, 32, true, &sequence(32, 16)).unwrap();
+        send(&mut buf, 64, true, &sequence(64, 16)).unwrap();
+        send(&mut buf, 0, true, &sequence(0, 16)).unwrap();
+        send(&mut buf, 80, false, &sequence(80, 16)).unwrap();
+        assert_eq!(buf.try_finalize().unwrap().payload(), &sequence(0, 16 * 6));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::*;
+use alloc::vec::Vec;
+
+/// Reassembly progress of a [`TpBuf`] (e.g. to diagnose why a TP stream
+/// never completed).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TpBufProgress {
+    /// Number of payload bytes received so far (bytes received multiple
+    /// times are only counted once).
+    pub received_len: u32,
+
+    /// End of the payload, known once the segment without the "more
+    /// segments" flag was received.
+    pub expected_end: Option<u32>,
+
+    /// Number of successfully consumed segments.
+    pub segment_count: u64,
+
+    /// Ranges of the payload that were not yet received (sorted by offset).
+    ///
+    /// If the end is not yet known only the gaps before the highest
+    /// received offset are contained.
+    pub missing_ranges: Vec<TpRange>,
+}
+
+impl TpBufProgress {
+    /// Returns true if all payload bytes were received.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.expected_end == Some(self.received_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn debug_clone_eq() {
+        let progress = TpBufProgress {
+            received_len: 16,
+            expected_end: None,
+            segment_count: 1,
+            missing_ranges: vec![TpRange { start: 0, end: 16 }],
+        };
+        let _ = format!("{:?}", progress);
+        assert_eq!(progress, progress.clone());
+    }
+
+    #[test]
+    fn is_complete() {
+        let progress = |received_len, expected_end| TpBufProgress {
+            received_len,
+            expected_end,
+            segment_count: 1,
+            missing_ranges: Vec::new(),
+        };
+        assert!(false == progress(16, None).is_complete());
+        assert!(false == progress(16, Some(32)).is_complete());
+        assert!(progress(32, Some(32)).is_complete());
+    }
+}
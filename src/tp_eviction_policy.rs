@@ -0,0 +1,24 @@
+/// Policy deciding which TP stream gets dropped when a new stream has to
+/// be started but no buffer is available anymore.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TpEvictionPolicy {
+    /// Drop the stream that has not received a segment for the longest time.
+    #[default]
+    LeastRecentlyUsed,
+    /// Drop the stream that was started first.
+    Oldest,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let policy = TpEvictionPolicy::default();
+        assert_eq!(TpEvictionPolicy::LeastRecentlyUsed, policy);
+        assert_eq!(policy, policy.clone());
+        assert_eq!("Oldest", format!("{:?}", TpEvictionPolicy::Oldest));
+    }
+}
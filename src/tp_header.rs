@@ -2,6 +2,11 @@ use crate::*;
 
 ///Additional header when a packet contains a TP header (transporting large SOME/IP messages).
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "TpHeaderFields")
+)]
 pub struct TpHeader {
     ///Offset of the payload relativ the start of the completly assempled payload.
     offset: u32,
@@ -9,6 +14,25 @@ pub struct TpHeader {
     pub more_segment: bool,
 }
 
+/// Unchecked fields of a [`TpHeader`] (used to validate the offset when
+/// deserializing).
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "TpHeader")]
+struct TpHeaderFields {
+    offset: u32,
+    more_segment: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<TpHeaderFields> for TpHeader {
+    type Error = err::TpOffsetNotMultipleOf16Error;
+
+    fn try_from(fields: TpHeaderFields) -> Result<Self, Self::Error> {
+        TpHeader::with_offset(fields.offset, fields.more_segment)
+    }
+}
+
 impl TpHeader {
     ///Creates a tp header with offset 0 and the given "move_segment" flag.
     ///
@@ -295,4 +319,21 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let header = TpHeader::with_offset(32, true).unwrap();
+        let json = r#"{"offset":32,"more_segment":true}"#;
+        assert_eq!(json, serde_json::to_string(&header).unwrap());
+        assert_eq!(header, serde_json::from_str(json).unwrap());
+
+        // offsets that are not a multiple of 16 are rejected
+        let err =
+            serde_json::from_str::<TpHeader>(r#"{"offset":31,"more_segment":true}"#).unwrap_err();
+        assert!(format!("{err}").contains(&format!(
+            "{}",
+            TpOffsetNotMultipleOf16Error { bad_offset: 31 }
+        )));
+    }
 }
@@ -0,0 +1,32 @@
+/// Policy deciding how a TP buffer handles segments overlapping data that
+/// was already received (e.g. retransmitted or maliciously crafted segments).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TpOverlapPolicy {
+    /// Overlapping bytes are overwritten by the newest segment.
+    #[default]
+    AcceptLast,
+    /// Already received bytes are kept, only the not yet received parts
+    /// of a segment are stored.
+    AcceptFirst,
+    /// Segments with overlapping bytes that differ from the already
+    /// received bytes are rejected with
+    /// [`crate::err::TpReassembleError::ConflictingOverlap`].
+    RejectConflicting,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let policy = TpOverlapPolicy::default();
+        assert_eq!(TpOverlapPolicy::AcceptLast, policy);
+        assert_eq!(policy, policy.clone());
+        assert_eq!(
+            "RejectConflicting",
+            format!("{:?}", TpOverlapPolicy::RejectConflicting)
+        );
+    }
+}
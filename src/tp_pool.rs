@@ -5,15 +5,18 @@ use std::collections::HashMap;
 /// Pool of buffers to reconstruct multiple SOMEIP TP packet streams in
 /// parallel (re-uses buffers to minimize allocations).
 ///
-/// # This implementation is NOT safe against "Out of Memory" attacks
+/// # Limits
 ///
-/// If you use the [`TpPool`] in an untrusted environment an attacker could
-/// cause an "out of memory error" by opening up multiple parallel TP streams,
-/// never ending them and filling them up with as much data as possible.
+/// By default the pool is NOT safe against "Out of Memory" attacks. If you
+/// use the [`TpPool`] in an untrusted environment an attacker could cause an
+/// "out of memory error" by opening up multiple parallel TP streams, never
+/// ending them and filling them up with as much data as possible.
 ///
-/// Mitigations will hopefully be offered in future versions but if you have
-/// take care right now you can still use [`TpBuf`] directly and implement the
-/// connection handling and mitigation yourself.
+/// To prevent this configure [`TpPoolLimits`] via [`TpPool::with_limits`]
+/// or [`TpPool::set_limits`]. Depending on the configured
+/// [`TpPoolOverflowPolicy`] segments exceeding a limit either cause the
+/// streams with the oldest timestamps to be evicted (reported via
+/// [`TpPool::drain_drop_events`]) or get rejected with an error.
 #[derive(Debug, Clone)]
 pub struct TpPool<ChannelId, Timestamp>
 where
@@ -21,13 +24,27 @@ where
     Timestamp: Sized + core::fmt::Debug + Clone,
 {
     /// Currently reconstructing TP streams.
-    active: HashMap<(ChannelId, u32), (TpBuf, Timestamp)>,
+    active: HashMap<TpStreamKey<ChannelId>, TpPoolStream<Timestamp>>,
 
     /// Buffers that have finished receiving data and can be re-used.
     finished: Vec<TpBuf>,
 
     /// Configuration that should be used for new buffers.
     buf_config: TpBufConfig,
+
+    /// Limits of the pool.
+    limits: TpPoolLimits,
+
+    /// Header fields used to separate the streams.
+    key_fields: TpStreamKeyFields,
+
+    /// Streams evicted because of the limits (not yet drained by the user).
+    drop_events: Vec<TpPoolDropEvent<ChannelId, Timestamp>>,
+
+    /// Compares the timestamps of streams when searching the oldest stream
+    /// to evict (set together with the limits, as only the limits require
+    /// the timestamps to be comparable).
+    timestamp_cmp: fn(&Timestamp, &Timestamp) -> Option<core::cmp::Ordering>,
 }
 
 impl<ChannelId, Timestamp: Sized> TpPool<ChannelId, Timestamp>
@@ -40,6 +57,10 @@ where
             active: HashMap::new(),
             finished: Vec::new(),
             buf_config,
+            limits: Default::default(),
+            key_fields: Default::default(),
+            drop_events: Vec::new(),
+            timestamp_cmp: |_, _| None,
         }
     }
 
@@ -57,6 +78,10 @@ where
                 v
             },
             buf_config,
+            limits: Default::default(),
+            key_fields: Default::default(),
+            drop_events: Vec::new(),
+            timestamp_cmp: |_, _| None,
         }
     }
 
@@ -70,7 +95,7 @@ where
     }
 
     #[inline]
-    pub fn active_bufs(&self) -> &HashMap<(ChannelId, u32), (TpBuf, Timestamp)> {
+    pub fn active_bufs(&self) -> &HashMap<TpStreamKey<ChannelId>, TpPoolStream<Timestamp>> {
         &self.active
     }
 
@@ -84,6 +109,63 @@ where
         &self.buf_config
     }
 
+    #[inline]
+    pub fn limits(&self) -> &TpPoolLimits {
+        &self.limits
+    }
+
+    #[inline]
+    pub fn key_fields(&self) -> TpStreamKeyFields {
+        self.key_fields
+    }
+
+    /// Sets the SOMEIP header fields used to separate the streams (should
+    /// be set before the first segment is consumed, as already active
+    /// streams can no longer be continued once the key changes).
+    pub fn set_key_fields(&mut self, key_fields: TpStreamKeyFields) {
+        self.key_fields = key_fields;
+    }
+
+    /// Returns the key that identifies the stream of the given message.
+    #[inline]
+    pub fn stream_key(
+        &self,
+        id: ChannelId,
+        someip_slice: &SomeipMsgSlice,
+    ) -> TpStreamKey<ChannelId> {
+        TpStreamKey::new(id, someip_slice, self.key_fields)
+    }
+
+    /// Returns an active stream (e.g. to inspect the reassembly progress
+    /// via [`TpBuf::progress`]).
+    #[inline]
+    pub fn stream(&self, key: &TpStreamKey<ChannelId>) -> Option<&TpPoolStream<Timestamp>> {
+        self.active.get(key)
+    }
+
+    /// Number of bytes buffered over all active streams.
+    pub fn buffered_len(&self) -> usize {
+        self.active.values().map(|s| s.buf.buffered_len()).sum()
+    }
+
+    /// Number of bytes allocated over all active streams (the value
+    /// limited via [`TpPoolLimits::max_buffered_bytes`]).
+    pub fn allocated_len(&self) -> usize {
+        self.active.values().map(|s| s.buf.allocated_len()).sum()
+    }
+
+    /// Returns the streams that were evicted because of the limits since
+    /// the last call.
+    ///
+    /// The events are kept until they are drained, so this should be called
+    /// regularly if limits with [`TpPoolOverflowPolicy::EvictOldest`] are
+    /// configured.
+    pub fn drain_drop_events(
+        &mut self,
+    ) -> std::vec::Drain<'_, TpPoolDropEvent<ChannelId, Timestamp>> {
+        self.drop_events.drain(..)
+    }
+
     pub fn consume<'a: 'c, 'b: 'c, 'c: 'a + 'b>(
         &'a mut self,
         id: ChannelId,
@@ -91,17 +173,76 @@ where
         someip_slice: SomeipMsgSlice<'b>,
     ) -> Result<Option<SomeipMsgSlice<'c>>, err::TpReassembleError> {
         if someip_slice.is_tp() {
+            let key = self.stream_key(id, &someip_slice);
+
+            // validate the segment before any stream gets evicted
+            let segment_end = match self.active.get_mut(&key) {
+                Some(stream) => match stream.buf.check_tp(&someip_slice) {
+                    Ok(segment) => segment.end(),
+                    // the buffer rejects the segment with the same error
+                    // (but still counts it as duplicate or overlap)
+                    Err(_) => return stream.buf.consume_tp(someip_slice).map(|_| None),
+                },
+                None => TpSegment::from_slice(
+                    &someip_slice,
+                    self.buf_config.tp_max_payload_len(),
+                    None,
+                )?
+                .end(),
+            };
+            let required_len = SOMEIP_HEADER_LENGTH + segment_end as usize;
+            let limit_bytes = self.limits.max_buffered_bytes.is_some();
+
+            // allocate the memory before any stream gets evicted (if the
+            // memory is limited only the required bytes get allocated)
+            let new_buf = match self.active.get_mut(&key) {
+                Some(stream) => {
+                    let prev_alloc_len = stream.buf.allocated_len();
+                    Self::alloc_buf(&mut stream.buf, required_len, limit_bytes)?;
+                    if let Err(err) = self.enforce_limits(&key, required_len) {
+                        // give back the memory (shrinking can not fail)
+                        if let Some(stream) = self.active.get_mut(&key) {
+                            let _ = stream.buf.try_set_alloc_len(prev_alloc_len);
+                        }
+                        return Err(err);
+                    }
+                    None
+                }
+                None => {
+                    // new stream get a finished or new buffer
+                    let mut buf = if let Some(mut b) = self.finished.pop() {
+                        b.clear();
+                        b
+                    } else if limit_bytes {
+                        TpBuf::with_alloc_len(self.buf_config.clone(), required_len)
+                    } else {
+                        TpBuf::new(self.buf_config.clone())
+                    };
+                    let result = Self::alloc_buf(&mut buf, required_len, limit_bytes)
+                        .and_then(|_| self.enforce_limits(&key, required_len));
+                    if let Err(err) = result {
+                        // keep the buffer for re-use
+                        self.push_finished(buf);
+                        return Err(err);
+                    }
+                    Some(buf)
+                }
+            };
+
+            // consume the data (can no longer fail as the segment was
+            // validated & the memory was already allocated)
             use std::collections::hash_map::Entry::*;
-            match self.active.entry((id, someip_slice.request_id())) {
-                Occupied(mut o) => {
+            match (self.active.entry(key), new_buf) {
+                (Occupied(mut o), _) => {
                     // stream already known consume the data & update the timestamp
-                    o.get_mut().0.consume_tp(someip_slice)?;
-                    o.get_mut().1 = timestamp;
+                    o.get_mut().buf.consume_tp(someip_slice)?;
+                    o.get_mut().last_timestamp = timestamp;
 
                     // check if the stream is complete
-                    if o.get().0.is_complete() {
+                    if o.get().buf.is_complete() {
                         // if done move the buffer to the finished list and return the result
-                        self.finished.push(o.remove().0);
+                        let buf = o.remove().buf;
+                        self.push_finished(buf);
                         Ok(Some(
                             self.finished.last_mut().unwrap().try_finalize().unwrap(),
                         ))
@@ -109,31 +250,28 @@ where
                         Ok(None)
                     }
                 }
-                Vacant(v) => {
-                    // new stream get a finished or new buffer
-                    let mut buf = if let Some(mut b) = self.finished.pop() {
-                        b.clear();
-                        b
-                    } else {
-                        TpBuf::new(self.buf_config.clone())
-                    };
-
-                    // consume the data
+                (Vacant(v), Some(mut buf)) => {
                     buf.consume_tp(someip_slice)?;
 
                     // check if the stream is complete
                     if buf.is_complete() {
                         // if done move the buffer to the finished list and return the result
-                        self.finished.push(buf);
+                        self.push_finished(buf);
                         Ok(Some(
                             self.finished.last_mut().unwrap().try_finalize().unwrap(),
                         ))
                     } else {
                         // stream is not yet done, keep it around until done
-                        v.insert((buf, timestamp));
+                        v.insert(TpPoolStream {
+                            buf,
+                            first_timestamp: timestamp.clone(),
+                            last_timestamp: timestamp,
+                        });
                         Ok(None)
                     }
                 }
+                // enforcing the limits never evicts the stream of the segment
+                (Vacant(_), None) => unreachable!(),
             }
         } else {
             Ok(Some(someip_slice))
@@ -145,20 +283,209 @@ where
     where
         F: Fn(&Timestamp) -> bool,
     {
+        self.retain_dropped(f);
+    }
+
+    /// Retains only the elements specified by the predicate and returns
+    /// the dropped streams.
+    pub fn retain_dropped<F>(&mut self, f: F) -> Vec<TpPoolDropEvent<ChannelId, Timestamp>>
+    where
+        F: Fn(&Timestamp) -> bool,
+    {
+        let mut dropped = Vec::new();
         // check if any entry has to be removed
-        if self.active.iter().any(|(_, (_, t))| false == f(t)) {
+        if self.active.values().any(|s| false == f(&s.last_timestamp)) {
+            let mut finished = Vec::new();
             self.active = self
                 .active
                 .drain()
                 .filter_map(|(k, v)| {
-                    if f(&v.1) {
+                    if f(&v.last_timestamp) {
                         Some((k, v))
                     } else {
-                        self.finished.push(v.0);
+                        dropped.push(TpPoolDropEvent::new(k, &v, TpPoolDropReason::Retain));
+                        finished.push(v.buf);
                         None
                     }
                 })
                 .collect();
+            for buf in finished {
+                self.push_finished(buf);
+            }
+        }
+        dropped
+    }
+
+    /// Allocates the memory to grow the buffer to `required_len` bytes
+    /// (exactly the required memory if `exact` is set).
+    fn alloc_buf(
+        buf: &mut TpBuf,
+        required_len: usize,
+        exact: bool,
+    ) -> Result<(), err::TpReassembleError> {
+        if exact {
+            buf.try_set_alloc_len(core::cmp::max(buf.buffered_len(), required_len))
+        } else {
+            buf.try_reserve_len(required_len)
+        }
+    }
+
+    /// Moves a buffer to the finished list (dropping old buffers if the
+    /// maximum number of finished buffers is reached).
+    fn push_finished(&mut self, buf: TpBuf) {
+        if let Some(max) = self.limits.max_finished_bufs {
+            // always keep the pushed buffer (it might be referenced by the result)
+            self.finished.truncate(max.saturating_sub(1));
+        }
+        self.finished.push(buf);
+    }
+
+    /// Evicts streams (or returns an error) so a segment of the stream
+    /// with the given key and the given required buffer length fits into
+    /// the configured limits.
+    fn enforce_limits(
+        &mut self,
+        key: &TpStreamKey<ChannelId>,
+        required_len: usize,
+    ) -> Result<(), err::TpReassembleError> {
+        use err::TpReassembleError::*;
+
+        let limits = self.limits;
+
+        // fail early if evicting other streams would not help
+        let new_stream = false == self.active.contains_key(key);
+        if new_stream {
+            if let Some(0) = limits.max_streams_per_channel {
+                return Err(ChannelStreamLimitReached {
+                    max_streams_per_channel: 0,
+                });
+            }
+            if let Some(0) = limits.max_streams {
+                return Err(StreamLimitReached { max_streams: 0 });
+            }
+        }
+        if let Some(max) = limits.max_buffered_bytes {
+            let stream_len = self.active.get(key).map_or(0, |s| s.buf.buffered_len());
+            let required = core::cmp::max(stream_len, required_len);
+            if required > max {
+                return Err(BufferedBytesLimitReached {
+                    max_buffered_bytes: max,
+                    required,
+                });
+            }
+        }
+
+        if new_stream {
+            if let Some(max) = limits.max_streams_per_channel {
+                while self
+                    .active
+                    .keys()
+                    .filter(|k| k.channel_id == key.channel_id)
+                    .count()
+                    >= max
+                {
+                    if false
+                        == self.evict_oldest(
+                            key,
+                            |k| k.channel_id == key.channel_id,
+                            TpPoolDropReason::MaxStreamsPerChannel,
+                        )
+                    {
+                        return Err(ChannelStreamLimitReached {
+                            max_streams_per_channel: max,
+                        });
+                    }
+                }
+            }
+            if let Some(max) = limits.max_streams {
+                while self.active.len() >= max {
+                    if false == self.evict_oldest(key, |_| true, TpPoolDropReason::MaxStreams) {
+                        return Err(StreamLimitReached { max_streams: max });
+                    }
+                }
+            }
+        }
+        if let Some(max) = limits.max_buffered_bytes {
+            loop {
+                // the stream of the segment gets exactly the memory it needs
+                let (current_alloc, current_len) = self
+                    .active
+                    .get(key)
+                    .map_or((0, 0), |s| (s.buf.allocated_len(), s.buf.buffered_len()));
+                let required = self.allocated_len() - current_alloc
+                    + core::cmp::max(current_len, required_len);
+                if required <= max {
+                    break;
+                }
+                if false == self.evict_oldest(key, |_| true, TpPoolDropReason::MaxBufferedBytes) {
+                    return Err(BufferedBytesLimitReached {
+                        max_buffered_bytes: max,
+                        required,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Evicts the stream with the oldest timestamp matching the filter
+    /// (never the stream with the given key). Returns false if no stream
+    /// could be evicted.
+    fn evict_oldest<F>(
+        &mut self,
+        key: &TpStreamKey<ChannelId>,
+        filter: F,
+        reason: TpPoolDropReason,
+    ) -> bool
+    where
+        F: Fn(&TpStreamKey<ChannelId>) -> bool,
+    {
+        if TpPoolOverflowPolicy::Reject == self.limits.overflow_policy {
+            return false;
+        }
+        let oldest = self
+            .active
+            .iter()
+            .filter(|(k, _)| *k != key && filter(k))
+            .min_by(|a, b| {
+                (self.timestamp_cmp)(&a.1.last_timestamp, &b.1.last_timestamp)
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+            .map(|(k, _)| k.clone());
+        if let Some(oldest) = oldest {
+            let stream = self.active.remove(&oldest).unwrap();
+            self.drop_events
+                .push(TpPoolDropEvent::new(oldest, &stream, reason));
+            self.push_finished(stream.buf);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<ChannelId, Timestamp: Sized> TpPool<ChannelId, Timestamp>
+where
+    ChannelId: Hash + Eq + PartialEq + Clone + Sized,
+    Timestamp: core::fmt::Debug + Clone + Sized + PartialOrd,
+{
+    /// Creates a pool that enforces the given limits.
+    pub fn with_limits(
+        buf_config: TpBufConfig,
+        limits: TpPoolLimits,
+    ) -> TpPool<ChannelId, Timestamp> {
+        let mut pool = TpPool::new(buf_config);
+        pool.set_limits(limits);
+        pool
+    }
+
+    /// Sets the limits of the pool (already active streams exceeding the
+    /// new limits are only evicted once new segments are received).
+    pub fn set_limits(&mut self, limits: TpPoolLimits) {
+        self.limits = limits;
+        self.timestamp_cmp = Timestamp::partial_cmp;
+        if let Some(max) = limits.max_finished_bufs {
+            self.finished.truncate(max);
         }
     }
 }
@@ -172,6 +499,7 @@ where
         self.active == other.active
             && self.finished == other.finished
             && self.buf_config == other.buf_config
+            && self.limits == other.limits
     }
 }
 
@@ -225,7 +553,7 @@ mod tests {
                 request_id,
                 offset,
                 more_segments,
-                payload: payload.iter().copied().collect(),
+                payload: payload.to_vec(),
             }
         }
 
@@ -263,6 +591,26 @@ mod tests {
         }
     }
 
+    /// Key of a stream of test packets.
+    fn key<ChannelId>(channel_id: ChannelId, request_id: u32) -> TpStreamKey<ChannelId> {
+        TpStreamKey {
+            channel_id,
+            message_id: 1234,
+            request_id,
+            interface_version: 0,
+        }
+    }
+
+    /// Progress of a stream that only received a first segment with the given length.
+    fn first_segment_progress(len: u32) -> TpBufProgress {
+        TpBufProgress {
+            received_len: len,
+            expected_end: None,
+            segment_count: 1,
+            missing_ranges: Vec::new(),
+        }
+    }
+
     /// Returns a u8 vec counting up from "start" until len is reached (truncating bits greater then u8).
     fn sequence(start: usize, len: usize) -> Vec<u8> {
         let mut result = Vec::with_capacity(len);
@@ -282,7 +630,7 @@ mod tests {
             // build a non tp packet
             let header = SomeipHeader {
                 message_id: 1234,
-                length: 8 + 8 as u32,
+                length: 8 + 8,
                 request_id: 234,
                 interface_version: 1,
                 message_type: MessageType::Notification,
@@ -353,7 +701,7 @@ mod tests {
             for a in actions {
                 let packet = a.1.to_vec();
                 let slice = SomeipMsgSlice::from_slice(&packet).unwrap();
-                let result = pool.consume(a.0.clone(), (), slice).unwrap();
+                let result = pool.consume(a.0, (), slice).unwrap();
                 if let Some(expected_payload) = a.2 {
                     let msg = result.unwrap();
                     assert_eq!(msg.to_header(), a.1.result_header(expected_payload.len() as u32));
@@ -407,7 +755,13 @@ mod tests {
             let slice = SomeipMsgSlice::from_slice(&packet).unwrap();
             let result = pool.consume(2u16, 123u32, slice).unwrap();
             assert!(result.is_none());
-            assert_eq!(123, pool.active_bufs().get(&(2u16, 1u32)).unwrap().1);
+            assert_eq!(
+                123,
+                pool.active_bufs()
+                    .get(&key(2u16, 1))
+                    .unwrap()
+                    .last_timestamp
+            );
         }
         // request id 1, channel id 2, timestamp 124
         {
@@ -416,7 +770,13 @@ mod tests {
             let result = pool.consume(2u16, 124u32, slice).unwrap();
             assert!(result.is_none());
             // check the timestamp was overwritten by the newer packet
-            assert_eq!(124, pool.active_bufs().get(&(2u16, 1u32)).unwrap().1);
+            assert_eq!(
+                124,
+                pool.active_bufs()
+                    .get(&key(2u16, 1))
+                    .unwrap()
+                    .last_timestamp
+            );
         }
         // request id 1, channel id 3, timestamp 125
         {
@@ -431,6 +791,585 @@ mod tests {
 
         assert_eq!(1, pool.active.len());
         assert_eq!(1, pool.finished.len());
-        assert_eq!(125, pool.active_bufs().get(&(3u16, 1u32)).unwrap().1);
+        assert_eq!(
+            125,
+            pool.active_bufs()
+                .get(&key(3u16, 1))
+                .unwrap()
+                .last_timestamp
+        );
+    }
+
+    fn send(
+        pool: &mut TpPool<u32, u32>,
+        id: u32,
+        timestamp: u32,
+        packet: TestPacket,
+    ) -> Result<Option<Vec<u8>>, err::TpReassembleError> {
+        let packet = packet.to_vec();
+        pool.consume(id, timestamp, SomeipMsgSlice::from_slice(&packet).unwrap())
+            .map(|r| r.map(|msg| msg.payload().to_vec()))
+    }
+
+    #[test]
+    fn limits_streams() {
+        use err::TpReassembleError::*;
+
+        let limits = TpPoolLimits {
+            max_streams: Some(2),
+            max_streams_per_channel: Some(1),
+            ..Default::default()
+        };
+
+        // evict oldest
+        {
+            let mut pool = TpPool::with_limits(TpBufConfig::new(1024, 2048).unwrap(), limits);
+            assert_eq!(&limits, pool.limits());
+            send(
+                &mut pool,
+                1,
+                10,
+                TestPacket::new(1, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+            send(
+                &mut pool,
+                2,
+                5,
+                TestPacket::new(1, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+            assert_eq!(0, pool.drain_drop_events().count());
+
+            // second stream on channel 1 evicts the first stream of the channel
+            send(
+                &mut pool,
+                1,
+                20,
+                TestPacket::new(2, 0, true, &sequence(0, 32)),
+            )
+            .unwrap();
+            assert_eq!(
+                vec![TpPoolDropEvent {
+                    key: key(1, 1),
+                    first_timestamp: 10,
+                    last_timestamp: 10,
+                    buffered_len: SOMEIP_HEADER_LENGTH + 16,
+                    progress: first_segment_progress(16),
+                    reason: TpPoolDropReason::MaxStreamsPerChannel,
+                }],
+                pool.drain_drop_events().collect::<Vec<_>>()
+            );
+
+            // third channel evicts the stream with the oldest timestamp
+            send(
+                &mut pool,
+                3,
+                30,
+                TestPacket::new(1, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+            assert_eq!(
+                vec![TpPoolDropEvent {
+                    key: key(2, 1),
+                    first_timestamp: 5,
+                    last_timestamp: 5,
+                    buffered_len: SOMEIP_HEADER_LENGTH + 16,
+                    progress: first_segment_progress(16),
+                    reason: TpPoolDropReason::MaxStreams,
+                }],
+                pool.drain_drop_events().collect::<Vec<_>>()
+            );
+            assert_eq!(2, pool.active_bufs().len());
+
+            // known streams are not affected by the stream limits
+            send(
+                &mut pool,
+                1,
+                40,
+                TestPacket::new(2, 32, true, &sequence(32, 16)),
+            )
+            .unwrap();
+            assert_eq!(0, pool.drain_drop_events().count());
+        }
+
+        // reject
+        {
+            let mut pool = TpPool::with_limits(
+                TpBufConfig::new(1024, 2048).unwrap(),
+                TpPoolLimits {
+                    overflow_policy: TpPoolOverflowPolicy::Reject,
+                    ..limits
+                },
+            );
+            send(
+                &mut pool,
+                1,
+                10,
+                TestPacket::new(1, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+            assert_eq!(
+                Err(ChannelStreamLimitReached {
+                    max_streams_per_channel: 1
+                }),
+                send(
+                    &mut pool,
+                    1,
+                    20,
+                    TestPacket::new(2, 0, true, &sequence(0, 16))
+                )
+            );
+            send(
+                &mut pool,
+                2,
+                10,
+                TestPacket::new(1, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+            assert_eq!(
+                Err(StreamLimitReached { max_streams: 2 }),
+                send(
+                    &mut pool,
+                    3,
+                    20,
+                    TestPacket::new(1, 0, true, &sequence(0, 16))
+                )
+            );
+            assert_eq!(2, pool.active_bufs().len());
+            assert_eq!(0, pool.drain_drop_events().count());
+        }
+    }
+
+    #[test]
+    fn limits_buffered_bytes() {
+        use err::TpReassembleError::*;
+
+        let mut pool = TpPool::with_limits(
+            TpBufConfig::new(1024, 2048).unwrap(),
+            TpPoolLimits {
+                max_buffered_bytes: Some(2 * SOMEIP_HEADER_LENGTH + 64),
+                ..Default::default()
+            },
+        );
+        send(
+            &mut pool,
+            1,
+            10,
+            TestPacket::new(1, 0, true, &sequence(0, 32)),
+        )
+        .unwrap();
+        send(
+            &mut pool,
+            1,
+            20,
+            TestPacket::new(2, 0, true, &sequence(0, 32)),
+        )
+        .unwrap();
+        assert_eq!(2 * SOMEIP_HEADER_LENGTH + 64, pool.buffered_len());
+
+        // growing the second stream evicts the first one
+        send(
+            &mut pool,
+            1,
+            30,
+            TestPacket::new(2, 32, true, &sequence(32, 16)),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![TpPoolDropEvent {
+                key: key(1, 1),
+                first_timestamp: 10,
+                last_timestamp: 10,
+                buffered_len: SOMEIP_HEADER_LENGTH + 32,
+                progress: first_segment_progress(32),
+                reason: TpPoolDropReason::MaxBufferedBytes,
+            }],
+            pool.drain_drop_events().collect::<Vec<_>>()
+        );
+        assert_eq!(SOMEIP_HEADER_LENGTH + 48, pool.buffered_len());
+
+        // a segment that can never fit results in an error
+        assert_eq!(
+            Err(BufferedBytesLimitReached {
+                max_buffered_bytes: 2 * SOMEIP_HEADER_LENGTH + 64,
+                required: SOMEIP_HEADER_LENGTH + 128,
+            }),
+            send(
+                &mut pool,
+                1,
+                40,
+                TestPacket::new(2, 112, true, &sequence(112, 16))
+            )
+        );
+        assert_eq!(1, pool.active_bufs().len());
+        assert_eq!(pool.buffered_len(), pool.allocated_len());
+    }
+
+    #[test]
+    fn limits_buffered_bytes_allocation() {
+        // flooding first segments with the default config (which
+        // pre-allocates memory for new buffers) stays within the limit
+        let max_buffered_bytes = 64 * 1024;
+        let mut pool = TpPool::with_limits(
+            Default::default(),
+            TpPoolLimits {
+                max_buffered_bytes: Some(max_buffered_bytes),
+                ..Default::default()
+            },
+        );
+        for request_id in 0..1000 {
+            send(
+                &mut pool,
+                1,
+                request_id,
+                TestPacket::new(request_id, 0, true, &sequence(0, 1392)),
+            )
+            .unwrap();
+            assert!(pool.allocated_len() <= max_buffered_bytes);
+        }
+        assert_eq!(
+            max_buffered_bytes / (SOMEIP_HEADER_LENGTH + 1392),
+            pool.active_bufs().len()
+        );
+        assert_eq!(
+            1000 - pool.active_bufs().len(),
+            pool.drain_drop_events().count()
+        );
+
+        // reused finished buffers only keep the required memory
+        send(
+            &mut pool,
+            2,
+            1000,
+            TestPacket::new(0, 0, true, &sequence(0, 16)),
+        )
+        .unwrap();
+        assert!(pool.allocated_len() <= max_buffered_bytes);
+        assert_eq!(
+            Some(SOMEIP_HEADER_LENGTH + 16),
+            pool.stream(&key(2, 0)).map(|s| s.buf.allocated_len())
+        );
+    }
+
+    #[test]
+    fn limits_rejected_segments_do_not_evict() {
+        use err::TpReassembleError::*;
+
+        let mut pool = TpPool::with_limits(
+            TpBufConfig::new(1024, 2048).unwrap(),
+            TpPoolLimits {
+                max_buffered_bytes: Some(2 * SOMEIP_HEADER_LENGTH + 64),
+                max_streams: Some(2),
+                ..Default::default()
+            },
+        );
+        send(
+            &mut pool,
+            1,
+            10,
+            TestPacket::new(1, 0, true, &sequence(0, 32)),
+        )
+        .unwrap();
+        send(
+            &mut pool,
+            1,
+            20,
+            TestPacket::new(2, 16, false, &sequence(16, 16)),
+        )
+        .unwrap();
+
+        // invalid segment of a new stream
+        assert_eq!(
+            Err(UnalignedTpPayloadLen {
+                offset: 0,
+                payload_len: 15
+            }),
+            send(
+                &mut pool,
+                1,
+                30,
+                TestPacket::new(3, 0, true, &sequence(0, 15))
+            )
+        );
+
+        // segment growing a stream beyond its end
+        assert_eq!(
+            Err(ConflictingEnd {
+                previous_end: 32,
+                conflicting_end: 64
+            }),
+            send(
+                &mut pool,
+                1,
+                30,
+                TestPacket::new(2, 48, true, &sequence(48, 16))
+            )
+        );
+
+        assert_eq!(0, pool.drain_drop_events().count());
+        assert_eq!(2, pool.active_bufs().len());
+    }
+
+    #[test]
+    fn limits_errors_keep_streams_and_bufs() {
+        use err::TpReassembleError::*;
+
+        // rejected new streams return the re-used buffer to the finished list
+        {
+            let mut pool = TpPool::with_limits(
+                TpBufConfig::new(1024, 2048).unwrap(),
+                TpPoolLimits {
+                    max_streams: Some(1),
+                    overflow_policy: TpPoolOverflowPolicy::Reject,
+                    ..Default::default()
+                },
+            );
+            pool.reserve(2);
+            send(
+                &mut pool,
+                1,
+                10,
+                TestPacket::new(1, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+            assert_eq!(1, pool.finished_bufs().len());
+            assert_eq!(
+                Err(StreamLimitReached { max_streams: 1 }),
+                send(
+                    &mut pool,
+                    1,
+                    20,
+                    TestPacket::new(2, 0, true, &sequence(0, 16))
+                )
+            );
+            assert_eq!(1, pool.finished_bufs().len());
+            assert_eq!(1, pool.active_bufs().len());
+        }
+
+        // segments that can never fit do not evict other streams
+        {
+            let max_buffered_bytes = 2 * SOMEIP_HEADER_LENGTH + 64;
+            let mut pool = TpPool::with_limits(
+                TpBufConfig::new(1024, 2048).unwrap(),
+                TpPoolLimits {
+                    max_buffered_bytes: Some(max_buffered_bytes),
+                    ..Default::default()
+                },
+            );
+            pool.reserve(1);
+            for request_id in 1..=2 {
+                send(
+                    &mut pool,
+                    1,
+                    request_id,
+                    TestPacket::new(request_id, 0, true, &sequence(0, 32)),
+                )
+                .unwrap();
+            }
+            let allocated_len = pool.allocated_len();
+            assert_eq!(
+                Err(BufferedBytesLimitReached {
+                    max_buffered_bytes,
+                    required: SOMEIP_HEADER_LENGTH + 128,
+                }),
+                send(
+                    &mut pool,
+                    1,
+                    30,
+                    TestPacket::new(3, 112, true, &sequence(112, 16))
+                )
+            );
+            assert_eq!(
+                Err(BufferedBytesLimitReached {
+                    max_buffered_bytes,
+                    required: SOMEIP_HEADER_LENGTH + 128,
+                }),
+                send(
+                    &mut pool,
+                    1,
+                    30,
+                    TestPacket::new(2, 112, true, &sequence(112, 16))
+                )
+            );
+            assert_eq!(0, pool.drain_drop_events().count());
+            assert_eq!(2, pool.active_bufs().len());
+            assert_eq!(allocated_len, pool.allocated_len());
+        }
+    }
+
+    #[test]
+    fn timestamp_without_order() {
+        // timestamps only have to be comparable if limits are used
+        #[derive(Debug, Clone, PartialEq)]
+        struct Tick(u8);
+
+        let mut pool = TpPool::<u32, Tick>::new(TpBufConfig::new(1024, 2048).unwrap());
+        let first = TestPacket::new(1, 0, true, &sequence(0, 16)).to_vec();
+        let last = TestPacket::new(1, 16, false, &sequence(16, 16)).to_vec();
+        assert_eq!(
+            None,
+            pool.consume(1, Tick(0), SomeipMsgSlice::from_slice(&first).unwrap())
+                .unwrap()
+        );
+        assert_eq!(
+            Some(sequence(0, 32)),
+            pool.consume(1, Tick(1), SomeipMsgSlice::from_slice(&last).unwrap())
+                .unwrap()
+                .map(|msg| msg.payload().to_vec())
+        );
+    }
+
+    #[test]
+    fn limits_finished_bufs() {
+        let mut pool = TpPool::<u32, u32>::with_capacity(TpBufConfig::new(1024, 2048).unwrap(), 4);
+        pool.set_limits(TpPoolLimits {
+            max_finished_bufs: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(2, pool.finished_bufs().len());
+
+        for request_id in 0..4 {
+            send(
+                &mut pool,
+                1,
+                0,
+                TestPacket::new(request_id, 0, true, &sequence(0, 16)),
+            )
+            .unwrap();
+        }
+        assert_eq!(0, pool.finished_bufs().len());
+        for request_id in 0..4 {
+            assert_eq!(
+                Ok(Some(sequence(0, 32))),
+                send(
+                    &mut pool,
+                    1,
+                    0,
+                    TestPacket::new(request_id, 16, false, &sequence(16, 16))
+                )
+            );
+            assert!(pool.finished_bufs().len() <= 2);
+        }
+        assert_eq!(2, pool.finished_bufs().len());
+    }
+
+    #[test]
+    fn retain_dropped() {
+        let mut pool = TpPool::<u32, u32>::new(TpBufConfig::new(1024, 2048).unwrap());
+        send(
+            &mut pool,
+            1,
+            10,
+            TestPacket::new(1, 0, true, &sequence(0, 16)),
+        )
+        .unwrap();
+        send(
+            &mut pool,
+            1,
+            12,
+            TestPacket::new(1, 32, false, &sequence(32, 16)),
+        )
+        .unwrap();
+        send(
+            &mut pool,
+            2,
+            20,
+            TestPacket::new(1, 0, true, &sequence(0, 32)),
+        )
+        .unwrap();
+
+        assert!(pool.retain_dropped(|_| true).is_empty());
+        assert_eq!(
+            vec![TpPoolDropEvent {
+                key: key(1, 1),
+                first_timestamp: 10,
+                last_timestamp: 12,
+                buffered_len: SOMEIP_HEADER_LENGTH + 48,
+                progress: TpBufProgress {
+                    received_len: 32,
+                    expected_end: Some(48),
+                    segment_count: 2,
+                    missing_ranges: vec![TpRange { start: 16, end: 32 }],
+                },
+                reason: TpPoolDropReason::Retain,
+            }],
+            pool.retain_dropped(|t| *t > 15)
+        );
+        assert_eq!(1, pool.active_bufs().len());
+        assert_eq!(1, pool.finished_bufs().len());
+        // retain does not produce drop events
+        assert_eq!(0, pool.drain_drop_events().count());
+    }
+
+    #[test]
+    fn key_fields() {
+        // two services sending a stream with the same request id in parallel
+        let packets = [
+            (1234, TestPacket::new(1, 0, true, &[1; 16])),
+            (1235, TestPacket::new(1, 0, true, &[2; 16])),
+            (1234, TestPacket::new(1, 16, false, &[1; 16])),
+            (1235, TestPacket::new(1, 16, false, &[2; 16])),
+        ];
+        let run = |pool: &mut TpPool<(), ()>| -> Vec<Option<Vec<u8>>> {
+            packets
+                .iter()
+                .map(|(message_id, packet)| {
+                    let mut bytes = packet.to_vec();
+                    bytes[..4].copy_from_slice(&(*message_id as u32).to_be_bytes());
+                    let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+                    pool.consume((), (), slice)
+                        .unwrap()
+                        .map(|msg| msg.payload().to_vec())
+                })
+                .collect()
+        };
+
+        // default separates the streams by message id
+        {
+            let mut pool: TpPool<(), ()> = TpPool::new(TpBufConfig::new(1024, 2048).unwrap());
+            assert_eq!(TpStreamKeyFields::MessageIdAndRequestId, pool.key_fields());
+            assert_eq!(
+                vec![None, None, Some(vec![1; 32]), Some(vec![2; 32])],
+                run(&mut pool)
+            );
+        }
+
+        // only the request id mixes the streams
+        {
+            let mut pool: TpPool<(), ()> = TpPool::new(TpBufConfig::new(1024, 2048).unwrap());
+            pool.set_key_fields(TpStreamKeyFields::RequestId);
+            let result = run(&mut pool);
+            assert_eq!(Some([[2u8; 16], [1u8; 16]].concat()), result[2]);
+        }
+    }
+
+    #[test]
+    fn stream() {
+        let mut pool: TpPool<u16, u32> = TpPool::new(TpBufConfig::new(1024, 2048).unwrap());
+        let packet = TestPacket::new(1, 32, false, &sequence(32, 16)).to_vec();
+        let slice = SomeipMsgSlice::from_slice(&packet).unwrap();
+        let stream_key = pool.stream_key(2, &slice);
+        assert_eq!(key(2, 1), stream_key);
+        assert!(pool.stream(&stream_key).is_none());
+
+        pool.consume(2, 123, slice).unwrap();
+        {
+            let packet = TestPacket::new(1, 0, true, &sequence(0, 16)).to_vec();
+            let slice = SomeipMsgSlice::from_slice(&packet).unwrap();
+            pool.consume(2, 124, slice).unwrap();
+        }
+        let stream = pool.stream(&stream_key).unwrap();
+        assert_eq!(123, stream.first_timestamp);
+        assert_eq!(124, stream.last_timestamp);
+        assert_eq!(
+            TpBufProgress {
+                received_len: 32,
+                expected_end: Some(48),
+                segment_count: 2,
+                missing_ranges: vec![TpRange { start: 16, end: 32 }],
+            },
+            stream.buf.progress()
+        );
     }
 }
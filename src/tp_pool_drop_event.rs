@@ -0,0 +1,68 @@
+use crate::*;
+
+/// Unfinished stream that was dropped by a [`TpPool`] (either evicted
+/// because of a limit or removed via [`TpPool::retain_dropped`]).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TpPoolDropEvent<ChannelId, Timestamp> {
+    /// Key of the dropped stream.
+    pub key: TpStreamKey<ChannelId>,
+
+    /// Timestamp of the first segment received for the stream.
+    pub first_timestamp: Timestamp,
+
+    /// Timestamp of the last segment received for the stream.
+    pub last_timestamp: Timestamp,
+
+    /// Number of bytes that were buffered for the stream.
+    pub buffered_len: usize,
+
+    /// Reassembly progress of the stream when it was dropped (e.g. the
+    /// missing ranges).
+    pub progress: TpBufProgress,
+
+    /// Reason the stream was dropped.
+    pub reason: TpPoolDropReason,
+}
+
+impl<ChannelId, Timestamp: Clone> TpPoolDropEvent<ChannelId, Timestamp> {
+    /// Creates an event describing the given stream.
+    pub(crate) fn new(
+        key: TpStreamKey<ChannelId>,
+        stream: &TpPoolStream<Timestamp>,
+        reason: TpPoolDropReason,
+    ) -> TpPoolDropEvent<ChannelId, Timestamp> {
+        TpPoolDropEvent {
+            key,
+            first_timestamp: stream.first_timestamp.clone(),
+            last_timestamp: stream.last_timestamp.clone(),
+            buffered_len: stream.buf.buffered_len(),
+            progress: stream.buf.progress(),
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let event = TpPoolDropEvent {
+            key: TpStreamKey {
+                channel_id: 1u8,
+                message_id: 2,
+                request_id: 2,
+                interface_version: 1,
+            },
+            first_timestamp: 3u64,
+            last_timestamp: 4u64,
+            buffered_len: 4,
+            progress: TpBuf::new(Default::default()).progress(),
+            reason: TpPoolDropReason::Retain,
+        };
+        let _ = format!("{:?}", event);
+        assert_eq!(event, event.clone());
+    }
+}
@@ -0,0 +1,25 @@
+/// Reason why an unfinished stream was dropped by a [`crate::TpPool`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TpPoolDropReason {
+    /// Evicted to stay within [`crate::TpPoolLimits::max_buffered_bytes`].
+    MaxBufferedBytes,
+    /// Evicted to stay within [`crate::TpPoolLimits::max_streams`].
+    MaxStreams,
+    /// Evicted to stay within [`crate::TpPoolLimits::max_streams_per_channel`].
+    MaxStreamsPerChannel,
+    /// Removed by [`crate::TpPool::retain`] or [`crate::TpPool::retain_dropped`].
+    Retain,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let reason = TpPoolDropReason::Retain;
+        assert_eq!(reason, reason.clone());
+        assert_eq!("MaxStreams", format!("{:?}", TpPoolDropReason::MaxStreams));
+    }
+}
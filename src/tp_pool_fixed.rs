@@ -0,0 +1,428 @@
+use crate::*;
+
+/// Allocation free pool of a fixed number of buffers to reconstruct
+/// multiple SOMEIP TP packet streams in parallel (for `no_std` targets
+/// without a heap).
+///
+/// Works like [`TpPool`] (streams are identified by a [`TpStreamKey`]
+/// containing the channel id & the header fields configured via
+/// [`TpPoolFixed::set_key_fields`]) but uses `SLOTS` [`TpBufFixed`]
+/// buffers of `N` bytes each.
+/// If a new stream is started while all slots are in use, one of the
+/// active streams is dropped based on the configured [`TpEvictionPolicy`]
+/// (the number of dropped streams is counted in
+/// [`TpPoolFixed::evicted_count`]).
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::*;
+///
+/// // 4 parallel streams with up to 1024 - 16 bytes of payload each
+/// let mut pool = TpPoolFixed::<u8, u64, 4, 1024>::new(TpEvictionPolicy::LeastRecentlyUsed);
+///
+/// let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+///     .tp_header(TpHeader::with_offset(0, false).unwrap())
+///     .build(3)
+///     .unwrap();
+/// let mut segment = [0u8; 64];
+/// let len = SomeipMsgWriter::write(&header, &[1, 2, 3], &mut segment).unwrap();
+///
+/// let channel = 0u8;
+/// let timestamp = 0u64;
+/// let msg = pool
+///     .consume(channel, timestamp, SomeipMsgSlice::from_slice(&segment[..len]).unwrap())
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(&[1, 2, 3], msg.payload());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TpPoolFixed<
+    ChannelId,
+    Timestamp,
+    const SLOTS: usize,
+    const N: usize,
+    const SECTIONS: usize = TP_BUF_FIXED_DEFAULT_SECTIONS,
+> {
+    /// Buffers (a buffer is in use if the stream with the same index is set).
+    bufs: [TpBufFixed<N, SECTIONS>; SLOTS],
+
+    /// Streams currently being reconstructed.
+    streams: [Option<TpPoolFixedStream<ChannelId, Timestamp>>; SLOTS],
+
+    /// Policy used when all slots are in use.
+    eviction_policy: TpEvictionPolicy,
+
+    /// Header fields used to separate the streams.
+    key_fields: TpStreamKeyFields,
+
+    /// Counter used to order the streams by start & last use.
+    tick: u64,
+
+    /// Number of streams that were dropped to make room for new streams.
+    evicted_count: u64,
+}
+
+#[derive(Debug, Clone)]
+struct TpPoolFixedStream<ChannelId, Timestamp> {
+    key: TpStreamKey<ChannelId>,
+    timestamp: Timestamp,
+    started: u64,
+    last_used: u64,
+}
+
+impl<ChannelId, Timestamp, const SLOTS: usize, const N: usize, const SECTIONS: usize>
+    TpPoolFixed<ChannelId, Timestamp, SLOTS, N, SECTIONS>
+where
+    ChannelId: Eq,
+{
+    pub fn new(
+        eviction_policy: TpEvictionPolicy,
+    ) -> TpPoolFixed<ChannelId, Timestamp, SLOTS, N, SECTIONS> {
+        const { assert!(SLOTS > 0, "a TpPoolFixed needs at least one slot") };
+        TpPoolFixed {
+            bufs: core::array::from_fn(|_| TpBufFixed::new()),
+            streams: core::array::from_fn(|_| None),
+            eviction_policy,
+            key_fields: Default::default(),
+            tick: 0,
+            evicted_count: 0,
+        }
+    }
+
+    #[inline]
+    pub fn eviction_policy(&self) -> TpEvictionPolicy {
+        self.eviction_policy
+    }
+
+    #[inline]
+    pub fn key_fields(&self) -> TpStreamKeyFields {
+        self.key_fields
+    }
+
+    /// Sets the SOMEIP header fields used to separate the streams (should
+    /// be set before the first segment is consumed, as already active
+    /// streams can no longer be continued once the key changes).
+    pub fn set_key_fields(&mut self, key_fields: TpStreamKeyFields) {
+        self.key_fields = key_fields;
+    }
+
+    /// Sets the handling of segments overlapping already received data
+    /// for all buffers of the pool.
+    pub fn set_overlap_policy(&mut self, overlap_policy: TpOverlapPolicy) {
+        for buf in self.bufs.iter_mut() {
+            buf.set_overlap_policy(overlap_policy);
+        }
+    }
+
+    /// Number of streams currently being reconstructed.
+    #[inline]
+    pub fn active_count(&self) -> usize {
+        self.streams.iter().filter(|s| s.is_some()).count()
+    }
+
+    /// Iterator over the key & last timestamp of the streams currently
+    /// being reconstructed.
+    pub fn active_streams(&self) -> impl Iterator<Item = (&TpStreamKey<ChannelId>, &Timestamp)> {
+        self.streams
+            .iter()
+            .flatten()
+            .map(|s| (&s.key, &s.timestamp))
+    }
+
+    /// Number of streams that were dropped to make room for new streams.
+    #[inline]
+    pub fn evicted_count(&self) -> u64 {
+        self.evicted_count
+    }
+
+    /// Returns the index of the slot that should be used for a new stream
+    /// (evicting an active stream if no slot is free).
+    fn free_slot(&mut self) -> usize {
+        if let Some(index) = self.streams.iter().position(|s| s.is_none()) {
+            return index;
+        }
+        let key = |s: &Option<TpPoolFixedStream<ChannelId, Timestamp>>| {
+            s.as_ref().map_or(0, |s| match self.eviction_policy {
+                TpEvictionPolicy::LeastRecentlyUsed => s.last_used,
+                TpEvictionPolicy::Oldest => s.started,
+            })
+        };
+        let (index, _) = self
+            .streams
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, s)| key(s))
+            .unwrap();
+        self.streams[index] = None;
+        self.evicted_count += 1;
+        index
+    }
+
+    pub fn consume<'a: 'c, 'b: 'c, 'c: 'a + 'b>(
+        &'a mut self,
+        id: ChannelId,
+        timestamp: Timestamp,
+        someip_slice: SomeipMsgSlice<'b>,
+    ) -> Result<Option<SomeipMsgSlice<'c>>, err::TpReassembleError> {
+        if false == someip_slice.is_tp() {
+            return Ok(Some(someip_slice));
+        }
+
+        self.tick += 1;
+        let key = TpStreamKey::new(id, &someip_slice, self.key_fields);
+        let index = self
+            .streams
+            .iter()
+            .position(|s| matches!(s, Some(s) if s.key == key));
+
+        let index = if let Some(index) = index {
+            // stream already known consume the data & update the timestamp
+            self.bufs[index].consume_tp(someip_slice)?;
+            let stream = self.streams[index].as_mut().unwrap();
+            stream.timestamp = timestamp;
+            stream.last_used = self.tick;
+            index
+        } else {
+            // validate the segment before an active stream gets evicted
+            // (a cleared buffer accepts every valid segment)
+            TpSegment::from_slice(
+                &someip_slice,
+                TpBufFixed::<N, SECTIONS>::MAX_PAYLOAD_LEN,
+                None,
+            )?;
+
+            // new stream get a free slot
+            let index = self.free_slot();
+            let buf = &mut self.bufs[index];
+            buf.clear();
+            buf.consume_tp(someip_slice)?;
+            self.streams[index] = Some(TpPoolFixedStream {
+                key,
+                timestamp,
+                started: self.tick,
+                last_used: self.tick,
+            });
+            index
+        };
+
+        // check if the stream is complete
+        if self.bufs[index].is_complete() {
+            // free the slot, the data stays valid until the buffer is reused
+            self.streams[index] = None;
+            Ok(self.bufs[index].try_finalize())
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Retains only the streams specified by the predicate and returns
+    /// the number of dropped streams.
+    pub fn retain<F>(&mut self, f: F) -> usize
+    where
+        F: Fn(&Timestamp) -> bool,
+    {
+        let mut dropped = 0;
+        for stream in self.streams.iter_mut() {
+            if matches!(stream, Some(s) if false == f(&s.timestamp)) {
+                *stream = None;
+                dropped += 1;
+            }
+        }
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec::Vec};
+
+    fn packet(request_id: u32, offset: u32, more_segments: bool, payload: &[u8]) -> Vec<u8> {
+        packet_with_message_id(1234, request_id, offset, more_segments, payload)
+    }
+
+    fn packet_with_message_id(
+        message_id: u32,
+        request_id: u32,
+        offset: u32,
+        more_segments: bool,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id,
+            length: 8 + 4 + payload.len() as u32,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: Some(TpHeader::with_offset(offset, more_segments).unwrap()),
+        };
+        let mut result = header.base_to_bytes().to_vec();
+        result.extend_from_slice(&header.tp_header.as_ref().unwrap().to_bytes());
+        result.extend_from_slice(payload);
+        result
+    }
+
+    type Pool = TpPoolFixed<u8, u32, 2, { 16 + 64 }>;
+
+    fn send(
+        pool: &mut Pool,
+        id: u8,
+        timestamp: u32,
+        request_id: u32,
+        offset: u32,
+        more_segments: bool,
+    ) -> Result<Option<Vec<u8>>, err::TpReassembleError> {
+        let packet = packet(request_id, offset, more_segments, &[request_id as u8; 16]);
+        pool.consume(id, timestamp, SomeipMsgSlice::from_slice(&packet).unwrap())
+            .map(|r| r.map(|msg| msg.payload().to_vec()))
+    }
+
+    #[test]
+    fn debug_clone() {
+        let pool = Pool::new(TpEvictionPolicy::Oldest);
+        let _ = format!("{:?}", pool.clone());
+        assert_eq!(TpEvictionPolicy::Oldest, pool.eviction_policy());
+        assert_eq!(0, pool.active_count());
+        assert_eq!(0, pool.evicted_count());
+    }
+
+    #[test]
+    fn consume() {
+        let mut pool = Pool::new(TpEvictionPolicy::LeastRecentlyUsed);
+
+        // non tp packets are passed through
+        {
+            let header = SomeipHeaderBuilder::notification(1, 2).build(0).unwrap();
+            let bytes = header.base_to_bytes();
+            let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+            assert_eq!(Ok(Some(slice.clone())), pool.consume(0, 0, slice));
+        }
+
+        // streams are separated by channel & request id
+        assert_eq!(Ok(None), send(&mut pool, 0, 1, 1, 0, true));
+        assert_eq!(Ok(None), send(&mut pool, 1, 2, 1, 0, true));
+        assert_eq!(2, pool.active_count());
+        assert_eq!(
+            Ok(Some([[1u8; 16], [1u8; 16]].concat())),
+            send(&mut pool, 1, 3, 1, 16, false)
+        );
+        assert_eq!(1, pool.active_count());
+        assert_eq!(
+            Some((
+                &TpStreamKey {
+                    channel_id: 0,
+                    message_id: 1234,
+                    request_id: 1,
+                    interface_version: 0,
+                },
+                &1
+            )),
+            pool.active_streams().next()
+        );
+
+        // errors of a new stream do not occupy a slot
+        let packet = packet(5, 0, true, &[0u8; 15]);
+        assert!(pool
+            .consume(0, 4, SomeipMsgSlice::from_slice(&packet).unwrap())
+            .is_err());
+        assert_eq!(1, pool.active_count());
+        assert_eq!(0, pool.evicted_count());
+
+        // errors of a new stream do not evict a stream of a full pool
+        assert_eq!(Ok(None), send(&mut pool, 0, 5, 6, 0, true));
+        assert_eq!(2, pool.active_count());
+        assert!(pool
+            .consume(0, 6, SomeipMsgSlice::from_slice(&packet).unwrap())
+            .is_err());
+        assert_eq!(0, pool.evicted_count());
+        assert_eq!(2, pool.active_count());
+    }
+
+    #[test]
+    fn key_fields() {
+        // two services sending a stream with the same request id in parallel
+        let send = |pool: &mut Pool, message_id: u32, offset: u32, more_segments: bool| {
+            let packet = packet_with_message_id(
+                message_id,
+                1,
+                offset,
+                more_segments,
+                &[message_id as u8; 16],
+            );
+            pool.consume(0, 0, SomeipMsgSlice::from_slice(&packet).unwrap())
+                .map(|r| r.map(|msg| msg.payload().to_vec()))
+        };
+
+        // by default the message id separates the streams
+        let mut pool = Pool::new(TpEvictionPolicy::LeastRecentlyUsed);
+        assert_eq!(TpStreamKeyFields::MessageIdAndRequestId, pool.key_fields());
+        assert_eq!(Ok(None), send(&mut pool, 1234, 0, true));
+        assert_eq!(Ok(None), send(&mut pool, 1235, 0, true));
+        assert_eq!(2, pool.active_count());
+        assert_eq!(
+            Ok(Some([[1234u32 as u8; 16], [1234u32 as u8; 16]].concat())),
+            send(&mut pool, 1234, 16, false)
+        );
+        assert_eq!(
+            Ok(Some([[1235u32 as u8; 16], [1235u32 as u8; 16]].concat())),
+            send(&mut pool, 1235, 16, false)
+        );
+
+        // only separated by the request id the streams get mixed up
+        let mut pool = Pool::new(TpEvictionPolicy::LeastRecentlyUsed);
+        pool.set_key_fields(TpStreamKeyFields::RequestId);
+        assert_eq!(TpStreamKeyFields::RequestId, pool.key_fields());
+        assert_eq!(Ok(None), send(&mut pool, 1234, 0, true));
+        assert_eq!(Ok(None), send(&mut pool, 1235, 0, true));
+        assert_eq!(1, pool.active_count());
+    }
+
+    #[test]
+    fn eviction() {
+        for (policy, expected_remaining) in [
+            (TpEvictionPolicy::LeastRecentlyUsed, 1),
+            (TpEvictionPolicy::Oldest, 2),
+        ] {
+            let mut pool = Pool::new(policy);
+            assert_eq!(Ok(None), send(&mut pool, 0, 0, 1, 0, true));
+            assert_eq!(Ok(None), send(&mut pool, 0, 0, 2, 0, true));
+            // stream 1 gets used again (stream 2 is now the least recently used)
+            assert_eq!(Ok(None), send(&mut pool, 0, 0, 1, 16, true));
+            // starting stream 3 evicts a stream
+            assert_eq!(Ok(None), send(&mut pool, 0, 0, 3, 0, true));
+            assert_eq!(1, pool.evicted_count());
+            assert_eq!(2, pool.active_count());
+            assert!(pool
+                .active_streams()
+                .any(|(key, _)| key.request_id == expected_remaining));
+            assert!(pool.active_streams().any(|(key, _)| key.request_id == 3));
+        }
+    }
+
+    #[test]
+    fn retain() {
+        let mut pool = Pool::new(TpEvictionPolicy::LeastRecentlyUsed);
+        assert_eq!(Ok(None), send(&mut pool, 0, 10, 1, 0, true));
+        assert_eq!(Ok(None), send(&mut pool, 0, 20, 2, 0, true));
+        assert_eq!(0, pool.retain(|_| true));
+        assert_eq!(2, pool.active_count());
+        assert_eq!(1, pool.retain(|t| *t > 15));
+        assert_eq!(1, pool.active_count());
+        assert_eq!(
+            Some((2, &20)),
+            pool.active_streams()
+                .next()
+                .map(|(key, t)| (key.request_id, t))
+        );
+        assert_eq!(0, pool.evicted_count());
+
+        // the slot of a dropped stream gets reused without an eviction
+        assert_eq!(Ok(None), send(&mut pool, 0, 30, 3, 0, true));
+        assert_eq!(2, pool.active_count());
+        assert_eq!(0, pool.evicted_count());
+        assert_eq!(2, pool.retain(|_| false));
+        assert_eq!(0, pool.active_count());
+    }
+}
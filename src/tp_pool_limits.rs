@@ -0,0 +1,64 @@
+use crate::*;
+
+/// Limits of a [`TpPool`] protecting against an unbounded memory usage
+/// (e.g. by a flood of first segments that never get completed).
+///
+/// All limits are disabled (`None`) by default.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let limits = TpPoolLimits {
+///     max_buffered_bytes: Some(1024 * 1024),
+///     max_streams: Some(64),
+///     max_streams_per_channel: Some(8),
+///     max_finished_bufs: Some(4),
+///     overflow_policy: TpPoolOverflowPolicy::EvictOldest,
+/// };
+/// let pool: TpPool<u32, u64> = TpPool::with_limits(Default::default(), limits);
+/// assert_eq!(&limits, pool.limits());
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TpPoolLimits {
+    /// Maximum number of bytes (SOMEIP header + reassembled payload)
+    /// allocated over all active streams (see [`TpPool::allocated_len`]).
+    ///
+    /// If set, the buffers of the active streams only allocate the memory
+    /// required for the data received so far (instead of pre-allocating
+    /// [`TpBufConfig::tp_buffer_start_payload_alloc_len`] bytes).
+    pub max_buffered_bytes: Option<usize>,
+
+    /// Maximum number of active streams.
+    pub max_streams: Option<usize>,
+
+    /// Maximum number of active streams with the same channel id.
+    pub max_streams_per_channel: Option<usize>,
+
+    /// Maximum number of finished buffers kept for re-use (the buffer of
+    /// the last completed stream is always kept as the returned slice
+    /// references it).
+    pub max_finished_bufs: Option<usize>,
+
+    /// Reaction if a segment would exceed one of the limits.
+    pub overflow_policy: TpPoolOverflowPolicy,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let limits = TpPoolLimits::default();
+        let _ = format!("{:?}", limits);
+        assert_eq!(limits, limits.clone());
+        assert_eq!(None, limits.max_buffered_bytes);
+        assert_eq!(None, limits.max_streams);
+        assert_eq!(None, limits.max_streams_per_channel);
+        assert_eq!(None, limits.max_finished_bufs);
+        assert_eq!(TpPoolOverflowPolicy::EvictOldest, limits.overflow_policy);
+    }
+}
@@ -0,0 +1,25 @@
+/// Policy deciding how a [`crate::TpPool`] reacts if a segment would
+/// exceed one of the configured [`crate::TpPoolLimits`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TpPoolOverflowPolicy {
+    /// Drop the streams with the oldest timestamps until the segment fits
+    /// (reported via [`crate::TpPool::drain_drop_events`]).
+    #[default]
+    EvictOldest,
+    /// Reject the segment with an error and keep the active streams.
+    Reject,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let policy = TpPoolOverflowPolicy::default();
+        assert_eq!(TpPoolOverflowPolicy::EvictOldest, policy);
+        assert_eq!(policy, policy.clone());
+        assert_eq!("Reject", format!("{:?}", TpPoolOverflowPolicy::Reject));
+    }
+}
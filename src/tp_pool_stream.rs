@@ -0,0 +1,31 @@
+use crate::*;
+
+/// Unfinished TP stream held by a [`TpPool`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TpPoolStream<Timestamp> {
+    /// Buffer containing the data received so far.
+    pub buf: TpBuf,
+
+    /// Timestamp of the first segment received for the stream.
+    pub first_timestamp: Timestamp,
+
+    /// Timestamp of the last segment received for the stream.
+    pub last_timestamp: Timestamp,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let stream = TpPoolStream {
+            buf: TpBuf::new(Default::default()),
+            first_timestamp: 1u32,
+            last_timestamp: 2u32,
+        };
+        let _ = format!("{:?}", stream);
+        assert_eq!(stream, stream.clone());
+    }
+}
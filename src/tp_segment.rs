@@ -0,0 +1,272 @@
+use crate::*;
+
+/// How a segment relates to the already received data of a TP stream.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum TpSegmentOverlap {
+    /// No byte of the segment was received before.
+    None,
+    /// All bytes of the segment were received before.
+    Duplicate,
+    /// Some bytes of the segment were received before.
+    Overlap,
+}
+
+/// Validated SOMEIP TP segment (shared between the different TP
+/// reassembly buffers so all of them apply the same rules).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct TpSegment<'a> {
+    /// SOMEIP header of the segment (without the TP header).
+    pub header: &'a [u8],
+    /// Offset of the segment payload in the reassembled payload.
+    pub offset: u32,
+    /// Value of the "more segments" flag.
+    pub more_segments: bool,
+    /// Payload of the segment (without TP header).
+    pub payload: &'a [u8],
+}
+
+impl<'a> TpSegment<'a> {
+    /// Validates a TP segment against the maximum allowed payload length
+    /// and the end of the stream (if a segment without the "more segments"
+    /// flag was previously received).
+    ///
+    /// The caller must ensure that `someip_slice.is_tp()` is `true`.
+    pub fn from_slice(
+        someip_slice: &SomeipMsgSlice<'a>,
+        max_payload_len: u32,
+        previous_end: Option<u32>,
+    ) -> Result<TpSegment<'a>, err::TpReassembleError> {
+        use err::TpReassembleError::*;
+
+        assert!(someip_slice.is_tp());
+
+        let tp_header = someip_slice.tp_header().unwrap();
+        let offset = tp_header.offset();
+        let payload = someip_slice.payload();
+
+        // validate lengths
+        if (max_payload_len < offset) || ((max_payload_len - offset) as usize) < payload.len() {
+            return Err(SegmentTooBig {
+                offset,
+                payload_len: payload.len(),
+                max: max_payload_len,
+            });
+        }
+
+        // validate that the payload len is a multiple of 16 in case it is not the end
+        if tp_header.more_segment && 0 != payload.len() & 0b1111 {
+            return Err(UnalignedTpPayloadLen {
+                offset,
+                payload_len: payload.len(),
+            });
+        }
+
+        let result = TpSegment {
+            header: &someip_slice.slice()[..SOMEIP_HEADER_LENGTH],
+            offset,
+            more_segments: tp_header.more_segment,
+            payload,
+        };
+
+        // check the section is not already ended
+        if let Some(previous_end) = previous_end {
+            let end = result.end();
+            // either the end is after the current position
+            if previous_end < end || ((false == result.more_segments) && end != previous_end) {
+                return Err(ConflictingEnd {
+                    previous_end,
+                    conflicting_end: end,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Offset + payload length of the segment.
+    #[inline]
+    pub fn end(&self) -> u32 {
+        // can not overflow as it is checked against the maximum payload len
+        self.offset + self.payload.len() as u32
+    }
+
+    /// Range of the reassembled payload covered by the segment.
+    #[inline]
+    pub fn range(&self) -> TpRange {
+        TpRange {
+            start: self.offset,
+            end: self.end(),
+        }
+    }
+
+    /// Copies the SOMEIP header of the segment without the TP flag to
+    /// the start of `target`.
+    #[inline]
+    pub fn write_header(&self, target: &mut [u8]) {
+        target[..SOMEIP_HEADER_LENGTH].copy_from_slice(self.header);
+        // remove TP flag
+        target[4 * 3 + 2] &= !SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG;
+    }
+
+    /// Checks that the message id, interface version, message type (without
+    /// the TP flag) & return code of the segment match the given SOMEIP
+    /// header of a previously received segment.
+    #[cfg(feature = "alloc")]
+    pub fn check_header(
+        &self,
+        expected: &[u8; SOMEIP_HEADER_LENGTH],
+    ) -> Result<(), err::TpReassembleError> {
+        use err::TpReassembleError::*;
+
+        let message_id = |h: &[u8]| u32::from_be_bytes([h[0], h[1], h[2], h[3]]);
+        if message_id(expected) != message_id(self.header) {
+            return Err(MessageIdMismatch {
+                expected: message_id(expected),
+                actual: message_id(self.header),
+            });
+        }
+        if expected[13] != self.header[13] {
+            return Err(InterfaceVersionMismatch {
+                expected: expected[13],
+                actual: self.header[13],
+            });
+        }
+        let message_type = |h: &[u8]| h[14] & !SOMEIP_HEADER_MESSAGE_TYPE_TP_FLAG;
+        if message_type(expected) != message_type(self.header) {
+            return Err(MessageTypeMismatch {
+                expected: message_type(expected),
+                actual: message_type(self.header),
+            });
+        }
+        if expected[15] != self.header[15] {
+            return Err(ReturnCodeMismatch {
+                expected: expected[15],
+                actual: self.header[15],
+            });
+        }
+        Ok(())
+    }
+
+    /// Determines how the segment overlaps the already received `sections`.
+    pub fn overlap(&self, sections: &[TpRange]) -> TpSegmentOverlap {
+        let range = self.range();
+        let mut overlapping = false;
+        for section in sections {
+            if section.start <= range.start && range.end <= section.end && range.start < range.end {
+                // sections are merged, so a covered segment is always
+                // covered by a single section
+                return TpSegmentOverlap::Duplicate;
+            }
+            overlapping = overlapping || (section.start < range.end && range.start < section.end);
+        }
+        if overlapping {
+            TpSegmentOverlap::Overlap
+        } else {
+            TpSegmentOverlap::None
+        }
+    }
+
+    /// Writes the header (if the offset is 0) & payload of the segment to
+    /// `data` (SOMEIP header + reassembled payload) applying the given
+    /// policy to the bytes already received in `sections`.
+    ///
+    /// `data` must be big enough to store the segment. In case of an error
+    /// `data` is left unchanged.
+    pub fn write_to(
+        &self,
+        data: &mut [u8],
+        sections: &[TpRange],
+        policy: TpOverlapPolicy,
+    ) -> Result<(), err::TpReassembleError> {
+        let payload_data = &mut data[SOMEIP_HEADER_LENGTH..];
+        match policy {
+            TpOverlapPolicy::AcceptLast => {
+                if 0 == self.offset {
+                    self.write_header(data);
+                }
+                let start = self.offset as usize;
+                data[SOMEIP_HEADER_LENGTH + start
+                    ..SOMEIP_HEADER_LENGTH + start + self.payload.len()]
+                    .copy_from_slice(self.payload);
+            }
+            TpOverlapPolicy::AcceptFirst => {
+                let first_at_zero = sections.iter().any(|s| 0 == s.start && s.start < s.end);
+                self.for_each_part(sections, |start, end, received| {
+                    if false == received {
+                        payload_data[start as usize..end as usize].copy_from_slice(
+                            &self.payload
+                                [(start - self.offset) as usize..(end - self.offset) as usize],
+                        );
+                    }
+                    Ok(())
+                })?;
+                if 0 == self.offset && false == first_at_zero {
+                    self.write_header(data);
+                }
+            }
+            TpOverlapPolicy::RejectConflicting => {
+                // check all received parts before changing anything
+                self.check_conflicts(data, sections, policy)?;
+                return self.write_to(data, sections, TpOverlapPolicy::AcceptLast);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the segment would be accepted by [`TpSegment::write_to`]
+    /// with the given policy (only [`TpOverlapPolicy::RejectConflicting`]
+    /// rejects segments whose bytes differ from the already received bytes).
+    pub fn check_conflicts(
+        &self,
+        data: &[u8],
+        sections: &[TpRange],
+        policy: TpOverlapPolicy,
+    ) -> Result<(), err::TpReassembleError> {
+        if TpOverlapPolicy::RejectConflicting != policy {
+            return Ok(());
+        }
+        // data is empty as long as nothing was received
+        let payload_data = data.get(SOMEIP_HEADER_LENGTH..).unwrap_or(&[]);
+        self.for_each_part(sections, |start, end, received| {
+            if received {
+                let existing = &payload_data[start as usize..end as usize];
+                let new =
+                    &self.payload[(start - self.offset) as usize..(end - self.offset) as usize];
+                if let Some(pos) = existing.iter().zip(new).position(|(a, b)| a != b) {
+                    return Err(err::TpReassembleError::ConflictingOverlap {
+                        offset: self.offset,
+                        payload_len: self.payload.len(),
+                        conflict_offset: start + pos as u32,
+                    });
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Calls `f` with the start, end & "already received" flag of the
+    /// consecutive parts of the segment (in ascending order).
+    fn for_each_part<F>(&self, sections: &[TpRange], mut f: F) -> Result<(), err::TpReassembleError>
+    where
+        F: FnMut(u32, u32, bool) -> Result<(), err::TpReassembleError>,
+    {
+        let end = self.end();
+        let mut pos = self.offset;
+        while pos < end {
+            if let Some(section) = sections.iter().find(|s| s.start <= pos && pos < s.end) {
+                let part_end = core::cmp::min(section.end, end);
+                f(pos, part_end, true)?;
+                pos = part_end;
+            } else {
+                let part_end = sections
+                    .iter()
+                    .filter(|s| pos < s.start)
+                    .map(|s| s.start)
+                    .fold(end, core::cmp::min);
+                f(pos, part_end, false)?;
+                pos = part_end;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::*;
+
+/// Key identifying a TP stream in a [`TpPool`].
+///
+/// Fields that are not part of the configured [`TpStreamKeyFields`] are
+/// set to 0.
+///
+/// ```
+/// use someip_parse::*;
+///
+/// let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+///     .request_id(0x0001_0002)
+///     .build(0)
+///     .unwrap();
+/// let bytes = header.base_to_bytes();
+/// let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+///
+/// assert_eq!(
+///     TpStreamKey {
+///         channel_id: 1u8,
+///         message_id: 0x1234_8001,
+///         request_id: 0x0001_0002,
+///         interface_version: 0,
+///     },
+///     TpStreamKey::new(1u8, &slice, TpStreamKeyFields::MessageIdAndRequestId)
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TpStreamKey<ChannelId> {
+    /// Channel the segments were received on (e.g. the source address).
+    pub channel_id: ChannelId,
+
+    /// Message id (service id & method/event id).
+    pub message_id: u32,
+
+    /// Request id (client id & session id).
+    pub request_id: u32,
+
+    /// Interface version.
+    pub interface_version: u8,
+}
+
+impl<ChannelId> TpStreamKey<ChannelId> {
+    /// Creates the key of the stream the given SOMEIP message belongs to.
+    pub fn new(
+        channel_id: ChannelId,
+        someip_slice: &SomeipMsgSlice,
+        fields: TpStreamKeyFields,
+    ) -> TpStreamKey<ChannelId> {
+        use TpStreamKeyFields::*;
+        TpStreamKey {
+            channel_id,
+            message_id: match fields {
+                RequestId => 0,
+                MessageIdAndRequestId | MessageIdRequestIdAndInterfaceVersion => {
+                    someip_slice.message_id()
+                }
+            },
+            request_id: someip_slice.request_id(),
+            interface_version: match fields {
+                RequestId | MessageIdAndRequestId => 0,
+                MessageIdRequestIdAndInterfaceVersion => someip_slice.interface_version(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq() {
+        let key = TpStreamKey {
+            channel_id: 1u8,
+            message_id: 2,
+            request_id: 3,
+            interface_version: 4,
+        };
+        let _ = format!("{:?}", key);
+        assert_eq!(key, key.clone());
+    }
+
+    #[test]
+    fn new() {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: SOMEIP_LEN_OFFSET_TO_PAYLOAD,
+            request_id: 0x0001_0002,
+            interface_version: 3,
+            message_type: MessageType::Notification,
+            ..Default::default()
+        };
+        let bytes = header.base_to_bytes();
+        let slice = SomeipMsgSlice::from_slice(&bytes).unwrap();
+
+        use TpStreamKeyFields::*;
+        for (fields, message_id, interface_version) in [
+            (RequestId, 0, 0),
+            (MessageIdAndRequestId, 0x1234_8001, 0),
+            (MessageIdRequestIdAndInterfaceVersion, 0x1234_8001, 3),
+        ] {
+            assert_eq!(
+                TpStreamKey {
+                    channel_id: 5u8,
+                    message_id,
+                    request_id: 0x0001_0002,
+                    interface_version,
+                },
+                TpStreamKey::new(5u8, &slice, fields)
+            );
+        }
+    }
+}
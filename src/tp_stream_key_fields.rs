@@ -0,0 +1,27 @@
+/// SOMEIP header fields (in addition to the channel id) used by a
+/// [`crate::TpPool`] to separate TP streams.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TpStreamKeyFields {
+    /// Only the request id (client id & session id) is used.
+    RequestId,
+    /// The message id & request id are used (as defined by the SOMEIP TP
+    /// specification).
+    #[default]
+    MessageIdAndRequestId,
+    /// The message id, request id & interface version are used.
+    MessageIdRequestIdAndInterfaceVersion,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn debug_clone_eq_default() {
+        let fields = TpStreamKeyFields::default();
+        assert_eq!(TpStreamKeyFields::MessageIdAndRequestId, fields);
+        assert_eq!(fields, fields.clone());
+        assert_eq!("RequestId", format!("{:?}", TpStreamKeyFields::RequestId));
+    }
+}
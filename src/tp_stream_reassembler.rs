@@ -0,0 +1,540 @@
+use crate::*;
+use alloc::vec::Vec;
+
+/// Reassembles one SOMEIP TP packet stream and hands the payload to a
+/// sink as soon as a contiguous prefix of it was received (instead of
+/// buffering the complete message like [`TpBuf`]).
+///
+/// Only segments arriving out of order are buffered. They must lie within
+/// the reorder window (the configured number of bytes after the already
+/// delivered payload), otherwise they are rejected with
+/// [`err::TpReassembleError::ReorderWindowExceeded`]. The segments are
+/// validated with the same rules as [`TpBuf::consume_tp`].
+///
+/// Payload that was already handed to the sink can not be changed anymore,
+/// so bytes of later segments overlapping it are ignored.
+///
+/// As with [`TpBuf`] the caller has to ensure that only segments with
+/// matching message id, request id & sender are passed to the reassembler.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::*;
+///
+/// // build three segments
+/// let mut segments = [[0u8; SOMEIP_HEADER_LENGTH + 4 + 16]; 3];
+/// for (i, segment) in segments.iter_mut().enumerate() {
+///     let header = SomeipHeaderBuilder::notification(0x1234, 0x0001)
+///         .tp_header(TpHeader::with_offset(16 * i as u32, i < 2).unwrap())
+///         .build(16)
+///         .unwrap();
+///     SomeipMsgWriter::write(&header, &[i as u8; 16], segment).unwrap();
+/// }
+///
+/// // allow up to 1024 bytes of out of order data to be buffered
+/// let mut reassembler = TpStreamReassembler::new(TpBufConfig::MAX_TP_PAYLOAD_LEN, 1024).unwrap();
+/// let mut received = Vec::new();
+/// for i in [0, 2, 1] {
+///     let slice = SomeipMsgSlice::from_slice(&segments[i]).unwrap();
+///     reassembler
+///         .consume_tp(slice, |payload| received.extend_from_slice(payload))
+///         .unwrap();
+/// }
+/// assert!(reassembler.is_complete());
+/// assert_eq!(48, received.len());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TpStreamReassembler {
+    /// Maximum allowed payload length of the reassembled message.
+    max_payload_len: u32,
+    /// Maximum number of bytes after the delivered payload that can be buffered.
+    reorder_window: u32,
+    /// SOMEIP header of the segment with offset 0 (without the TP flag).
+    header: Option<[u8; SOMEIP_HEADER_LENGTH]>,
+    /// Number of payload bytes handed to the sink.
+    delivered: u32,
+    /// Set to the extended end size.
+    end: Option<u32>,
+    /// Out of order data (index 0 corresponds to the payload offset `delivered`).
+    pending: Vec<u8>,
+    /// Ranges of the buffered out of order data (absolute payload offsets).
+    sections: Vec<TpRange>,
+}
+
+impl TpStreamReassembler {
+    /// Creates a reassembler accepting messages with up to `max_payload_len`
+    /// bytes of payload and buffering up to `reorder_window` bytes of out of
+    /// order data.
+    pub fn new(
+        max_payload_len: u32,
+        reorder_window: u32,
+    ) -> Result<TpStreamReassembler, err::TpBufConfigError> {
+        if max_payload_len > TpBufConfig::MAX_TP_PAYLOAD_LEN {
+            return Err(err::TpBufConfigError::MaxPayloadLenTooBig {
+                allowed_max: TpBufConfig::MAX_TP_PAYLOAD_LEN,
+                actual: max_payload_len,
+            });
+        }
+        Ok(TpStreamReassembler {
+            max_payload_len,
+            reorder_window,
+            header: None,
+            delivered: 0,
+            end: None,
+            pending: Vec::new(),
+            sections: Vec::new(),
+        })
+    }
+
+    /// Reset the reassembler to the starting state (e.g. to receive the next message).
+    pub fn clear(&mut self) {
+        self.header = None;
+        self.delivered = 0;
+        self.end = None;
+        self.pending.clear();
+        self.sections.clear();
+    }
+
+    #[inline]
+    pub fn max_payload_len(&self) -> u32 {
+        self.max_payload_len
+    }
+
+    #[inline]
+    pub fn reorder_window(&self) -> u32 {
+        self.reorder_window
+    }
+
+    /// Number of payload bytes already handed to the sink.
+    #[inline]
+    pub fn delivered_len(&self) -> u32 {
+        self.delivered
+    }
+
+    /// End of the payload, known once the segment without the "more
+    /// segments" flag was received.
+    #[inline]
+    pub fn expected_end(&self) -> Option<u32> {
+        self.end
+    }
+
+    /// Number of out of order bytes currently buffered.
+    #[inline]
+    pub fn pending_len(&self) -> usize {
+        self.sections
+            .iter()
+            .map(|s| (s.end - s.start) as usize)
+            .sum()
+    }
+
+    /// Returns true if the complete payload was handed to the sink.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.end == Some(self.delivered) && self.header.is_some()
+    }
+
+    /// SOMEIP header of the segment with offset 0 (without TP flag).
+    ///
+    /// The length field is set based on the expected end if it is known,
+    /// otherwise based on the number of already delivered payload bytes.
+    pub fn header(&self) -> Option<SomeipHeader> {
+        let mut bytes = self.header?;
+        bytes[4..8].copy_from_slice(&SOMEIP_LEN_OFFSET_TO_PAYLOAD.to_be_bytes());
+        let mut header = SomeipMsgSlice::from_slice(&bytes).ok()?.to_header();
+        header.length = SOMEIP_LEN_OFFSET_TO_PAYLOAD + self.end.unwrap_or(self.delivered);
+        Some(header)
+    }
+
+    /// Consume a TP SOMEIP slice (caller must ensure that `someip_slice.is_tp()`
+    /// is `true`) and pass the newly available contiguous payload to `sink`.
+    pub fn consume_tp<F>(
+        &mut self,
+        someip_slice: SomeipMsgSlice,
+        mut sink: F,
+    ) -> Result<(), err::TpReassembleError>
+    where
+        F: FnMut(&[u8]),
+    {
+        self.try_consume_tp(someip_slice, |payload| {
+            sink(payload);
+            Ok(())
+        })
+    }
+
+    /// Consume a TP SOMEIP slice (caller must ensure that `someip_slice.is_tp()`
+    /// is `true`) and write the newly available contiguous payload to `writer`.
+    ///
+    /// If writing fails the not yet written payload stays buffered (if it
+    /// was received out of order) or has to be received again.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn consume_tp_write<W: std::io::Write>(
+        &mut self,
+        someip_slice: SomeipMsgSlice,
+        writer: &mut W,
+    ) -> Result<(), err::TpStreamWriteError> {
+        self.try_consume_tp(someip_slice, |payload| {
+            writer
+                .write_all(payload)
+                .map_err(err::TpStreamWriteError::Io)
+        })
+    }
+
+    fn try_consume_tp<E, F>(&mut self, someip_slice: SomeipMsgSlice, mut sink: F) -> Result<(), E>
+    where
+        E: From<err::TpReassembleError>,
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        let segment = TpSegment::from_slice(&someip_slice, self.max_payload_len, self.end)?;
+
+        // the payload delivered so far can not conflict with the end
+        if false == segment.more_segments && segment.end() < self.delivered {
+            return Err(err::TpReassembleError::ConflictingEnd {
+                previous_end: self.delivered,
+                conflicting_end: segment.end(),
+            }
+            .into());
+        }
+
+        // the buffered payload can not conflict with the end
+        if false == segment.more_segments {
+            if let Some(buffered_end) = self.sections.iter().map(|s| s.end).max() {
+                if buffered_end > segment.end() {
+                    return Err(err::TpReassembleError::ConflictingEnd {
+                        previous_end: buffered_end,
+                        conflicting_end: segment.end(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        // check the segment fits into the reorder window (only required
+        // if the segment can not be delivered directly)
+        let window_end = self.delivered.saturating_add(self.reorder_window);
+        let direct =
+            segment.offset <= self.delivered && (self.header.is_some() || 0 == segment.offset);
+        if false == direct && segment.end() > window_end {
+            return Err(err::TpReassembleError::ReorderWindowExceeded {
+                offset: segment.offset,
+                payload_len: segment.payload.len(),
+                window_end,
+            }
+            .into());
+        }
+
+        // the end is only set once the data of the segment was accepted
+        let expected_end = if segment.more_segments {
+            self.end
+        } else {
+            Some(segment.end())
+        };
+
+        if direct {
+            if self.header.is_none() {
+                let mut header = [0u8; SOMEIP_HEADER_LENGTH];
+                segment.write_header(&mut header);
+                self.header = Some(header);
+            }
+            // skip the already delivered part
+            let skip = core::cmp::min(
+                (self.delivered - segment.offset) as usize,
+                segment.payload.len(),
+            );
+            let new_data = &segment.payload[skip..];
+            if false == new_data.is_empty() {
+                sink(new_data)?;
+                self.advance(new_data.len() as u32);
+            }
+            self.end = expected_end;
+        } else {
+            // buffer the part that was not yet delivered
+            let start = core::cmp::max(segment.offset, self.delivered);
+            let end = segment.end();
+            if start < end {
+                let pending_start = (start - self.delivered) as usize;
+                let pending_end = (end - self.delivered) as usize;
+                if self.pending.len() < pending_end {
+                    self.pending.resize(pending_end, 0);
+                }
+                self.pending[pending_start..pending_end]
+                    .copy_from_slice(&segment.payload[(start - segment.offset) as usize..]);
+
+                let mut new_section = TpRange { start, end };
+                self.sections.retain(|it| {
+                    if let Some(merged) = new_section.merge(*it) {
+                        new_section = merged;
+                        false
+                    } else {
+                        true
+                    }
+                });
+                self.sections.push(new_section);
+            }
+            self.end = expected_end;
+        }
+
+        // deliver buffered data that became contiguous
+        if self.header.is_some() {
+            while let Some(section) = self
+                .sections
+                .iter()
+                .find(|s| s.start <= self.delivered)
+                .copied()
+            {
+                let len = section.end - self.delivered;
+                sink(&self.pending[..len as usize])?;
+                self.advance(len);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Marks `len` bytes as delivered & removes them from the pending data.
+    fn advance(&mut self, len: u32) {
+        self.delivered += len;
+        let pending_len = core::cmp::min(len as usize, self.pending.len());
+        self.pending.drain(..pending_len);
+        let delivered = self.delivered;
+        self.sections.retain(|s| s.end > delivered);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec, vec::Vec};
+    use err::TpReassembleError::*;
+
+    fn packet(offset: u32, more_segments: bool, payload: &[u8]) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id: 1234,
+            length: 8 + 4 + payload.len() as u32,
+            request_id: 23,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header: Some(TpHeader::with_offset(offset, more_segments).unwrap()),
+        };
+        let mut result = header.base_to_bytes().to_vec();
+        result.extend_from_slice(&header.tp_header.as_ref().unwrap().to_bytes());
+        result.extend_from_slice(payload);
+        result
+    }
+
+    /// Returns a u8 vec counting up from "start" until len is reached (truncating bits greater then u8).
+    fn sequence(start: usize, len: usize) -> Vec<u8> {
+        (start..start + len).map(|i| (i & 0xff) as u8).collect()
+    }
+
+    /// Sends a segment and returns the chunks handed to the sink.
+    fn send(
+        reassembler: &mut TpStreamReassembler,
+        offset: u32,
+        more_segments: bool,
+        payload: &[u8],
+    ) -> Result<Vec<Vec<u8>>, err::TpReassembleError> {
+        let packet = packet(offset, more_segments, payload);
+        let mut chunks = Vec::new();
+        reassembler.consume_tp(SomeipMsgSlice::from_slice(&packet).unwrap(), |data| {
+            chunks.push(data.to_vec())
+        })?;
+        Ok(chunks)
+    }
+
+    #[test]
+    fn debug_clone_eq() {
+        let reassembler = TpStreamReassembler::new(1024, 64).unwrap();
+        let _ = format!("{:?}", reassembler);
+        assert_eq!(reassembler, reassembler.clone());
+        assert_eq!(1024, reassembler.max_payload_len());
+        assert_eq!(64, reassembler.reorder_window());
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            Err(err::TpBufConfigError::MaxPayloadLenTooBig {
+                allowed_max: TpBufConfig::MAX_TP_PAYLOAD_LEN,
+                actual: TpBufConfig::MAX_TP_PAYLOAD_LEN + 1,
+            }),
+            TpStreamReassembler::new(TpBufConfig::MAX_TP_PAYLOAD_LEN + 1, 0)
+        );
+    }
+
+    #[test]
+    fn in_order() {
+        // no reorder window required for in order data
+        let mut r = TpStreamReassembler::new(1024, 0).unwrap();
+        assert_eq!(
+            Ok(vec![sequence(0, 16)]),
+            send(&mut r, 0, true, &sequence(0, 16))
+        );
+        assert_eq!(
+            Ok(vec![sequence(16, 32)]),
+            send(&mut r, 16, true, &sequence(16, 32))
+        );
+        assert!(false == r.is_complete());
+        assert_eq!(
+            Ok(vec![sequence(48, 5)]),
+            send(&mut r, 48, false, &sequence(48, 5))
+        );
+        assert!(r.is_complete());
+        assert_eq!(53, r.delivered_len());
+        assert_eq!(0, r.pending_len());
+        assert_eq!(
+            SomeipHeader {
+                message_id: 1234,
+                length: 8 + 53,
+                request_id: 23,
+                interface_version: 1,
+                message_type: MessageType::Notification,
+                return_code: 0,
+                tp_header: None,
+            },
+            r.header().unwrap()
+        );
+
+        r.clear();
+        assert_eq!(None, r.header());
+        assert_eq!(0, r.delivered_len());
+        assert_eq!(None, r.expected_end());
+    }
+
+    #[test]
+    fn out_of_order() {
+        let mut r = TpStreamReassembler::new(1024, 64).unwrap();
+
+        // data before the first segment gets buffered
+        assert_eq!(Ok(vec![]), send(&mut r, 32, true, &sequence(32, 16)));
+        assert_eq!(Ok(vec![]), send(&mut r, 16, true, &sequence(16, 16)));
+        assert_eq!(32, r.pending_len());
+        assert_eq!(None, r.header());
+
+        // first segment delivers all contiguous data
+        assert_eq!(
+            Ok(vec![sequence(0, 16), sequence(16, 32)]),
+            send(&mut r, 0, true, &sequence(0, 16))
+        );
+        assert_eq!(0, r.pending_len());
+        assert_eq!(48 + 8, r.header().unwrap().length);
+
+        // overlapping segments only deliver new data
+        assert_eq!(Ok(vec![]), send(&mut r, 64, false, &sequence(64, 16)));
+        assert_eq!(Some(80), r.expected_end());
+        assert_eq!(
+            Ok(vec![sequence(48, 16), sequence(64, 16)]),
+            send(&mut r, 32, true, &sequence(32, 32))
+        );
+        assert!(r.is_complete());
+        assert_eq!(80 + 8, r.header().unwrap().length);
+
+        // duplicates deliver nothing
+        assert_eq!(Ok(vec![]), send(&mut r, 0, true, &sequence(0, 16)));
+    }
+
+    #[test]
+    fn errors() {
+        let mut r = TpStreamReassembler::new(128, 32).unwrap();
+
+        // shared validation with TpBuf
+        assert_eq!(
+            Err(UnalignedTpPayloadLen {
+                offset: 0,
+                payload_len: 15
+            }),
+            send(&mut r, 0, true, &sequence(0, 15))
+        );
+        assert_eq!(
+            Err(SegmentTooBig {
+                offset: 128,
+                payload_len: 16,
+                max: 128
+            }),
+            send(&mut r, 128, true, &sequence(0, 16))
+        );
+
+        // reorder window
+        assert_eq!(
+            Err(ReorderWindowExceeded {
+                offset: 32,
+                payload_len: 16,
+                window_end: 32
+            }),
+            send(&mut r, 32, true, &sequence(32, 16))
+        );
+        send(&mut r, 0, true, &sequence(0, 32)).unwrap();
+        send(&mut r, 48, true, &sequence(48, 16)).unwrap();
+
+        // end before the already delivered data
+        assert_eq!(
+            Err(ConflictingEnd {
+                previous_end: 32,
+                conflicting_end: 16
+            }),
+            send(&mut r, 0, false, &sequence(0, 16))
+        );
+
+        // end before the already buffered data
+        assert_eq!(
+            Err(ConflictingEnd {
+                previous_end: 64,
+                conflicting_end: 48
+            }),
+            send(&mut r, 32, false, &sequence(32, 16))
+        );
+        assert_eq!(None, r.expected_end());
+        assert_eq!(
+            Ok(vec![sequence(32, 16), sequence(48, 16)]),
+            send(&mut r, 32, true, &sequence(32, 16))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn consume_tp_write() {
+        let mut r = TpStreamReassembler::new(1024, 64).unwrap();
+        let mut out = Vec::new();
+        for (offset, more) in [(16, true), (0, true), (32, false)] {
+            let packet = packet(offset, more, &sequence(offset as usize, 16));
+            r.consume_tp_write(SomeipMsgSlice::from_slice(&packet).unwrap(), &mut out)
+                .unwrap();
+        }
+        assert!(r.is_complete());
+        assert_eq!(sequence(0, 48), out);
+
+        // io errors are forwarded
+        {
+            let mut r = TpStreamReassembler::new(1024, 64).unwrap();
+            let packet = packet(0, false, &sequence(0, 16));
+            let mut buffer = [0u8; 8];
+            let mut writer = &mut buffer[..];
+            assert!(r
+                .consume_tp_write(SomeipMsgSlice::from_slice(&packet).unwrap(), &mut writer)
+                .unwrap_err()
+                .io_error()
+                .is_some());
+
+            // the end of a segment is only set once its data was written
+            assert_eq!(None, r.expected_end());
+            assert!(false == r.is_complete());
+        }
+
+        // reassembly errors are forwarded
+        {
+            let mut r = TpStreamReassembler::new(1024, 64).unwrap();
+            let packet = packet(0, true, &sequence(0, 15));
+            assert_eq!(
+                Some(UnalignedTpPayloadLen {
+                    offset: 0,
+                    payload_len: 15
+                }),
+                r.consume_tp_write(SomeipMsgSlice::from_slice(&packet).unwrap(), &mut out)
+                    .unwrap_err()
+                    .reassemble_error()
+            );
+        }
+    }
+}
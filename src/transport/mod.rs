@@ -0,0 +1,133 @@
+use crate::{err::TransportError, *};
+use std::vec::Vec;
+
+mod tcp_recv_buf;
+pub(crate) use tcp_recv_buf::*;
+
+mod udp_recv_buf;
+pub(crate) use udp_recv_buf::*;
+
+mod udp_segments;
+pub(crate) use udp_segments::*;
+
+mod tcp_endpoint;
+pub use tcp_endpoint::*;
+
+mod udp_endpoint;
+pub use udp_endpoint::*;
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+mod someip_tcp_stream;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use someip_tcp_stream::*;
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+mod someip_udp_socket;
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub use someip_udp_socket::*;
+
+/// Default maximum length of a SOMEIP message (header + payload) that is
+/// accepted when receiving messages via TCP.
+pub const DEFAULT_TCP_MAX_MESSAGE_LEN: usize = 4 * 1024 * 1024;
+
+/// Maximum length of a SOMEIP message (header + payload) based on the
+/// limits of the length field.
+pub(crate) fn max_message_len() -> usize {
+    usize::try_from(u64::from(SOMEIP_MAX_PAYLOAD_LEN) + SOMEIP_HEADER_LENGTH as u64)
+        .unwrap_or(usize::MAX)
+}
+
+/// Appends the serialized header & payload to `out`.
+///
+/// The `length` field of the header is ignored and instead calculated based
+/// on the payload length & the presence of a TP header.
+pub(crate) fn write_msg(
+    header: &SomeipHeader,
+    payload: &[u8],
+    out: &mut Vec<u8>,
+) -> Result<(), TransportError> {
+    let tp_len = if header.tp_header.is_some() {
+        TP_HEADER_LENGTH
+    } else {
+        0
+    };
+    let len = payload.len().saturating_add(SOMEIP_HEADER_LENGTH + tp_len);
+    let max_len = max_message_len();
+    if len > max_len {
+        return Err(TransportError::MessageTooBig { len, max_len });
+    }
+
+    let header = SomeipHeader {
+        length: (len - (SOMEIP_HEADER_LENGTH - SOMEIP_LEN_OFFSET_TO_PAYLOAD as usize)) as u32,
+        ..header.clone()
+    };
+    out.reserve(len);
+    out.extend_from_slice(&header.base_to_bytes());
+    if let Some(tp) = &header.tp_header {
+        out.extend_from_slice(&tp.to_bytes());
+    }
+    out.extend_from_slice(payload);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_msg() {
+        // without tp header
+        {
+            let header = SomeipHeader {
+                message_id: 0x1234_5678,
+                // wrong length should be ignored
+                length: 0,
+                request_id: 0x9abc_def0,
+                interface_version: 1,
+                message_type: MessageType::Request,
+                return_code: 0,
+                tp_header: None,
+            };
+            let mut out = Vec::new();
+            super::write_msg(&header, &[1, 2, 3], &mut out).unwrap();
+            let msg = SomeipMsgSlice::from_slice(&out).unwrap();
+            assert_eq!(out.len(), msg.slice().len());
+            assert_eq!(
+                msg.to_header(),
+                SomeipHeader {
+                    length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 3,
+                    ..header
+                }
+            );
+            assert_eq!(msg.payload(), &[1, 2, 3]);
+        }
+        // with tp header
+        {
+            let header = SomeipHeader {
+                message_id: 0x1234_5678,
+                length: 0,
+                request_id: 0x9abc_def0,
+                interface_version: 1,
+                message_type: MessageType::Notification,
+                return_code: 0,
+                tp_header: Some(TpHeader::with_offset(32, true).unwrap()),
+            };
+            let mut out = Vec::new();
+            super::write_msg(&header, &[4; 16], &mut out).unwrap();
+            let msg = SomeipMsgSlice::from_slice(&out).unwrap();
+            assert_eq!(out.len(), msg.slice().len());
+            assert_eq!(
+                msg.to_header(),
+                SomeipHeader {
+                    length: SOMEIP_LEN_OFFSET_TO_PAYLOAD + 4 + 16,
+                    ..header
+                }
+            );
+            assert_eq!(msg.payload(), &[4; 16]);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use super::*;
+use std::{io, net::SocketAddr};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+/// Asynchronous TCP stream sending & receiving SOMEIP messages.
+///
+/// Received data is split into SOMEIP messages based on the `length` field
+/// of the SOMEIP headers. Magic cookies are skipped and used to
+/// resynchronize the stream after a broken message was received.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{transport::SomeipTcpStream, MagicCookie, MessageType, SomeipHeader};
+/// use tokio::net::TcpListener;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), someip_parse::err::TransportError> {
+/// let listener = TcpListener::bind("127.0.0.1:0").await?;
+/// let mut client = SomeipTcpStream::connect(listener.local_addr()?).await?;
+/// let mut server = SomeipTcpStream::new(listener.accept().await?.0, MagicCookie::Server);
+///
+/// let header = SomeipHeader {
+///     message_id: 0x1234_0001,
+///     length: 0, // calculated when sending
+///     request_id: 0x0001_0001,
+///     interface_version: 1,
+///     message_type: MessageType::Request,
+///     return_code: 0,
+///     tp_header: None,
+/// };
+/// client.send_magic_cookie().await?;
+/// client.send(&header, &[1, 2, 3]).await?;
+///
+/// let msg = server.recv().await?.unwrap();
+/// assert_eq!(0x1234_0001, msg.message_id());
+/// assert_eq!(&[1, 2, 3], msg.payload());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SomeipTcpStream {
+    stream: TcpStream,
+    recv_buf: TcpRecvBuf,
+    send_buf: Vec<u8>,
+    magic_cookie: MagicCookie,
+}
+
+impl SomeipTcpStream {
+    /// Connects to the given address as a client.
+    ///
+    /// Magic cookies sent via [`SomeipTcpStream::send_magic_cookie`] are
+    /// client magic cookies.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> Result<SomeipTcpStream, TransportError> {
+        Ok(SomeipTcpStream::new(
+            TcpStream::connect(addr).await?,
+            MagicCookie::Client,
+        ))
+    }
+
+    /// Creates a SOMEIP stream from a connected tokio TCP stream.
+    ///
+    /// `magic_cookie` is the type of magic cookie sent via
+    /// [`SomeipTcpStream::send_magic_cookie`] (client or server).
+    pub fn new(stream: TcpStream, magic_cookie: MagicCookie) -> SomeipTcpStream {
+        SomeipTcpStream {
+            stream,
+            recv_buf: Default::default(),
+            send_buf: Vec::new(),
+            magic_cookie,
+        }
+    }
+
+    /// Returns a reference to the underlying tokio TCP stream.
+    #[inline]
+    pub fn get_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    /// Consumes the SOMEIP stream and returns the underlying tokio TCP
+    /// stream. Received data that was not yet returned is discarded.
+    #[inline]
+    pub fn into_inner(self) -> TcpStream {
+        self.stream
+    }
+
+    /// Returns the local address of the stream.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.local_addr()
+    }
+
+    /// Returns the address of the remote peer.
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    /// Type of magic cookie sent via [`SomeipTcpStream::send_magic_cookie`].
+    #[inline]
+    pub fn magic_cookie(&self) -> MagicCookie {
+        self.magic_cookie
+    }
+
+    /// Maximum length of a received message (header + payload). Bigger
+    /// messages are rejected with a [`TransportError::MessageTooBig`] error.
+    ///
+    /// Defaults to [`DEFAULT_TCP_MAX_MESSAGE_LEN`].
+    #[inline]
+    pub fn max_message_len(&self) -> usize {
+        self.recv_buf.max_message_len()
+    }
+
+    /// Sets the maximum length of a received message (header + payload).
+    #[inline]
+    pub fn set_max_message_len(&mut self, max_message_len: usize) {
+        self.recv_buf.set_max_message_len(max_message_len);
+    }
+
+    /// Sends a SOMEIP message with the given header & payload.
+    ///
+    /// The `length` field of the header is ignored and instead calculated
+    /// based on the payload length & the presence of a TP header.
+    pub async fn send(
+        &mut self,
+        header: &SomeipHeader,
+        payload: &[u8],
+    ) -> Result<(), TransportError> {
+        self.send_buf.clear();
+        write_msg(header, payload, &mut self.send_buf)?;
+        self.stream.write_all(&self.send_buf).await?;
+        Ok(())
+    }
+
+    /// Sends a magic cookie allowing the receiver to resynchronize.
+    pub async fn send_magic_cookie(&mut self) -> Result<(), TransportError> {
+        self.stream.write_all(&self.magic_cookie.to_bytes()).await?;
+        Ok(())
+    }
+
+    /// Receives the next SOMEIP message.
+    ///
+    /// `Ok(None)` is returned if the stream was closed by the peer after a
+    /// complete message. If the stream is closed in the middle of a message
+    /// an [`std::io::ErrorKind::UnexpectedEof`] error is returned. After
+    /// errors when decoding a message the stream skips data until the
+    /// next magic cookie and can continue to be used.
+    ///
+    /// This method is cancel safe.
+    pub async fn recv(&mut self) -> Result<Option<SomeipMsgSlice<'_>>, TransportError> {
+        loop {
+            if let Some(result) = self.recv_buf.next() {
+                let range = result?;
+                return Ok(Some(self.recv_buf.get(range)));
+            }
+            let len = self.stream.read(self.recv_buf.read_buf()).await?;
+            if 0 == len {
+                return if self.recv_buf.has_pending() {
+                    Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+                } else {
+                    Ok(None)
+                };
+            }
+            self.recv_buf.advance(len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn header(request_id: u32) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_0001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Request,
+            return_code: 0,
+            tp_header: None,
+        }
+    }
+
+    async fn pair() -> (SomeipTcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = SomeipTcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn send() {
+        let (mut client, server) = pair().await;
+        assert_eq!(MagicCookie::Client, client.magic_cookie());
+        assert_eq!(client.local_addr().unwrap(), server.peer_addr().unwrap());
+        assert_eq!(client.peer_addr().unwrap(), server.local_addr().unwrap());
+
+        let mut server = SomeipTcpStream::new(server, MagicCookie::Server);
+        client.send_magic_cookie().await.unwrap();
+        client.send(&header(1), &[1, 2, 3]).await.unwrap();
+        client.send(&header(2), &[]).await.unwrap();
+        server.send_magic_cookie().await.unwrap();
+        server.send(&header(3), &[4]).await.unwrap();
+
+        {
+            let msg = server.recv().await.unwrap().unwrap();
+            assert_eq!(1, msg.request_id());
+            assert_eq!(&[1, 2, 3], msg.payload());
+        }
+        assert_eq!(2, server.recv().await.unwrap().unwrap().request_id());
+        {
+            let msg = client.recv().await.unwrap().unwrap();
+            assert_eq!(3, msg.request_id());
+            assert_eq!(&[4], msg.payload());
+        }
+
+        // clean close
+        drop(client);
+        assert!(server.recv().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn recv() {
+        let (mut client, mut server) = pair().await;
+
+        // split writes
+        let mut data = Vec::new();
+        write_msg(&header(1), &[1, 2, 3], &mut data).unwrap();
+        server.write_all(&data[..5]).await.unwrap();
+        server.flush().await.unwrap();
+        tokio::task::yield_now().await;
+        server.write_all(&data[5..]).await.unwrap();
+        assert_eq!(1, client.recv().await.unwrap().unwrap().request_id());
+
+        // resync after garbage
+        let mut data = vec![0xff; 16];
+        data[4..8].copy_from_slice(&8u32.to_be_bytes());
+        data.extend_from_slice(&MagicCookie::Server.to_bytes());
+        write_msg(&header(2), &[], &mut data).unwrap();
+        server.write_all(&data).await.unwrap();
+        assert!(matches!(client.recv().await, Err(TransportError::Slice(_))));
+        assert_eq!(2, client.recv().await.unwrap().unwrap().request_id());
+
+        // too big message
+        client.set_max_message_len(SOMEIP_HEADER_LENGTH);
+        assert_eq!(SOMEIP_HEADER_LENGTH, client.max_message_len());
+        let mut data = Vec::new();
+        write_msg(&header(3), &[1], &mut data).unwrap();
+        server.write_all(&data).await.unwrap();
+        assert!(matches!(
+            client.recv().await,
+            Err(TransportError::MessageTooBig {
+                len: 17,
+                max_len: 16
+            })
+        ));
+
+        // eof in the middle of a message
+        server
+            .write_all(&MagicCookie::Server.to_bytes())
+            .await
+            .unwrap();
+        server.write_all(&data[..4]).await.unwrap();
+        drop(server);
+        let err = client.recv().await.unwrap_err().io_error().unwrap();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+
+        let _ = client.get_ref();
+        let _ = client.into_inner();
+    }
+}
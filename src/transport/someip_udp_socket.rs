@@ -0,0 +1,320 @@
+use super::*;
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{Duration, Instant},
+};
+use tokio::net::{ToSocketAddrs, UdpSocket};
+
+/// Asynchronous UDP socket sending & receiving SOMEIP messages.
+///
+/// Received datagrams are split into the contained SOMEIP messages and
+/// SOMEIP TP segments are reassembled (separately for each sender) before
+/// they are returned. Messages with a payload bigger then
+/// [`SOMEIP_MAX_PAYLOAD_LEN_UDP`] are automatically split into SOMEIP TP
+/// segments when sending.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{transport::SomeipUdpSocket, MessageType, SomeipHeader};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), someip_parse::err::TransportError> {
+/// let mut server = SomeipUdpSocket::bind("127.0.0.1:0").await?;
+/// let client = SomeipUdpSocket::bind("127.0.0.1:0").await?;
+///
+/// let header = SomeipHeader {
+///     message_id: 0x1234_0001,
+///     length: 0, // calculated when sending
+///     request_id: 0x0001_0001,
+///     interface_version: 1,
+///     message_type: MessageType::Request,
+///     return_code: 0,
+///     tp_header: None,
+/// };
+/// client.send_to(&header, &[1, 2, 3], server.local_addr()?).await?;
+///
+/// let (msg, source) = server.recv_from().await?;
+/// assert_eq!(0x1234_0001, msg.message_id());
+/// assert_eq!(&[1, 2, 3], msg.payload());
+/// assert_eq!(client.local_addr()?, source);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SomeipUdpSocket {
+    socket: UdpSocket,
+    recv_buf: UdpRecvBuf,
+}
+
+impl SomeipUdpSocket {
+    /// Creates a new socket bound to the given address with the default
+    /// TP reassembly configuration.
+    pub async fn bind<A: ToSocketAddrs>(addr: A) -> Result<SomeipUdpSocket, TransportError> {
+        Ok(SomeipUdpSocket::from_socket(
+            UdpSocket::bind(addr).await?,
+            Default::default(),
+        ))
+    }
+
+    /// Creates a new SOMEIP socket from an already bound tokio UDP socket.
+    ///
+    /// `tp_buf_config` is used for the reassembly of SOMEIP TP messages.
+    pub fn from_socket(socket: UdpSocket, tp_buf_config: TpBufConfig) -> SomeipUdpSocket {
+        SomeipUdpSocket {
+            socket,
+            recv_buf: UdpRecvBuf::new(tp_buf_config),
+        }
+    }
+
+    /// Returns a reference to the underlying tokio UDP socket.
+    #[inline]
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Consumes the SOMEIP socket and returns the underlying tokio UDP socket.
+    #[inline]
+    pub fn into_inner(self) -> UdpSocket {
+        self.socket
+    }
+
+    /// Returns the local address the socket is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Returns the pool used to reassemble SOMEIP TP messages.
+    #[inline]
+    pub fn tp_pool(&self) -> &TpPool<SocketAddr, Instant> {
+        self.recv_buf.tp_pool()
+    }
+
+    /// Discards all unfinished SOMEIP TP messages that did not receive a
+    /// segment within the given duration.
+    pub fn retain_tp_streams(&mut self, max_age: Duration) {
+        self.recv_buf
+            .tp_pool_mut()
+            .retain(|last_update| last_update.elapsed() <= max_age);
+    }
+
+    /// Joins the given IPv4 multicast group (e.g. the SOMEIP SD
+    /// multicast group) on the given interface.
+    #[inline]
+    pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.socket.join_multicast_v4(multiaddr, interface)
+    }
+
+    /// Leaves the given IPv4 multicast group on the given interface.
+    #[inline]
+    pub fn leave_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        self.socket.leave_multicast_v4(multiaddr, interface)
+    }
+
+    /// Joins the given IPv6 multicast group (e.g. the SOMEIP SD
+    /// multicast group) on the interface with the given index.
+    #[inline]
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.socket.join_multicast_v6(multiaddr, interface)
+    }
+
+    /// Leaves the given IPv6 multicast group on the interface with the
+    /// given index.
+    #[inline]
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.socket.leave_multicast_v6(multiaddr, interface)
+    }
+
+    /// Sends a SOMEIP message with the given header & payload to the given
+    /// target.
+    ///
+    /// The `length` field of the header is ignored and instead calculated
+    /// based on the payload length & the presence of a TP header. If the
+    /// header contains no TP header and the payload is bigger then
+    /// [`SOMEIP_MAX_PAYLOAD_LEN_UDP`] the message is split into SOMEIP TP
+    /// segments, each sent as a separate datagram.
+    pub async fn send_to<A: ToSocketAddrs>(
+        &self,
+        header: &SomeipHeader,
+        payload: &[u8],
+        target: A,
+    ) -> Result<(), TransportError> {
+        // resolve the address once so all segments get sent to the same target
+        let target = tokio::net::lookup_host(target)
+            .await?
+            .next()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::AddrNotAvailable))?;
+
+        let mut datagram = Vec::new();
+        for (segment_header, segment_payload) in UdpSegments::new(header, payload)? {
+            datagram.clear();
+            write_msg(&segment_header, segment_payload, &mut datagram)?;
+            if datagram.len() > MAX_UDP_PAYLOAD_LEN {
+                return Err(TransportError::MessageTooBig {
+                    len: datagram.len(),
+                    max_len: MAX_UDP_PAYLOAD_LEN,
+                });
+            }
+            self.socket.send_to(&datagram, target).await?;
+        }
+        Ok(())
+    }
+
+    /// Receives the next SOMEIP message & the address of its sender.
+    ///
+    /// SOMEIP TP segments are consumed internally and only the reassembled
+    /// message is returned once all segments have been received. Errors
+    /// when decoding a message are returned, the socket can continue to be
+    /// used afterwards.
+    ///
+    /// This method is cancel safe.
+    pub async fn recv_from(&mut self) -> Result<(SomeipMsgSlice<'_>, SocketAddr), TransportError> {
+        loop {
+            if let Some(result) = self.recv_buf.next() {
+                let location = result?;
+                return Ok(self.recv_buf.get(&location));
+            }
+            let (len, source) = self.socket.recv_from(self.recv_buf.datagram_buf()).await?;
+            self.recv_buf.set_received(len, source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(request_id: u32, tp_header: Option<TpHeader>) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header,
+        }
+    }
+
+    #[tokio::test]
+    async fn send_recv() {
+        let mut receiver = SomeipUdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let sender = SomeipUdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let target = receiver.local_addr().unwrap();
+        let sender_addr = sender.local_addr().unwrap();
+
+        // plain message
+        sender
+            .send_to(&header(1, None), &[1, 2, 3], target)
+            .await
+            .unwrap();
+        {
+            let (msg, source) = receiver.recv_from().await.unwrap();
+            assert_eq!(1, msg.request_id());
+            assert_eq!(&[1, 2, 3], msg.payload());
+            assert_eq!(sender_addr, source);
+        }
+
+        // tp segments
+        sender
+            .send_to(
+                &header(2, Some(TpHeader::with_offset(0, true).unwrap())),
+                &[1; 16],
+                target,
+            )
+            .await
+            .unwrap();
+        sender
+            .send_to(
+                &header(2, Some(TpHeader::with_offset(16, false).unwrap())),
+                &[2; 3],
+                target,
+            )
+            .await
+            .unwrap();
+        {
+            let (msg, source) = receiver.recv_from().await.unwrap();
+            assert_eq!(2, msg.request_id());
+            assert_eq!(false, msg.is_tp());
+            assert_eq!(19, msg.payload().len());
+            assert_eq!(sender_addr, source);
+        }
+
+        // unfinished tp stream
+        sender
+            .send_to(
+                &header(3, Some(TpHeader::with_offset(0, true).unwrap())),
+                &[1; 16],
+                target,
+            )
+            .await
+            .unwrap();
+        // multiple messages in one datagram
+        {
+            let mut datagram = Vec::new();
+            write_msg(&header(4, None), &[4], &mut datagram).unwrap();
+            write_msg(&header(5, None), &[5], &mut datagram).unwrap();
+            sender.socket().send_to(&datagram, target).await.unwrap();
+        }
+        assert_eq!(4, receiver.recv_from().await.unwrap().0.request_id());
+        assert_eq!(1, receiver.tp_pool().active_bufs().len());
+        assert_eq!(5, receiver.recv_from().await.unwrap().0.request_id());
+
+        receiver.retain_tp_streams(Duration::from_secs(60));
+        assert_eq!(1, receiver.tp_pool().active_bufs().len());
+        std::thread::sleep(Duration::from_millis(1));
+        receiver.retain_tp_streams(Duration::ZERO);
+        assert_eq!(0, receiver.tp_pool().active_bufs().len());
+
+        // broken datagram
+        sender.socket().send_to(&[0; 4], target).await.unwrap();
+        assert!(matches!(
+            receiver.recv_from().await,
+            Err(TransportError::Slice(_))
+        ));
+
+        // automatically segmented message
+        {
+            let payload: Vec<u8> = (0..5000u32).map(|v| v as u8).collect();
+            sender
+                .send_to(&header(6, None), &payload, target)
+                .await
+                .unwrap();
+            let (msg, _) = receiver.recv_from().await.unwrap();
+            assert_eq!(6, msg.request_id());
+            assert_eq!(&payload[..], msg.payload());
+        }
+
+        // too big tp segment
+        assert!(matches!(
+            sender
+                .send_to(
+                    &header(7, Some(TpHeader::new(false))),
+                    &[0; MAX_UDP_PAYLOAD_LEN],
+                    target
+                )
+                .await,
+            Err(TransportError::MessageTooBig { .. })
+        ));
+
+        let socket = receiver.into_inner();
+        assert_eq!(target, socket.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn multicast() {
+        let socket = SomeipUdpSocket::bind("0.0.0.0:0").await.unwrap();
+        let group = Ipv4Addr::new(224, 244, 224, 245);
+        // joining can fail if no multicast capable interface is present
+        if socket
+            .join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+            .is_ok()
+        {
+            socket
+                .leave_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+                .unwrap();
+        }
+    }
+}
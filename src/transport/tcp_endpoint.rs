@@ -0,0 +1,334 @@
+use super::*;
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+/// Blocking TCP endpoint sending & receiving SOMEIP messages.
+///
+/// Received data is split into SOMEIP messages based on the `length` field
+/// of the SOMEIP headers. Magic cookies are skipped and used to
+/// resynchronize the stream after a broken message was received.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{transport::TcpEndpoint, MagicCookie, MessageType, SomeipHeader};
+/// use std::net::TcpListener;
+///
+/// # fn main() -> Result<(), someip_parse::err::TransportError> {
+/// let listener = TcpListener::bind("127.0.0.1:0")?;
+/// let mut client = TcpEndpoint::connect(listener.local_addr()?)?;
+/// let mut server = TcpEndpoint::new(listener.accept()?.0, MagicCookie::Server);
+///
+/// let header = SomeipHeader {
+///     message_id: 0x1234_0001,
+///     length: 0, // calculated when sending
+///     request_id: 0x0001_0001,
+///     interface_version: 1,
+///     message_type: MessageType::Request,
+///     return_code: 0,
+///     tp_header: None,
+/// };
+/// client.send_magic_cookie()?;
+/// client.send(&header, &[1, 2, 3])?;
+///
+/// let msg = server.recv()?.unwrap();
+/// assert_eq!(0x1234_0001, msg.message_id());
+/// assert_eq!(&[1, 2, 3], msg.payload());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TcpEndpoint {
+    stream: TcpStream,
+    recv_buf: TcpRecvBuf,
+    send_buf: Vec<u8>,
+    magic_cookie: MagicCookie,
+}
+
+impl TcpEndpoint {
+    /// Connects to the given address as a client.
+    ///
+    /// Magic cookies sent via [`TcpEndpoint::send_magic_cookie`] are
+    /// client magic cookies.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> Result<TcpEndpoint, TransportError> {
+        Ok(TcpEndpoint::new(
+            TcpStream::connect(addr)?,
+            MagicCookie::Client,
+        ))
+    }
+
+    /// Connects to the given address as a client with a timeout.
+    pub fn connect_timeout(
+        addr: &SocketAddr,
+        timeout: Duration,
+    ) -> Result<TcpEndpoint, TransportError> {
+        Ok(TcpEndpoint::new(
+            TcpStream::connect_timeout(addr, timeout)?,
+            MagicCookie::Client,
+        ))
+    }
+
+    /// Creates an endpoint from a connected TCP stream.
+    ///
+    /// `magic_cookie` is the type of magic cookie sent via
+    /// [`TcpEndpoint::send_magic_cookie`] (client or server).
+    pub fn new(stream: TcpStream, magic_cookie: MagicCookie) -> TcpEndpoint {
+        TcpEndpoint {
+            stream,
+            recv_buf: Default::default(),
+            send_buf: Vec::new(),
+            magic_cookie,
+        }
+    }
+
+    /// Returns a reference to the underlying TCP stream.
+    #[inline]
+    pub fn get_ref(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    /// Consumes the endpoint and returns the underlying TCP stream.
+    /// Received data that was not yet returned is discarded.
+    #[inline]
+    pub fn into_inner(self) -> TcpStream {
+        self.stream
+    }
+
+    /// Returns the local address of the endpoint.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.local_addr()
+    }
+
+    /// Returns the address of the remote peer.
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    /// Sets the timeout of [`TcpEndpoint::recv`] (`None` blocks
+    /// indefinitely).
+    ///
+    /// If the timeout is reached an IO error with the kind
+    /// `WouldBlock` or `TimedOut` (depending on the platform) is returned.
+    /// Already received parts of a message are kept and the message can
+    /// still be received by calling [`TcpEndpoint::recv`] again.
+    #[inline]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.stream.set_read_timeout(timeout)
+    }
+
+    /// Returns the timeout of [`TcpEndpoint::recv`].
+    #[inline]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.stream.read_timeout()
+    }
+
+    /// Sets the timeout of [`TcpEndpoint::send`] (`None` blocks
+    /// indefinitely).
+    #[inline]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.stream.set_write_timeout(timeout)
+    }
+
+    /// Returns the timeout of [`TcpEndpoint::send`].
+    #[inline]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        self.stream.write_timeout()
+    }
+
+    /// Type of magic cookie sent via [`TcpEndpoint::send_magic_cookie`].
+    #[inline]
+    pub fn magic_cookie(&self) -> MagicCookie {
+        self.magic_cookie
+    }
+
+    /// Maximum length of a received message (header + payload). Bigger
+    /// messages are rejected with a [`TransportError::MessageTooBig`] error.
+    ///
+    /// Defaults to [`DEFAULT_TCP_MAX_MESSAGE_LEN`].
+    #[inline]
+    pub fn max_message_len(&self) -> usize {
+        self.recv_buf.max_message_len()
+    }
+
+    /// Sets the maximum length of a received message (header + payload).
+    #[inline]
+    pub fn set_max_message_len(&mut self, max_message_len: usize) {
+        self.recv_buf.set_max_message_len(max_message_len);
+    }
+
+    /// Sends a SOMEIP message with the given header & payload.
+    ///
+    /// The `length` field of the header is ignored and instead calculated
+    /// based on the payload length & the presence of a TP header.
+    pub fn send(&mut self, header: &SomeipHeader, payload: &[u8]) -> Result<(), TransportError> {
+        self.send_buf.clear();
+        write_msg(header, payload, &mut self.send_buf)?;
+        self.stream.write_all(&self.send_buf)?;
+        Ok(())
+    }
+
+    /// Sends a magic cookie allowing the receiver to resynchronize.
+    pub fn send_magic_cookie(&mut self) -> Result<(), TransportError> {
+        self.stream.write_all(&self.magic_cookie.to_bytes())?;
+        Ok(())
+    }
+
+    /// Receives the next SOMEIP message.
+    ///
+    /// `Ok(None)` is returned if the stream was closed by the peer after a
+    /// complete message. If the stream is closed in the middle of a message
+    /// an [`std::io::ErrorKind::UnexpectedEof`] error is returned. After
+    /// errors when decoding a message the endpoint skips data until the
+    /// next magic cookie and can continue to be used.
+    pub fn recv(&mut self) -> Result<Option<SomeipMsgSlice<'_>>, TransportError> {
+        loop {
+            if let Some(result) = self.recv_buf.next() {
+                let range = result?;
+                return Ok(Some(self.recv_buf.get(range)));
+            }
+            let len = match self.stream.read(self.recv_buf.read_buf()) {
+                Ok(len) => len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if 0 == len {
+                return if self.recv_buf.has_pending() {
+                    Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+                } else {
+                    Ok(None)
+                };
+            }
+            self.recv_buf.advance(len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn header(request_id: u32) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_0001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Request,
+            return_code: 0,
+            tp_header: None,
+        }
+    }
+
+    fn pair() -> (TcpEndpoint, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client =
+            TcpEndpoint::connect_timeout(&listener.local_addr().unwrap(), Duration::from_secs(5))
+                .unwrap();
+        let (server, _) = listener.accept().unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        (client, server)
+    }
+
+    #[test]
+    fn send() {
+        let (mut client, server) = pair();
+        assert_eq!(MagicCookie::Client, client.magic_cookie());
+        assert_eq!(client.local_addr().unwrap(), server.peer_addr().unwrap());
+        assert_eq!(client.peer_addr().unwrap(), server.local_addr().unwrap());
+
+        let mut server = TcpEndpoint::new(server, MagicCookie::Server);
+        server
+            .set_write_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(
+            Some(Duration::from_secs(5)),
+            server.write_timeout().unwrap()
+        );
+        client.send_magic_cookie().unwrap();
+        client.send(&header(1), &[1, 2, 3]).unwrap();
+        client.send(&header(2), &[]).unwrap();
+        server.send_magic_cookie().unwrap();
+        server.send(&header(3), &[4]).unwrap();
+
+        {
+            let msg = server.recv().unwrap().unwrap();
+            assert_eq!(1, msg.request_id());
+            assert_eq!(&[1, 2, 3], msg.payload());
+        }
+        assert_eq!(2, server.recv().unwrap().unwrap().request_id());
+        {
+            let msg = client.recv().unwrap().unwrap();
+            assert_eq!(3, msg.request_id());
+            assert_eq!(&[4], msg.payload());
+        }
+
+        // clean close
+        drop(client);
+        assert!(server.recv().unwrap().is_none());
+    }
+
+    #[test]
+    fn recv() {
+        let (mut client, mut server) = pair();
+        assert_eq!(Some(Duration::from_secs(5)), client.read_timeout().unwrap());
+
+        // timeout with a partially received message
+        let mut data = Vec::new();
+        write_msg(&header(1), &[1, 2, 3], &mut data).unwrap();
+        server.write_all(&data[..5]).unwrap();
+        client
+            .set_read_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+        let err = client.recv().unwrap_err().io_error().unwrap();
+        assert!(matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        ));
+        client
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        server.write_all(&data[5..]).unwrap();
+        assert_eq!(1, client.recv().unwrap().unwrap().request_id());
+
+        // resync after garbage
+        let mut data = std::vec![0xff; 16];
+        data[4..8].copy_from_slice(&8u32.to_be_bytes());
+        data.extend_from_slice(&MagicCookie::Server.to_bytes());
+        write_msg(&header(2), &[], &mut data).unwrap();
+        server.write_all(&data).unwrap();
+        assert!(matches!(client.recv(), Err(TransportError::Slice(_))));
+        assert_eq!(2, client.recv().unwrap().unwrap().request_id());
+
+        // too big message
+        client.set_max_message_len(SOMEIP_HEADER_LENGTH);
+        assert_eq!(SOMEIP_HEADER_LENGTH, client.max_message_len());
+        let mut data = Vec::new();
+        write_msg(&header(3), &[1], &mut data).unwrap();
+        server.write_all(&data).unwrap();
+        assert!(matches!(
+            client.recv(),
+            Err(TransportError::MessageTooBig {
+                len: 17,
+                max_len: 16
+            })
+        ));
+
+        // eof in the middle of a message
+        server.write_all(&MagicCookie::Server.to_bytes()).unwrap();
+        server.write_all(&data[..4]).unwrap();
+        drop(server);
+        let err = client.recv().unwrap_err().io_error().unwrap();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+
+        let _ = client.get_ref();
+        let _ = client.into_inner();
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{err::TransportError, *};
+use core::ops::Range;
+use std::vec::Vec;
+
+/// Number of bytes that are at least made available for each read call.
+const READ_CHUNK_LEN: usize = 0x4000;
+
+/// Buffer collecting the data received via a TCP stream and splitting it
+/// into SOMEIP messages.
+///
+/// Magic cookies are skipped and used to resynchronize the stream in case
+/// a broken message is encountered. The buffer is shared between the
+/// different TCP transport implementations and does not do any IO itself.
+#[derive(Debug)]
+pub(crate) struct TcpRecvBuf {
+    /// Received data.
+    data: Vec<u8>,
+    /// Start of the not yet decoded data in `data`.
+    start: usize,
+    /// End of the received data in `data`.
+    end: usize,
+    /// Maximum accepted length of a message (header + payload).
+    max_message_len: usize,
+}
+
+impl TcpRecvBuf {
+    pub(crate) fn new(max_message_len: usize) -> TcpRecvBuf {
+        TcpRecvBuf {
+            data: Vec::new(),
+            start: 0,
+            end: 0,
+            max_message_len,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn max_message_len(&self) -> usize {
+        self.max_message_len
+    }
+
+    #[inline]
+    pub(crate) fn set_max_message_len(&mut self, max_message_len: usize) {
+        self.max_message_len = max_message_len;
+    }
+
+    /// Returns true if received data is present that was not yet decoded.
+    #[inline]
+    pub(crate) fn has_pending(&self) -> bool {
+        self.start < self.end
+    }
+
+    /// Buffer into which the next data should be read.
+    ///
+    /// After the read [`TcpRecvBuf::advance`] has to be called with the
+    /// number of received bytes.
+    pub(crate) fn read_buf(&mut self) -> &mut [u8] {
+        // move the not yet decoded data to the start
+        if self.start > 0 {
+            self.data.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+        if self.data.len() < self.end + READ_CHUNK_LEN {
+            self.data.resize(self.end + READ_CHUNK_LEN, 0);
+        }
+        &mut self.data[self.end..]
+    }
+
+    /// Marks `len` bytes of [`TcpRecvBuf::read_buf`] as received.
+    #[inline]
+    pub(crate) fn advance(&mut self, len: usize) {
+        self.end += len;
+        debug_assert!(self.end <= self.data.len());
+    }
+
+    /// Decodes the next message from the received data.
+    ///
+    /// `None` is returned if more data has to be received before the next
+    /// message can be decoded. Magic cookies are skipped.
+    pub(crate) fn next(&mut self) -> Option<Result<Range<usize>, TransportError>> {
+        loop {
+            let pending = &self.data[self.start..self.end];
+            if pending.len() < SOMEIP_HEADER_LENGTH {
+                return None;
+            }
+
+            // determine the length of the message
+            let len_field = u64::from(u32::from_be_bytes([
+                pending[4], pending[5], pending[6], pending[7],
+            ]));
+            let len =
+                (SOMEIP_HEADER_LENGTH - SOMEIP_LEN_OFFSET_TO_PAYLOAD as usize) as u64 + len_field;
+            if len > self.max_message_len as u64 {
+                self.resync();
+                return Some(Err(TransportError::MessageTooBig {
+                    len: usize::try_from(len).unwrap_or(usize::MAX),
+                    max_len: self.max_message_len,
+                }));
+            }
+            let len = len as usize;
+            if pending.len() < len {
+                return None;
+            }
+
+            let range = self.start..self.start + len;
+            match SomeipMsgSlice::from_slice(&self.data[range.clone()]) {
+                Ok(msg) => {
+                    self.start = range.end;
+                    if false == msg.is_magic_cookie() {
+                        return Some(Ok(range));
+                    }
+                }
+                Err(err) => {
+                    self.resync();
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+    }
+
+    /// Returns the message in the given range (as returned by
+    /// [`TcpRecvBuf::next`]).
+    pub(crate) fn get(&self, range: Range<usize>) -> SomeipMsgSlice<'_> {
+        // already validated in `next`
+        SomeipMsgSlice::from_slice(&self.data[range]).unwrap()
+    }
+
+    /// Skips data until the next magic cookie.
+    ///
+    /// If no magic cookie is present in the received data, all data
+    /// except a trailing partial magic cookie is discarded.
+    fn resync(&mut self) {
+        let client = MagicCookie::Client.to_bytes();
+        let server = MagicCookie::Server.to_bytes();
+
+        // skip at least one byte so the broken message is not decoded again
+        let search_start = self.start + 1;
+        let searched = &self.data[search_start..self.end];
+        if let Some(pos) = searched
+            .windows(MagicCookie::LEN)
+            .position(|w| w == client || w == server)
+        {
+            self.start = search_start + pos;
+            return;
+        }
+
+        // keep the end of the data if it is the start of a magic cookie
+        let max_partial = core::cmp::min(MagicCookie::LEN - 1, searched.len());
+        let partial = (1..=max_partial)
+            .rev()
+            .find(|&len| {
+                let tail = &searched[searched.len() - len..];
+                tail == &client[..len] || tail == &server[..len]
+            })
+            .unwrap_or(0);
+        self.start = self.end - partial;
+    }
+}
+
+impl Default for TcpRecvBuf {
+    fn default() -> Self {
+        TcpRecvBuf::new(transport::DEFAULT_TCP_MAX_MESSAGE_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(request_id: u32, payload: &[u8]) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id: 0x1234_0001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Request,
+            return_code: 0,
+            tp_header: None,
+        };
+        let mut result = Vec::new();
+        super::super::write_msg(&header, payload, &mut result).unwrap();
+        result
+    }
+
+    fn receive(buf: &mut TcpRecvBuf, data: &[u8]) {
+        let target = buf.read_buf();
+        assert!(target.len() >= data.len());
+        target[..data.len()].copy_from_slice(data);
+        buf.advance(data.len());
+    }
+
+    #[test]
+    fn default() {
+        let buf: TcpRecvBuf = Default::default();
+        assert_eq!(
+            transport::DEFAULT_TCP_MAX_MESSAGE_LEN,
+            buf.max_message_len()
+        );
+        assert_eq!(false, buf.has_pending());
+    }
+
+    #[test]
+    fn next() {
+        // split messages & magic cookies
+        {
+            let mut data = MagicCookie::Client.to_bytes().to_vec();
+            data.extend_from_slice(&msg(1, &[1, 2, 3]));
+            data.extend_from_slice(&MagicCookie::Server.to_bytes());
+            data.extend_from_slice(&msg(2, &[4]));
+
+            let mut buf = TcpRecvBuf::default();
+            let mut received = Vec::new();
+            for b in data {
+                receive(&mut buf, &[b]);
+                while let Some(r) = buf.next() {
+                    let m = buf.get(r.unwrap());
+                    received.push((m.request_id(), m.payload().to_vec()));
+                }
+            }
+            assert_eq!(received, vec![(1, vec![1, 2, 3]), (2, vec![4])]);
+            assert_eq!(false, buf.has_pending());
+        }
+
+        // resync via magic cookie after garbage
+        {
+            let mut data = vec![0xff; 20];
+            data[0..4].copy_from_slice(&[0x12, 0x34, 0x00, 0x01]);
+            data[4..8].copy_from_slice(&8u32.to_be_bytes());
+            data.extend_from_slice(&MagicCookie::Client.to_bytes());
+            data.extend_from_slice(&msg(3, &[5]));
+
+            let mut buf = TcpRecvBuf::default();
+            receive(&mut buf, &data);
+            assert!(matches!(buf.next(), Some(Err(TransportError::Slice(_)))));
+            let r = buf.next().unwrap().unwrap();
+            assert_eq!(3, buf.get(r).request_id());
+            assert!(buf.next().is_none());
+        }
+
+        // resync without magic cookie keeps the partial cookie at the end
+        {
+            let mut data = vec![0xff; 40];
+            data[4..8].copy_from_slice(&8u32.to_be_bytes());
+            let cookie = MagicCookie::Server.to_bytes();
+            let mut buf = TcpRecvBuf::default();
+            receive(&mut buf, &data);
+            receive(&mut buf, &cookie[..4]);
+            assert!(matches!(buf.next(), Some(Err(TransportError::Slice(_)))));
+            assert!(buf.next().is_none());
+            receive(&mut buf, &cookie[4..]);
+            receive(&mut buf, &msg(4, &[]));
+            let r = buf.next().unwrap().unwrap();
+            assert_eq!(4, buf.get(r).request_id());
+        }
+
+        // message too big
+        {
+            let mut buf = TcpRecvBuf::new(SOMEIP_HEADER_LENGTH + 1);
+            assert_eq!(SOMEIP_HEADER_LENGTH + 1, buf.max_message_len());
+            receive(&mut buf, &msg(5, &[1]));
+            let r = buf.next().unwrap().unwrap();
+            assert_eq!(5, buf.get(r).request_id());
+
+            receive(&mut buf, &msg(6, &[1, 2]));
+            assert!(matches!(
+                buf.next(),
+                Some(Err(TransportError::MessageTooBig {
+                    len: 18,
+                    max_len: 17
+                }))
+            ));
+
+            buf.set_max_message_len(SOMEIP_HEADER_LENGTH + 2);
+            receive(&mut buf, &MagicCookie::Client.to_bytes());
+            receive(&mut buf, &msg(7, &[1, 2]));
+            let r = buf.next().unwrap().unwrap();
+            assert_eq!(7, buf.get(r).request_id());
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use super::*;
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+/// Blocking UDP endpoint sending & receiving SOMEIP messages.
+///
+/// Received datagrams are split into the contained SOMEIP messages and
+/// SOMEIP TP segments are reassembled (separately for each sender) before
+/// they are returned. Messages with a payload bigger then
+/// [`SOMEIP_MAX_PAYLOAD_LEN_UDP`] are automatically split into SOMEIP TP
+/// segments when sending.
+///
+/// # Example
+///
+/// ```
+/// use someip_parse::{transport::UdpEndpoint, MessageType, SomeipHeader};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), someip_parse::err::TransportError> {
+/// let mut server = UdpEndpoint::bind("127.0.0.1:0")?;
+/// server.set_read_timeout(Some(Duration::from_secs(5)))?;
+/// let client = UdpEndpoint::bind("127.0.0.1:0")?;
+///
+/// let header = SomeipHeader {
+///     message_id: 0x1234_0001,
+///     length: 0, // calculated when sending
+///     request_id: 0x0001_0001,
+///     interface_version: 1,
+///     message_type: MessageType::Request,
+///     return_code: 0,
+///     tp_header: None,
+/// };
+/// // big payloads get sent as multiple SOMEIP TP segments
+/// client.send_to(&header, &[1; 4000], server.local_addr()?)?;
+///
+/// let (msg, source) = server.recv_from()?;
+/// assert_eq!(0x1234_0001, msg.message_id());
+/// assert_eq!(&[1; 4000], msg.payload());
+/// assert_eq!(client.local_addr()?, source);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct UdpEndpoint {
+    socket: UdpSocket,
+    recv_buf: UdpRecvBuf,
+}
+
+impl UdpEndpoint {
+    /// Creates a new endpoint bound to the given address with the default
+    /// TP reassembly configuration.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<UdpEndpoint, TransportError> {
+        Ok(UdpEndpoint::from_socket(
+            UdpSocket::bind(addr)?,
+            Default::default(),
+        ))
+    }
+
+    /// Creates a new endpoint from an already bound UDP socket.
+    ///
+    /// `tp_buf_config` is used for the reassembly of SOMEIP TP messages.
+    pub fn from_socket(socket: UdpSocket, tp_buf_config: TpBufConfig) -> UdpEndpoint {
+        UdpEndpoint {
+            socket,
+            recv_buf: UdpRecvBuf::new(tp_buf_config),
+        }
+    }
+
+    /// Returns a reference to the underlying UDP socket.
+    #[inline]
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Consumes the endpoint and returns the underlying UDP socket.
+    #[inline]
+    pub fn into_inner(self) -> UdpSocket {
+        self.socket
+    }
+
+    /// Returns the local address the endpoint is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Sets the timeout of [`UdpEndpoint::recv_from`] (`None` blocks
+    /// indefinitely).
+    ///
+    /// If the timeout is reached an IO error with the kind
+    /// `WouldBlock` or `TimedOut` (depending on the platform) is returned.
+    #[inline]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.socket.set_read_timeout(timeout)
+    }
+
+    /// Returns the timeout of [`UdpEndpoint::recv_from`].
+    #[inline]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.socket.read_timeout()
+    }
+
+    /// Sets the timeout of [`UdpEndpoint::send_to`] (`None` blocks
+    /// indefinitely).
+    #[inline]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.socket.set_write_timeout(timeout)
+    }
+
+    /// Returns the timeout of [`UdpEndpoint::send_to`].
+    #[inline]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        self.socket.write_timeout()
+    }
+
+    /// Returns the pool used to reassemble SOMEIP TP messages.
+    #[inline]
+    pub fn tp_pool(&self) -> &TpPool<SocketAddr, Instant> {
+        self.recv_buf.tp_pool()
+    }
+
+    /// Discards all unfinished SOMEIP TP messages that did not receive a
+    /// segment within the given duration.
+    pub fn retain_tp_streams(&mut self, max_age: Duration) {
+        self.recv_buf
+            .tp_pool_mut()
+            .retain(|last_update| last_update.elapsed() <= max_age);
+    }
+
+    /// Joins the given IPv4 multicast group (e.g. the SOMEIP SD
+    /// multicast group) on the given interface.
+    #[inline]
+    pub fn join_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
+        self.socket.join_multicast_v4(multiaddr, interface)
+    }
+
+    /// Leaves the given IPv4 multicast group on the given interface.
+    #[inline]
+    pub fn leave_multicast_v4(&self, multiaddr: &Ipv4Addr, interface: &Ipv4Addr) -> io::Result<()> {
+        self.socket.leave_multicast_v4(multiaddr, interface)
+    }
+
+    /// Joins the given IPv6 multicast group (e.g. the SOMEIP SD
+    /// multicast group) on the interface with the given index.
+    #[inline]
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.socket.join_multicast_v6(multiaddr, interface)
+    }
+
+    /// Leaves the given IPv6 multicast group on the interface with the
+    /// given index.
+    #[inline]
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        self.socket.leave_multicast_v6(multiaddr, interface)
+    }
+
+    /// Sends a SOMEIP message with the given header & payload to the given
+    /// target.
+    ///
+    /// The `length` field of the header is ignored and instead calculated
+    /// based on the payload length & the presence of a TP header. If the
+    /// header contains no TP header and the payload is bigger then
+    /// [`SOMEIP_MAX_PAYLOAD_LEN_UDP`] the message is split into SOMEIP TP
+    /// segments, each sent as a separate datagram.
+    pub fn send_to<A: ToSocketAddrs>(
+        &self,
+        header: &SomeipHeader,
+        payload: &[u8],
+        target: A,
+    ) -> Result<(), TransportError> {
+        // resolve the address once so all segments get sent to the same target
+        let target = target
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::AddrNotAvailable))?;
+
+        let mut datagram = Vec::new();
+        for (segment_header, segment_payload) in UdpSegments::new(header, payload)? {
+            datagram.clear();
+            write_msg(&segment_header, segment_payload, &mut datagram)?;
+            if datagram.len() > MAX_UDP_PAYLOAD_LEN {
+                return Err(TransportError::MessageTooBig {
+                    len: datagram.len(),
+                    max_len: MAX_UDP_PAYLOAD_LEN,
+                });
+            }
+            self.socket.send_to(&datagram, target)?;
+        }
+        Ok(())
+    }
+
+    /// Receives the next SOMEIP message & the address of its sender.
+    ///
+    /// SOMEIP TP segments are consumed internally and only the reassembled
+    /// message is returned once all segments have been received. Errors
+    /// when decoding a message are returned, the endpoint can continue to
+    /// be used afterwards.
+    pub fn recv_from(&mut self) -> Result<(SomeipMsgSlice<'_>, SocketAddr), TransportError> {
+        loop {
+            if let Some(result) = self.recv_buf.next() {
+                let location = result?;
+                return Ok(self.recv_buf.get(&location));
+            }
+            let (len, source) = self.socket.recv_from(self.recv_buf.datagram_buf())?;
+            self.recv_buf.set_received(len, source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(request_id: u32, tp_header: Option<TpHeader>) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header,
+        }
+    }
+
+    #[test]
+    fn send_recv() {
+        let mut receiver = UdpEndpoint::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(
+            Some(Duration::from_secs(5)),
+            receiver.read_timeout().unwrap()
+        );
+        let sender = UdpEndpoint::bind("127.0.0.1:0").unwrap();
+        sender
+            .set_write_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert_eq!(
+            Some(Duration::from_secs(5)),
+            sender.write_timeout().unwrap()
+        );
+        let target = receiver.local_addr().unwrap();
+        let sender_addr = sender.local_addr().unwrap();
+
+        // plain message
+        sender
+            .send_to(&header(1, None), &[1, 2, 3], target)
+            .unwrap();
+        {
+            let (msg, source) = receiver.recv_from().unwrap();
+            assert_eq!(1, msg.request_id());
+            assert_eq!(&[1, 2, 3], msg.payload());
+            assert_eq!(sender_addr, source);
+        }
+
+        // automatically segmented message
+        {
+            let payload: Vec<u8> = (0..5000u32).map(|v| v as u8).collect();
+            sender.send_to(&header(2, None), &payload, target).unwrap();
+            let (msg, source) = receiver.recv_from().unwrap();
+            assert_eq!(2, msg.request_id());
+            assert_eq!(false, msg.is_tp());
+            assert_eq!(&payload[..], msg.payload());
+            assert_eq!(sender_addr, source);
+        }
+
+        // unfinished tp stream
+        sender
+            .send_to(
+                &header(3, Some(TpHeader::with_offset(0, true).unwrap())),
+                &[1; 16],
+                target,
+            )
+            .unwrap();
+        sender.send_to(&header(4, None), &[4], target).unwrap();
+        assert_eq!(4, receiver.recv_from().unwrap().0.request_id());
+        assert_eq!(1, receiver.tp_pool().active_bufs().len());
+        receiver.retain_tp_streams(Duration::from_secs(60));
+        assert_eq!(1, receiver.tp_pool().active_bufs().len());
+        std::thread::sleep(Duration::from_millis(1));
+        receiver.retain_tp_streams(Duration::ZERO);
+        assert_eq!(0, receiver.tp_pool().active_bufs().len());
+
+        // broken datagram
+        sender.socket().send_to(&[0; 4], target).unwrap();
+        assert!(matches!(
+            receiver.recv_from(),
+            Err(TransportError::Slice(_))
+        ));
+
+        // too big tp segment
+        assert!(matches!(
+            sender.send_to(
+                &header(5, Some(TpHeader::new(false))),
+                &[0; MAX_UDP_PAYLOAD_LEN],
+                target
+            ),
+            Err(TransportError::MessageTooBig { .. })
+        ));
+
+        // timeout
+        receiver
+            .set_read_timeout(Some(Duration::from_millis(1)))
+            .unwrap();
+        let err = receiver.recv_from().unwrap_err().io_error().unwrap();
+        assert!(matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        ));
+
+        let socket = receiver.into_inner();
+        assert_eq!(target, socket.local_addr().unwrap());
+    }
+
+    #[test]
+    fn multicast() {
+        let endpoint = UdpEndpoint::bind("0.0.0.0:0").unwrap();
+        let group = Ipv4Addr::new(224, 244, 224, 245);
+        // joining can fail if no multicast capable interface is present
+        if endpoint
+            .join_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)
+            .is_ok()
+        {
+            endpoint
+                .leave_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)
+                .unwrap();
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{err::TransportError, *};
+use core::ops::Range;
+use std::{net::SocketAddr, time::Instant, vec, vec::Vec};
+
+/// Maximum length of an UDP payload.
+pub(crate) const MAX_UDP_PAYLOAD_LEN: usize = 0xffff;
+
+/// Location of a message decoded by an [`UdpRecvBuf`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum UdpRecvLocation {
+    /// Message is located in the given range of the last received datagram.
+    Datagram(Range<usize>),
+    /// Message was reassembled from multiple TP segments.
+    Reassembled,
+}
+
+/// Buffer storing the last received UDP datagram and splitting it into
+/// SOMEIP messages (including the reassembly of SOMEIP TP messages).
+///
+/// The buffer is shared between the different UDP transport implementations
+/// and does not do any IO itself.
+#[derive(Debug)]
+pub(crate) struct UdpRecvBuf {
+    /// Data of the last received datagram.
+    datagram: Vec<u8>,
+    /// Start of the not yet decoded data in `datagram`.
+    pos: usize,
+    /// Length of the last received datagram.
+    len: usize,
+    /// Sender of the last received datagram.
+    source: Option<SocketAddr>,
+    /// Pool used to reassemble TP messages.
+    tp_pool: TpPool<SocketAddr, Instant>,
+    /// Copy of the last reassembled TP message.
+    reassembled: Vec<u8>,
+}
+
+impl UdpRecvBuf {
+    pub(crate) fn new(tp_buf_config: TpBufConfig) -> UdpRecvBuf {
+        UdpRecvBuf {
+            datagram: vec![0; MAX_UDP_PAYLOAD_LEN],
+            pos: 0,
+            len: 0,
+            source: None,
+            tp_pool: TpPool::new(tp_buf_config),
+            reassembled: Vec::new(),
+        }
+    }
+
+    /// Buffer into which the next datagram should be received.
+    #[inline]
+    pub(crate) fn datagram_buf(&mut self) -> &mut [u8] {
+        &mut self.datagram
+    }
+
+    /// Set the length & sender of the datagram received into
+    /// [`UdpRecvBuf::datagram_buf`].
+    #[inline]
+    pub(crate) fn set_received(&mut self, len: usize, source: SocketAddr) {
+        self.pos = 0;
+        self.len = len;
+        self.source = Some(source);
+    }
+
+    #[inline]
+    pub(crate) fn tp_pool(&self) -> &TpPool<SocketAddr, Instant> {
+        &self.tp_pool
+    }
+
+    #[inline]
+    pub(crate) fn tp_pool_mut(&mut self) -> &mut TpPool<SocketAddr, Instant> {
+        &mut self.tp_pool
+    }
+
+    /// Decodes the next message from the last received datagram.
+    ///
+    /// `None` is returned if all messages of the datagram have been
+    /// consumed and a new datagram has to be received.
+    pub(crate) fn next(&mut self) -> Option<Result<UdpRecvLocation, TransportError>> {
+        let source = self.source?;
+        while self.pos < self.len {
+            let msg = match SomeipMsgSlice::from_slice(&self.datagram[self.pos..self.len]) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    // the rest of the datagram can not be decoded
+                    self.pos = self.len;
+                    return Some(Err(err.into()));
+                }
+            };
+            let range = self.pos..self.pos + msg.slice().len();
+            self.pos = range.end;
+
+            if false == msg.is_tp() {
+                return Some(Ok(UdpRecvLocation::Datagram(range)));
+            }
+
+            match self.tp_pool.consume(source, Instant::now(), msg) {
+                Ok(Some(reassembled)) => {
+                    self.reassembled.clear();
+                    self.reassembled.extend_from_slice(reassembled.slice());
+                    return Some(Ok(UdpRecvLocation::Reassembled));
+                }
+                Ok(None) => {}
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+        None
+    }
+
+    /// Returns the message & sender at the given location.
+    pub(crate) fn get(&self, location: &UdpRecvLocation) -> (SomeipMsgSlice<'_>, SocketAddr) {
+        let data = match location {
+            UdpRecvLocation::Datagram(range) => &self.datagram[range.clone()],
+            UdpRecvLocation::Reassembled => &self.reassembled[..],
+        };
+        (
+            // already validated in `next`
+            SomeipMsgSlice::from_slice(data).unwrap(),
+            self.source.unwrap(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    fn msg(request_id: u32, tp_header: Option<TpHeader>, payload: &[u8]) -> Vec<u8> {
+        let header = SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header,
+        };
+        let mut result = Vec::new();
+        super::super::write_msg(&header, payload, &mut result).unwrap();
+        result
+    }
+
+    fn receive(buf: &mut UdpRecvBuf, datagram: &[u8], source: SocketAddr) {
+        buf.datagram_buf()[..datagram.len()].copy_from_slice(datagram);
+        buf.set_received(datagram.len(), source);
+    }
+
+    #[test]
+    fn next() {
+        let source_a = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 1234));
+        let source_b = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 2345));
+        let mut buf = UdpRecvBuf::new(Default::default());
+
+        // nothing received yet
+        assert!(buf.next().is_none());
+
+        // multiple messages in one datagram
+        {
+            let mut datagram = msg(1, None, &[1, 2]);
+            datagram.extend_from_slice(&msg(2, None, &[3]));
+            receive(&mut buf, &datagram, source_a);
+
+            let loc = buf.next().unwrap().unwrap();
+            let (m, s) = buf.get(&loc);
+            assert_eq!(1, m.request_id());
+            assert_eq!(&[1, 2], m.payload());
+            assert_eq!(source_a, s);
+
+            let loc = buf.next().unwrap().unwrap();
+            let (m, s) = buf.get(&loc);
+            assert_eq!(2, m.request_id());
+            assert_eq!(&[3], m.payload());
+            assert_eq!(source_a, s);
+
+            assert!(buf.next().is_none());
+        }
+
+        // tp segments from different sources
+        {
+            let seg0 = msg(3, Some(TpHeader::with_offset(0, true).unwrap()), &[1; 16]);
+            let seg1 = msg(3, Some(TpHeader::with_offset(16, false).unwrap()), &[2; 4]);
+
+            receive(&mut buf, &seg0, source_a);
+            assert!(buf.next().is_none());
+            receive(&mut buf, &seg0, source_b);
+            assert!(buf.next().is_none());
+            assert_eq!(2, buf.tp_pool().active_bufs().len());
+
+            receive(&mut buf, &seg1, source_b);
+            let loc = buf.next().unwrap().unwrap();
+            assert_eq!(UdpRecvLocation::Reassembled, loc);
+            let (m, s) = buf.get(&loc);
+            assert_eq!(false, m.is_tp());
+            assert_eq!(3, m.request_id());
+            assert_eq!(source_b, s);
+            assert_eq!(&[[1u8; 16].as_slice(), &[2; 4]].concat()[..], m.payload());
+            assert!(buf.next().is_none());
+            assert_eq!(1, buf.tp_pool().active_bufs().len());
+
+            buf.tp_pool_mut().retain(|_| false);
+            assert_eq!(0, buf.tp_pool().active_bufs().len());
+        }
+
+        // broken message after a valid message
+        {
+            let mut datagram = msg(4, None, &[]);
+            datagram.extend_from_slice(&[0; 4]);
+            receive(&mut buf, &datagram, source_a);
+
+            let loc = buf.next().unwrap().unwrap();
+            assert_eq!(4, buf.get(&loc).0.request_id());
+            assert!(matches!(buf.next(), Some(Err(TransportError::Slice(_)))));
+            assert!(buf.next().is_none());
+        }
+
+        // tp error
+        {
+            let bad = msg(5, Some(TpHeader::with_offset(0, true).unwrap()), &[1; 15]);
+            receive(&mut buf, &bad, source_a);
+            assert!(matches!(
+                buf.next(),
+                Some(Err(TransportError::TpReassemble(_)))
+            ));
+            assert!(buf.next().is_none());
+        }
+    }
+}
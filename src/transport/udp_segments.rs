@@ -0,0 +1,138 @@
+use crate::{err::TransportError, *};
+
+/// Iterator splitting a SOMEIP message into SOMEIP TP segments if the
+/// payload is too big to be sent via a single UDP datagram.
+///
+/// Messages with a payload of up to [`SOMEIP_MAX_PAYLOAD_LEN_UDP`] bytes and
+/// messages that already contain a TP header are not split and returned as
+/// a single element.
+#[derive(Clone, Debug)]
+pub(crate) struct UdpSegments<'a> {
+    header: &'a SomeipHeader,
+    payload: &'a [u8],
+    /// Offset of the next segment in the payload.
+    offset: usize,
+    /// True if the payload has to be split into TP segments.
+    segmented: bool,
+    /// True if all segments have been returned.
+    done: bool,
+}
+
+impl<'a> UdpSegments<'a> {
+    pub(crate) fn new(
+        header: &'a SomeipHeader,
+        payload: &'a [u8],
+    ) -> Result<UdpSegments<'a>, TransportError> {
+        let segmented =
+            header.tp_header.is_none() && payload.len() > SOMEIP_MAX_PAYLOAD_LEN_UDP as usize;
+        if segmented {
+            let len = payload
+                .len()
+                .saturating_add(SOMEIP_HEADER_LENGTH + TP_HEADER_LENGTH);
+            let max_len = super::max_message_len();
+            if len > max_len {
+                return Err(TransportError::MessageTooBig { len, max_len });
+            }
+        }
+        Ok(UdpSegments {
+            header,
+            payload,
+            offset: 0,
+            segmented,
+            done: false,
+        })
+    }
+}
+
+impl<'a> Iterator for UdpSegments<'a> {
+    type Item = (SomeipHeader, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if false == self.segmented {
+            self.done = true;
+            return Some((self.header.clone(), self.payload));
+        }
+
+        let start = self.offset;
+        let end = core::cmp::min(start + TP_UDP_MAX_SEGMENT_LEN_ALIGNED, self.payload.len());
+        let more_segment = end < self.payload.len();
+        self.offset = end;
+        self.done = false == more_segment;
+        Some((
+            SomeipHeader {
+                // start is a multiple of 16 & fits into an u32 (checked in new)
+                tp_header: Some(TpHeader::with_offset(start as u32, more_segment).unwrap()),
+                ..self.header.clone()
+            },
+            &self.payload[start..end],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    fn header(tp_header: Option<TpHeader>) -> SomeipHeader {
+        SomeipHeader {
+            message_id: 0x1234_8001,
+            length: 0,
+            request_id: 0x1,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: 0,
+            tp_header,
+        }
+    }
+
+    #[test]
+    fn next() {
+        // not segmented
+        for payload_len in [0, 1, SOMEIP_MAX_PAYLOAD_LEN_UDP as usize] {
+            let header = header(None);
+            let payload = std::vec![1u8; payload_len];
+            let actual: Vec<_> = UdpSegments::new(&header, &payload).unwrap().collect();
+            assert_eq!(actual, std::vec![(header.clone(), &payload[..])]);
+        }
+
+        // already tp segment
+        {
+            let header = header(Some(TpHeader::with_offset(16, false).unwrap()));
+            let payload = [1u8; 2000];
+            let actual: Vec<_> = UdpSegments::new(&header, &payload).unwrap().collect();
+            assert_eq!(actual, std::vec![(header.clone(), &payload[..])]);
+        }
+
+        // segmented
+        for (payload_len, expected_lens) in [
+            (1401, &[1392, 9][..]),
+            (1392 * 2, &[1392, 1392][..]),
+            (1392 * 2 + 1, &[1392, 1392, 1][..]),
+        ] {
+            let header = header(None);
+            let payload: Vec<u8> = (0..payload_len).map(|v| v as u8).collect();
+            let actual: Vec<_> = UdpSegments::new(&header, &payload).unwrap().collect();
+            assert_eq!(expected_lens.len(), actual.len());
+            let mut offset = 0;
+            for (index, (seg_header, seg_payload)) in actual.iter().enumerate() {
+                assert_eq!(
+                    seg_header,
+                    &SomeipHeader {
+                        tp_header: Some(
+                            TpHeader::with_offset(offset as u32, index + 1 < expected_lens.len())
+                                .unwrap()
+                        ),
+                        ..header.clone()
+                    }
+                );
+                assert_eq!(expected_lens[index], seg_payload.len());
+                assert_eq!(&payload[offset..offset + seg_payload.len()], *seg_payload);
+                offset += seg_payload.len();
+            }
+        }
+    }
+}